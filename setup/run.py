@@ -454,8 +454,8 @@ def read_dev_mode_from_env() -> bool:
     return False
 
 
-def compose_up(dev_mode: bool, build: bool = False) -> bool:
-    """Start containers (optionally with rebuild)."""
+def compose_up(dev_mode: bool, build: bool = False, no_cache: bool = False) -> bool:
+    """Start containers (optionally with rebuild, bypassing the build cache if no_cache)."""
     # Auto-detect and export HOST_HOSTNAME if not already set
     if "HOST_HOSTNAME" not in os.environ:
         import platform
@@ -529,6 +529,13 @@ def compose_up(dev_mode: bool, build: bool = False) -> bool:
     print("   (Pulling images if needed... this may take a few minutes on first run)")
     sys.stdout.flush()  # Ensure message is displayed immediately
 
+    # `compose up --build` can't skip the cache, so build explicitly first
+    if build and no_cache:
+        result = subprocess.run(get_compose_cmd(dev_mode) + ["build", "--no-cache"], cwd=str(PROJECT_ROOT))
+        if result.returncode != 0:
+            print_color(Colors.RED, f"{Icons.ERROR} Failed to build images")
+            return False
+
     cmd = get_compose_cmd(dev_mode) + ["up", "-d"]
     if build:
         cmd.append("--build")
@@ -670,6 +677,7 @@ def main():
     parser.add_argument("--up", action="store_true", help="Start containers (no rebuild)")
     parser.add_argument("--down", action="store_true", help="Stop containers")
     parser.add_argument("--build", action="store_true", help="Rebuild and start containers")
+    parser.add_argument("--no-cache", action="store_true", help="With --build, rebuild images without the build cache")
     parser.add_argument("--restart", action="store_true", help="Restart containers")
     args = parser.parse_args()
 
@@ -696,7 +704,7 @@ def main():
             # Ensure secrets before build
             secrets_file = PROJECT_ROOT / "config" / "SECRETS" / "secrets.yaml"
             ensure_secrets_yaml(str(secrets_file))
-            sys.exit(0 if compose_up(dev_mode, build=True) else 1)
+            sys.exit(0 if compose_up(dev_mode, build=True, no_cache=args.no_cache) else 1)
 
     # Full setup flow
     dev_mode = args.dev and not args.prod
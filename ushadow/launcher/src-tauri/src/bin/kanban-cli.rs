@@ -1,8 +1,18 @@
 use rusqlite::{Connection, params};
+use serde::Serialize;
 use std::path::PathBuf;
 use std::env;
+use std::sync::OnceLock;
 
-#[derive(Debug)]
+#[path = "../commands/ticket_ids.rs"]
+mod ticket_ids;
+
+use ticket_ids::{get_next_ticket_number, get_or_create_ticket_prefix};
+
+const VALID_STATUSES: [&str; 6] = ["backlog", "todo", "in_progress", "in_review", "done", "archived"];
+const VALID_PRIORITIES: [&str; 4] = ["low", "medium", "high", "urgent"];
+
+#[derive(Debug, Serialize)]
 #[allow(dead_code)]
 struct Ticket {
     id: String,
@@ -13,8 +23,18 @@ struct Ticket {
     tmux_window_name: Option<String>,
 }
 
+/// Database path given with the global `--db-path` option
+static DB_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
 /// Get the path to the SQLite database
 fn get_db_path() -> Result<PathBuf, String> {
+    if let Some(path) = DB_PATH_OVERRIDE.get() {
+        if !path.exists() {
+            return Err(format!("Database file does not exist: {:?}", path));
+        }
+        return Ok(path.clone());
+    }
+
     let data_dir = dirs::data_dir().ok_or("Failed to get data directory")?;
     let launcher_dir = data_dir.join("com.ushadow.launcher");
 
@@ -25,11 +45,17 @@ fn get_db_path() -> Result<PathBuf, String> {
     Ok(launcher_dir.join("kanban.db"))
 }
 
-/// Get a database connection
+/// Get a database connection (WAL + busy timeout, matching the launcher's settings)
 fn get_db_connection() -> Result<Connection, String> {
     let db_path = get_db_path()?;
-    Connection::open(&db_path)
-        .map_err(|e| format!("Failed to open database: {}", e))
+    let conn = Connection::open(&db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+
+    // The launcher UI writes to the same file; wait for its lock instead of failing
+    conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;")
+        .map_err(|e| format!("Failed to configure database: {}", e))?;
+
+    Ok(conn)
 }
 
 /// Find tickets by worktree path
@@ -113,17 +139,131 @@ fn find_tickets_by_tmux_window(window_name: &str) -> Result<Vec<Ticket>, String>
     Ok(tickets)
 }
 
-/// Update ticket status
-fn update_ticket_status(ticket_id: &str, new_status: &str) -> Result<(), String> {
-    // Validate status
-    let valid_statuses = ["backlog", "todo", "in_progress", "in_review", "done", "archived"];
-    if !valid_statuses.contains(&new_status) {
+/// List tickets ordered by board order, optionally filtered by status and project
+fn list_tickets(status: Option<&str>, project_id: Option<&str>) -> Result<Vec<Ticket>, String> {
+    if let Some(status) = status {
+        validate_status(status)?;
+    }
+
+    let conn = get_db_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, title, status, worktree_path, branch_name, tmux_window_name
+         FROM tickets
+         WHERE (?1 IS NULL OR status = ?1) AND (?2 IS NULL OR project_id = ?2)
+         ORDER BY \"order\", id"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let tickets = stmt.query_map(params![status, project_id], |row| {
+        Ok(Ticket {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            status: row.get(2)?,
+            worktree_path: row.get(3)?,
+            branch_name: row.get(4)?,
+            tmux_window_name: row.get(5)?,
+        })
+    })
+    .map_err(|e| format!("Failed to query tickets: {}", e))?
+    .filter_map(|r| r.ok())
+    .collect();
+
+    Ok(tickets)
+}
+
+/// Create a backlog ticket with the next sequential id, exactly like the launcher's create_ticket
+fn create_ticket(
+    title: &str,
+    description: Option<&str>,
+    priority: &str,
+    project_id: Option<&str>,
+) -> Result<String, String> {
+    if !VALID_PRIORITIES.contains(&priority) {
+        return Err(format!(
+            "Invalid priority '{}'. Must be one of: {}",
+            priority,
+            VALID_PRIORITIES.join(", ")
+        ));
+    }
+
+    let mut conn = get_db_connection()?;
+    // Take the write lock up front so a concurrent create can't pick the same number
+    let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let prefix = get_or_create_ticket_prefix(&tx, project_id)?;
+    let id = format!("{}-{}", prefix, get_next_ticket_number(&tx, &prefix)?);
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let order: i32 = tx.query_row(
+        "SELECT COALESCE(MAX(\"order\"), -1) + 1 FROM tickets WHERE status = 'backlog'",
+        [],
+        |row| row.get(0),
+    ).map_err(|e| format!("Failed to compute order: {}", e))?;
+
+    tx.execute(
+        "INSERT INTO tickets (id, title, description, status, priority, tags, project_id, \"order\", created_at, updated_at)
+         VALUES (?1, ?2, ?3, 'backlog', ?4, '[]', ?5, ?6, ?7, ?7)",
+        params![&id, title, description, priority, project_id, order, &now],
+    ).map_err(|e| format!("Failed to insert ticket: {}", e))?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit ticket: {}", e))?;
+
+    Ok(id)
+}
+
+/// Find tickets by worktree path, then branch, then tmux window
+fn find_tickets_by_identifier(identifier: &str) -> Vec<Ticket> {
+    let lookups: [fn(&str) -> Result<Vec<Ticket>, String>; 3] =
+        [find_tickets_by_worktree, find_tickets_by_branch, find_tickets_by_tmux_window];
+
+    lookups
+        .iter()
+        .filter_map(|find| find(identifier).ok())
+        .find(|tickets| !tickets.is_empty())
+        .unwrap_or_default()
+}
+
+/// Move every ticket matching `identifier` to `status`
+fn move_tickets(identifier: &str, status: &str) -> Result<(), String> {
+    let tickets = find_tickets_by_identifier(identifier);
+
+    if tickets.is_empty() {
+        eprintln!("⚠ No tickets found for identifier: {}", identifier);
+        eprintln!("  This is OK - not all worktrees have associated tickets");
+        return Ok(());
+    }
+
+    let mut errors = Vec::new();
+    for ticket in &tickets {
+        match update_ticket_status(&ticket.id, status) {
+            Ok(_) => println!("✓ Moved ticket to {}: {} - {}", status, ticket.id, ticket.title),
+            Err(e) => errors.push(format!("Failed to update {}: {}", ticket.id, e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+fn validate_status(status: &str) -> Result<(), String> {
+    if !VALID_STATUSES.contains(&status) {
         return Err(format!(
             "Invalid status '{}'. Must be one of: {}",
-            new_status,
-            valid_statuses.join(", ")
+            status,
+            VALID_STATUSES.join(", ")
         ));
     }
+    Ok(())
+}
+
+/// Update ticket status
+fn update_ticket_status(ticket_id: &str, new_status: &str) -> Result<(), String> {
+    validate_status(new_status)?;
 
     let conn = get_db_connection()?;
     let now = chrono::Utc::now().to_rfc3339();
@@ -141,13 +281,22 @@ fn update_ticket_status(ticket_id: &str, new_status: &str) -> Result<(), String>
 }
 
 fn print_usage() {
-    eprintln!("Usage: kanban-cli <command> [options]");
+    eprintln!("Usage: kanban-cli [--db-path <path>] <command> [options]");
+    eprintln!();
+    eprintln!("Global options:");
+    eprintln!("  --db-path <path>                     Use this kanban.db instead of the launcher's");
     eprintln!();
     eprintln!("Commands:");
     eprintln!("  set-status <ticket-id> <status>     Update ticket status");
     eprintln!("  find-by-path <worktree-path>         Find tickets by worktree path");
     eprintln!("  find-by-branch <branch-name>         Find tickets by branch name");
     eprintln!("  find-by-window <tmux-window>         Find tickets by tmux window name");
+    eprintln!("  list [--status <s>] [--project <id>] [--json]");
+    eprintln!("                                       List tickets in board order");
+    eprintln!("  create --title <t> [--description <d>] [--priority <p>] [--project <id>]");
+    eprintln!("                                       Create a backlog ticket, prints its id");
+    eprintln!("  move-to-backlog <identifier>         Move ticket(s) to 'backlog' status");
+    eprintln!("  move-to-todo <identifier>            Move ticket(s) to 'todo' status");
     eprintln!("  move-to-review <identifier>          Move ticket(s) to 'in_review' status");
     eprintln!("  move-to-progress <identifier>        Move ticket(s) to 'in_progress' status");
     eprintln!("  move-to-done <identifier>            Move ticket(s) to 'done' status");
@@ -163,6 +312,9 @@ fn print_usage() {
     eprintln!("  # Find tickets by worktree path");
     eprintln!("  kanban-cli find-by-path /path/to/worktree");
     eprintln!();
+    eprintln!("  # Dump in-progress tickets for a script");
+    eprintln!("  kanban-cli list --status in_progress --json");
+    eprintln!();
     eprintln!("  # Agent self-reporting workflow");
     eprintln!("  kanban-cli move-to-progress $BRANCH_NAME  # Agent starts working");
     eprintln!("  kanban-cli move-to-review $BRANCH_NAME    # Agent waits for human");
@@ -170,7 +322,19 @@ fn print_usage() {
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+
+    // Global options come before the subcommand
+    while args.len() > 1 && args[1] == "--db-path" {
+        if args.len() < 3 {
+            eprintln!("Error: --db-path requires a path");
+            print_usage();
+            std::process::exit(1);
+        }
+        let path = args.remove(2);
+        args.remove(1);
+        let _ = DB_PATH_OVERRIDE.set(PathBuf::from(path));
+    }
 
     if args.len() < 2 {
         print_usage();
@@ -266,6 +430,86 @@ fn main() {
                 Err(e) => Err(e),
             }
         }
+        "list" => {
+            let mut status = None;
+            let mut project = None;
+            let mut json = false;
+
+            let mut rest = args[2..].iter();
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "--status" => status = rest.next().cloned(),
+                    "--project" => project = rest.next().cloned(),
+                    "--json" => json = true,
+                    other => {
+                        eprintln!("Error: Unknown list option '{}'", other);
+                        print_usage();
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            match list_tickets(status.as_deref(), project.as_deref()) {
+                Ok(tickets) if json => serde_json::to_string_pretty(&tickets)
+                    .map(|out| println!("{}", out))
+                    .map_err(|e| format!("Failed to serialize tickets: {}", e)),
+                Ok(tickets) => {
+                    if tickets.is_empty() {
+                        println!("No tickets found");
+                    } else {
+                        println!("Found {} ticket(s):", tickets.len());
+                        for ticket in tickets {
+                            println!("  {} - {} ({})", ticket.id, ticket.title, ticket.status);
+                        }
+                    }
+                    Ok(())
+                }
+                Err(e) => Err(e),
+            }
+        }
+        "create" => {
+            let mut title = None;
+            let mut description = None;
+            let mut priority = "medium".to_string();
+            let mut project = None;
+
+            let mut rest = args[2..].iter();
+            while let Some(arg) = rest.next() {
+                let value = rest.next().cloned();
+                match (arg.as_str(), value) {
+                    ("--title", Some(v)) => title = Some(v),
+                    ("--description", Some(v)) => description = Some(v),
+                    ("--priority", Some(v)) => priority = v,
+                    ("--project", Some(v)) => project = Some(v),
+                    (other, _) => {
+                        eprintln!("Error: Invalid create option '{}'", other);
+                        print_usage();
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            let title = match title.filter(|t| !t.trim().is_empty()) {
+                Some(title) => title,
+                None => {
+                    eprintln!("Error: create requires --title");
+                    print_usage();
+                    std::process::exit(1);
+                }
+            };
+
+            create_ticket(&title, description.as_deref(), &priority, project.as_deref())
+                .map(|id| println!("{}", id))
+        }
+        "move-to-backlog" | "move-to-todo" => {
+            if args.len() < 3 {
+                eprintln!("Error: {} requires identifier (path, branch, or window)", command);
+                print_usage();
+                std::process::exit(1);
+            }
+            let status = if command == "move-to-backlog" { "backlog" } else { "todo" };
+            move_tickets(&args[2], status)
+        }
         "move-to-review" => {
             if args.len() < 3 {
                 eprintln!("Error: move-to-review requires identifier (path, branch, or window)");
@@ -0,0 +1,390 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use ignore::WalkBuilder;
+use serde::Deserialize;
+use walkdir::WalkDir;
+use crate::models::{EnvironmentDiskUsage, HeavyDir, ResourceSize};
+use super::container_runtime::container_runtime;
+use super::utils::silent_command;
+use super::worktree::{
+    compose_project_for_env, environment_containers, environment_has_running_containers, list_worktrees, project_volumes,
+};
+
+/// Total size in bytes of the files under `path` (symlinks are not followed)
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Top-most directories under `root` that .gitignore excludes (node_modules, target, ...).
+/// Only the non-ignored tree is walked, so ignored directories are never descended into.
+fn ignored_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut visited = HashSet::new();
+    let mut dirs = Vec::new();
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    for entry in walker.filter_map(|entry| entry.ok()) {
+        if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            dirs.push(entry.path().to_path_buf());
+        }
+        visited.insert(entry.into_path());
+    }
+
+    let mut ignored = Vec::new();
+    for dir in dirs {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            if is_dir && entry.file_name() != ".git" && !visited.contains(&entry.path()) {
+                ignored.push(entry.path());
+            }
+        }
+    }
+    ignored
+}
+
+/// Ignored directories of a worktree with their sizes, largest first
+fn heavy_dirs(root: &Path) -> Vec<HeavyDir> {
+    let mut heavy: Vec<HeavyDir> = ignored_dirs(root)
+        .into_iter()
+        .map(|dir| HeavyDir {
+            path: dir.strip_prefix(root).unwrap_or(&dir).to_string_lossy().to_string(),
+            size_bytes: dir_size(&dir),
+        })
+        .collect();
+    heavy.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.path.cmp(&b.path)));
+    heavy
+}
+
+/// Largest gitignored directories in a worktree (dependencies, build output), largest first
+#[tauri::command]
+pub async fn get_untracked_heavy_dirs(worktree_path: String) -> Result<Vec<HeavyDir>, String> {
+    let root = PathBuf::from(&worktree_path);
+    if !root.is_dir() {
+        return Err(format!("Worktree not found: {}", worktree_path));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || heavy_dirs(&root))
+        .await
+        .map_err(|e| format!("Failed to scan worktree: {}", e))
+}
+
+/// Resolve a requested artifact directory, refusing anything outside the worktree or not gitignored
+fn validate_artifact_target(root: &Path, ignored: &[PathBuf], target: &str) -> Result<PathBuf, String> {
+    let path = root
+        .join(target)
+        .canonicalize()
+        .map_err(|e| format!("Artifact directory '{}' not found: {}", target, e))?;
+    if path == root || !path.starts_with(root) {
+        return Err(format!("'{}' is outside the worktree", target));
+    }
+    if !path.is_dir() {
+        return Err(format!("'{}' is not a directory", target));
+    }
+    if !ignored.iter().any(|dir| path.starts_with(dir)) {
+        return Err(format!("'{}' is not gitignored", target));
+    }
+    Ok(path)
+}
+
+/// Whether git tracks any file under `path` (force-added files inside ignored directories)
+fn has_tracked_files(root: &Path, path: &Path) -> Result<bool, String> {
+    let output = silent_command("git")
+        .arg("-C")
+        .arg(root)
+        .args(["ls-files", "--"])
+        .arg(path)
+        .output()
+        .map_err(|e| format!("Failed to run git ls-files: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("git ls-files failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(!output.stdout.is_empty())
+}
+
+/// Delete gitignored directories under `root`, returning the bytes reclaimed.
+/// Every target is validated before anything is deleted.
+fn clean_artifacts(root: &Path, targets: &[String]) -> Result<u64, String> {
+    let root = root
+        .canonicalize()
+        .map_err(|e| format!("Worktree not found: {}", e))?;
+    let ignored = ignored_dirs(&root);
+
+    let mut paths = Vec::new();
+    for target in targets {
+        let path = validate_artifact_target(&root, &ignored, target)?;
+        if has_tracked_files(&root, &path)? {
+            return Err(format!("'{}' contains tracked files", target));
+        }
+        paths.push(path);
+    }
+
+    let mut reclaimed = 0;
+    for path in paths {
+        // A target nested in an earlier one may already be gone
+        if !path.exists() {
+            continue;
+        }
+        let size = dir_size(&path);
+        fs::remove_dir_all(&path)
+            .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+        reclaimed += size;
+    }
+    Ok(reclaimed)
+}
+
+/// Delete gitignored artifact directories (as listed by `get_untracked_heavy_dirs`) from a
+/// worktree, returning the bytes reclaimed. Refused while the environment is running since
+/// its containers may mount them.
+#[tauri::command]
+pub async fn clean_worktree_artifacts(worktree_path: String, targets: Vec<String>) -> Result<u64, String> {
+    let root = PathBuf::from(&worktree_path);
+    let env_name = root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .ok_or_else(|| format!("Invalid worktree path: {}", worktree_path))?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if environment_has_running_containers(&env_name) {
+            return Err(format!("Stop environment '{}' before cleaning its artifacts", env_name));
+        }
+        clean_artifacts(&root, &targets)
+    })
+    .await
+    .map_err(|e| format!("Failed to clean worktree artifacts: {}", e))?
+}
+
+/// The parts of `docker system df -v --format '{{json .}}'` used for disk usage
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase")]
+struct SystemDf {
+    #[serde(default)]
+    containers: Vec<DfEntry>,
+    #[serde(default)]
+    volumes: Vec<DfEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DfEntry {
+    /// Set for containers
+    #[serde(default)]
+    names: String,
+    /// Set for volumes
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    size: String,
+}
+
+/// Bytes in a docker size string ("0B", "12.3kB", "1.5GB"); docker uses decimal units
+fn parse_docker_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let split = size.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+    let (number, unit) = size.split_at(split);
+    let multiplier: f64 = match unit.to_ascii_uppercase().as_str() {
+        "B" => 1.0,
+        "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        _ => return None,
+    };
+    number.parse::<f64>().ok().map(|n| (n * multiplier).round() as u64)
+}
+
+/// Sizes of the named resources found in `entries`, in the order given
+fn sizes_of(entries: &[DfEntry], names: &[String], name_of: impl Fn(&DfEntry) -> &str) -> Vec<ResourceSize> {
+    names
+        .iter()
+        .map(|name| ResourceSize {
+            name: name.clone(),
+            size_bytes: entries
+                .iter()
+                .find(|entry| name_of(entry) == name)
+                .and_then(|entry| parse_docker_size(&entry.size))
+                .unwrap_or(0),
+        })
+        .collect()
+}
+
+/// Combine `docker system df -v` sizes of an environment's containers and volumes with its worktree size
+fn environment_disk_usage(df: &SystemDf, containers: &[String], volumes: &[String], worktree: Option<&Path>) -> EnvironmentDiskUsage {
+    let containers = sizes_of(&df.containers, containers, |entry| entry.names.as_str());
+    let volumes = sizes_of(&df.volumes, volumes, |entry| entry.name.as_str());
+    let worktree_bytes = worktree.map(dir_size).unwrap_or(0);
+    let total_bytes = containers.iter().chain(&volumes).map(|r| r.size_bytes).sum::<u64>() + worktree_bytes;
+    EnvironmentDiskUsage { containers, volumes, worktree_bytes, total_bytes }
+}
+
+/// Disk space an environment takes, broken down by container, volume and worktree checkout
+#[tauri::command]
+pub async fn get_environment_disk_usage(
+    state: tauri::State<'_, crate::AppState>,
+    env_name: String,
+) -> Result<EnvironmentDiskUsage, String> {
+    let env_name = env_name.to_lowercase();
+    let main_repo = state
+        .project_root
+        .lock()
+        .map_err(|e| e.to_string())?
+        .clone()
+        .ok_or("Project root not set")?;
+
+    let worktree = list_worktrees(main_repo.clone(), None, None)
+        .await?
+        .into_iter()
+        .find(|wt| wt.name == env_name)
+        .map(|wt| PathBuf::from(wt.path));
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = silent_command(container_runtime().binary())
+            .args(["system", "df", "-v", "--format", "{{json .}}"])
+            .output()
+            .map_err(|e| format!("Failed to query docker disk usage: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to query docker disk usage: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        let df: SystemDf = serde_json::from_slice(&output.stdout)
+            .map_err(|e| format!("Failed to parse docker disk usage: {}", e))?;
+
+        let containers = environment_containers(&main_repo, &env_name);
+        let volumes = project_volumes(&compose_project_for_env(&env_name));
+        Ok(environment_disk_usage(&df, &containers, &volumes, worktree.as_deref()))
+    })
+    .await
+    .map_err(|e| format!("Failed to measure disk usage: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(root: &Path, rel: &str, len: usize) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![b'x'; len]).unwrap();
+    }
+
+    #[test]
+    fn test_heavy_dirs_lists_ignored_dirs_by_size() {
+        let root = std::env::temp_dir().join(format!("ushadow-artifacts-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join(".gitignore"), "node_modules/\nbuild/\n*.log\n").unwrap();
+        write_file(&root, "src/main.rs", 100);
+        write_file(&root, "node_modules/pkg/index.js", 1000);
+        write_file(&root, "node_modules/pkg/lib/util.js", 500);
+        write_file(&root, "build/out.bin", 3000);
+        write_file(&root, "web/node_modules/dep/a.js", 200);
+        write_file(&root, "web/.cache/keep.txt", 50);
+        write_file(&root, "debug.log", 10_000);
+
+        assert_eq!(dir_size(&root.join("node_modules")), 1500);
+        assert_eq!(
+            heavy_dirs(&root),
+            vec![
+                HeavyDir { path: "build".to_string(), size_bytes: 3000 },
+                HeavyDir { path: "node_modules".to_string(), size_bytes: 1500 },
+                HeavyDir { path: Path::new("web").join("node_modules").to_string_lossy().to_string(), size_bytes: 200 },
+            ]
+        );
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_clean_artifacts_only_deletes_ignored_untracked_dirs() {
+        let root = std::env::temp_dir().join(format!("ushadow-clean-artifacts-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git").args(args).current_dir(&root).output().unwrap();
+            assert!(output.status.success(), "git {:?} failed: {}", args, String::from_utf8_lossy(&output.stderr));
+        };
+        git(&["init", "-q"]);
+        fs::write(root.join(".gitignore"), "node_modules/\nvendor/\n").unwrap();
+        write_file(&root, "src/main.rs", 100);
+        write_file(&root, "node_modules/pkg/index.js", 1000);
+        write_file(&root, "node_modules/pkg/lib/util.js", 500);
+        write_file(&root, "vendor/keep.txt", 20);
+        git(&["add", "-f", "vendor/keep.txt", "src/main.rs", ".gitignore"]);
+
+        let targets = |t: &[&str]| t.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(clean_artifacts(&root, &targets(&["src"])).unwrap_err().contains("not gitignored"));
+        assert!(clean_artifacts(&root, &targets(&["../"])).unwrap_err().contains("outside the worktree"));
+        assert!(clean_artifacts(&root, &targets(&["vendor"])).unwrap_err().contains("tracked files"));
+        // A bad target anywhere in the list means nothing is deleted
+        assert!(clean_artifacts(&root, &targets(&["node_modules", "src"])).is_err());
+        assert!(root.join("node_modules").exists());
+
+        assert_eq!(clean_artifacts(&root, &targets(&["node_modules"])).unwrap(), 1500);
+        assert!(!root.join("node_modules").exists());
+        assert!(root.join("src/main.rs").exists());
+        assert!(root.join("vendor/keep.txt").exists());
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_environment_disk_usage() {
+        let root = std::env::temp_dir().join(format!("ushadow-disk-usage-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        write_file(&root, "README.md", 1200);
+        write_file(&root, "src/main.rs", 300);
+        write_file(&root, ".git/objects/pack", 4000);
+        write_file(&root, "node_modules/pkg/index.js", 2500);
+
+        assert_eq!(parse_docker_size("0B"), Some(0));
+        assert_eq!(parse_docker_size("12.3kB"), Some(12_300));
+        assert_eq!(parse_docker_size("1.5GB"), Some(1_500_000_000));
+        assert_eq!(parse_docker_size("N/A"), None);
+
+        let df: SystemDf = serde_json::from_str(
+            r#"{"Images":[],"Containers":[
+                {"Names":"ushadow-gold-backend","Size":"2MB"},
+                {"Names":"ushadow-gold-mem0-backend-3f9a2c","Size":"9GB"},
+                {"Names":"ushadow-gold-webui","Size":"500kB"}],
+              "Volumes":[
+                {"Name":"ushadow-gold_mongo_data","Size":"1.2GB"},
+                {"Name":"ushadow-blue_mongo_data","Size":"3GB"}],
+              "BuildCache":[]}"#,
+        ).unwrap();
+        let names = |n: &[&str]| n.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let usage = environment_disk_usage(
+            &df,
+            &names(&["ushadow-gold-backend", "ushadow-gold-webui"]),
+            &names(&["ushadow-gold_mongo_data"]),
+            Some(&root),
+        );
+        assert_eq!(usage.worktree_bytes, 8000);
+        assert_eq!(
+            usage.containers,
+            vec![
+                ResourceSize { name: "ushadow-gold-backend".to_string(), size_bytes: 2_000_000 },
+                ResourceSize { name: "ushadow-gold-webui".to_string(), size_bytes: 500_000 },
+            ]
+        );
+        assert_eq!(usage.volumes, vec![ResourceSize { name: "ushadow-gold_mongo_data".to_string(), size_bytes: 1_200_000_000 }]);
+        assert_eq!(usage.total_bytes, 2_000_000 + 500_000 + 1_200_000_000 + 8000);
+
+        // No worktree on disk (e.g. an environment whose checkout was removed)
+        assert_eq!(environment_disk_usage(&df, &[], &[], None).total_bytes, 0);
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -3,6 +3,8 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use super::settings::read_launcher_settings;
+use super::utils::{expand_tilde, posix_quote};
 
 /// A single event captured from a Claude Code session hook
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,11 +16,11 @@ pub struct ClaudeSessionEvent {
     pub data: Value,         // event-specific payload
 }
 
-/// The Python hook script content, embedded at compile time.
+/// The Python hook script content, embedded at compile time; `hook_script` fills in the log path.
 /// Installed to ~/.claude/hooks/ushadow_launcher_hook.py
-const HOOK_SCRIPT: &str = r#"#!/usr/bin/env python3
+const HOOK_SCRIPT_TEMPLATE: &str = r#"#!/usr/bin/env python3
 # Ushadow Launcher - Claude Code session event logger
-# Appends hook events to ~/.claude/ushadow_sessions.jsonl
+# Appends hook events to the configured session log (default ~/.claude/ushadow_sessions.jsonl)
 import sys
 import json
 import os
@@ -79,15 +81,52 @@ elif hook_name in ("SessionStart", "SessionEnd", "SubagentStop"):
 elif hook_name == "PreCompact":
     event["data"] = {"compaction_type": data.get("compaction_type", "")}
 
-log_path = os.path.expanduser("~/.claude/ushadow_sessions.jsonl")
+log_path = os.path.expanduser(__USHADOW_SESSION_LOG__)
 os.makedirs(os.path.dirname(log_path), exist_ok=True)
 with open(log_path, "a") as f:
     f.write(json.dumps(event) + "\n")
 "#;
 
-/// Hook entries to merge into ~/.claude/settings.json
-const HOOK_COMMAND: &str =
-    "python3 ~/.claude/hooks/ushadow_launcher_hook.py";
+/// Session log used when `session_log_path` isn't set
+const DEFAULT_SESSION_LOG: &str = "~/.claude/ushadow_sessions.jsonl";
+
+/// Hook script writing to `log_path` (may start with `~`; the script expands it)
+fn hook_script(log_path: &str) -> String {
+    // A JSON string literal is also a valid Python string literal
+    let literal = serde_json::to_string(log_path).unwrap_or_else(|_| format!("{:?}", DEFAULT_SESSION_LOG));
+    HOOK_SCRIPT_TEMPLATE.replace("__USHADOW_SESSION_LOG__", &literal)
+}
+
+/// Configured session log path, unexpanded
+fn session_log_setting() -> String {
+    read_launcher_settings()
+        .ok()
+        .and_then(|s| s.session_log_path)
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_SESSION_LOG.to_string())
+}
+
+/// File name of the installed hook script (also how our settings.json entries are recognised)
+const HOOK_SCRIPT_NAME: &str = "ushadow_launcher_hook.py";
+
+/// Hook command for settings.json: an absolute script path, since the shell that runs hooks
+/// doesn't expand `~` on Windows, where the interpreter is also `python` rather than `python3`.
+/// Windows paths use forward slashes so the command works under both cmd and Git Bash.
+fn hook_command_for(windows: bool, script_path: &std::path::Path) -> String {
+    let path = script_path.to_string_lossy();
+    if windows {
+        format!("python \"{}\"", path.replace('\\', "/"))
+    } else {
+        format!("python3 {}", posix_quote(&path))
+    }
+}
+
+/// Hook command for this platform, pointing at ~/.claude/hooks/ushadow_launcher_hook.py
+fn hook_command() -> Result<String, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    let script_path = home.join(".claude").join("hooks").join(HOOK_SCRIPT_NAME);
+    Ok(hook_command_for(cfg!(target_os = "windows"), &script_path))
+}
 
 fn get_claude_hooks_dir() -> Result<PathBuf, String> {
     let home = dirs::home_dir().ok_or("Could not determine home directory")?;
@@ -109,9 +148,98 @@ fn get_claude_settings_path() -> Result<PathBuf, String> {
     Ok(claude_dir.join("settings.json"))
 }
 
-fn get_sessions_file_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
-    Ok(home.join(".claude").join("ushadow_sessions.jsonl"))
+pub(crate) fn get_sessions_file_path() -> Result<PathBuf, String> {
+    let path = expand_tilde(&session_log_setting());
+    if path.starts_with('~') {
+        return Err("Could not determine home directory".to_string());
+    }
+    Ok(PathBuf::from(path))
+}
+
+/// Hook events registered without a tool matcher
+const SIMPLE_HOOK_EVENTS: [&str; 6] = [
+    "SessionStart",
+    "SessionEnd",
+    "UserPromptSubmit",
+    "SubagentStop",
+    "Stop",
+    "Notification",
+];
+
+/// Hook events registered with a `*` tool matcher
+const WILDCARD_HOOK_EVENTS: [&str; 2] = ["PreToolUse", "PostToolUse"];
+
+/// Every event our hook should be registered for (PreCompact gets auto/manual matchers)
+fn expected_hook_events() -> impl Iterator<Item = &'static str> {
+    SIMPLE_HOOK_EVENTS
+        .into_iter()
+        .chain(WILDCARD_HOOK_EVENTS)
+        .chain(std::iter::once("PreCompact"))
+}
+
+/// Detailed install state of the launcher's Claude Code hooks
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HooksStatus {
+    pub script_path: String,
+    pub script_installed: bool,
+    /// Whether the installed script matches the one this launcher ships
+    pub script_up_to_date: bool,
+    pub settings_path: String,
+    pub registered_events: Vec<String>,
+    pub missing_events: Vec<String>,
+    /// Script present and every expected event registered
+    pub fully_installed: bool,
+}
+
+/// Build the hooks status from the installed script (None if missing), the script this launcher
+/// would install, and the parsed settings.json
+fn hooks_status_from(
+    script_path: &std::path::Path,
+    script_contents: Option<&str>,
+    expected_script: &str,
+    settings_path: &std::path::Path,
+    settings: &Value,
+) -> HooksStatus {
+    let (registered_events, missing_events): (Vec<String>, Vec<String>) = expected_hook_events()
+        .map(|event| event.to_string())
+        .partition(|event| is_already_registered(settings, event));
+
+    HooksStatus {
+        script_path: script_path.display().to_string(),
+        script_installed: script_contents.is_some(),
+        script_up_to_date: script_contents == Some(expected_script),
+        settings_path: settings_path.display().to_string(),
+        fully_installed: script_contents.is_some() && missing_events.is_empty(),
+        registered_events,
+        missing_events,
+    }
+}
+
+/// Whether a settings.json hook handler runs our script (under any interpreter/path spelling)
+fn is_launcher_handler(handler: &Value) -> bool {
+    handler["command"]
+        .as_str()
+        .map(|cmd| cmd.contains(HOOK_SCRIPT_NAME))
+        .unwrap_or(false)
+}
+
+/// Point every existing launcher handler at `command` (e.g. the old `python3 ~/...` form).
+/// Returns how many handlers were changed.
+fn migrate_hook_commands(settings: &mut Value, command: &str) -> usize {
+    let mut changed = 0;
+    if let Some(events) = settings["hooks"].as_object_mut() {
+        for entries in events.values_mut().filter_map(|v| v.as_array_mut()) {
+            for handlers in entries.iter_mut().filter_map(|e| e["hooks"].as_array_mut()) {
+                for handler in handlers.iter_mut().filter(|h| is_launcher_handler(h)) {
+                    if handler["command"].as_str() != Some(command) {
+                        handler["command"] = Value::String(command.to_string());
+                        changed += 1;
+                    }
+                }
+            }
+        }
+    }
+    changed
 }
 
 /// Check if our hook command is already in a given event's hook array
@@ -122,110 +250,109 @@ fn is_already_registered(settings: &Value, event_name: &str) -> bool {
             arr.iter().any(|entry| {
                 entry["hooks"]
                     .as_array()
-                    .map(|h| h.iter().any(|handler| handler["command"].as_str() == Some(HOOK_COMMAND)))
+                    .map(|h| h.iter().any(is_launcher_handler))
                     .unwrap_or(false)
             })
         })
         .unwrap_or(false)
 }
 
-/// Install the Ushadow launcher hooks into Claude Code's global settings.
-/// Merges our hook entries without overwriting existing user hooks.
-#[tauri::command]
-pub async fn install_claude_hooks() -> Result<String, String> {
-    // 1. Write the Python hook script
-    let hooks_dir = get_claude_hooks_dir()?;
-    let script_path = hooks_dir.join("ushadow_launcher_hook.py");
-    fs::write(&script_path, HOOK_SCRIPT)
+/// Write the hook script (logging to the configured session log) and make it executable
+fn write_hook_script(script_path: &std::path::Path) -> Result<(), String> {
+    fs::write(script_path, hook_script(&session_log_setting()))
         .map_err(|e| format!("Failed to write hook script: {}", e))?;
 
     // Make it executable on Unix
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(&script_path)
+        let mut perms = fs::metadata(script_path)
             .map_err(|e| format!("Failed to read script permissions: {}", e))?
             .permissions();
         perms.set_mode(0o755);
-        fs::set_permissions(&script_path, perms)
+        fs::set_permissions(script_path, perms)
             .map_err(|e| format!("Failed to set script permissions: {}", e))?;
     }
 
-    // 2. Read existing ~/.claude/settings.json (or start fresh)
-    let settings_path = get_claude_settings_path()?;
-    let mut settings: Value = if settings_path.exists() {
-        let raw = fs::read_to_string(&settings_path)
-            .map_err(|e| format!("Failed to read settings.json: {}", e))?;
-        serde_json::from_str(&raw).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
+    Ok(())
+}
 
-    // 3. Ensure "hooks" key is an object
+/// Add our handler to every expected event it isn't registered for, leaving existing
+/// entries (ours and the user's) untouched. Returns the events that were added.
+fn register_missing_hooks(settings: &mut Value, command: &str) -> Vec<String> {
+    // Ensure "hooks" key is an object
     if !settings.get("hooks").map(|v| v.is_object()).unwrap_or(false) {
         settings["hooks"] = serde_json::json!({});
     }
 
     let hook_handler = serde_json::json!({
         "type": "command",
-        "command": HOOK_COMMAND,
+        "command": command,
         "async": true
     });
 
-    // 4. Events without tool matchers
-    let simple_events = [
-        "SessionStart",
-        "SessionEnd",
-        "UserPromptSubmit",
-        "SubagentStop",
-        "Stop",
-        "Notification",
-    ];
-
-    for event_name in &simple_events {
+    let mut added = Vec::new();
+    for event_name in expected_hook_events() {
         if !settings["hooks"][event_name].is_array() {
             settings["hooks"][event_name] = serde_json::json!([]);
         }
-        if !is_already_registered(&settings, event_name) {
-            let new_entry = serde_json::json!({ "hooks": [hook_handler.clone()] });
-            settings["hooks"][event_name].as_array_mut().unwrap().push(new_entry);
+        if is_already_registered(settings, event_name) {
+            continue;
         }
-    }
 
-    // 5. Events with wildcard tool matcher
-    let wildcard_events = ["PreToolUse", "PostToolUse"];
-    for event_name in &wildcard_events {
-        if !settings["hooks"][event_name].is_array() {
-            settings["hooks"][event_name] = serde_json::json!([]);
-        }
-        if !is_already_registered(&settings, event_name) {
-            let new_entry = serde_json::json!({
-                "matcher": "*",
-                "hooks": [hook_handler.clone()]
-            });
-            settings["hooks"][event_name].as_array_mut().unwrap().push(new_entry);
+        let entries = settings["hooks"][event_name].as_array_mut().unwrap();
+        if WILDCARD_HOOK_EVENTS.contains(&event_name) {
+            // Tool events need a wildcard matcher
+            entries.push(serde_json::json!({ "matcher": "*", "hooks": [hook_handler.clone()] }));
+        } else if event_name == "PreCompact" {
+            // PreCompact needs separate auto/manual matcher entries
+            for compact_type in &["auto", "manual"] {
+                entries.push(serde_json::json!({ "matcher": compact_type, "hooks": [hook_handler.clone()] }));
+            }
+        } else {
+            entries.push(serde_json::json!({ "hooks": [hook_handler.clone()] }));
         }
+        added.push(event_name.to_string());
     }
 
-    // 6. PreCompact needs separate auto/manual matcher entries
-    if !settings["hooks"]["PreCompact"].is_array() {
-        settings["hooks"]["PreCompact"] = serde_json::json!([]);
-    }
-    if !is_already_registered(&settings, "PreCompact") {
-        for compact_type in &["auto", "manual"] {
-            let new_entry = serde_json::json!({
-                "matcher": compact_type,
-                "hooks": [hook_handler.clone()]
-            });
-            settings["hooks"]["PreCompact"].as_array_mut().unwrap().push(new_entry);
-        }
-    }
+    added
+}
 
-    // 7. Write back
-    let json_out = serde_json::to_string_pretty(&settings)
+/// Write ~/.claude/settings.json back out
+fn write_claude_settings(settings_path: &std::path::Path, settings: &Value) -> Result<(), String> {
+    let json_out = serde_json::to_string_pretty(settings)
         .map_err(|e| format!("Failed to serialize settings: {}", e))?;
-    fs::write(&settings_path, json_out)
-        .map_err(|e| format!("Failed to write settings.json: {}", e))?;
+    fs::write(settings_path, json_out)
+        .map_err(|e| format!("Failed to write settings.json: {}", e))
+}
+
+/// Install the Ushadow launcher hooks into Claude Code's global settings.
+/// Merges our hook entries without overwriting existing user hooks.
+#[tauri::command]
+pub async fn install_claude_hooks() -> Result<String, String> {
+    // 1. Write the Python hook script
+    let hooks_dir = get_claude_hooks_dir()?;
+    let script_path = hooks_dir.join(HOOK_SCRIPT_NAME);
+    write_hook_script(&script_path)?;
+
+    // 2. Read existing ~/.claude/settings.json (or start fresh)
+    let settings_path = get_claude_settings_path()?;
+    let mut settings: Value = if settings_path.exists() {
+        let raw = fs::read_to_string(&settings_path)
+            .map_err(|e| format!("Failed to read settings.json: {}", e))?;
+        serde_json::from_str(&raw).unwrap_or(serde_json::json!({}))
+    } else {
+        serde_json::json!({})
+    };
+
+    // 3. Register our handler for each event that doesn't have it yet, and move
+    //    handlers from older installs onto this platform's command
+    let command = hook_command()?;
+    register_missing_hooks(&mut settings, &command);
+    migrate_hook_commands(&mut settings, &command);
+
+    // 4. Write back
+    write_claude_settings(&settings_path, &settings)?;
 
     Ok(format!(
         "Hooks installed to {} and {}",
@@ -234,6 +361,114 @@ pub async fn install_claude_hooks() -> Result<String, String> {
     ))
 }
 
+/// Re-add only the missing pieces of the launcher hooks: the script if it's gone, and handler
+/// entries for events that lost theirs. Existing entries are never duplicated.
+#[tauri::command]
+pub async fn repair_claude_hooks() -> Result<String, String> {
+    let mut repaired = Vec::new();
+
+    let script_path = get_claude_hooks_dir()?.join(HOOK_SCRIPT_NAME);
+    if !script_path.exists() {
+        write_hook_script(&script_path)?;
+        repaired.push("hook script".to_string());
+    }
+
+    let settings_path = get_claude_settings_path()?;
+    let mut settings: Value = if settings_path.exists() {
+        let raw = fs::read_to_string(&settings_path)
+            .map_err(|e| format!("Failed to read settings.json: {}", e))?;
+        // Refuse to rewrite a file we can't parse rather than dropping the user's settings
+        serde_json::from_str(&raw)
+            .map_err(|e| format!("{} is not valid JSON, fix it before repairing: {}", settings_path.display(), e))?
+    } else {
+        serde_json::json!({})
+    };
+
+    let added = register_missing_hooks(&mut settings, &hook_command()?);
+    if !added.is_empty() {
+        write_claude_settings(&settings_path, &settings)?;
+        repaired.push(format!("{} hook(s): {}", added.len(), added.join(", ")));
+    }
+
+    eprintln!("[repair_claude_hooks] Repaired: {:?}", repaired);
+    if repaired.is_empty() {
+        Ok("Hooks are already fully installed".to_string())
+    } else {
+        Ok(format!("Repaired {}", repaired.join(" and ")))
+    }
+}
+
+/// Remove our handlers from every event array, dropping entries and event arrays they leave
+/// empty. Other handlers are kept in place. Returns how many handlers were removed.
+fn remove_launcher_hooks(settings: &mut Value) -> usize {
+    let events = match settings["hooks"].as_object_mut() {
+        Some(events) => events,
+        None => return 0,
+    };
+
+    let mut removed = 0;
+    for entries in events.values_mut().filter_map(|v| v.as_array_mut()) {
+        entries.retain_mut(|entry| {
+            let handlers = match entry["hooks"].as_array_mut() {
+                Some(handlers) => handlers,
+                None => return true,
+            };
+            let before = handlers.len();
+            handlers.retain(|h| !is_launcher_handler(h));
+            removed += before - handlers.len();
+            // Drop the entry only if we emptied it
+            !(handlers.is_empty() && before > 0)
+        });
+    }
+    let emptied: Vec<String> = events
+        .iter()
+        .filter(|(_, entries)| entries.as_array().map(|a| a.is_empty()).unwrap_or(false))
+        .map(|(event, _)| event.clone())
+        .collect();
+    for event in emptied {
+        events.remove(&event);
+    }
+
+    removed
+}
+
+/// Remove our hooks from `settings_path` and delete the hook script. Returns the handlers removed.
+fn uninstall_hooks_at(settings_path: &std::path::Path, script_path: &std::path::Path) -> Result<usize, String> {
+    let mut removed = 0;
+    if settings_path.exists() {
+        let raw = fs::read_to_string(settings_path)
+            .map_err(|e| format!("Failed to read settings.json: {}", e))?;
+        let mut settings: Value = serde_json::from_str(&raw)
+            .map_err(|e| format!("{} is not valid JSON: {}", settings_path.display(), e))?;
+
+        removed = remove_launcher_hooks(&mut settings);
+        if removed > 0 {
+            write_claude_settings(settings_path, &settings)?;
+        }
+    }
+
+    if script_path.exists() {
+        fs::remove_file(script_path)
+            .map_err(|e| format!("Failed to delete hook script: {}", e))?;
+    }
+
+    Ok(removed)
+}
+
+/// Remove the launcher's hook handlers from ~/.claude/settings.json and delete the hook script,
+/// leaving any other hooks untouched
+#[tauri::command]
+pub async fn uninstall_claude_hooks() -> Result<String, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    let settings_path = home.join(".claude").join("settings.json");
+    let script_path = home.join(".claude").join("hooks").join(HOOK_SCRIPT_NAME);
+
+    let removed = uninstall_hooks_at(&settings_path, &script_path)?;
+    eprintln!("[uninstall_claude_hooks] Removed {} hook handlers", removed);
+
+    Ok(format!("Removed {} hook handlers and {}", removed, script_path.display()))
+}
+
 /// Check whether the Ushadow launcher hook script is installed.
 #[tauri::command]
 pub async fn get_hooks_installed() -> Result<bool, String> {
@@ -241,10 +476,147 @@ pub async fn get_hooks_installed() -> Result<bool, String> {
     let script_path = home
         .join(".claude")
         .join("hooks")
-        .join("ushadow_launcher_hook.py");
+        .join(HOOK_SCRIPT_NAME);
     Ok(script_path.exists())
 }
 
+/// The newest whole lines of `content` that fit in `max_bytes`
+fn recent_lines_within(content: &str, max_bytes: usize) -> &str {
+    if content.len() <= max_bytes {
+        return content;
+    }
+    let start = content.len() - max_bytes;
+    if content.as_bytes()[start - 1] == b'\n' {
+        return &content[start..];
+    }
+    // Skip the partial line we landed in ('\n' is ASCII, so the slice stays on a char boundary)
+    match content.as_bytes()[start..].iter().position(|&b| b == b'\n') {
+        Some(offset) => &content[start + offset + 1..],
+        None => "",
+    }
+}
+
+/// Rotate the session log once it exceeds `max_mb`: the full log moves to `<log>.1` (replacing
+/// any previous one) and the log keeps its newest events, up to half the limit.
+/// Returns whether a rotation happened.
+#[tauri::command]
+pub async fn rotate_session_log(max_mb: u64) -> Result<bool, String> {
+    if max_mb == 0 {
+        return Err("max_mb must be at least 1".to_string());
+    }
+
+    let sessions_path = get_sessions_file_path()?;
+    let max_bytes = max_mb.saturating_mul(1024 * 1024);
+    let size = match fs::metadata(&sessions_path) {
+        Ok(meta) => meta.len(),
+        Err(_) => return Ok(false),
+    };
+    if size <= max_bytes {
+        return Ok(false);
+    }
+
+    let content = fs::read(&sessions_path)
+        .map_err(|e| format!("Failed to read sessions file: {}", e))?;
+    let content = String::from_utf8_lossy(&content);
+    let keep = recent_lines_within(&content, (max_bytes / 2) as usize);
+
+    let rotated_path = PathBuf::from(format!("{}.1", sessions_path.display()));
+    fs::rename(&sessions_path, &rotated_path)
+        .map_err(|e| format!("Failed to rotate sessions file: {}", e))?;
+    fs::write(&sessions_path, keep)
+        .map_err(|e| format!("Failed to write sessions file: {}", e))?;
+
+    eprintln!(
+        "[rotate_session_log] Rotated {} ({} bytes) to {}, kept {} bytes",
+        sessions_path.display(), size, rotated_path.display(), keep.len()
+    );
+    Ok(true)
+}
+
+/// Report which parts of the launcher hooks are installed: the script and each settings.json event
+#[tauri::command]
+pub async fn get_claude_hooks_status() -> Result<HooksStatus, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    let script_path = home.join(".claude").join("hooks").join(HOOK_SCRIPT_NAME);
+    let settings_path = home.join(".claude").join("settings.json");
+
+    let script_contents = fs::read_to_string(&script_path).ok();
+    // A missing or unparseable settings.json just means nothing is registered
+    let settings: Value = fs::read_to_string(&settings_path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or(serde_json::json!({}));
+
+    let expected_script = hook_script(&session_log_setting());
+    Ok(hooks_status_from(&script_path, script_contents.as_deref(), &expected_script, &settings_path, &settings))
+}
+
+/// How often one path (or Bash command) was touched by a tool
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PathStat {
+    pub path: String,
+    pub count: u32,
+}
+
+/// Invocation count for one tool
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ToolStat {
+    pub tool: String,
+    pub count: u32,
+    /// Most-touched paths, for Bash (its command when there's no path) and Edit
+    pub top_paths: Vec<PathStat>,
+}
+
+/// Number of paths reported per tool in `ToolStat::top_paths`
+const TOP_PATHS_PER_TOOL: usize = 5;
+
+/// Count tool invocations (one per `PreToolUse`) by tool name, busiest first
+fn tool_usage_stats(events: &[ClaudeSessionEvent]) -> Vec<ToolStat> {
+    let mut counts: HashMap<String, (u32, HashMap<String, u32>)> = HashMap::new();
+
+    for event in events.iter().filter(|e| e.event_type == "PreToolUse") {
+        let tool = match event.data["tool"].as_str().filter(|t| !t.is_empty()) {
+            Some(tool) => tool,
+            None => continue,
+        };
+        let (count, paths) = counts.entry(tool.to_string()).or_default();
+        *count += 1;
+
+        if tool == "Bash" || tool == "Edit" {
+            let path = event.data["path"]
+                .as_str()
+                .filter(|p| !p.is_empty())
+                .or_else(|| event.data["description"].as_str().filter(|_| tool == "Bash"))
+                .filter(|p| !p.is_empty());
+            if let Some(path) = path {
+                *paths.entry(path.to_string()).or_default() += 1;
+            }
+        }
+    }
+
+    let mut stats: Vec<ToolStat> = counts
+        .into_iter()
+        .map(|(tool, (count, paths))| {
+            let mut top_paths: Vec<PathStat> = paths
+                .into_iter()
+                .map(|(path, count)| PathStat { path, count })
+                .collect();
+            top_paths.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.path.cmp(&b.path)));
+            top_paths.truncate(TOP_PATHS_PER_TOOL);
+            ToolStat { tool, count, top_paths }
+        })
+        .collect();
+    stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tool.cmp(&b.tool)));
+    stats
+}
+
+/// Tool invocation counts over the last `hours`, for one worktree or across all sessions
+#[tauri::command]
+pub async fn get_tool_usage_stats(worktree_path: Option<String>, hours: i64) -> Result<Vec<ToolStat>, String> {
+    let events = read_session_events(worktree_path.as_deref(), hours)?;
+    Ok(tool_usage_stats(&events))
+}
+
 /// A single message in a Claude conversation transcript
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCallInfo {
@@ -299,14 +671,29 @@ fn extract_user_text(content: &Value) -> Option<String> {
     }
 }
 
+/// Number of transcript messages returned when no limit is given
+const DEFAULT_TRANSCRIPT_LIMIT: usize = 30;
+
 /// Read the full conversation transcript for a specific Claude session.
 /// Maps CWD to the ~/.claude/projects/{dir}/{session_id}.jsonl path.
-/// Deduplicates streaming chunks by keeping only the last entry per message ID.
+/// Only messages at or after `since` (RFC3339) with a role in `roles` are kept, then the
+/// last `limit` (default 30) are returned.
 #[tauri::command]
 pub async fn read_claude_transcript(
     session_id: String,
     cwd: String,
+    since: Option<String>,
+    roles: Option<Vec<String>>,
+    limit: Option<usize>,
 ) -> Result<Vec<TranscriptMessage>, String> {
+    let since = since
+        .map(|s| {
+            chrono::DateTime::parse_from_rfc3339(&s)
+                .map(|ts| ts.with_timezone(&chrono::Utc))
+                .map_err(|e| format!("Invalid since timestamp '{}': {}", s, e))
+        })
+        .transpose()?;
+
     let home = dirs::home_dir().ok_or("Could not determine home directory")?;
 
     // Map CWD to project dir: replace "/" with "-"
@@ -326,6 +713,42 @@ pub async fn read_claude_transcript(
     let content = fs::read_to_string(&session_file)
         .map_err(|e| format!("Failed to read transcript: {}", e))?;
 
+    Ok(filter_transcript(
+        parse_transcript(&content),
+        since,
+        roles.as_deref(),
+        limit.unwrap_or(DEFAULT_TRANSCRIPT_LIMIT),
+    ))
+}
+
+/// Keep messages at or after `since` whose role is in `roles`, then the last `limit` of them.
+/// Messages with an unparseable timestamp are kept.
+fn filter_transcript(
+    messages: Vec<TranscriptMessage>,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    roles: Option<&[String]>,
+    limit: usize,
+) -> Vec<TranscriptMessage> {
+    let mut messages: Vec<TranscriptMessage> = messages
+        .into_iter()
+        .filter(|m| roles.map(|roles| roles.contains(&m.role)).unwrap_or(true))
+        .filter(|m| match since {
+            Some(since) => chrono::DateTime::parse_from_rfc3339(&m.timestamp)
+                .map(|ts| ts.with_timezone(&chrono::Utc) >= since)
+                .unwrap_or(true),
+            None => true,
+        })
+        .collect();
+
+    if messages.len() > limit {
+        messages.drain(..messages.len() - limit);
+    }
+    messages
+}
+
+/// Parse a transcript JSONL file into messages, in order.
+/// Deduplicates streaming chunks by keeping only the last entry per message ID.
+fn parse_transcript(content: &str) -> Vec<TranscriptMessage> {
     // Deduplicate by message_id — streaming sends incremental chunks sharing the same ID,
     // so we only want the last (most complete) entry per ID, in original order.
     let mut order: Vec<String> = Vec::new();
@@ -446,45 +869,28 @@ pub async fn read_claude_transcript(
         }
     }
 
-    // Return the last 30 messages to keep the payload manageable
-    if messages.len() > 30 {
-        let skip = messages.len() - 30;
-        messages = messages.into_iter().skip(skip).collect();
-    }
-
-    Ok(messages)
+    messages
 }
 
-/// Send an approval (y) or denial (n) keystroke to the tmux pane running Claude
-/// in the given working directory. Matches panes by longest path prefix,
-/// preferring panes whose current command is claude/node.
-#[tauri::command]
-pub async fn send_claude_approval(cwd: String, approve: bool) -> Result<String, String> {
-    let output = std::process::Command::new("tmux")
-        .args([
-            "list-panes", "-a", "-F",
-            "#{pane_current_path}\t#{session_name}:#{window_index}.#{pane_index}\t#{pane_current_command}",
-        ])
-        .output()
-        .map_err(|e| format!("tmux unavailable: {}", e))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-
+/// Pick the tmux pane for `cwd` from `list-panes -F "path\ttarget\tcommand"` output.
+/// Matches panes by longest path prefix, preferring panes whose current command is claude/node.
+fn select_claude_pane(list_panes: &str, cwd: &str) -> Option<String> {
     // Normalize trailing slashes so /foo/ and /foo both match
-    let norm_cwd = cwd.trim_end_matches('/');
+    let norm_cwd = normalize_cwd(cwd);
 
     // Collect all matching panes: (path_match_len, is_claude_pane, target)
-    let mut candidates: Vec<(usize, bool, String)> = stdout
+    let mut candidates: Vec<(usize, bool, String)> = list_panes
         .lines()
         .filter_map(|line| {
             let mut parts = line.splitn(3, '\t');
-            let pane_path = parts.next()?.trim_end_matches('/');
+            let pane_path = normalize_cwd(parts.next()?);
             let pane_target = parts.next()?;
             let pane_cmd = parts.next().unwrap_or("");
 
             let is_claude = pane_cmd.contains("claude") || pane_cmd.contains("node");
 
-            if norm_cwd.starts_with(pane_path) || pane_path.starts_with(norm_cwd) {
+            // Either path may be inside the other, but only on a path boundary (/repos/gold is not /repos/golden)
+            if cwd_in_worktree(norm_cwd, pane_path) || cwd_in_worktree(pane_path, norm_cwd) {
                 Some((pane_path.len(), is_claude, pane_target.to_string()))
             } else {
                 None
@@ -495,10 +901,38 @@ pub async fn send_claude_approval(cwd: String, approve: bool) -> Result<String,
     // Sort: longest path match first, then prefer claude/node panes
     candidates.sort_by(|(la, ca, _), (lb, cb, _)| lb.cmp(la).then(cb.cmp(ca)));
 
-    let target = candidates
-        .into_iter()
-        .next()
-        .map(|(_, _, t)| t)
+    candidates.into_iter().next().map(|(_, _, t)| t)
+}
+
+/// `tmux send-keys` arguments that type `text` literally: `-l` stops words like `Enter` or `C-c`
+/// being read as key names, `--` keeps a leading `-` from being parsed as a flag, and a trailing
+/// `;` is escaped so tmux doesn't treat it as a command separator. Arguments are passed to tmux
+/// directly (no shell), so spaces survive.
+fn literal_send_keys_args(target: &str, text: &str) -> Vec<String> {
+    let text = match text.strip_suffix(';') {
+        Some(rest) => format!("{}\\;", rest),
+        None => text.to_string(),
+    };
+    ["send-keys", "-t", target, "-l", "--"]
+        .iter()
+        .map(|s| s.to_string())
+        .chain(std::iter::once(text))
+        .collect()
+}
+
+/// Type `text` into the tmux pane running Claude in the given working directory,
+/// pressing Enter afterwards when `submit` is true
+#[tauri::command]
+pub async fn send_claude_input(cwd: String, text: String, submit: bool) -> Result<String, String> {
+    let output = std::process::Command::new("tmux")
+        .args([
+            "list-panes", "-a", "-F",
+            "#{pane_current_path}\t#{session_name}:#{window_index}.#{pane_index}\t#{pane_current_command}",
+        ])
+        .output()
+        .map_err(|e| format!("tmux unavailable: {}", e))?;
+
+    let target = select_claude_pane(&String::from_utf8_lossy(&output.stdout), &cwd)
         .ok_or_else(|| {
             format!(
                 "No tmux pane found for: {}. Is claude running in tmux?",
@@ -506,30 +940,58 @@ pub async fn send_claude_approval(cwd: String, approve: bool) -> Result<String,
             )
         })?;
 
-    let key = if approve { "y" } else { "n" };
-    std::process::Command::new("tmux")
-        .args(["send-keys", "-t", &target, key, "Enter"])
-        .output()
-        .map_err(|e| format!("Failed to send key: {}", e))?;
+    if !text.is_empty() {
+        let sent = std::process::Command::new("tmux")
+            .args(literal_send_keys_args(&target, &text))
+            .output()
+            .map_err(|e| format!("Failed to send text: {}", e))?;
+        if !sent.status.success() {
+            return Err(format!("Failed to send text: {}", String::from_utf8_lossy(&sent.stderr)));
+        }
+    }
 
-    Ok(format!("Sent '{}' to {}", key, target))
+    if submit {
+        std::process::Command::new("tmux")
+            .args(["send-keys", "-t", &target, "Enter"])
+            .output()
+            .map_err(|e| format!("Failed to send key: {}", e))?;
+    }
+
+    Ok(format!("Sent '{}' to {}", text, target))
 }
 
-/// Read Claude session events from the JSONL log file.
-/// Returns all events from the last 24 hours across all projects.
+/// Send an approval (y) or denial (n) keystroke to the tmux pane running Claude
+/// in the given working directory (see `send_claude_input`).
 #[tauri::command]
-pub async fn read_claude_sessions(_project_root: String) -> Result<Vec<ClaudeSessionEvent>, String> {
-    let sessions_path = get_sessions_file_path()?;
-
-    if !sessions_path.exists() {
-        return Ok(vec![]);
-    }
+pub async fn send_claude_approval(cwd: String, approve: bool) -> Result<String, String> {
+    let key = if approve { "y" } else { "n" };
+    send_claude_input(cwd, key.to_string(), true).await
+}
 
-    let content = fs::read_to_string(&sessions_path)
-        .map_err(|e| format!("Failed to read sessions file: {}", e))?;
+/// Strip trailing path separators so `/foo/` and `/foo` compare equal
+fn normalize_cwd(path: &str) -> &str {
+    path.trim_end_matches(['/', '\\'])
+}
 
-    let cutoff = chrono::Utc::now() - chrono::Duration::hours(24);
+/// Whether an event's cwd is the worktree or somewhere inside it
+fn cwd_in_worktree(cwd: &str, worktree_path: &str) -> bool {
+    let cwd = normalize_cwd(cwd);
+    let worktree = normalize_cwd(worktree_path);
+    !worktree.is_empty()
+        && cwd
+            .strip_prefix(worktree)
+            .map(|rest| rest.is_empty() || rest.starts_with(['/', '\\']))
+            .unwrap_or(false)
+}
 
+/// Parse session log lines, keeping events newer than `cutoff` that pass `keep`,
+/// limited to the most recent `limit`
+fn parse_session_events(
+    content: &str,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    limit: usize,
+    keep: impl Fn(&ClaudeSessionEvent) -> bool,
+) -> Vec<ClaudeSessionEvent> {
     let mut events: Vec<ClaudeSessionEvent> = content
         .lines()
         .filter(|line| !line.trim().is_empty())
@@ -539,13 +1001,463 @@ pub async fn read_claude_sessions(_project_root: String) -> Result<Vec<ClaudeSes
                 .map(|ts| ts.with_timezone(&chrono::Utc) > cutoff)
                 .unwrap_or(true)
         })
+        .filter(|event| keep(event))
         .collect();
 
+    if events.len() > limit {
+        events.drain(..events.len() - limit);
+    }
+    events
+}
+
+/// Read the session log, or an empty string if it doesn't exist yet
+fn read_session_log() -> Result<String, String> {
+    let sessions_path = get_sessions_file_path()?;
+
+    if !sessions_path.exists() {
+        return Ok(String::new());
+    }
+
+    fs::read_to_string(&sessions_path)
+        .map_err(|e| format!("Failed to read sessions file: {}", e))
+}
+
+/// Session log size above which `read_claude_sessions` compacts it first
+const COMPACT_THRESHOLD_BYTES: u64 = 5 * 1024 * 1024;
+/// Events kept when `read_claude_sessions` compacts the log
+const COMPACT_KEEP_EVENTS: usize = 2000;
+
+/// Rewrite the log keeping only its newest `max_events` lines, atomically (temp file + rename).
+/// Events appended by the hook while rewriting are lost. Returns how many events were dropped.
+fn compact_session_log_at(path: &std::path::Path, max_events: usize) -> Result<usize, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(format!("Failed to read sessions file: {}", e)),
+    };
+
+    let lines: Vec<&str> = content.lines().filter(|line| !line.trim().is_empty()).collect();
+    if lines.len() <= max_events {
+        return Ok(0);
+    }
+    let dropped = lines.len() - max_events;
+    let mut kept = lines[dropped..].join("\n");
+    kept.push('\n');
+
+    let temp_path = PathBuf::from(format!("{}.tmp", path.display()));
+    fs::write(&temp_path, kept)
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+    fs::rename(&temp_path, path)
+        .map_err(|e| format!("Failed to replace sessions file: {}", e))?;
+
+    Ok(dropped)
+}
+
+/// Rewrite the session log keeping only the most recent `max_events` events.
+/// Returns how many older events were removed.
+#[tauri::command]
+pub async fn compact_claude_sessions(max_events: usize) -> Result<usize, String> {
+    let sessions_path = get_sessions_file_path()?;
+    let dropped = compact_session_log_at(&sessions_path, max_events)?;
+    eprintln!("[compact_claude_sessions] Dropped {} old events from {}", dropped, sessions_path.display());
+    Ok(dropped)
+}
+
+/// Read Claude session events from the JSONL log file.
+/// Returns all events from the last 24 hours across all projects.
+#[tauri::command]
+pub async fn read_claude_sessions(_project_root: String) -> Result<Vec<ClaudeSessionEvent>, String> {
+    // Keep the log from growing without bound; a failed compaction shouldn't block reading
+    let sessions_path = get_sessions_file_path()?;
+    if fs::metadata(&sessions_path).map(|m| m.len() > COMPACT_THRESHOLD_BYTES).unwrap_or(false) {
+        match compact_session_log_at(&sessions_path, COMPACT_KEEP_EVENTS) {
+            Ok(dropped) => eprintln!("[read_claude_sessions] Compacted session log, dropped {} events", dropped),
+            Err(e) => eprintln!("[read_claude_sessions] Session log compaction failed: {}", e),
+        }
+    }
+
+    let content = read_session_log()?;
+    let cutoff = chrono::Utc::now() - chrono::Duration::hours(24);
+
     // Most recent 500 events to keep payload manageable
-    if events.len() > 500 {
-        let skip = events.len() - 500;
-        events = events.into_iter().skip(skip).collect();
+    Ok(parse_session_events(&content, cutoff, 500, |_| true))
+}
+
+/// All session events from the last `hours`, for one worktree or across all sessions
+pub(crate) fn read_session_events(worktree_path: Option<&str>, hours: i64) -> Result<Vec<ClaudeSessionEvent>, String> {
+    let content = read_session_log()?;
+    let cutoff = chrono::Utc::now() - chrono::Duration::hours(hours.max(0));
+
+    Ok(parse_session_events(&content, cutoff, usize::MAX, |event| {
+        worktree_path.map(|path| cwd_in_worktree(&event.cwd, path)).unwrap_or(true)
+    }))
+}
+
+/// Session events from the last `hours` whose cwd is the worktree (or inside it), newest `limit`
+#[tauri::command]
+pub async fn get_session_events_for_worktree(
+    worktree_path: String,
+    hours: i64,
+    limit: usize,
+) -> Result<Vec<ClaudeSessionEvent>, String> {
+    let content = read_session_log()?;
+    let cutoff = chrono::Utc::now() - chrono::Duration::hours(hours.max(0));
+
+    Ok(parse_session_events(&content, cutoff, limit, |event| cwd_in_worktree(&event.cwd, &worktree_path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const HOOK_SCRIPT: &str = "#!/usr/bin/env python3\n# hook";
+    const LEGACY_COMMAND: &str = "python3 ~/.claude/hooks/ushadow_launcher_hook.py";
+    const COMMAND: &str = "python3 '/home/me/.claude/hooks/ushadow_launcher_hook.py'";
+
+    fn registered(events: &[&str]) -> Value {
+        let mut hooks = serde_json::Map::new();
+        for event in events {
+            hooks.insert(
+                event.to_string(),
+                serde_json::json!([{ "hooks": [{ "type": "command", "command": LEGACY_COMMAND }] }]),
+            );
+        }
+        serde_json::json!({ "hooks": hooks })
+    }
+
+    #[test]
+    fn test_hooks_status_fully_installed() {
+        let all: Vec<&str> = expected_hook_events().collect();
+        let status = hooks_status_from(Path::new("/h/hook.py"), Some(HOOK_SCRIPT), HOOK_SCRIPT, Path::new("/h/settings.json"), &registered(&all));
+
+        assert!(status.fully_installed);
+        assert!(status.script_up_to_date);
+        assert_eq!(status.registered_events.len(), 9);
+        assert!(status.missing_events.is_empty());
+    }
+
+    #[test]
+    fn test_register_missing_hooks_adds_only_missing() {
+        let mut settings = registered(&["Stop", "PreToolUse"]);
+        settings["hooks"]["Stop"].as_array_mut().unwrap()
+            .push(serde_json::json!({ "hooks": [{ "type": "command", "command": "user-hook" }] }));
+
+        let added = register_missing_hooks(&mut settings, COMMAND);
+        assert_eq!(added.len(), 7);
+        assert!(!added.contains(&"Stop".to_string()));
+        assert!(!added.contains(&"PreToolUse".to_string()));
+
+        // Existing entries (ours and the user's) are kept as-is, nothing duplicated
+        assert_eq!(settings["hooks"]["Stop"].as_array().unwrap().len(), 2);
+        assert_eq!(settings["hooks"]["PreToolUse"].as_array().unwrap().len(), 1);
+        assert_eq!(settings["hooks"]["PostToolUse"][0]["matcher"], "*");
+        assert_eq!(settings["hooks"]["PreCompact"].as_array().unwrap().len(), 2);
+
+        let status = hooks_status_from(Path::new("/h/hook.py"), Some(HOOK_SCRIPT), HOOK_SCRIPT, Path::new("/h/settings.json"), &settings);
+        assert!(status.fully_installed);
+
+        // A second pass is a no-op
+        assert!(register_missing_hooks(&mut settings, COMMAND).is_empty());
+        assert_eq!(settings["hooks"]["PreCompact"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_hooks_status_half_installed() {
+        // Settings entries present but the script was deleted
+        let status = hooks_status_from(Path::new("/h/hook.py"), None, HOOK_SCRIPT, Path::new("/h/settings.json"), &registered(&["Stop", "PreToolUse"]));
+        assert!(!status.fully_installed);
+        assert!(!status.script_installed);
+        assert_eq!(status.registered_events, vec!["Stop", "PreToolUse"]);
+        assert!(status.missing_events.contains(&"SessionStart".to_string()));
+        assert!(status.missing_events.contains(&"PreCompact".to_string()));
+
+        // Script present (but an older version) and settings.json without our handler
+        let mut settings = registered(&[]);
+        settings["hooks"]["Stop"] = serde_json::json!([{ "hooks": [{ "type": "command", "command": "other-hook" }] }]);
+        let status = hooks_status_from(Path::new("/h/hook.py"), Some("#!/old"), HOOK_SCRIPT, Path::new("/h/settings.json"), &settings);
+        assert!(status.script_installed);
+        assert!(!status.script_up_to_date);
+        assert!(status.registered_events.is_empty());
+        assert_eq!(status.missing_events.len(), 9);
+    }
+
+    #[test]
+    fn test_hook_command_per_platform() {
+        assert_eq!(
+            hook_command_for(false, Path::new("/home/me/.claude/hooks/ushadow_launcher_hook.py")),
+            "python3 '/home/me/.claude/hooks/ushadow_launcher_hook.py'"
+        );
+        assert_eq!(
+            hook_command_for(false, Path::new("/home/o'neil/.claude/hooks/ushadow_launcher_hook.py")),
+            "python3 '/home/o'\\''neil/.claude/hooks/ushadow_launcher_hook.py'"
+        );
+        assert_eq!(
+            hook_command_for(true, Path::new(r"C:\Users\Jane Doe\.claude\hooks\ushadow_launcher_hook.py")),
+            "python \"C:/Users/Jane Doe/.claude/hooks/ushadow_launcher_hook.py\""
+        );
+
+        let command = hook_command().unwrap();
+        assert!(command.contains(HOOK_SCRIPT_NAME));
+        assert!(!command.contains('~'));
+        #[cfg(target_os = "windows")]
+        assert!(command.starts_with("python \""));
+        #[cfg(not(target_os = "windows"))]
+        assert!(command.starts_with("python3 '/"));
+    }
+
+    #[test]
+    fn test_migrate_legacy_hook_commands() {
+        let mut settings = registered(&["Stop", "PreToolUse"]);
+        settings["hooks"]["Stop"].as_array_mut().unwrap()
+            .push(serde_json::json!({ "hooks": [{ "type": "command", "command": "user-hook" }] }));
+
+        // Legacy entries still count as registered, so nothing is duplicated
+        assert!(is_already_registered(&settings, "Stop"));
+        assert_eq!(migrate_hook_commands(&mut settings, COMMAND), 2);
+        assert_eq!(settings["hooks"]["Stop"][0]["hooks"][0]["command"], COMMAND);
+        assert_eq!(settings["hooks"]["Stop"][1]["hooks"][0]["command"], "user-hook");
+        assert_eq!(migrate_hook_commands(&mut settings, COMMAND), 0);
+    }
+
+    #[test]
+    fn test_hook_script_uses_configured_log_path() {
+        let script = hook_script("~/logs/claude events.jsonl");
+        assert!(script.contains(r#"log_path = os.path.expanduser("~/logs/claude events.jsonl")"#));
+        assert!(!script.contains("__USHADOW_SESSION_LOG__"));
+
+        // Windows paths and quotes are escaped as a valid string literal
+        let script = hook_script(r#"C:\logs\"x".jsonl"#);
+        assert!(script.contains(r#"os.path.expanduser("C:\\logs\\\"x\".jsonl")"#));
+        assert!(hook_script(DEFAULT_SESSION_LOG).contains(r#"expanduser("~/.claude/ushadow_sessions.jsonl")"#));
+    }
+
+    #[test]
+    fn test_recent_lines_within_keeps_newest_whole_lines() {
+        let log = "{\"n\":1}\n{\"n\":2}\n{\"n\":3}\n";
+        assert_eq!(recent_lines_within(log, 100), log);
+        assert_eq!(recent_lines_within(log, 16), "{\"n\":2}\n{\"n\":3}\n");
+        assert_eq!(recent_lines_within(log, 17), "{\"n\":2}\n{\"n\":3}\n");
+        assert_eq!(recent_lines_within(log, 15), "{\"n\":3}\n");
+        assert_eq!(recent_lines_within(log, 3), "");
+        // Multi-byte characters around the cut don't split a char
+        assert_eq!(recent_lines_within("é\nüü\nok\n", 5), "ok\n");
+    }
+
+    #[test]
+    fn test_uninstall_removes_only_launcher_hooks() {
+        let dir = std::env::temp_dir().join(format!("ushadow-hooks-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let settings_path = dir.join("settings.json");
+        let script_path = dir.join(HOOK_SCRIPT_NAME);
+        fs::write(&script_path, HOOK_SCRIPT).unwrap();
+
+        let mut settings = serde_json::json!({ "model": "opus", "hooks": {} });
+        register_missing_hooks(&mut settings, COMMAND);
+        // A foreign handler sharing an entry with ours, and a foreign entry of its own
+        settings["hooks"]["Stop"][0]["hooks"].as_array_mut().unwrap()
+            .push(serde_json::json!({ "type": "command", "command": "say done" }));
+        settings["hooks"]["PreToolUse"].as_array_mut().unwrap()
+            .push(serde_json::json!({ "matcher": "Bash", "hooks": [{ "type": "command", "command": "audit.sh" }] }));
+        fs::write(&settings_path, serde_json::to_string_pretty(&settings).unwrap()).unwrap();
+
+        // PreCompact has two entries, the other 8 events one each
+        assert_eq!(uninstall_hooks_at(&settings_path, &script_path).unwrap(), 10);
+        assert!(!script_path.exists());
+
+        let settings: Value = serde_json::from_str(&fs::read_to_string(&settings_path).unwrap()).unwrap();
+        assert_eq!(settings["model"], "opus");
+        let hooks = settings["hooks"].as_object().unwrap();
+        let mut events: Vec<&String> = hooks.keys().collect();
+        events.sort();
+        assert_eq!(events, vec!["PreToolUse", "Stop"]);
+        assert_eq!(settings["hooks"]["Stop"], serde_json::json!([{ "hooks": [{ "type": "command", "command": "say done" }] }]));
+        assert_eq!(settings["hooks"]["PreToolUse"][0]["matcher"], "Bash");
+        assert_eq!(settings["hooks"]["PreToolUse"].as_array().unwrap().len(), 1);
+
+        // Running again is a no-op
+        assert_eq!(uninstall_hooks_at(&settings_path, &script_path).unwrap(), 0);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    fn event_line(cwd: &str, timestamp: &str, event_type: &str) -> String {
+        serde_json::json!({
+            "event_type": event_type, "session_id": "s1", "cwd": cwd, "timestamp": timestamp, "data": {}
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_cwd_in_worktree() {
+        assert!(cwd_in_worktree("/repos/gold", "/repos/gold"));
+        assert!(cwd_in_worktree("/repos/gold/", "/repos/gold"));
+        assert!(cwd_in_worktree("/repos/gold", "/repos/gold/"));
+        assert!(cwd_in_worktree("/repos/gold/backend", "/repos/gold"));
+        assert!(!cwd_in_worktree("/repos/golden", "/repos/gold"));
+        assert!(!cwd_in_worktree("/repos", "/repos/gold"));
+        assert!(!cwd_in_worktree("/repos/gold", ""));
+    }
+
+    #[test]
+    fn test_parse_session_events_filters_by_worktree_and_age() {
+        let now = chrono::Utc::now();
+        let recent = (now - chrono::Duration::minutes(5)).to_rfc3339();
+        let old = (now - chrono::Duration::hours(30)).to_rfc3339();
+        let content = [
+            event_line("/repos/gold", &old, "SessionStart"),
+            event_line("/repos/gold/", &recent, "UserPromptSubmit"),
+            event_line("/repos/golden", &recent, "Stop"),
+            "not json".to_string(),
+            event_line("/repos/gold/webui", &recent, "PreToolUse"),
+            event_line("/repos/gold", &recent, "Stop"),
+        ]
+        .join("\n");
+
+        let cutoff = now - chrono::Duration::hours(24);
+        let in_gold = |e: &ClaudeSessionEvent| cwd_in_worktree(&e.cwd, "/repos/gold");
+
+        let events = parse_session_events(&content, cutoff, 100, in_gold);
+        let types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, vec!["UserPromptSubmit", "PreToolUse", "Stop"]);
+
+        // The limit keeps the newest events
+        let events = parse_session_events(&content, cutoff, 2, in_gold);
+        let types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+        assert_eq!(types, vec!["PreToolUse", "Stop"]);
+
+        assert_eq!(parse_session_events(&content, cutoff, 100, |_| true).len(), 4);
     }
 
-    Ok(events)
+    #[test]
+    fn test_tool_usage_stats() {
+        let tool_event = |event_type: &str, data: Value| ClaudeSessionEvent {
+            event_type: event_type.to_string(),
+            session_id: "s1".to_string(),
+            cwd: "/repos/gold".to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            data,
+        };
+        let events = vec![
+            tool_event("PreToolUse", serde_json::json!({ "tool": "Edit", "path": "src/a.rs" })),
+            tool_event("PostToolUse", serde_json::json!({ "tool": "Edit" })),
+            tool_event("PreToolUse", serde_json::json!({ "tool": "Edit", "path": "src/b.rs" })),
+            tool_event("PreToolUse", serde_json::json!({ "tool": "Edit", "path": "src/a.rs" })),
+            tool_event("PreToolUse", serde_json::json!({ "tool": "Bash", "description": "cargo test", "path": "" })),
+            tool_event("PreToolUse", serde_json::json!({ "tool": "Read", "path": "README.md" })),
+            tool_event("PreToolUse", serde_json::json!({ "tool": "Bash", "description": "cargo test" })),
+            tool_event("PreToolUse", serde_json::json!({ "tool": "" })),
+            tool_event("UserPromptSubmit", serde_json::json!({ "message": "hi" })),
+        ];
+
+        let stats = tool_usage_stats(&events);
+        let summary: Vec<(&str, u32)> = stats.iter().map(|s| (s.tool.as_str(), s.count)).collect();
+        assert_eq!(summary, vec![("Edit", 3), ("Bash", 2), ("Read", 1)]);
+
+        assert_eq!(stats[0].top_paths, vec![
+            PathStat { path: "src/a.rs".to_string(), count: 2 },
+            PathStat { path: "src/b.rs".to_string(), count: 1 },
+        ]);
+        assert_eq!(stats[1].top_paths, vec![PathStat { path: "cargo test".to_string(), count: 2 }]);
+        // Paths are only tracked for Bash and Edit
+        assert!(stats[2].top_paths.is_empty());
+    }
+
+    #[test]
+    fn test_compact_session_log_keeps_newest_in_order() {
+        let dir = std::env::temp_dir().join(format!("ushadow-compact-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sessions.jsonl");
+
+        let log: String = (0..1000)
+            .map(|i| event_line("/repos/gold", "2026-01-01T00:00:00Z", &format!("E{}", i)) + "\n")
+            .collect();
+        fs::write(&path, log).unwrap();
+
+        assert_eq!(compact_session_log_at(&path, 100).unwrap(), 900);
+
+        let content = fs::read_to_string(&path).unwrap();
+        let events = parse_session_events(&content, chrono::Utc::now() - chrono::Duration::days(36500), usize::MAX, |_| true);
+        let types: Vec<String> = events.into_iter().map(|e| e.event_type).collect();
+        let expected: Vec<String> = (900..1000).map(|i| format!("E{}", i)).collect();
+        assert_eq!(types, expected);
+        assert!(!dir.join("sessions.jsonl.tmp").exists());
+
+        // Already small enough, or missing: nothing to do
+        assert_eq!(compact_session_log_at(&path, 100).unwrap(), 0);
+        assert_eq!(compact_session_log_at(&dir.join("missing.jsonl"), 10).unwrap(), 0);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_select_claude_pane_scoring() {
+        let panes = "/repos\tmain:0.0\tzsh\n\
+                     /repos/gold\tush-gold:1.0\tzsh\n\
+                     /repos/gold/\tush-gold:1.1\tnode\n\
+                     /repos/golden\tush-golden:1.0\tclaude\n\
+                     /other\tx:0.0\tclaude\n";
+
+        // Longest matching path wins, claude/node panes preferred on a tie
+        assert_eq!(select_claude_pane(panes, "/repos/gold/"), Some("ush-gold:1.1".to_string()));
+        // A deeper cwd still matches its worktree pane
+        assert_eq!(select_claude_pane(panes, "/repos/gold/backend"), Some("ush-gold:1.1".to_string()));
+        assert_eq!(select_claude_pane(panes, "/repos/golden"), Some("ush-golden:1.0".to_string()));
+        assert_eq!(select_claude_pane(panes, "/repos/silver"), Some("main:0.0".to_string()));
+        assert_eq!(select_claude_pane("", "/repos/gold"), None);
+    }
+
+    #[test]
+    fn test_literal_send_keys_args() {
+        let args = |text: &str| literal_send_keys_args("ush-gold:1.0", text);
+
+        assert_eq!(args("Use option 2 please"), vec!["send-keys", "-t", "ush-gold:1.0", "-l", "--", "Use option 2 please"]);
+        // Key names are typed, not pressed
+        assert_eq!(args("Enter").last().unwrap(), "Enter");
+        assert_eq!(args("C-c")[3..], ["-l", "--", "C-c"]);
+        // Leading dashes and trailing semicolons are kept literal
+        assert_eq!(args("-n").last().unwrap(), "-n");
+        assert_eq!(args("run it;").last().unwrap(), "run it\\;");
+        assert_eq!(args("a;b").last().unwrap(), "a;b");
+    }
+
+    const TRANSCRIPT_FIXTURE: &str = r#"{"type":"user","uuid":"u1","timestamp":"2026-03-01T10:00:00Z","message":{"role":"user","content":"Add a login page"}}
+{"type":"assistant","timestamp":"2026-03-01T10:00:05Z","message":{"id":"a1","content":[{"type":"text","text":"Looking"}]}}
+{"type":"assistant","timestamp":"2026-03-01T10:00:06Z","message":{"id":"a1","content":[{"type":"text","text":"Looking at the routes"},{"type":"tool_use","name":"Read","input":{"file_path":"src/routes.ts"}}]}}
+{"type":"summary","summary":"ignored"}
+{"type":"user","uuid":"u2","timestamp":"2026-03-01T11:00:00Z","message":{"role":"user","content":"Use the existing form component"}}
+{"type":"assistant","timestamp":"2026-03-01T11:00:10Z","message":{"id":"a2","content":[{"type":"text","text":"Done"}]}}
+"#;
+
+    #[test]
+    fn test_parse_transcript_dedups_streaming_chunks() {
+        let messages = parse_transcript(TRANSCRIPT_FIXTURE);
+        let ids: Vec<&str> = messages.iter().map(|m| m.message_id.as_str()).collect();
+        assert_eq!(ids, vec!["u1", "a1", "u2", "a2"]);
+        // The last chunk for a1 wins
+        assert_eq!(messages[1].text.as_deref(), Some("Looking at the routes"));
+        assert_eq!(messages[1].tools.len(), 1);
+    }
+
+    #[test]
+    fn test_filter_transcript_by_time_and_role() {
+        let since = |s: &str| Some(chrono::DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&chrono::Utc));
+        let ids = |messages: Vec<TranscriptMessage>| -> Vec<String> { messages.into_iter().map(|m| m.message_id).collect() };
+        let assistant = vec!["assistant".to_string()];
+
+        let all = filter_transcript(parse_transcript(TRANSCRIPT_FIXTURE), None, None, DEFAULT_TRANSCRIPT_LIMIT);
+        assert_eq!(ids(all), vec!["u1", "a1", "u2", "a2"]);
+
+        let recent = filter_transcript(parse_transcript(TRANSCRIPT_FIXTURE), since("2026-03-01T11:00:00Z"), None, 30);
+        assert_eq!(ids(recent), vec!["u2", "a2"]);
+
+        let replies = filter_transcript(parse_transcript(TRANSCRIPT_FIXTURE), None, Some(&assistant), 30);
+        assert_eq!(ids(replies), vec!["a1", "a2"]);
+
+        let both = filter_transcript(parse_transcript(TRANSCRIPT_FIXTURE), since("2026-03-01T10:30:00+00:00"), Some(&assistant), 30);
+        assert_eq!(ids(both), vec!["a2"]);
+
+        // The limit applies after filtering and keeps the newest
+        let last = filter_transcript(parse_transcript(TRANSCRIPT_FIXTURE), None, Some(&assistant), 1);
+        assert_eq!(ids(last), vec!["a2"]);
+    }
 }
@@ -2,6 +2,7 @@ use crate::config::LauncherConfig;
 use crate::models::{EnvironmentStatus, InfraService, UshadowEnvironment};
 use serde_json::Value;
 use super::utils::silent_command;
+use super::container_runtime::container_runtime;
 
 /// Information about a discovered container
 #[derive(Debug, Clone)]
@@ -35,7 +36,7 @@ pub fn discover_environment_containers(
     };
 
     // Query Docker for containers with this compose project label
-    let output = silent_command("docker")
+    let output = silent_command(container_runtime().binary())
         .args([
             "ps",
             "-a",
@@ -71,7 +72,7 @@ pub fn discover_environment_containers(
 
 /// Inspect a single container to extract service name, status, and ports
 fn inspect_container(container_name: &str) -> Result<ContainerInfo, String> {
-    let output = silent_command("docker")
+    let output = silent_command(container_runtime().binary())
         .args(["inspect", container_name])
         .output()
         .map_err(|e| format!("Failed to inspect container: {}", e))?;
@@ -165,7 +166,7 @@ fn extract_port_mappings(container: &Value) -> Result<Vec<PortMapping>, String>
 pub fn discover_infrastructure_containers(
     config: &LauncherConfig,
 ) -> Result<Vec<InfraService>, String> {
-    let output = silent_command("docker")
+    let output = silent_command(container_runtime().binary())
         .args([
             "ps",
             "-a",
@@ -211,6 +212,7 @@ pub fn discover_infrastructure_containers(
                 display_name: capitalize(&info.service_name),
                 running: info.status == "running",
                 ports: ports_str,
+                health: None,
             });
         }
     }
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use super::settings::read_launcher_settings;
+use super::utils::shell_command;
+
+/// Container engine used for all container/compose invocations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+}
+
+impl ContainerRuntime {
+    /// CLI binary name
+    pub fn binary(&self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+        }
+    }
+
+    /// Build a runtime command string, e.g. `command("ps -a")` -> "docker ps -a"
+    pub fn command(&self, args: &str) -> String {
+        format!("{} {}", self.binary(), args)
+    }
+
+    /// Build a compose command string, e.g. "docker compose -p infra down" / "podman compose -p infra down"
+    pub fn compose_command(&self, args: &str) -> String {
+        format!("{} compose {}", self.binary(), args)
+    }
+}
+
+static RUNTIME: OnceLock<ContainerRuntime> = OnceLock::new();
+
+/// Probe PATH for a working binary (uses a login shell so Homebrew/Podman Desktop paths are visible)
+fn binary_available(binary: &str) -> bool {
+    shell_command(&format!("{} --version", binary))
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Auto-detect the runtime: prefer Docker, fall back to Podman, default to Docker
+fn detect_runtime() -> ContainerRuntime {
+    if binary_available("docker") {
+        ContainerRuntime::Docker
+    } else if binary_available("podman") {
+        ContainerRuntime::Podman
+    } else {
+        ContainerRuntime::Docker
+    }
+}
+
+/// Get the container runtime, resolved once from launcher settings or auto-detected
+pub fn container_runtime() -> ContainerRuntime {
+    *RUNTIME.get_or_init(|| {
+        let configured = read_launcher_settings()
+            .ok()
+            .and_then(|s| s.container_runtime);
+
+        let runtime = configured.unwrap_or_else(detect_runtime);
+        eprintln!(
+            "[container_runtime] Using {} ({})",
+            runtime.binary(),
+            if configured.is_some() { "from settings" } else { "auto-detected" }
+        );
+        runtime
+    })
+}
+
+/// Get the active container runtime name ("docker" or "podman")
+#[tauri::command]
+pub async fn get_container_runtime() -> Result<String, String> {
+    Ok(container_runtime().binary().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_docker_command_strings() {
+        let rt = ContainerRuntime::Docker;
+        assert_eq!(rt.binary(), "docker");
+        assert_eq!(rt.command("ps -a --format '{{.Names}}'"), "docker ps -a --format '{{.Names}}'");
+        assert_eq!(rt.command("network create infra-network"), "docker network create infra-network");
+        assert_eq!(rt.compose_command("-p infra down"), "docker compose -p infra down");
+    }
+
+    #[test]
+    fn test_podman_command_strings() {
+        let rt = ContainerRuntime::Podman;
+        assert_eq!(rt.binary(), "podman");
+        assert_eq!(rt.command("start a b"), "podman start a b");
+        assert_eq!(rt.command("inspect ushadow-gold-backend"), "podman inspect ushadow-gold-backend");
+        assert_eq!(rt.compose_command("-p ushadow-gold down"), "podman compose -p ushadow-gold down");
+    }
+
+    #[test]
+    fn test_runtime_setting_serialization() {
+        assert_eq!(serde_json::to_string(&ContainerRuntime::Podman).unwrap(), "\"podman\"");
+        let rt: ContainerRuntime = serde_json::from_str("\"docker\"").unwrap();
+        assert_eq!(rt, ContainerRuntime::Docker);
+    }
+}
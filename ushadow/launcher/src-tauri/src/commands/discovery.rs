@@ -1,11 +1,16 @@
-use std::collections::{HashMap, HashSet};
-use std::sync::Mutex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, UNIX_EPOCH};
-use crate::models::{DiscoveryResult, EnvironmentStatus, InfraService, UshadowEnvironment, WorktreeInfo};
+use crate::models::{ContainerInspectTiming, DiscoveryResult, DiscoveryTimings, EnvRef, EnvironmentStatus, HealthState, InfraService, PortConflict, ServiceDef, TrayEnvItem, UshadowEnvironment, WorktreeInfo};
 use super::prerequisites::{check_docker, check_tailscale};
 use super::utils::silent_command;
+use super::container_runtime::container_runtime;
 use super::worktree::{list_worktrees, get_colors_for_name};
 use super::bundled;
+use super::settings::read_launcher_settings;
+use super::docker::parse_published_host_ports;
+use super::status_history::record_environment_statuses;
+use tracing::{info, warn};
 
 /// Infrastructure service patterns (fallback when compose file not available)
 const INFRA_PATTERNS: &[(&str, &str)] = &[
@@ -36,7 +41,7 @@ fn get_display_name(service_name: &str) -> String {
     }.to_string()
 }
 
-/// A resolved infra service pattern derived from the compose file
+/// A resolved infra service pattern (from the compose file, launcher settings or INFRA_PATTERNS)
 struct InfraPattern {
     /// The container name to match against `docker ps` output
     container_name: String,
@@ -44,6 +49,62 @@ struct InfraPattern {
     service_name: String,
     /// Human-readable display name
     display_name: String,
+    /// Declared in the compose file, so `container_name` is exact and fuzzy matching stays narrow
+    from_compose: bool,
+}
+
+impl InfraPattern {
+    /// Pattern for a service without a known container name
+    fn fuzzy(service_name: &str, display_name: &str) -> Self {
+        InfraPattern {
+            container_name: service_name.to_string(),
+            service_name: service_name.to_string(),
+            display_name: display_name.to_string(),
+            from_compose: false,
+        }
+    }
+
+    /// Whether a `docker ps` container name belongs to this service
+    fn matches(&self, name: &str) -> bool {
+        let service = &self.service_name;
+        // Primary match: exact container name; fallback: suffix match for default compose names
+        name == self.container_name
+            || name.ends_with(&format!("-{}", service))
+            || name.ends_with(&format!("_{}", service))
+            || (!self.from_compose
+                && (name.ends_with(&format!("-{}-1", service)) || name.contains(&format!("_{}", service))))
+    }
+}
+
+/// Infra patterns in precedence order: compose file, then user config, then built-in defaults.
+/// Built-in defaults are only used when there is no compose file; user config display names
+/// override the others.
+fn merge_infra_patterns(compose: Vec<InfraPattern>, configured: &HashMap<String, String>) -> Vec<InfraPattern> {
+    let use_builtin = compose.is_empty();
+    let mut patterns = compose;
+
+    let mut configured: Vec<(&String, &String)> = configured.iter().collect();
+    configured.sort();
+    for (service, display_name) in configured {
+        match patterns.iter_mut().find(|p| &p.service_name == service) {
+            Some(pattern) => pattern.display_name = display_name.clone(),
+            None => patterns.push(InfraPattern::fuzzy(service, display_name)),
+        }
+    }
+
+    if use_builtin {
+        for (service, display_name) in INFRA_PATTERNS {
+            if !patterns.iter().any(|p| p.service_name == *service) {
+                patterns.push(InfraPattern::fuzzy(service, display_name));
+            }
+        }
+    }
+    patterns
+}
+
+/// Infra patterns configured in launcher settings
+fn configured_infra_patterns() -> HashMap<String, String> {
+    read_launcher_settings().map(|s| s.infra_patterns).unwrap_or_default()
 }
 
 /// Load infra service patterns from docker-compose.infra.yml.
@@ -106,12 +167,233 @@ fn load_compose_infra_patterns(project_root: &str) -> Vec<InfraPattern> {
 
         let display_name = get_display_name(&service_name);
 
-        patterns.push(InfraPattern { container_name, service_name, display_name });
+        patterns.push(InfraPattern { container_name, service_name, display_name, from_compose: true });
     }
 
     patterns
 }
 
+/// Published host port of each running infra service (compose service name -> first host port),
+/// matched from `docker ps` Names/Ports lines against the infra compose patterns
+fn infra_ports_from_ps(ps_output: &str, patterns: &[InfraPattern]) -> Vec<(String, u16)> {
+    ps_output
+        .lines()
+        .filter_map(|line| {
+            let (name, ports) = line.split_once('\t')?;
+            let pattern = patterns.iter().find(|p| p.container_name == name.trim())?;
+            let port = *parse_published_host_ports(ports).first()?;
+            Some((pattern.service_name.clone(), port))
+        })
+        .collect()
+}
+
+/// Host ports of the running infra services declared in the project's infra compose file
+pub(crate) fn running_infra_ports(project_root: &str) -> Result<Vec<(String, u16)>, String> {
+    let patterns = load_compose_infra_patterns(project_root);
+    if patterns.is_empty() {
+        return Err("No infra services found in docker-compose.infra.yml".to_string());
+    }
+
+    let output = silent_command(container_runtime().binary())
+        .args(["ps", "--format", "{{.Names}}\t{{.Ports}}"])
+        .output()
+        .map_err(|e| format!("Failed to list containers: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Failed to list containers: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    Ok(infra_ports_from_ps(&String::from_utf8_lossy(&output.stdout), &patterns))
+}
+
+/// Per-environment service names used before compose/settings lookup existed
+pub(crate) const DEFAULT_ENV_SERVICES: &[&str] = &["backend", "webui", "frontend", "worker", "tailscale"];
+
+/// Compose files Docker Compose loads by default, in precedence order
+const COMPOSE_FILE_NAMES: &[&str] = &["compose.yaml", "compose.yml", "docker-compose.yaml", "docker-compose.yml"];
+
+/// The main compose file in `root` plus its override file, if any
+fn default_compose_files(root: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let main = match COMPOSE_FILE_NAMES.iter().map(|name| root.join(name)).find(|p| p.exists()) {
+        Some(main) => main,
+        None => return Vec::new(),
+    };
+
+    // compose.yaml -> compose.override.yaml, docker-compose.yml -> docker-compose.override.yml
+    let file_name = main.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let (stem, ext) = file_name.rsplit_once('.').unwrap_or((file_name, "yml"));
+    let override_file = root.join(format!("{}.override.{}", stem, ext));
+
+    let mut files = vec![main];
+    if override_file.exists() {
+        files.push(override_file);
+    }
+    files
+}
+
+/// Port mapping as written in compose: short form strings/numbers or the long `target`/`published` form
+fn compose_port_spec(port: &serde_yaml::Value) -> Option<String> {
+    if let Some(s) = port.as_str() {
+        return Some(s.to_string());
+    }
+    if let Some(n) = port.as_u64() {
+        return Some(n.to_string());
+    }
+
+    let scalar = |key: &str| {
+        port.get(key).and_then(|v| v.as_str().map(String::from).or_else(|| v.as_u64().map(|n| n.to_string())))
+    };
+    let target = scalar("target")?;
+    Some(match scalar("published") {
+        Some(published) => format!("{}:{}", published, target),
+        None => target,
+    })
+}
+
+/// Parse service definitions from the default compose files in `root`, following `include:` entries.
+/// Services redefined by later files (overrides) are merged into the first definition.
+pub(crate) fn load_compose_service_defs(root: &std::path::Path) -> Vec<ServiceDef> {
+    use serde_yaml::Value;
+
+    fn collect(path: &std::path::Path, services: &mut Vec<ServiceDef>, depth: usize) {
+        let yaml: Value = match std::fs::read_to_string(path).ok().and_then(|c| serde_yaml::from_str(&c).ok()) {
+            Some(v) => v,
+            None => return,
+        };
+
+        if let Some(map) = yaml.get("services").and_then(|s| s.as_mapping()) {
+            for (key, config) in map {
+                let name = match key.as_str() {
+                    Some(name) => name,
+                    None => continue,
+                };
+                let image = config.get("image").and_then(|i| i.as_str()).map(String::from);
+                let ports: Vec<String> = config.get("ports")
+                    .and_then(|p| p.as_sequence())
+                    .map(|seq| seq.iter().filter_map(compose_port_spec).collect())
+                    .unwrap_or_default();
+                let profiles: Vec<String> = config.get("profiles")
+                    .and_then(|p| p.as_sequence())
+                    .map(|seq| seq.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                    .unwrap_or_default();
+
+                match services.iter_mut().find(|s| s.name == name) {
+                    Some(existing) => {
+                        if image.is_some() {
+                            existing.image = image;
+                        }
+                        for port in ports {
+                            if !existing.ports.contains(&port) {
+                                existing.ports.push(port);
+                            }
+                        }
+                        for profile in profiles {
+                            if !existing.profiles.contains(&profile) {
+                                existing.profiles.push(profile);
+                            }
+                        }
+                    }
+                    None => services.push(ServiceDef {
+                        name: name.to_string(),
+                        image,
+                        ports,
+                        profiles,
+                        file: path.to_string_lossy().to_string(),
+                    }),
+                }
+            }
+        }
+
+        // Guard against include cycles
+        if depth >= 4 {
+            return;
+        }
+
+        let base = path.parent().unwrap_or_else(|| std::path::Path::new("."));
+        if let Some(includes) = yaml.get("include").and_then(|i| i.as_sequence()) {
+            for entry in includes {
+                // Short form `- compose/backend.yml` or long form `- path: compose/backend.yml`
+                let include = entry.as_str().or_else(|| entry.get("path").and_then(|p| p.as_str()));
+                if let Some(include) = include {
+                    collect(&base.join(include), services, depth + 1);
+                }
+            }
+        }
+    }
+
+    let mut services = Vec::new();
+    for file in default_compose_files(root) {
+        collect(&file, &mut services, 0);
+    }
+    services
+}
+
+/// Collect service names from the project's compose file, following `include:` entries.
+/// Returns an empty vec if the file is missing or unparseable.
+pub(crate) fn load_compose_environment_services(project_root: &str) -> Vec<String> {
+    load_compose_service_defs(std::path::Path::new(project_root))
+        .into_iter()
+        .map(|s| s.name)
+        .collect()
+}
+
+/// Services an environment declares in its worktree's compose files (what should be running)
+#[tauri::command]
+pub async fn get_environment_services(worktree_path: String) -> Result<Vec<ServiceDef>, String> {
+    let root = std::path::Path::new(&worktree_path);
+    if !root.is_dir() {
+        return Err(format!("Worktree not found: {}", worktree_path));
+    }
+    if default_compose_files(root).is_empty() {
+        return Err(format!("No compose file found in {}", worktree_path));
+    }
+
+    Ok(load_compose_service_defs(root))
+}
+
+/// Profiles declared by any service, sorted and deduplicated
+pub(crate) fn compose_profiles(defs: &[ServiceDef]) -> Vec<String> {
+    defs.iter()
+        .flat_map(|def| def.profiles.iter().cloned())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Compose profiles declared across a worktree's services, for opting into optional services at start
+#[tauri::command]
+pub async fn get_compose_profiles(worktree_path: String) -> Result<Vec<String>, String> {
+    Ok(compose_profiles(&get_environment_services(worktree_path).await?))
+}
+
+/// Service names that make up an environment: launcher settings override, otherwise the
+/// built-in list extended with the project's compose services
+pub(crate) fn environment_services(project_root: Option<&str>) -> Vec<String> {
+    if let Some(configured) = read_launcher_settings().ok().and_then(|s| s.services) {
+        if !configured.is_empty() {
+            return configured;
+        }
+    }
+
+    let mut services: Vec<String> = DEFAULT_ENV_SERVICES.iter().map(|s| s.to_string()).collect();
+    if let Some(root) = project_root {
+        for service in load_compose_environment_services(root) {
+            if !services.contains(&service) {
+                services.push(service);
+            }
+        }
+    }
+    services
+}
+
+/// Whether a container is an environment's own service container: `ushadow-{env}-{service}`.
+/// Compose-generated service containers (`ushadow-{env}-{svc}-backend-1`) don't match.
+fn is_environment_container(name: &str, services: &[String]) -> bool {
+    name.strip_prefix("ushadow-")
+        .and_then(|rest| rest.split_once('-'))
+        .map(|(env, service)| !env.is_empty() && services.iter().any(|s| s == service))
+        .unwrap_or(false)
+}
+
 /// Read ports from environment's .env file
 /// Returns (backend_port, webui_port)
 fn read_env_ports(worktree_path: &str) -> (Option<u16>, Option<u16>) {
@@ -145,7 +427,7 @@ fn read_env_ports(worktree_path: &str) -> (Option<u16>, Option<u16>) {
 
 /// Determine base branch from branch name suffix
 /// Branch names follow pattern: envname/branchname-basebranch (e.g., rouge/myfeature-dev)
-fn determine_base_branch(_repo_path: &str, branch: &str) -> Option<String> {
+pub(crate) fn determine_base_branch(_repo_path: &str, branch: &str) -> Option<String> {
     // Parse suffix from branch name
     if branch.ends_with("-dev") {
         Some("dev".to_string())
@@ -161,6 +443,63 @@ fn determine_base_branch(_repo_path: &str, branch: &str) -> Option<String> {
     }
 }
 
+/// Max concurrent `docker inspect` lookups during discovery
+const INSPECT_CONCURRENCY: usize = 8;
+
+/// Run a blocking function over `items` with at most `limit` calls in flight.
+/// Results are returned in input order (None if a task panicked).
+async fn run_blocking_bounded<T, R, F>(items: Vec<T>, limit: usize, f: F) -> Vec<Option<R>>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> R + Send + Sync + 'static,
+{
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(limit.max(1)));
+    let f = Arc::new(f);
+    let mut handles = Vec::with_capacity(items.len());
+
+    for item in items {
+        let permit = match semaphore.clone().acquire_owned().await {
+            Ok(permit) => permit,
+            Err(_) => break,
+        };
+        let f = f.clone();
+        handles.push(tauri::async_runtime::spawn_blocking(move || {
+            let result = f(item);
+            drop(permit);
+            result
+        }));
+    }
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(handle.await.ok());
+    }
+    results
+}
+
+/// Ports declared by more than one environment, given each environment's declared ports
+fn find_port_conflicts(declared: &[(String, Vec<u16>)]) -> Vec<PortConflict> {
+    let mut by_port: BTreeMap<u16, Vec<String>> = BTreeMap::new();
+    for (env_name, ports) in declared {
+        for port in ports {
+            let envs = by_port.entry(*port).or_default();
+            if !envs.contains(env_name) {
+                envs.push(env_name.clone());
+            }
+        }
+    }
+
+    by_port
+        .into_iter()
+        .filter(|(_, envs)| envs.len() > 1)
+        .map(|(port, mut envs)| {
+            envs.sort();
+            PortConflict { port, envs }
+        })
+        .collect()
+}
+
 /// Environment container info
 struct EnvContainerInfo {
     backend_port: Option<u16>,
@@ -168,11 +507,71 @@ struct EnvContainerInfo {
     has_running: bool,
     working_dir: Option<String>,
     created_at: Option<i64>,
+    health: Option<HealthState>,
+}
+
+/// Healthcheck state from a `docker ps` status such as `Up 2 hours (unhealthy)`.
+/// None when the container isn't up or has no healthcheck.
+pub(crate) fn parse_health_status(status: &str) -> Option<HealthState> {
+    if !status.starts_with("Up") {
+        return None;
+    }
+    if status.contains("(unhealthy)") {
+        Some(HealthState::Unhealthy)
+    } else if status.contains("(health: starting)") {
+        Some(HealthState::Starting)
+    } else if status.contains("(healthy)") {
+        Some(HealthState::Healthy)
+    } else {
+        None
+    }
+}
+
+/// Milliseconds since `start`
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// Run `f`, adding its duration to `total_ms`
+fn timed<R>(total_ms: &mut f64, f: impl FnOnce() -> R) -> R {
+    let start = Instant::now();
+    let result = f();
+    *total_ms += elapsed_ms(start);
+    result
 }
 
 // Cache tailscale status for 10 seconds to avoid slow repeated checks
 static TAILSCALE_CACHE: Mutex<Option<(bool, Instant)>> = Mutex::new(None);
 
+/// Recent discovery results per main repo
+#[derive(Default)]
+struct DiscoveryCache {
+    entries: HashMap<String, (DiscoveryResult, Instant)>,
+}
+
+impl DiscoveryCache {
+    /// The result cached for `main_repo`, if it is younger than `ttl`
+    fn get(&self, main_repo: &str, ttl: Duration) -> Option<DiscoveryResult> {
+        self.entries
+            .get(main_repo)
+            .filter(|(_, cached_at)| cached_at.elapsed() < ttl)
+            .map(|(result, _)| result.clone())
+    }
+
+    fn insert(&mut self, main_repo: String, result: DiscoveryResult) {
+        self.entries.insert(main_repo, (result, Instant::now()));
+    }
+}
+
+static DISCOVERY_CACHE: Mutex<Option<DiscoveryCache>> = Mutex::new(None);
+
+/// Drop cached discovery results so the next discovery queries docker and git again.
+/// The UI calls this after creating, deleting, starting or stopping an environment.
+#[tauri::command]
+pub fn invalidate_discovery_cache() {
+    *DISCOVERY_CACHE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
 /// Discover Ushadow environments and infrastructure (running and stopped)
 #[tauri::command]
 pub async fn discover_environments(state: tauri::State<'_, crate::AppState>) -> Result<DiscoveryResult, String> {
@@ -191,6 +590,53 @@ pub async fn discover_environments_with_config(
     main_repo: Option<String>,
     _worktrees_dir: Option<String>,
 ) -> Result<DiscoveryResult, String> {
+    let ttl = Duration::from_millis(read_launcher_settings().unwrap_or_default().discovery_cache_ttl_ms);
+    let key = main_repo.clone().unwrap_or_default();
+
+    let cached = DISCOVERY_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|cache| cache.get(&key, ttl));
+    if let Some(result) = cached {
+        return Ok(result);
+    }
+
+    let result = discover_environments_timed(main_repo, &mut DiscoveryTimings::default()).await?;
+    DISCOVERY_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(DiscoveryCache::default)
+        .insert(key, result.clone());
+    Ok(result)
+}
+
+/// Run one discovery pass and report how long each phase took.
+/// Opt-in profiling aid; the regular discovery commands don't return timings.
+#[tauri::command]
+pub async fn benchmark_discovery(main_repo: String) -> Result<DiscoveryTimings, String> {
+    let mut timings = DiscoveryTimings::default();
+    let start = Instant::now();
+    let result = discover_environments_timed(Some(main_repo), &mut timings).await?;
+    timings.total_ms = elapsed_ms(start);
+    timings.environment_count = result.environments.len();
+
+    info!(
+        target: "benchmark_discovery",
+        "Discovery of {} environments took {:.1}ms (worktrees {:.1}ms, docker ps {:.1}ms, inspects {:.1}ms, env reads {:.1}ms, tailscale {:.1}ms)",
+        timings.environment_count, timings.total_ms, timings.worktree_list_ms, timings.docker_ps_ms,
+        timings.container_inspect_ms, timings.env_reads_ms, timings.tailscale_probes_ms
+    );
+    Ok(timings)
+}
+
+/// Discovery, recording per-phase durations into `timings`
+async fn discover_environments_timed(
+    main_repo: Option<String>,
+    timings: &mut DiscoveryTimings,
+) -> Result<DiscoveryResult, String> {
+    let phase_start = Instant::now();
+
     // Check prerequisites
     let (docker_installed, docker_running, _) = check_docker();
 
@@ -215,6 +661,7 @@ pub async fn discover_environments_with_config(
             ok
         }
     };
+    timings.prerequisites_ms = elapsed_ms(phase_start);
 
     let docker_ok = docker_installed && docker_running;
 
@@ -223,16 +670,18 @@ pub async fn discover_environments_with_config(
     let main_repo = main_repo.unwrap_or_else(|| format!("{}/repos/ushadow", home));
 
     // Get worktrees first (source of truth for environments)
-    let worktrees = match list_worktrees(main_repo.clone()).await {
+    let phase_start = Instant::now();
+    let worktrees = match list_worktrees(main_repo.clone(), None, None).await {
         Ok(wt) => {
-            eprintln!("[discovery] Found {} worktrees from {}", wt.len(), main_repo);
+            info!(target: "discovery", "Found {} worktrees from {}", wt.len(), main_repo);
             wt
         }
         Err(e) => {
-            eprintln!("[discovery] Failed to list worktrees from {}: {}", main_repo, e);
+            warn!(target: "discovery", "Failed to list worktrees from {}: {}", main_repo, e);
             Vec::new()
         }
     };
+    timings.worktree_list_ms = elapsed_ms(phase_start);
 
     // Build a map of worktree name -> worktree info
     let mut worktree_map: HashMap<String, WorktreeInfo> = HashMap::new();
@@ -240,20 +689,24 @@ pub async fn discover_environments_with_config(
         worktree_map.insert(wt.name.clone(), wt);
     }
 
-    // Load infra service definitions from compose file (dynamic), fall back to static list
-    let compose_patterns = load_compose_infra_patterns(&main_repo);
-    let use_compose = !compose_patterns.is_empty();
+    // Infra service definitions: compose file (dynamic), user config, then the static list
+    let infra_patterns = merge_infra_patterns(load_compose_infra_patterns(&main_repo), &configured_infra_patterns());
 
     // Infrastructure and environment maps
     let mut infrastructure = Vec::new();
     let mut found_infra = HashSet::new();
     let mut env_map: HashMap<String, EnvContainerInfo> = HashMap::new();
+    // (env_name, backend container) pairs that still need `docker inspect`, in `docker ps` order
+    let mut inspect_queue: Vec<(String, String)> = Vec::new();
+    let env_services = environment_services(Some(&main_repo));
 
     if docker_ok {
-        let output = silent_command("docker")
+        let phase_start = Instant::now();
+        let output = silent_command(container_runtime().binary())
             .args(["ps", "-a", "--format", "{{.Names}}|{{.Status}}|{{.Ports}}"])
             .output()
             .map_err(|e| format!("Failed to get containers: {}", e))?;
+        timings.docker_ps_ms = elapsed_ms(phase_start);
 
         if output.status.success() {
             let stdout = String::from_utf8_lossy(&output.stdout);
@@ -268,70 +721,31 @@ pub async fn discover_environments_with_config(
                 let status = parts[1].trim();
                 let ports = if parts.len() > 2 { Some(parts[2].trim().to_string()) } else { None };
                 let is_running = status.contains("Up");
-
-                // Match against infra services from compose file (or static fallback)
-                if use_compose {
-                    for pattern in &compose_patterns {
-                        // Primary match: exact container_name from compose
-                        // Fallback: fuzzy suffix match for containers without explicit container_name
-                        let is_match = name == pattern.container_name
-                            || name.ends_with(&format!("-{}", pattern.service_name))
-                            || name.ends_with(&format!("_{}", pattern.service_name));
-
-                        if is_match {
-                            if !found_infra.contains(&pattern.service_name) {
-                                found_infra.insert(pattern.service_name.clone());
-                                infrastructure.push(InfraService {
-                                    name: pattern.service_name.clone(),
-                                    display_name: pattern.display_name.clone(),
-                                    running: is_running,
-                                    ports: ports.clone(),
-                                });
-                            } else if is_running {
-                                if let Some(service) = infrastructure.iter_mut().find(|s| s.name == pattern.service_name) {
-                                    service.running = true;
-                                    if ports.is_some() { service.ports = ports.clone(); }
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    // Static fallback (no compose file found)
-                    for (pattern, display_name) in INFRA_PATTERNS {
-                        let is_match = name == *pattern
-                            || name.ends_with(&format!("-{}", pattern))
-                            || name.ends_with(&format!("-{}-1", pattern))
-                            || name.ends_with(&format!("_{}", pattern))
-                            || name.contains(&format!("_{}", pattern));
-
-                        if is_match {
-                            if !found_infra.contains(*pattern) {
-                                found_infra.insert(pattern.to_string());
-                                infrastructure.push(InfraService {
-                                    name: pattern.to_string(),
-                                    display_name: display_name.to_string(),
-                                    running: is_running,
-                                    ports: ports.clone(),
-                                });
-                            } else if is_running {
-                                if let Some(service) = infrastructure.iter_mut().find(|s| s.name == *pattern) {
-                                    service.running = true;
-                                }
-                            }
+                let health = parse_health_status(status);
+
+                // Match against infra services (compose file, user config, static fallback)
+                for pattern in infra_patterns.iter().filter(|p| p.matches(name)) {
+                    if !found_infra.contains(&pattern.service_name) {
+                        found_infra.insert(pattern.service_name.clone());
+                        infrastructure.push(InfraService {
+                            name: pattern.service_name.clone(),
+                            display_name: pattern.display_name.clone(),
+                            running: is_running,
+                            ports: ports.clone(),
+                            health,
+                        });
+                    } else if is_running {
+                        if let Some(service) = infrastructure.iter_mut().find(|s| s.name == pattern.service_name) {
+                            service.running = true;
+                            service.health = health;
+                            if ports.is_some() { service.ports = ports.clone(); }
                         }
                     }
                 }
 
-                // Check Ushadow environment containers (backend, webui, frontend)
-                // Environment containers: ushadow-{env}-backend (3 parts)
-                // Service containers: ushadow-{env}-servicename-backend-hash (5+ parts)
-                // So filter by checking exact part count
-                let parts: Vec<&str> = name.split('-').collect();
-                let is_environment_container = parts.len() == 3
-                    && parts[0] == "ushadow"
-                    && matches!(parts[2], "backend" | "frontend" | "webui" | "tailscale");
-
-                if is_environment_container {
+                // Check Ushadow environment containers (ushadow-{env}-{service})
+                // Service containers (ushadow-{env}-servicename-backend-hash) are skipped
+                if is_environment_container(name, &env_services) {
                     let env_name = extract_env_name(name);
 
                     let entry = env_map.entry(env_name.clone()).or_insert(EnvContainerInfo {
@@ -340,9 +754,12 @@ pub async fn discover_environments_with_config(
                         has_running: false,
                         working_dir: None,
                         created_at: None,
+                        health: None,
                     });
 
                     entry.containers.push(name.to_string());
+                    // The environment is only as healthy as its least healthy container
+                    entry.health = entry.health.max(health);
 
                     if is_running {
                         entry.has_running = true;
@@ -351,32 +768,47 @@ pub async fn discover_environments_with_config(
                     // Extract backend port if this is the backend container
                     if name.contains("backend") && is_running {
                         if let Some(ref port_str) = ports {
-                            if let Some(port) = extract_port(port_str) {
+                            if let Some(port) = extract_port(port_str, Some(BACKEND_CONTAINER_PORT)) {
                                 entry.backend_port = Some(port);
                             }
                         }
                     }
 
-                    // Get working directory and creation time from container if we don't have it yet
-                    if name.contains("backend") {
-                        if entry.working_dir.is_none() {
-                            if let Some(wd) = get_container_working_dir(name) {
-                                entry.working_dir = Some(wd);
-                            }
-                        }
-                        if entry.created_at.is_none() {
-                            if let Some(timestamp) = get_container_created_at(name) {
-                                entry.created_at = Some(timestamp);
-                            }
-                        }
+                    // Queue the backend container for working dir / creation time lookup
+                    if name.contains("backend") && !inspect_queue.iter().any(|(env, _)| env == &env_name) {
+                        inspect_queue.push((env_name.clone(), name.to_string()));
                     }
                 }
             }
         }
     }
 
+    // Inspect backend containers concurrently; apply results in queue order so env_map is deterministic
+    let phase_start = Instant::now();
+    let inspected = run_blocking_bounded(inspect_queue, INSPECT_CONCURRENCY, |(env_name, container)| {
+        let start = Instant::now();
+        let working_dir = get_container_working_dir(&container);
+        let created_at = get_container_created_at(&container);
+        let timing = ContainerInspectTiming { container, duration_ms: elapsed_ms(start) };
+        (env_name, working_dir, created_at, timing)
+    }).await;
+    timings.container_inspect_ms = elapsed_ms(phase_start);
+
+    for (env_name, working_dir, created_at, timing) in inspected.into_iter().flatten() {
+        timings.container_inspects.push(timing);
+        if let Some(entry) = env_map.get_mut(&env_name) {
+            if entry.working_dir.is_none() {
+                entry.working_dir = working_dir;
+            }
+            if entry.created_at.is_none() {
+                entry.created_at = created_at;
+            }
+        }
+    }
+
     // Build environment list from worktrees, enriched with Docker status
     let mut environments = Vec::new();
+    let mut declared_ports: Vec<(String, Vec<u16>)> = Vec::new();
 
     for (name, wt) in &worktree_map {
         let (primary, _dark) = get_colors_for_name(name);
@@ -385,10 +817,11 @@ pub async fn discover_environments_with_config(
         let created_at = get_directory_created_at(&wt.path);
 
         // Read ports from .env file (source of truth)
-        let (env_backend_port, env_webui_port) = read_env_ports(&wt.path);
+        let (env_backend_port, env_webui_port) = timed(&mut timings.env_reads_ms, || read_env_ports(&wt.path));
+        declared_ports.push((name.clone(), env_backend_port.into_iter().chain(env_webui_port).collect()));
 
         // Check if this environment has Docker containers
-        let (status, backend_port, webui_port, localhost_url, tailscale_url, tailscale_active, containers, docker_created_at) =
+        let (status, backend_port, webui_port, localhost_url, tailscale_url, tailscale_active, containers, docker_created_at, health) =
             if let Some(info) = env_map.remove(name) {
                 // Use ports from .env file, fall back to Docker detection
                 let port = env_backend_port.or(info.backend_port).unwrap_or(8000);
@@ -397,7 +830,7 @@ pub async fn discover_environments_with_config(
                 let (url, ts_url, ts_active) = if info.has_running {
                     let localhost = wp.map(|p| format!("http://localhost:{}", p))
                         .unwrap_or_else(|| format!("http://localhost:{}", port));
-                    let ts = get_tailscale_url(name, port);
+                    let ts = timed(&mut timings.tailscale_probes_ms, || get_tailscale_url(name, port));
                     let active = ts.is_some();
                     (Some(localhost), ts, active)
                 } else {
@@ -410,10 +843,10 @@ pub async fn discover_environments_with_config(
                     EnvironmentStatus::Stopped
                 };
 
-                (env_status, Some(port), wp, url, ts_url, ts_active, info.containers, info.created_at)
+                (env_status, Some(port), wp, url, ts_url, ts_active, info.containers, info.created_at, info.health)
             } else {
                 // No Docker containers yet, but we have .env ports
-                (EnvironmentStatus::Available, env_backend_port, env_webui_port, None, None, false, Vec::new(), None)
+                (EnvironmentStatus::Available, env_backend_port, env_webui_port, None, None, false, Vec::new(), None, None)
             };
 
         let running = status == EnvironmentStatus::Running || status == EnvironmentStatus::Partial;
@@ -444,6 +877,7 @@ pub async fn discover_environments_with_config(
             is_worktree: true,
             created_at: final_created_at,
             base_branch,
+            health,
         });
     }
 
@@ -456,7 +890,7 @@ pub async fn discover_environments_with_config(
         let (localhost_url, tailscale_url, tailscale_active) = if info.has_running {
             let localhost = wp.map(|p| format!("http://localhost:{}", p))
                 .unwrap_or_else(|| format!("http://localhost:{}", port));
-            let ts = get_tailscale_url(&name, port);
+            let ts = timed(&mut timings.tailscale_probes_ms, || get_tailscale_url(&name, port));
             let active = ts.is_some();
             (Some(localhost), ts, active)
         } else {
@@ -505,6 +939,7 @@ pub async fn discover_environments_with_config(
             is_worktree: false,
             created_at: info.created_at,
             base_branch,
+            health: info.health,
         });
     }
 
@@ -518,9 +953,15 @@ pub async fn discover_environments_with_config(
         }
     });
 
-    eprintln!("[discovery] Returning {} environments:", environments.len());
+    let statuses: Vec<(String, EnvironmentStatus)> = environments
+        .iter()
+        .map(|env| (env.name.clone(), env.status.clone()))
+        .collect();
+    record_environment_statuses(&statuses);
+
+    info!(target: "discovery", "Returning {} environments:", environments.len());
     for env in &environments {
-        eprintln!("[discovery]   - {} (path: {:?}, branch: {:?})", env.name, env.path, env.branch);
+        info!(target: "discovery", "  - {} (path: {:?}, branch: {:?})", env.name, env.path, env.branch);
     }
 
     Ok(DiscoveryResult {
@@ -528,9 +969,222 @@ pub async fn discover_environments_with_config(
         environments,
         docker_ok,
         tailscale_ok,
+        port_conflicts: find_port_conflicts(&declared_ports),
     })
 }
 
+/// Build tray items from `docker ps --format '{{.Names}}|{{.Ports}}'` output (running containers only)
+fn parse_tray_environments(ps_output: &str, services: &[String]) -> Vec<TrayEnvItem> {
+    // env name -> (backend port, webui port)
+    let mut envs: Vec<(String, Option<u16>, Option<u16>)> = Vec::new();
+
+    for line in ps_output.lines() {
+        let mut fields = line.splitn(2, '|');
+        let name = fields.next().unwrap_or("").trim();
+        let ports = fields.next().unwrap_or("").trim();
+
+        // Same environment container shape as discovery: ushadow-{env}-{service}
+        if !is_environment_container(name, services) {
+            continue;
+        }
+        let service = name["ushadow-".len()..].split_once('-').map(|(_, svc)| svc).unwrap_or("");
+
+        let env_name = extract_env_name(name);
+        let idx = match envs.iter().position(|(n, _, _)| n == &env_name) {
+            Some(i) => i,
+            None => {
+                envs.push((env_name, None, None));
+                envs.len() - 1
+            }
+        };
+
+        match service {
+            "backend" => envs[idx].1 = envs[idx].1.or(extract_port(ports, Some(BACKEND_CONTAINER_PORT))),
+            "webui" | "frontend" => envs[idx].2 = envs[idx].2.or(extract_port(ports, None)),
+            _ => {}
+        }
+    }
+
+    let mut items: Vec<TrayEnvItem> = envs
+        .into_iter()
+        .map(|(name, backend_port, webui_port)| {
+            let (color, _dark) = get_colors_for_name(&name);
+            let webui_port = webui_port.or_else(|| backend_port.filter(|p| *p >= 8000).map(|p| p - 5000));
+            let url = webui_port.or(backend_port).map(|p| format!("http://localhost:{}", p));
+            TrayEnvItem { name, color, backend_port, url }
+        })
+        .collect();
+
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    items
+}
+
+/// Running environments for the tray menu: a single `docker ps`, no worktree scan or Tailscale probing
+pub fn collect_tray_environments(project_root: Option<String>) -> Result<Vec<TrayEnvItem>, String> {
+    let output = silent_command(container_runtime().binary())
+        .args(["ps", "--format", "{{.Names}}|{{.Ports}}"])
+        .output()
+        .map_err(|e| format!("Failed to get containers: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to get containers: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let services = environment_services(project_root.as_deref());
+    Ok(parse_tray_environments(&String::from_utf8_lossy(&output.stdout), &services))
+}
+
+/// Get running environments for the system tray menu
+#[tauri::command]
+pub async fn get_tray_environments(state: tauri::State<'_, crate::AppState>) -> Result<Vec<TrayEnvItem>, String> {
+    let project_root = state.project_root.lock().map_err(|e| e.to_string())?.clone();
+    tauri::async_runtime::spawn_blocking(move || collect_tray_environments(project_root))
+        .await
+        .map_err(|e| format!("Tray environment lookup failed: {}", e))?
+}
+
+/// Environment name from a compose project label: `ushadow` -> default env, `ushadow-{env}` -> env
+pub(crate) fn env_name_from_compose_project(project: &str) -> Option<String> {
+    if project == "ushadow" {
+        return Some("ushadow".to_string());
+    }
+    project
+        .strip_prefix("ushadow-")
+        .filter(|env| !env.is_empty())
+        .map(|env| env.to_string())
+}
+
+/// Whether `rest` (a container name without its `ushadow-[{env}-]` prefix) is one of `services`,
+/// optionally with a compose replica number (`backend`, `backend-1`)
+pub(crate) fn is_service_instance(rest: &str, services: &[String]) -> bool {
+    services.iter().any(|service| {
+        rest == service
+            || rest
+                .strip_prefix(service.as_str())
+                .and_then(|n| n.strip_prefix('-'))
+                .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+/// Environment name from a container name, matching the service suffix so dashed env names
+/// (`ushadow-my-feature-backend` -> `my-feature`) work. Default env containers are
+/// `ushadow-{service}[-N]`. Returns None for non-ushadow containers.
+pub(crate) fn env_name_from_container_name(name: &str, services: &[String]) -> Option<String> {
+    let rest = name.strip_prefix("ushadow-")?;
+
+    if is_service_instance(rest, services) {
+        return Some("ushadow".to_string());
+    }
+
+    services
+        .iter()
+        .filter_map(|service| rest.strip_suffix(service.as_str())?.strip_suffix('-'))
+        .filter(|env| !env.is_empty())
+        // Longest matching service wins (`gold-mobile-backend` is env `gold`, not `gold-mobile`)
+        .min_by_key(|env| env.len())
+        .map(|env| env.to_string())
+        .or_else(|| Some(extract_env_name(name)))
+}
+
+/// Pick the worktree for an environment: the one at the container's mounted path, else by name
+fn match_worktree<'a>(worktrees: &'a [WorktreeInfo], env_name: &str, mount_path: Option<&str>) -> Option<&'a WorktreeInfo> {
+    let normalize = |p: &str| p.trim_end_matches(['/', '\\']).to_string();
+    mount_path
+        .and_then(|mount| {
+            let mount = normalize(mount);
+            worktrees.iter().find(|wt| normalize(&wt.path) == mount)
+        })
+        .or_else(|| worktrees.iter().find(|wt| wt.name == env_name))
+}
+
+/// Find which environment (and worktree) a container belongs to; None for non-ushadow containers
+#[tauri::command]
+pub async fn get_environment_for_container(
+    state: tauri::State<'_, crate::AppState>,
+    container_name: String,
+) -> Result<Option<EnvRef>, String> {
+    let project_root = state.project_root.lock().map_err(|e| e.to_string())?.clone();
+
+    let label = silent_command(container_runtime().binary())
+        .args(["inspect", &container_name, "--format", "{{index .Config.Labels \"com.docker.compose.project\"}}"])
+        .output()
+        .map_err(|e| format!("Failed to inspect container: {}", e))?;
+    if !label.status.success() {
+        return Err(format!("Container not found: {}", container_name));
+    }
+    let compose_project = String::from_utf8_lossy(&label.stdout).trim().to_string();
+    let compose_project = (!compose_project.is_empty() && compose_project != "<no value>").then_some(compose_project);
+
+    // The compose label is authoritative; the name is the fallback for containers started without compose
+    let services = environment_services(project_root.as_deref());
+    let env_name = match compose_project.as_deref().and_then(env_name_from_compose_project) {
+        Some(env) => env,
+        None => match env_name_from_container_name(&container_name, &services) {
+            Some(env) => env,
+            None => return Ok(None),
+        },
+    };
+
+    let mount_path = get_container_working_dir(&container_name);
+    let worktrees = match &project_root {
+        Some(root) => list_worktrees(root.clone(), None, None).await.unwrap_or_default(),
+        None => Vec::new(),
+    };
+    let worktree = match_worktree(&worktrees, &env_name, mount_path.as_deref());
+
+    Ok(Some(EnvRef {
+        worktree_path: worktree.map(|wt| wt.path.clone()).or(mount_path),
+        branch: worktree.map(|wt| wt.branch.clone()),
+        env_name,
+        compose_project,
+    }))
+}
+
+/// Environment whose `.env` backend or webui port is `port`
+fn env_for_port_in_worktrees(
+    worktrees: &[WorktreeInfo],
+    port: u16,
+    read_ports: impl Fn(&str) -> (Option<u16>, Option<u16>),
+) -> Option<String> {
+    worktrees
+        .iter()
+        .find(|wt| {
+            let (backend, webui) = read_ports(&wt.path);
+            backend == Some(port) || webui == Some(port)
+        })
+        .map(|wt| wt.name.clone())
+}
+
+/// Environment of the ushadow container publishing host `port`, from `{name}\t{ports}` rows
+fn env_for_published_port(ps_lines: &str, port: u16, services: &[String]) -> Option<String> {
+    ps_lines
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .find(|(_, ports)| parse_published_host_ports(ports).contains(&port))
+        .and_then(|(name, _)| env_name_from_container_name(name.trim(), services))
+}
+
+/// Find which environment owns a port: `.env` ports across worktrees first, then the
+/// host ports published by running containers
+#[tauri::command]
+pub async fn get_environment_by_port(main_repo: String, port: u16) -> Result<Option<String>, String> {
+    let worktrees = list_worktrees(main_repo.clone(), None, None).await?;
+    if let Some(env) = env_for_port_in_worktrees(&worktrees, port, read_env_ports) {
+        return Ok(Some(env));
+    }
+
+    let output = silent_command(container_runtime().binary())
+        .args(["ps", "--format", "{{.Names}}\t{{.Ports}}"])
+        .output()
+        .map_err(|e| format!("Failed to list containers: {}", e))?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let services = environment_services(Some(&main_repo));
+    Ok(env_for_published_port(&String::from_utf8_lossy(&output.stdout), port, &services))
+}
+
 /// Extract environment name from container name
 /// Examples:
 ///   ushadow-gold-backend -> gold (colored environment)
@@ -553,13 +1207,38 @@ fn extract_env_name(container_name: &str) -> String {
     }
 }
 
-/// Extract port from Docker port string (e.g., "0.0.0.0:8000->8000/tcp" -> Some(8000))
-fn extract_port(port_str: &str) -> Option<u16> {
-    // Format: "0.0.0.0:8000->8000/tcp" or "8000/tcp"
-    port_str.split("->")
-        .next()
-        .and_then(|s| s.split(':').nth(1))
-        .and_then(|s| s.parse().ok())
+/// Port the backend listens on inside its container
+const BACKEND_CONTAINER_PORT: u16 = 8000;
+
+/// Published `(container_port, host_port)` pairs from a `docker ps` Ports column, in order.
+/// Handles IPv4 and IPv6 hosts ("0.0.0.0:8010->8000/tcp", "[::]:8010->8000/tcp", ":::8010->8000/tcp")
+/// and a missing host ("8010->8000/tcp"); unpublished ports ("8000/tcp") and ranges are skipped.
+pub(crate) fn extract_published_ports(port_str: &str) -> Vec<(u16, u16)> {
+    let mut mappings = Vec::new();
+    for mapping in port_str.split(',') {
+        let (host, container) = match mapping.trim().split_once("->") {
+            Some(parts) => parts,
+            None => continue,
+        };
+        let host_port = host.rsplit(':').next().and_then(|p| p.trim().parse().ok());
+        let container_port = container.split('/').next().and_then(|p| p.trim().parse().ok());
+        if let (Some(container_port), Some(host_port)) = (container_port, host_port) {
+            // IPv4 and IPv6 bindings of the same port show up as separate entries
+            if !mappings.contains(&(container_port, host_port)) {
+                mappings.push((container_port, host_port));
+            }
+        }
+    }
+    mappings
+}
+
+/// Host port publishing `container_port`, or the first published port when it's None or not published
+fn extract_port(port_str: &str, container_port: Option<u16>) -> Option<u16> {
+    let published = extract_published_ports(port_str);
+    container_port
+        .and_then(|wanted| published.iter().find(|(container, _)| *container == wanted))
+        .or(published.first())
+        .map(|(_, host)| *host)
 }
 
 /// Get Tailscale URL by querying the backend service's leader info endpoint
@@ -577,28 +1256,109 @@ fn get_tailscale_url(_env_name: &str, port: u16) -> Option<String> {
         return None;
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_leader_info_url(&output.stdout)
+}
 
-    // Parse JSON to extract ushadow_api_url
-    for line in stdout.split(',') {
-        if line.contains("ushadow_api_url") {
-            if let Some(start) = line.find("https://") {
-                let rest = &line[start..];
-                if let Some(end) = rest.find('"') {
-                    return Some(rest[..end].to_string());
-                }
-            }
-        }
+/// Fields of the backend's leader info response that discovery uses
+#[derive(serde::Deserialize)]
+struct LeaderInfo {
+    #[serde(default)]
+    ushadow_api_url: Option<String>,
+}
+
+/// Tailscale (https) URL from a leader info response body; None if it isn't valid JSON
+fn parse_leader_info_url(body: &[u8]) -> Option<String> {
+    let info: LeaderInfo = serde_json::from_slice(body).ok()?;
+    info.ushadow_api_url.filter(|url| url.starts_with("https://"))
+}
+
+/// Fields of the backend's login response used for authenticated URLs
+#[derive(serde::Deserialize)]
+struct LoginToken {
+    access_token: String,
+}
+
+/// URL to open an environment at: its Tailscale URL when it has one, localhost otherwise
+fn environment_base_url(env: &UshadowEnvironment) -> Option<String> {
+    env.tailscale_url.clone().or_else(|| env.localhost_url.clone())
+}
+
+/// `url` with a `token` query parameter appended
+fn append_token(url: &str, token: &str) -> Result<String, String> {
+    let mut url = reqwest::Url::parse(url).map_err(|e| format!("Invalid environment URL '{}': {}", url, e))?;
+    url.query_pairs_mut().append_pair("token", token);
+    Ok(url.to_string())
+}
+
+/// Log in to an environment's backend with the default admin credentials and return the access token
+async fn fetch_access_token(backend_port: u16) -> Result<String, String> {
+    let settings = read_launcher_settings().unwrap_or_default();
+    let (email, password) = match (settings.default_admin_email, settings.default_admin_password) {
+        (Some(email), Some(password)) => (email, password),
+        _ => return Err("No default admin credentials configured".to_string()),
+    };
+
+    let url = format!("http://localhost:{}/{}", backend_port, settings.auth_login_path.trim_start_matches('/'));
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let response = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .body(serde_json::json!({ "email": email, "password": password }).to_string())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Login at {} failed: {}", url, response.status()));
     }
 
-    None
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read login response from {}: {}", url, e))?;
+    serde_json::from_slice::<LoginToken>(&body)
+        .map(|login| login.access_token)
+        .map_err(|e| format!("Invalid login response from {}: {}", url, e))
+}
+
+/// URL to open an environment at (Tailscale or localhost). With `with_token`, a fresh access
+/// token from the environment's backend is appended as `?token=`; if the backend can't issue
+/// one (not running, no admin credentials), the plain URL is returned.
+#[tauri::command]
+pub async fn get_environment_url(env_name: String, main_repo: String, with_token: bool) -> Result<String, String> {
+    let discovery = discover_environments_with_config(Some(main_repo), None).await?;
+    let env = discovery
+        .environments
+        .into_iter()
+        .find(|env| env.name == env_name)
+        .ok_or_else(|| format!("Environment '{}' not found", env_name))?;
+    let url = environment_base_url(&env)
+        .ok_or_else(|| format!("Environment '{}' is not running", env_name))?;
+
+    if !with_token {
+        return Ok(url);
+    }
+    let backend_port = match env.backend_port {
+        Some(port) => port,
+        None => return Ok(url),
+    };
+
+    match fetch_access_token(backend_port).await {
+        Ok(token) => append_token(&url, &token),
+        Err(e) => {
+            warn!(target: "get_environment_url", "No token for '{}', returning plain URL: {}", env_name, e);
+            Ok(url)
+        }
+    }
 }
 
 /// Get working directory from Docker container using docker inspect
 /// This allows us to retrieve the path even for containers not started by the launcher
 fn get_container_working_dir(container_name: &str) -> Option<String> {
     // Use docker inspect to get container details
-    let output = silent_command("docker")
+    let output = silent_command(container_runtime().binary())
         .args(["inspect", container_name, "--format", "{{.Config.WorkingDir}}"])
         .output()
         .ok()?;
@@ -612,7 +1372,7 @@ fn get_container_working_dir(container_name: &str) -> Option<String> {
     // Docker returns the working dir inside the container (e.g., "/app")
     // We need to map this to the host path using volume mounts
     // Try to get the source path from volume mounts
-    let mount_output = silent_command("docker")
+    let mount_output = silent_command(container_runtime().binary())
         .args(["inspect", container_name, "--format", "{{range .Mounts}}{{if eq .Destination \"/app\"}}{{.Source}}{{end}}{{end}}"])
         .output()
         .ok()?;
@@ -631,7 +1391,7 @@ fn get_container_working_dir(container_name: &str) -> Option<String> {
 /// Get container creation time from Docker inspect
 /// Returns Unix timestamp in seconds
 fn get_container_created_at(container_name: &str) -> Option<i64> {
-    let output = silent_command("docker")
+    let output = silent_command(container_runtime().binary())
         .args(["inspect", container_name, "--format", "{{.Created}}"])
         .output()
         .ok()?;
@@ -672,3 +1432,379 @@ fn get_directory_created_at(path: &str) -> Option<i64> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn worktree(name: &str, path: &str, branch: &str) -> WorktreeInfo {
+        WorktreeInfo {
+            path: path.to_string(),
+            branch: branch.to_string(),
+            name: name.to_string(),
+            ahead: None,
+            behind: None,
+            dirty: false,
+            changed_files: 0,
+        }
+    }
+
+    #[test]
+    fn test_env_name_from_container_and_project() {
+        let services: Vec<String> = ["backend", "webui", "mobile-backend"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(env_name_from_compose_project("ushadow-my-feature"), Some("my-feature".to_string()));
+        assert_eq!(env_name_from_compose_project("ushadow"), Some("ushadow".to_string()));
+        assert_eq!(env_name_from_compose_project("infra"), None);
+
+        let env = |name: &str| env_name_from_container_name(name, &services);
+        assert_eq!(env("ushadow-gold-backend"), Some("gold".to_string()));
+        assert_eq!(env("ushadow-my-feature-webui"), Some("my-feature".to_string()));
+        assert_eq!(env("ushadow-gold-mobile-backend"), Some("gold".to_string()));
+        assert_eq!(env("ushadow-backend"), Some("ushadow".to_string()));
+        assert_eq!(env("ushadow-backend-2"), Some("ushadow".to_string()));
+        assert_eq!(env("mongo"), None);
+    }
+
+    #[test]
+    fn test_match_worktree_prefers_mount_path() {
+        let worktrees = vec![
+            worktree("ushadow", "/src/ushadow", "main"),
+            worktree("gold", "/w/ushadow/gold", "feat/gold"),
+            worktree("blue", "/w/ushadow/blue", "feat/blue"),
+        ];
+
+        let found = match_worktree(&worktrees, "gold", Some("/w/ushadow/blue/")).unwrap();
+        assert_eq!(found.branch, "feat/blue");
+        let found = match_worktree(&worktrees, "gold", None).unwrap();
+        assert_eq!(found.path, "/w/ushadow/gold");
+        assert!(match_worktree(&worktrees, "red", Some("/elsewhere")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_blocking_bounded_runs_concurrently_in_order() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let (in_flight_c, max_c) = (in_flight.clone(), max_in_flight.clone());
+        let start = Instant::now();
+
+        // 16 mocked "docker inspect" calls taking 100ms each
+        let results = run_blocking_bounded((0..16).collect(), INSPECT_CONCURRENCY, move |i: u32| {
+            let now = in_flight_c.fetch_add(1, Ordering::SeqCst) + 1;
+            max_c.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(100));
+            in_flight_c.fetch_sub(1, Ordering::SeqCst);
+            i * 10
+        }).await;
+
+        let elapsed = start.elapsed();
+
+        // Sequential would take 1.6s; two batches of 8 take ~200ms
+        assert!(elapsed < Duration::from_millis(1000), "took {:?}", elapsed);
+        assert!(max_in_flight.load(Ordering::SeqCst) <= INSPECT_CONCURRENCY);
+        assert!(max_in_flight.load(Ordering::SeqCst) > 1);
+
+        let values: Vec<u32> = results.into_iter().flatten().collect();
+        assert_eq!(values, (0..16).map(|i| i * 10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_parse_tray_environments() {
+        let ps = "ushadow-gold-backend|0.0.0.0:8010->8000/tcp\n\
+                  ushadow-gold-webui|0.0.0.0:3010->80/tcp\n\
+                  ushadow-red-backend|0.0.0.0:8020->8000/tcp\n\
+                  ushadow-gold-chronicle-backend-1|\n\
+                  mongo|0.0.0.0:27017->27017/tcp";
+
+        let services: Vec<String> = DEFAULT_ENV_SERVICES.iter().map(|s| s.to_string()).collect();
+        let items = parse_tray_environments(ps, &services);
+        let names: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["gold", "red"]);
+
+        assert_eq!(items[0].backend_port, Some(8010));
+        assert_eq!(items[0].url.as_deref(), Some("http://localhost:3010"));
+        // No webui container: derived from the backend port
+        assert_eq!(items[1].url.as_deref(), Some("http://localhost:3020"));
+    }
+
+    #[test]
+    fn test_environment_by_port() {
+        let worktree = |name: &str| WorktreeInfo {
+            path: format!("/repos/{}", name),
+            branch: name.to_string(),
+            name: name.to_string(),
+            ahead: None,
+            behind: None,
+            dirty: false,
+            changed_files: 0,
+        };
+        let worktrees = vec![worktree("ushadow"), worktree("gold"), worktree("silver")];
+        let read_ports = |path: &str| match path {
+            "/repos/ushadow" => (Some(8000), Some(3000)),
+            "/repos/gold" => (Some(8010), Some(3010)),
+            _ => (None, None),
+        };
+
+        assert_eq!(env_for_port_in_worktrees(&worktrees, 8010, read_ports), Some("gold".to_string()));
+        assert_eq!(env_for_port_in_worktrees(&worktrees, 3000, read_ports), Some("ushadow".to_string()));
+        assert_eq!(env_for_port_in_worktrees(&worktrees, 8020, read_ports), None);
+
+        let services: Vec<String> = DEFAULT_ENV_SERVICES.iter().map(|s| s.to_string()).collect();
+        let ps = "mongo\t0.0.0.0:27017->27017/tcp\n\
+                  ushadow-silver-backend\t0.0.0.0:8020->8000/tcp, :::8020->8000/tcp\n\
+                  ushadow-silver-webui\t0.0.0.0:3020->5173/tcp\n";
+        assert_eq!(env_for_published_port(ps, 3020, &services), Some("silver".to_string()));
+        assert_eq!(env_for_published_port(ps, 8020, &services), Some("silver".to_string()));
+        // Non-ushadow containers don't belong to an environment
+        assert_eq!(env_for_published_port(ps, 27017, &services), None);
+        assert_eq!(env_for_published_port(ps, 9999, &services), None);
+    }
+
+    #[test]
+    fn test_load_compose_service_defs() {
+        let root = std::env::temp_dir().join(format!("ushadow-service-defs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("compose")).unwrap();
+        std::fs::write(
+            root.join("docker-compose.yml"),
+            "include:\n  - compose/backend.yml\nservices:\n  webui:\n    image: ushadow/webui\n    ports:\n      - \"${WEBUI_PORT:-3000}:80\"\n",
+        ).unwrap();
+        std::fs::write(
+            root.join("compose/backend.yml"),
+            "services:\n  backend:\n    build: .\n    ports:\n      - 8000\n      - target: 9000\n        published: 19000\n  worker:\n    image: ushadow/worker\n    profiles: [async]\n",
+        ).unwrap();
+        std::fs::write(
+            root.join("docker-compose.override.yml"),
+            "services:\n  webui:\n    ports:\n      - \"3001:3001\"\n    profiles: [dev]\n",
+        ).unwrap();
+
+        let defs = load_compose_service_defs(&root);
+        let names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["webui", "backend", "worker"]);
+
+        assert_eq!(defs[0].image.as_deref(), Some("ushadow/webui"));
+        assert_eq!(defs[0].ports, vec!["${WEBUI_PORT:-3000}:80", "3001:3001"]);
+        assert_eq!(defs[0].profiles, vec!["dev"]);
+        assert!(defs[0].file.ends_with("docker-compose.yml"));
+
+        assert_eq!(defs[1].image, None);
+        assert_eq!(defs[1].ports, vec!["8000", "19000:9000"]);
+        assert!(defs[1].file.ends_with("backend.yml"));
+
+        assert_eq!(defs[2].profiles, vec!["async"]);
+        assert!(defs[2].ports.is_empty());
+        assert_eq!(compose_profiles(&defs), vec!["async", "dev"]);
+
+        assert!(load_compose_service_defs(&root.join("compose")).is_empty());
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_infra_ports_from_ps() {
+        let pattern = |container: &str, service: &str| InfraPattern {
+            container_name: container.to_string(),
+            service_name: service.to_string(),
+            display_name: get_display_name(service),
+            from_compose: true,
+        };
+        let patterns = vec![pattern("mongo", "mongo"), pattern("infra-redis-1", "redis"), pattern("qdrant", "qdrant")];
+
+        let ps = "mongo\t0.0.0.0:27018->27017/tcp, :::27018->27017/tcp\n\
+                  infra-redis-1\t127.0.0.1:6380->6379/tcp\n\
+                  qdrant\t6333/tcp\n\
+                  ushadow-gold-backend\t0.0.0.0:8010->8000/tcp\n";
+
+        assert_eq!(
+            infra_ports_from_ps(ps, &patterns),
+            vec![("mongo".to_string(), 27018), ("redis".to_string(), 6380)]
+        );
+    }
+
+    #[test]
+    fn test_timed_accumulates_durations() {
+        let mut total_ms = 0.0;
+        let value = timed(&mut total_ms, || {
+            std::thread::sleep(Duration::from_millis(5));
+            42
+        });
+        assert_eq!(value, 42);
+        assert!(total_ms >= 5.0);
+
+        let after_first = total_ms;
+        timed(&mut total_ms, || std::thread::sleep(Duration::from_millis(5)));
+        assert!(total_ms >= after_first + 5.0);
+    }
+
+    #[test]
+    fn test_parse_health_status() {
+        assert_eq!(parse_health_status("Up 2 hours (healthy)"), Some(HealthState::Healthy));
+        assert_eq!(parse_health_status("Up 5 minutes (unhealthy)"), Some(HealthState::Unhealthy));
+        assert_eq!(parse_health_status("Up 3 seconds (health: starting)"), Some(HealthState::Starting));
+        assert_eq!(parse_health_status("Up 2 hours"), None);
+        assert_eq!(parse_health_status("Up About a minute (Paused)"), None);
+        assert_eq!(parse_health_status("Exited (1) 3 hours ago"), None);
+        assert_eq!(parse_health_status("Restarting (1) 5 seconds ago"), None);
+
+        // Environment health is the worst of its containers'
+        let worst = [Some(HealthState::Healthy), None, Some(HealthState::Unhealthy), Some(HealthState::Starting)]
+            .into_iter()
+            .fold(None, Option::max);
+        assert_eq!(worst, Some(HealthState::Unhealthy));
+    }
+
+    #[test]
+    fn test_extract_published_ports() {
+        // IPv4 and IPv6 bindings of the same mapping collapse to one
+        assert_eq!(extract_published_ports("0.0.0.0:8010->8000/tcp, :::8010->8000/tcp"), vec![(8000, 8010)]);
+        assert_eq!(extract_published_ports("[::]:8010->8000/tcp"), vec![(8000, 8010)]);
+        assert_eq!(extract_published_ports("8010->8000/tcp"), vec![(8000, 8010)]);
+
+        // Multiple ports: the backend port is matched by container port, not position
+        let ports = "0.0.0.0:5678->5678/tcp, 0.0.0.0:8010->8000/tcp, [::]:8010->8000/tcp";
+        assert_eq!(extract_published_ports(ports), vec![(5678, 5678), (8000, 8010)]);
+        assert_eq!(extract_port(ports, Some(BACKEND_CONTAINER_PORT)), Some(8010));
+        assert_eq!(extract_port(ports, None), Some(5678));
+        assert_eq!(extract_port("0.0.0.0:3010->80/tcp", Some(BACKEND_CONTAINER_PORT)), Some(3010));
+
+        // Nothing published
+        assert_eq!(extract_published_ports("8000/tcp, 6379/tcp"), Vec::<(u16, u16)>::new());
+        assert_eq!(extract_published_ports(""), Vec::<(u16, u16)>::new());
+        assert_eq!(extract_port("8000/tcp", Some(BACKEND_CONTAINER_PORT)), None);
+    }
+
+    #[test]
+    fn test_parse_leader_info_url() {
+        let minified = br#"{"hostname":"gold","ushadow_api_url":"https://gold.tail1234.ts.net","role":"leader"}"#;
+        assert_eq!(parse_leader_info_url(minified), Some("https://gold.tail1234.ts.net".to_string()));
+
+        let pretty = br#"{
+  "role": "leader",
+  "ushadow_api_url": "https://gold.tail1234.ts.net",
+  "hostname": "gold"
+}"#;
+        assert_eq!(parse_leader_info_url(pretty), Some("https://gold.tail1234.ts.net".to_string()));
+
+        assert_eq!(parse_leader_info_url(br#"{"ushadow_api_url":null}"#), None);
+        assert_eq!(parse_leader_info_url(br#"{"ushadow_api_url":"http://localhost:8000"}"#), None);
+        assert_eq!(parse_leader_info_url(br#"{"hostname":"gold"}"#), None);
+        assert_eq!(parse_leader_info_url(b"<html>Bad Gateway</html>"), None);
+    }
+
+    #[test]
+    fn test_merge_infra_patterns() {
+        let compose = vec![InfraPattern {
+            container_name: "mongo".to_string(),
+            service_name: "mongo".to_string(),
+            display_name: get_display_name("mongo"),
+            from_compose: true,
+        }];
+        let configured: HashMap<String, String> = [
+            ("clickhouse".to_string(), "ClickHouse".to_string()),
+            ("mongo".to_string(), "Mongo (replica set)".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        // Compose first, then user config; user display names override compose ones
+        let merged = merge_infra_patterns(compose, &configured);
+        let names: Vec<(&str, &str)> = merged.iter().map(|p| (p.service_name.as_str(), p.display_name.as_str())).collect();
+        assert_eq!(names, vec![("mongo", "Mongo (replica set)"), ("clickhouse", "ClickHouse")]);
+
+        // Without a compose file, built-in defaults follow the user config
+        let configured: HashMap<String, String> = [
+            ("minio".to_string(), "MinIO".to_string()),
+            ("redis".to_string(), "Redis Stack".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let merged = merge_infra_patterns(Vec::new(), &configured);
+        assert_eq!(merged[0].service_name, "minio");
+        assert_eq!(merged[1].service_name, "redis");
+        assert_eq!(merged[1].display_name, "Redis Stack");
+        assert_eq!(merged.len(), 2 + INFRA_PATTERNS.len() - 1);
+        assert_eq!(merged.iter().filter(|p| p.service_name == "redis").count(), 1);
+    }
+
+    #[test]
+    fn test_user_defined_infra_pattern_matches_containers() {
+        let configured: HashMap<String, String> = [("nats".to_string(), "NATS".to_string())].into_iter().collect();
+        let merged = merge_infra_patterns(Vec::new(), &configured);
+        let nats = merged.iter().find(|p| p.service_name == "nats").unwrap();
+
+        assert!(nats.matches("nats"));
+        assert!(nats.matches("infra-nats-1"));
+        assert!(nats.matches("infra_nats_1"));
+        assert!(!nats.matches("ushadow-gold-backend"));
+        assert!(!nats.matches("natsbox"));
+
+        // Compose patterns keep the narrower matching
+        let compose = InfraPattern {
+            container_name: "infra-nats-1".to_string(),
+            service_name: "nats".to_string(),
+            display_name: "NATS".to_string(),
+            from_compose: true,
+        };
+        assert!(compose.matches("infra-nats-1"));
+        assert!(compose.matches("other-nats"));
+        assert!(!compose.matches("other_nats_2"));
+    }
+
+    #[test]
+    fn test_find_port_conflicts() {
+        let declared = vec![
+            ("gold".to_string(), vec![8010, 3010]),
+            ("blue".to_string(), vec![8020, 3020]),
+            ("red".to_string(), vec![8010, 3030]),
+            // An env declaring the same port twice doesn't conflict with itself
+            ("green".to_string(), vec![8040, 8040]),
+        ];
+        assert_eq!(
+            find_port_conflicts(&declared),
+            vec![PortConflict { port: 8010, envs: vec!["gold".to_string(), "red".to_string()] }]
+        );
+        assert!(find_port_conflicts(&declared[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_discovery_cache_hit_and_invalidation() {
+        let result = |docker_ok| DiscoveryResult {
+            infrastructure: Vec::new(),
+            environments: Vec::new(),
+            docker_ok,
+            tailscale_ok: false,
+            port_conflicts: Vec::new(),
+        };
+        let ttl = Duration::from_secs(60);
+
+        let mut cache = DiscoveryCache::default();
+        assert!(cache.get("/repos/ushadow", ttl).is_none());
+
+        cache.insert("/repos/ushadow".to_string(), result(true));
+        assert!(cache.get("/repos/ushadow", ttl).unwrap().docker_ok);
+        // Keyed by main repo, and expired entries are not served
+        assert!(cache.get("/repos/other", ttl).is_none());
+        assert!(cache.get("/repos/ushadow", Duration::ZERO).is_none());
+
+        *DISCOVERY_CACHE.lock().unwrap() = Some(cache);
+        invalidate_discovery_cache();
+        assert!(DISCOVERY_CACHE.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_environment_url_with_token() {
+        assert_eq!(
+            append_token("http://localhost:3010", "abc.def-ghi").unwrap(),
+            "http://localhost:3010/?token=abc.def-ghi"
+        );
+        assert_eq!(
+            append_token("https://gold.tail1234.ts.net/dashboard?tab=1", "a+b/c").unwrap(),
+            "https://gold.tail1234.ts.net/dashboard?tab=1&token=a%2Bb%2Fc"
+        );
+        assert!(append_token("not a url", "abc").is_err());
+
+        let body = br#"{"access_token":"jwt-123","token_type":"bearer","user":{"email":"admin@example.com"}}"#;
+        let login: LoginToken = serde_json::from_slice(body).unwrap();
+        assert_eq!(login.access_token, "jwt-123");
+    }
+}
@@ -30,7 +30,7 @@ pub async fn discover_environments_v2(
     let main_repo = main_repo.unwrap_or_else(|| project_root.clone());
 
     // Get worktrees (source of truth for environments)
-    let worktrees = match list_worktrees(main_repo.clone()).await {
+    let worktrees = match list_worktrees(main_repo.clone(), None, None).await {
         Ok(wt) => {
             eprintln!("[discovery_v2] Found {} worktrees from {}", wt.len(), main_repo);
             wt
@@ -125,6 +125,7 @@ pub async fn discover_environments_v2(
             is_worktree: true,
             created_at: None,  // TODO: Get actual creation timestamp from git worktree
             base_branch: None,  // TODO: Determine base branch (main/dev) from worktree
+            health: None,
         });
     }
 
@@ -133,5 +134,6 @@ pub async fn discover_environments_v2(
         environments,
         docker_ok,
         tailscale_ok,
+        port_conflicts: Vec::new(),
     })
 }
@@ -1,14 +1,22 @@
 use std::net::TcpListener;
 use std::sync::Mutex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::time::Duration;
 use tauri::State;
-use crate::models::{ContainerStatus, ServiceInfo, InfraService};
+use crate::models::{ComposeValidation, ContainerLogs, ContainerStatus, ServiceInfo, InfraService};
 use super::utils::{silent_command, shell_command, quote_path_buf};
 use super::platform::{Platform, PlatformOps};
 use super::bundled;
+use super::container_runtime::container_runtime;
+use super::discovery::{compose_profiles, env_name_from_compose_project, env_name_from_container_name, environment_services, is_service_instance, load_compose_service_defs};
+use super::kanban::flush_kanban_database;
+use super::recent_envs::record_environment_use;
+use super::worktree::list_worktrees;
+use super::operations::{output_cancellable, CancelToken, OperationGuard, OperationRegistry, OPERATION_CANCELLED};
 use crate::config::LauncherConfig;
 use serde_yaml::Value;
+use tracing::{debug, info, warn};
 
 /// Recursively copy a directory and all its contents
 fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
@@ -126,6 +134,8 @@ pub struct AppState {
     pub project_root: Mutex<Option<String>>,
     pub containers_running: Mutex<bool>,
     pub config: Mutex<Option<crate::config::LauncherConfig>>,
+    /// Cancellation tokens of running operations, by operation id
+    pub operations: OperationRegistry,
 }
 
 impl AppState {
@@ -134,6 +144,7 @@ impl AppState {
             project_root: Mutex::new(None),
             containers_running: Mutex::new(false),
             config: Mutex::new(None),
+            operations: OperationRegistry::default(),
         }
     }
 }
@@ -149,6 +160,103 @@ pub fn set_project_root(path: String, state: State<AppState>) -> Result<(), Stri
     Ok(())
 }
 
+/// Base backend/webui ports that environment offsets are added to
+const BASE_BACKEND_PORT: u16 = 8000;
+const BASE_WEBUI_PORT: u16 = 3000;
+/// Offsets are allocated in steps of 10 up to (not including) this value
+const MAX_PORT_OFFSET: u16 = 500;
+
+/// Initial offset hint derived from the env name (sum of bytes, 0..490 in steps of 10)
+fn hashed_port_offset(env_name: &str) -> u16 {
+    let hash: u32 = env_name.bytes().map(|b| b as u32).sum();
+    ((hash % 50) * 10) as u16
+}
+
+/// Host ports published in a `docker ps` Ports column,
+/// e.g. "0.0.0.0:8010->8000/tcp, :::8010->8000/tcp" -> [8010, 8010]
+pub(crate) fn parse_published_host_ports(ports: &str) -> Vec<u16> {
+    ports
+        .split(',')
+        .filter_map(|mapping| {
+            let (host, _container) = mapping.trim().split_once("->")?;
+            host.rsplit(':').next()?.parse().ok()
+        })
+        .collect()
+}
+
+/// Map a published host port back to the environment offset it implies
+fn port_to_offset(port: u16) -> Option<u16> {
+    [BASE_BACKEND_PORT, BASE_WEBUI_PORT]
+        .iter()
+        .find(|base| port >= **base && port < **base + MAX_PORT_OFFSET)
+        .map(|base| (port - base) / 10 * 10)
+}
+
+/// Offsets currently in use by running containers' published ports
+fn used_port_offsets() -> HashSet<u16> {
+    let output = shell_command(&container_runtime().command("ps --format '{{.Ports}}'")).output();
+
+    match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .flat_map(parse_published_host_ports)
+            .filter_map(port_to_offset)
+            .collect(),
+        _ => HashSet::new(),
+    }
+}
+
+/// Choose a port offset for an environment. Offset 0 is reserved for the default env.
+/// Order of preference: persisted offset, env-name hash, lowest free 10-increment slot.
+fn allocate_port_offset(env_name: &str, persisted: Option<u16>, used: &HashSet<u16>) -> u16 {
+    let is_free = |offset: &u16| *offset != 0 && !used.contains(offset);
+
+    persisted
+        .filter(is_free)
+        .or_else(|| Some(hashed_port_offset(env_name)).filter(is_free))
+        .or_else(|| (10..MAX_PORT_OFFSET).step_by(10).find(is_free))
+        .unwrap_or_else(|| hashed_port_offset(env_name))
+}
+
+/// Read PORT_OFFSET from the environment's .env, if present
+fn read_persisted_port_offset(working_dir: &str) -> Option<u16> {
+    let contents = std::fs::read_to_string(Path::new(working_dir).join(".env")).ok()?;
+    contents
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("PORT_OFFSET="))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// Write (or replace) PORT_OFFSET in the environment's .env
+fn persist_port_offset(working_dir: &str, offset: u16) -> Result<(), String> {
+    let env_path = Path::new(working_dir).join(".env");
+    let contents = std::fs::read_to_string(&env_path).unwrap_or_default();
+
+    let mut lines: Vec<String> = contents
+        .lines()
+        .filter(|line| !line.trim().starts_with("PORT_OFFSET="))
+        .map(|line| line.to_string())
+        .collect();
+    lines.push(format!("PORT_OFFSET={}", offset));
+
+    std::fs::write(&env_path, lines.join("\n") + "\n")
+        .map_err(|e| format!("Failed to write {}: {}", env_path.display(), e))
+}
+
+/// Whether a container name is one of an environment's own containers.
+/// Default env ("ushadow"/"default") containers are `ushadow-{service}[-N]` and named envs
+/// `ushadow-{env}-{service}[-N]` for a known service. Service-attached containers
+/// (`ushadow-{env}-{svc}-backend-{hash}`) are not the environment's own and never match.
+pub(crate) fn container_belongs_to_env(name: &str, env_name: &str, services: &[String]) -> bool {
+    let prefix = if env_name == "default" || env_name == "ushadow" {
+        "ushadow-".to_string()
+    } else {
+        format!("ushadow-{}-", env_name)
+    };
+    name.strip_prefix(&prefix)
+        .is_some_and(|rest| is_service_instance(rest, services))
+}
+
 /// Start shared infrastructure containers
 #[tauri::command]
 pub async fn start_infrastructure(state: State<'_, AppState>) -> Result<String, String> {
@@ -156,8 +264,9 @@ pub async fn start_infrastructure(state: State<'_, AppState>) -> Result<String,
     let project_root = root.clone().ok_or("Project root not set")?;
     drop(root);
 
+    let runtime = container_runtime();
     let mut log_messages = Vec::new();
-    log_messages.push("Creating Docker networks...".to_string());
+    log_messages.push(format!("Creating {} networks...", runtime.binary()));
 
     // Create Docker networks directly (don't require uv/Python)
     let networks = vec!["ushadow-network", "infra-network"];
@@ -165,8 +274,7 @@ pub async fn start_infrastructure(state: State<'_, AppState>) -> Result<String,
         log_messages.push(format!("Checking network: {}", network));
 
         // Check if network exists
-        let check_output = shell_command("docker")
-            .args(&["network", "inspect", network])
+        let check_output = shell_command(&runtime.command(&format!("network inspect {}", network)))
             .output();
 
         let network_exists = check_output.is_ok() && check_output.unwrap().status.success();
@@ -177,8 +285,7 @@ pub async fn start_infrastructure(state: State<'_, AppState>) -> Result<String,
             log_messages.push(format!("Creating network: {}", network));
 
             // Create network
-            let create_output = shell_command("docker")
-                .args(&["network", "create", network])
+            let create_output = shell_command(&runtime.command(&format!("network create {}", network)))
                 .output()
                 .map_err(|e| {
                     let error_log = log_messages.join("\n");
@@ -225,9 +332,9 @@ pub async fn start_infrastructure(state: State<'_, AppState>) -> Result<String,
 
     let compose_path_quoted = quote_path_buf(&working_compose_file);
 
-    log_messages.push(format!("Running: docker compose -f {} -p infra --profile infra up -d", compose_path_quoted));
+    let compose_command = runtime.compose_command(&format!("-f {} -p infra --profile infra up -d", compose_path_quoted));
+    log_messages.push(format!("Running: {}", compose_command));
 
-    let compose_command = format!("docker compose -f {} -p infra --profile infra up -d", compose_path_quoted);
     let infra_output = shell_command(&compose_command)
         .current_dir(&project_root)
         .output()
@@ -262,7 +369,7 @@ pub async fn stop_infrastructure(state: State<'_, AppState>) -> Result<String, S
     let project_root = root.clone().ok_or("Project root not set")?;
     drop(root);
 
-    let output = shell_command("docker compose -p infra down")
+    let output = shell_command(&container_runtime().compose_command("-p infra down"))
         .current_dir(&project_root)
         .output()
         .map_err(|e| format!("Failed to stop infrastructure (docker not found or not executable): {}", e))?;
@@ -283,12 +390,13 @@ pub async fn restart_infrastructure(state: State<'_, AppState>) -> Result<String
     drop(root);
 
     // Stop first
-    let _ = shell_command("docker compose -p infra down")
+    let runtime = container_runtime();
+    let _ = shell_command(&runtime.compose_command("-p infra down"))
         .current_dir(&project_root)
         .output();
 
     // Start again
-    let output = shell_command("docker compose -f compose/docker-compose.infra.yml -p infra --profile infra up -d")
+    let output = shell_command(&runtime.compose_command("-f compose/docker-compose.infra.yml -p infra --profile infra up -d"))
         .current_dir(&project_root)
         .output()
         .map_err(|e| format!("Failed to restart infrastructure (docker not found or not executable): {}", e))?;
@@ -301,12 +409,269 @@ pub async fn restart_infrastructure(state: State<'_, AppState>) -> Result<String
     Ok("Infrastructure restarted".to_string())
 }
 
-/// Start a specific environment by name
+/// What the environment setup script should do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SetupMode {
+    /// First-time setup: generate config and create containers
+    Initialize,
+    /// Rebuild images and recreate containers (optionally bypassing the build cache)
+    Rebuild { no_cache: bool },
+}
+
+impl SetupMode {
+    fn verb(&self) -> &'static str {
+        match self {
+            SetupMode::Initialize => "initialize",
+            SetupMode::Rebuild { .. } => "rebuild",
+        }
+    }
+}
+
+/// Build the default `run.py` invocation for a setup mode
+fn build_setup_script_command(uv_cmd: &str, run_py_quoted: &str, mode: SetupMode) -> String {
+    match mode {
+        SetupMode::Initialize => format!("{} run --with pyyaml {} --dev --quick", uv_cmd, run_py_quoted),
+        SetupMode::Rebuild { no_cache: false } => format!("{} run --with pyyaml {} --build", uv_cmd, run_py_quoted),
+        SetupMode::Rebuild { no_cache: true } => format!("{} run --with pyyaml {} --build --no-cache", uv_cmd, run_py_quoted),
+    }
+}
+
+/// Check that every requested compose profile is declared by some service
+fn validate_compose_profiles(requested: &[String], declared: &[String]) -> Result<(), String> {
+    let unknown: Vec<&str> = requested
+        .iter()
+        .filter(|p| !declared.contains(p))
+        .map(String::as_str)
+        .collect();
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown compose profile(s): {} (declared: {})",
+            unknown.join(", "),
+            if declared.is_empty() { "none".to_string() } else { declared.join(", ") }
+        ))
+    }
+}
+
+/// Run the environment setup script (custom `.launcher-config.yaml` command or bundled run.py)
+/// in `working_dir`, returning the user-visible status log or the status + debug log on failure.
+/// Compose `profiles` are enabled through `COMPOSE_PROFILES`, the env equivalent of `--profile`.
+/// The setup process is killed if `cancel` is cancelled while it runs.
+fn run_environment_setup(env_name: &str, working_dir: &str, mode: SetupMode, profiles: &[String], cancel: &CancelToken) -> Result<String, String> {
+    // Pick a port offset that no running environment is using.
+    // Prefer the offset persisted in .env, then the env-name hash, then the lowest free slot.
+    let port_offset = if env_name == "ushadow" || env_name.is_empty() {
+        0
+    } else {
+        let used = used_port_offsets();
+        let persisted = read_persisted_port_offset(working_dir);
+        let offset = allocate_port_offset(env_name, persisted, &used);
+        if let Err(e) = persist_port_offset(working_dir, offset) {
+            warn!(target: "env_setup", "could not persist PORT_OFFSET: {}", e);
+        }
+        offset
+    };
+
+    let mut status_log = Vec::new();  // User-visible status messages
+    let mut debug_log = Vec::new();   // Detailed debug info (only shown on error)
+
+    // Log to both status and debug
+    match mode {
+        SetupMode::Initialize => {
+            status_log.push(format!("Initializing environment '{}'...", env_name));
+            debug_log.push(format!("========== INITIALIZING ENVIRONMENT =========="));
+        }
+        SetupMode::Rebuild { no_cache } => {
+            status_log.push(format!("Rebuilding environment '{}'{}...", env_name, if no_cache { " (no cache)" } else { "" }));
+            debug_log.push(format!("========== REBUILDING ENVIRONMENT =========="));
+        }
+    }
+    debug_log.push(format!("Working directory: {}", working_dir));
+    debug_log.push(format!("ENV_NAME={}", env_name));
+    debug_log.push(format!("PORT_OFFSET={} (lowest free offset, env name hash as hint)", port_offset));
+
+    // Find uv executable (assumes uv is installed via prerequisites)
+    let uv_cmd = find_uv_executable();
+    debug_log.push(format!("Using uv at: {}", uv_cmd));
+
+    // Verify uv is accessible
+    let uv_check = if uv_cmd == "uv" {
+        // If using PATH, verify with --version
+        shell_command("uv --version").output().is_ok()
+    } else {
+        // If using specific path, verify file exists
+        std::path::Path::new(&uv_cmd).exists()
+    };
+
+    if !uv_check {
+        let error_msg = format!(
+            "uv not found or not accessible (tried: {})\n\nPlease install uv via the Prerequisites panel before starting an environment.",
+            uv_cmd
+        );
+        status_log.push(error_msg.clone());
+        debug_log.push(error_msg);
+        return Err(format!("{}\n\n=== Debug Log ===\n{}",
+            status_log.join("\n"),
+            debug_log.join("\n")));
+    }
+
+    // Run setup with uv in dev mode with calculated port offset
+    // Note: Removed --skip-admin flag so admin user can be auto-created from secrets.yaml
+    status_log.push(format!("Running setup script..."));
+
+    // Check if this worktree has a .launcher-config.yaml with a custom setup command
+    let mut setup_command = None;
+    let config_path = Path::new(working_dir).join(".launcher-config.yaml");
+    if config_path.exists() {
+        debug_log.push(format!("Found .launcher-config.yaml at: {:?}", config_path));
+        match LauncherConfig::load(&Path::new(working_dir).to_path_buf()) {
+            Ok(config) => {
+                if !config.setup.command.is_empty() {
+                    setup_command = Some(config.setup.command.clone());
+                    debug_log.push(format!("Using custom setup command from config: {}", config.setup.command));
+                } else {
+                    debug_log.push(format!("Config exists but setup.command is empty, falling back to default"));
+                }
+            }
+            Err(e) => {
+                debug_log.push(format!("Failed to load config: {}, falling back to default", e));
+            }
+        }
+    } else {
+        debug_log.push(format!("No .launcher-config.yaml found, using default ushadow setup"));
+    }
+
+    let command = if let Some(custom_command) = setup_command {
+        // Use the custom command from the config
+        custom_command
+    } else {
+        // Fall back to default ushadow setup
+        // Get bundled setup scripts if available
+        let bundled_setup_dir = bundled::get_setup_dir(working_dir);
+
+        // Copy bundled setup to working directory if it's from the bundled location
+        // This avoids permission issues on Windows where Program Files requires admin
+        let working_setup_dir = std::path::Path::new(working_dir).join("setup");
+
+        if bundled_setup_dir != working_setup_dir {
+            debug_log.push(format!("Copying bundled setup from {:?} to {:?}", bundled_setup_dir, working_setup_dir));
+
+            // Recursively copy the entire setup directory
+            if let Err(e) = copy_dir_recursive(&bundled_setup_dir, &working_setup_dir) {
+                debug_log.push(format!("Warning: Failed to copy setup directory: {}", e));
+                // Continue anyway - might be a partial copy that still works
+            } else {
+                debug_log.push(format!("[OK] Bundled setup copied successfully"));
+            }
+        }
+
+        let run_py_path = working_setup_dir.join("run.py");
+        let run_py_quoted = quote_path_buf(&run_py_path);
+        let command = build_setup_script_command(&uv_cmd, &run_py_quoted, mode);
+
+        debug_log.push(format!("Using setup script: {:?}", run_py_path));
+        debug_log.push(format!("Running: {}", command));
+
+        command
+    };
+
+    // Build the full command string using platform abstraction
+    // Pass PORT_OFFSET for compatibility with both old and new setup scripts
+    let mut env_vars = HashMap::new();
+    env_vars.insert("ENV_NAME".to_string(), env_name.to_string());
+    env_vars.insert("PORT_OFFSET".to_string(), port_offset.to_string());
+    if !profiles.is_empty() {
+        env_vars.insert("COMPOSE_PROFILES".to_string(), profiles.join(","));
+        debug_log.push(format!("COMPOSE_PROFILES={}", profiles.join(",")));
+    }
+
+    let setup_command = Platform::build_env_command(working_dir, env_vars, &command);
+
+    cancel.check()?;
+
+    let mut setup = shell_command(&setup_command);
+    setup.current_dir(working_dir);  // Run from working_dir so setup script finds correct PROJECT_ROOT
+    let output = output_cancellable(&mut setup, cancel)
+        .map_err(|e| {
+            let full_log = format!("{}\n\n=== Debug Log ===\n{}",
+                status_log.join("\n"),
+                debug_log.join("\n"));
+            format!("{}\n\nFailed to run setup (uv not found at '{}'. Try installing manually: https://docs.astral.sh/uv/getting-started/installation/): {}", full_log, uv_cmd, e)
+        })?
+        .ok_or_else(|| {
+            info!(target: "env_setup", "Setup of '{}' cancelled", env_name);
+            OPERATION_CANCELLED.to_string()
+        })?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if !stdout.is_empty() {
+        debug_log.push(format!("Setup stdout:\n{}", stdout));
+    }
+    if !stderr.is_empty() {
+        debug_log.push(format!("Setup stderr:\n{}", stderr));
+    }
+
+    if !output.status.success() {
+        // Get the full error message, not just the last line
+        let error_msg = if !stderr.is_empty() {
+            stderr.to_string()
+        } else {
+            stdout.to_string()
+        };
+
+        // Show last 10 lines of error for better context
+        let error_lines: Vec<&str> = error_msg.lines().collect();
+        let context_lines = if error_lines.len() > 10 {
+            &error_lines[error_lines.len()-10..]
+        } else {
+            &error_lines[..]
+        };
+
+        // On error, show both status and debug logs
+        let full_log = format!("{}\n\n=== Debug Log ===\n{}",
+            status_log.join("\n"),
+            debug_log.join("\n"));
+
+        return Err(format!(
+            "{}\n\n❌ Failed to {} environment '{}'\n\nError output:\n{}",
+            full_log,
+            mode.verb(),
+            env_name,
+            context_lines.join("\n")
+        ));
+    }
+
+    // On success, only show status log
+    let done = match mode {
+        SetupMode::Initialize => "initialized and started",
+        SetupMode::Rebuild { .. } => "rebuilt and started",
+    };
+    status_log.push(format!("[OK] Environment '{}' {}", env_name, done));
+    Ok(status_log.join("\n"))
+}
+
+/// Start a specific environment by name.
+/// Pass an `operation_id` to make the start cancellable with `cancel_operation`, and compose
+/// `profiles` to include optional services (see `get_compose_profiles`).
 #[tauri::command]
-pub async fn start_environment(state: State<'_, AppState>, env_name: String, env_path: Option<String>) -> Result<String, String> {
-    eprintln!("\n[start_env] ========================================");
-    eprintln!("[start_env] Starting environment: {}", env_name);
-    eprintln!("[start_env] ========================================");
+pub async fn start_environment(
+    state: State<'_, AppState>,
+    env_name: String,
+    env_path: Option<String>,
+    operation_id: Option<String>,
+    profiles: Option<Vec<String>>,
+) -> Result<String, String> {
+    info!(target: "start_env", "========================================");
+    info!(target: "start_env", "Starting environment: {}", env_name);
+    info!(target: "start_env", "========================================");
+
+    record_environment_use(&env_name);
+
+    let operation = OperationGuard::register(&state.operations, operation_id);
+    let cancel = &operation.token;
 
     let root = state.project_root.lock().map_err(|e| e.to_string())?;
     let project_root = root.clone().ok_or("Project root not set")?;
@@ -315,18 +680,23 @@ pub async fn start_environment(state: State<'_, AppState>, env_name: String, env
     // Use env_path if provided (for worktrees), otherwise use project_root
     let working_dir = env_path.unwrap_or_else(|| project_root.clone());
 
-    eprintln!("[start_env] Project root: {}", project_root);
-    eprintln!("[start_env] Working directory: {}", working_dir);
+    info!(target: "start_env", "Project root: {}", project_root);
+    info!(target: "start_env", "Working directory: {}", working_dir);
 
-    // Find all stopped containers for this environment by name pattern
-    let pattern = if env_name == "default" || env_name == "ushadow" {
-        "ushadow-".to_string()
-    } else {
-        format!("ushadow-{}-", env_name)
-    };
+    // Profile services may not have containers yet, so compose has to bring the env up
+    let profiles = profiles.unwrap_or_default();
+    if !profiles.is_empty() {
+        let declared = compose_profiles(&load_compose_service_defs(Path::new(&working_dir)));
+        validate_compose_profiles(&profiles, &declared)?;
+        info!(target: "start_env", "Starting with compose profiles: {}", profiles.join(", "));
+        return run_environment_setup(&env_name, &working_dir, SetupMode::Initialize, &profiles, cancel);
+    }
+
+    let services = environment_services(Some(&project_root));
 
+    // Find all stopped containers for this environment by name pattern
     // Get matching stopped container names
-    let output = shell_command("docker ps -a --filter status=exited --format '{{.Names}}'")
+    let output = shell_command(&container_runtime().command("ps -a --filter status=exited --format '{{.Names}}'"))
         .output()
         .map_err(|e| format!("Failed to list containers (docker not found or not executable): {}", e))?;
 
@@ -335,286 +705,201 @@ pub async fn start_environment(state: State<'_, AppState>, env_name: String, env
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    eprintln!("DEBUG: All stopped containers:\n{}", stdout);
+    debug!("All stopped containers:\n{}", stdout);
 
     let containers: Vec<&str> = stdout
         .lines()
-        .filter(|name| {
-            if env_name == "default" || env_name == "ushadow" {
-                // For default env, match ushadow-{service} or ushadow-{service}-{number}
-                // but NOT ushadow-{envname}-{service}
-                if !name.starts_with(&pattern) {
-                    return false;
-                }
-
-                // Check if this is a default env container by checking if it matches known services
-                let after_prefix = &name[pattern.len()..];
-                let services = ["backend", "webui", "frontend", "worker", "tailscale"];
-
-                services.iter().any(|service| {
-                    after_prefix == *service || after_prefix.starts_with(&format!("{}-", service))
-                })
-            } else {
-                name.starts_with(&pattern)
-            }
-        })
+        .filter(|name| container_belongs_to_env(name, &env_name, &services))
         .collect();
 
-    eprintln!("[start_env] Found {} stopped containers: {:?}", containers.len(), containers);
+    info!(target: "start_env", "Found {} stopped containers: {:?}", containers.len(), containers);
 
     // If no stopped containers found, check if ANY containers exist for this env
     if containers.is_empty() {
-        eprintln!("[start_env] No stopped containers, checking for running containers...");
+        info!(target: "start_env", "No stopped containers, checking for running containers...");
         // Check for running containers
-        let output = shell_command("docker ps --format '{{.Names}}'")
+        let output = shell_command(&container_runtime().command("ps --format '{{.Names}}'"))
             .output()
             .map_err(|e| format!("Failed to list running containers (docker not found or not executable): {}", e))?;
 
         if output.status.success() {
             let stdout = String::from_utf8_lossy(&output.stdout);
-            eprintln!("DEBUG: All running containers:\n{}", stdout);
+            debug!("All running containers:\n{}", stdout);
 
             let running: Vec<&str> = stdout
                 .lines()
-                .filter(|name| {
-                    if env_name == "default" || env_name == "ushadow" {
-                        // For default env, match ushadow-{service} or ushadow-{service}-{number}
-                        // but NOT ushadow-{envname}-{service}
-                        if !name.starts_with(&pattern) {
-                            return false;
-                        }
-
-                        // Check if this is a default env container by checking if it matches known services
-                        let after_prefix = &name[pattern.len()..];
-                        let services = ["backend", "webui", "frontend", "worker", "tailscale"];
-
-                        services.iter().any(|service| {
-                            after_prefix == *service || after_prefix.starts_with(&format!("{}-", service))
-                        })
-                    } else {
-                        name.starts_with(&pattern)
-                    }
-                })
+                .filter(|name| container_belongs_to_env(name, &env_name, &services))
                 .collect();
 
-            eprintln!("[start_env] Found {} running containers: {:?}", running.len(), running);
+            info!(target: "start_env", "Found {} running containers: {:?}", running.len(), running);
 
             if !running.is_empty() {
-                eprintln!("[start_env] Environment already running - nothing to do");
+                info!(target: "start_env", "Environment already running - nothing to do");
                 return Ok(format!("Environment '{}' is already running ({} containers: {})",
                     env_name, running.len(), running.join(", ")));
             }
         }
 
         // No containers exist - need to build and create them
-        eprintln!("[start_env] No containers exist - initializing environment");
+        info!(target: "start_env", "No containers exist - initializing environment");
 
-        // Calculate port offset from environment name to avoid conflicts
-        // Hash the env name to get a deterministic offset
-        let port_offset = if &env_name == "ushadow" || env_name.is_empty() {
-            0
-        } else {
-            // Simple hash: sum ASCII values and mod by reasonable range
-            let hash: u32 = env_name.bytes().map(|b| b as u32).sum();
-            ((hash % 50) * 10) as u16  // Gives offsets: 0, 10, 20, ... 490
-        };
+        return run_environment_setup(&env_name, &working_dir, SetupMode::Initialize, &[], cancel);
+    }
 
-        let mut status_log = Vec::new();  // User-visible status messages
-        let mut debug_log = Vec::new();   // Detailed debug info (only shown on error)
+    cancel.check()?;
 
-        // Log to both status and debug
-        status_log.push(format!("Initializing environment '{}'...", env_name));
-        debug_log.push(format!("========== INITIALIZING ENVIRONMENT =========="));
-        debug_log.push(format!("Working directory: {}", working_dir));
-        debug_log.push(format!("ENV_NAME={}", env_name));
-        debug_log.push(format!("PORT_OFFSET={} (calculated from env name hash)", port_offset));
+    // Containers exist and are stopped - just start them
+    info!(target: "start_env", "Found {} stopped containers: {:?}", containers.len(), containers);
 
-        // Find uv executable (assumes uv is installed via prerequisites)
-        let uv_cmd = find_uv_executable();
-        debug_log.push(format!("Using uv at: {}", uv_cmd));
+    let container_names = containers.join(" ");
+    let start_command = container_runtime().command(&format!("start {}", container_names));
+    info!(target: "start_env", "Starting containers: {}", start_command);
 
-        // Verify uv is accessible
-        let uv_check = if uv_cmd == "uv" {
-            // If using PATH, verify with --version
-            shell_command("uv --version").output().is_ok()
-        } else {
-            // If using specific path, verify file exists
-            std::path::Path::new(&uv_cmd).exists()
-        };
+    let output = output_cancellable(&mut shell_command(&start_command), cancel)
+        .map_err(|e| format!("Failed to start containers (docker not found or not executable): {}", e))?
+        .ok_or_else(|| OPERATION_CANCELLED.to_string())?;
 
-        if !uv_check {
-            let error_msg = format!(
-                "uv not found or not accessible (tried: {})\n\nPlease install uv via the Prerequisites panel before starting an environment.",
-                uv_cmd
-            );
-            status_log.push(error_msg.clone());
-            debug_log.push(error_msg);
-            return Err(format!("{}\n\n=== Debug Log ===\n{}",
-                status_log.join("\n"),
-                debug_log.join("\n")));
-        }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    info!(target: "start_env", "Start result - stdout: {}, stderr: {}", stdout, stderr);
 
-        // Run setup with uv in dev mode with calculated port offset
-        // Note: Removed --skip-admin flag so admin user can be auto-created from secrets.yaml
-        status_log.push(format!("Running setup script..."));
-
-        // Check if this worktree has a .launcher-config.yaml with a custom setup command
-        let mut setup_command = None;
-        let config_path = Path::new(&working_dir).join(".launcher-config.yaml");
-        if config_path.exists() {
-            debug_log.push(format!("Found .launcher-config.yaml at: {:?}", config_path));
-            match LauncherConfig::load(&Path::new(&working_dir).to_path_buf()) {
-                Ok(config) => {
-                    if !config.setup.command.is_empty() {
-                        setup_command = Some(config.setup.command.clone());
-                        debug_log.push(format!("Using custom setup command from config: {}", config.setup.command));
-                    } else {
-                        debug_log.push(format!("Config exists but setup.command is empty, falling back to default"));
-                    }
-                }
-                Err(e) => {
-                    debug_log.push(format!("Failed to load config: {}, falling back to default", e));
-                }
-            }
-        } else {
-            debug_log.push(format!("No .launcher-config.yaml found, using default ushadow setup"));
-        }
+    if !output.status.success() {
+        return Err(format!("Failed to start containers: {}", stderr));
+    }
 
-        let command = if let Some(custom_command) = setup_command {
-            // Use the custom command from the config
-            custom_command
-        } else {
-            // Fall back to default ushadow setup
-            // Get bundled setup scripts if available
-            let bundled_setup_dir = bundled::get_setup_dir(&working_dir);
+    let started: Vec<&str> = stdout.lines().collect();
+    Ok(format!("Environment '{}' started: {} containers ({})",
+        env_name,
+        started.len(),
+        started.join(", ")
+    ))
+}
 
-            // Copy bundled setup to working directory if it's from the bundled location
-            // This avoids permission issues on Windows where Program Files requires admin
-            let working_setup_dir = std::path::Path::new(&working_dir).join("setup");
+/// Stop all containers belonging to an environment, returning their names
+fn stop_environment_containers(env_name: &str, services: &[String]) -> Result<Vec<String>, String> {
+    // Find all containers for this environment by name pattern
+    // Get matching container names
+    let output = shell_command(&container_runtime().command("ps -a --format '{{.Names}}'"))
+        .output()
+        .map_err(|e| format!("Failed to list containers (docker not found or not executable): {}", e))?;
 
-            if bundled_setup_dir != working_setup_dir {
-                debug_log.push(format!("Copying bundled setup from {:?} to {:?}", bundled_setup_dir, working_setup_dir));
+    if !output.status.success() {
+        return Err("Failed to list containers".to_string());
+    }
 
-                // Recursively copy the entire setup directory
-                if let Err(e) = copy_dir_recursive(&bundled_setup_dir, &working_setup_dir) {
-                    debug_log.push(format!("Warning: Failed to copy setup directory: {}", e));
-                    // Continue anyway - might be a partial copy that still works
-                } else {
-                    debug_log.push(format!("[OK] Bundled setup copied successfully"));
-                }
-            }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let containers: Vec<String> = stdout
+        .lines()
+        .filter(|name| container_belongs_to_env(name, env_name, services))
+        .map(|name| name.to_string())
+        .collect();
 
-            let run_py_path = working_setup_dir.join("run.py");
-            let run_py_quoted = quote_path_buf(&run_py_path);
+    if containers.is_empty() {
+        return Ok(containers);
+    }
 
-            debug_log.push(format!("Using setup script: {:?}", run_py_path));
-            debug_log.push(format!("Running: {} run --with pyyaml {} --dev --quick", uv_cmd, run_py_quoted));
+    // Stop all matching containers
+    let container_names = containers.join(" ");
+    let stop_command = container_runtime().command(&format!("stop {}", container_names));
 
-            format!("{} run --with pyyaml {} --dev --quick", uv_cmd, run_py_quoted)
-        };
+    let output = shell_command(&stop_command)
+        .output()
+        .map_err(|e| format!("Failed to stop containers (docker not found or not executable): {}", e))?;
 
-        // Build the full command string using platform abstraction
-        // Pass PORT_OFFSET for compatibility with both old and new setup scripts
-        let mut env_vars = HashMap::new();
-        env_vars.insert("ENV_NAME".to_string(), env_name.clone());
-        env_vars.insert("PORT_OFFSET".to_string(), port_offset.to_string());
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Stop failed: {}", stderr));
+    }
 
-        let setup_command = Platform::build_env_command(&working_dir, env_vars, &command);
+    Ok(containers)
+}
 
-        let output = shell_command(&setup_command)
-            .current_dir(&working_dir)  // Run from working_dir so setup script finds correct PROJECT_ROOT
-            .output()
-            .map_err(|e| {
-                let full_log = format!("{}\n\n=== Debug Log ===\n{}",
-                    status_log.join("\n"),
-                    debug_log.join("\n"));
-                format!("{}\n\nFailed to run setup (uv not found at '{}'. Try installing manually: https://docs.astral.sh/uv/getting-started/installation/): {}", full_log, uv_cmd, e)
-            })?;
+/// Stop a specific environment by name
+#[tauri::command]
+pub async fn stop_environment(state: State<'_, AppState>, env_name: String) -> Result<String, String> {
+    let project_root = state.project_root.lock().map_err(|e| e.to_string())?.clone();
+    let services = environment_services(project_root.as_deref());
 
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
+    let containers = stop_environment_containers(&env_name, &services)?;
 
-        if !stdout.is_empty() {
-            debug_log.push(format!("Setup stdout:\n{}", stdout));
-        }
-        if !stderr.is_empty() {
-            debug_log.push(format!("Setup stderr:\n{}", stderr));
-        }
+    if containers.is_empty() {
+        return Ok(format!("No containers found for environment '{}'", env_name));
+    }
 
-        if !output.status.success() {
-            // Get the full error message, not just the last line
-            let error_msg = if !stderr.is_empty() {
-                stderr.to_string()
-            } else {
-                stdout.to_string()
-            };
+    Ok(format!("Environment '{}' stopped ({} containers)", env_name, containers.len()))
+}
 
-            // Show last 10 lines of error for better context
-            let error_lines: Vec<&str> = error_msg.lines().collect();
-            let context_lines = if error_lines.len() > 10 {
-                &error_lines[error_lines.len()-10..]
-            } else {
-                &error_lines[..]
-            };
+/// Restart policies accepted by `set_restart_policy`
+const RESTART_POLICIES: &[&str] = &["no", "on-failure", "always"];
 
-            // On error, show both status and debug logs
-            let full_log = format!("{}\n\n=== Debug Log ===\n{}",
-                status_log.join("\n"),
-                debug_log.join("\n"));
+fn validate_restart_policy(policy: &str) -> Result<(), String> {
+    if RESTART_POLICIES.contains(&policy) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid restart policy '{}' (expected one of: {})",
+            policy,
+            RESTART_POLICIES.join(", ")
+        ))
+    }
+}
 
-            return Err(format!(
-                "{}\n\n❌ Failed to initialize environment '{}'\n\nError output:\n{}",
-                full_log,
-                env_name,
-                context_lines.join("\n")
-            ));
-        }
+/// Change a container's restart policy, e.g. to `no` so a crash-looping container stays down
+#[tauri::command]
+pub async fn set_restart_policy(container_name: String, policy: String) -> Result<(), String> {
+    validate_restart_policy(&policy)?;
 
-        // On success, only show status log
-        status_log.push(format!("[OK] Environment '{}' initialized and started", env_name));
-        return Ok(status_log.join("\n"));
+    let runtime = container_runtime().binary();
+    let exists = silent_command(runtime)
+        .args(["inspect", "--format", "{{.Name}}", &container_name])
+        .output()
+        .map_err(|e| format!("Failed to inspect container (docker not found or not executable): {}", e))?;
+    if !exists.status.success() {
+        return Err(format!("Container '{}' not found", container_name));
     }
 
-    // Containers exist and are stopped - just start them
-    eprintln!("[start_env] Found {} stopped containers: {:?}", containers.len(), containers);
+    let output = silent_command(runtime)
+        .args(["update", &format!("--restart={}", policy), &container_name])
+        .output()
+        .map_err(|e| format!("Failed to update container: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to set restart policy on '{}': {}",
+            container_name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
 
-    let container_names = containers.join(" ");
-    let start_command = format!("docker start {}", container_names);
-    eprintln!("[start_env] Starting containers: {}", start_command);
+    info!(target: "set_restart_policy", "Set restart policy of {} to {}", container_name, policy);
+    Ok(())
+}
 
-    let output = shell_command(&start_command)
-        .output()
-        .map_err(|e| format!("Failed to start containers (docker not found or not executable): {}", e))?;
+/// Stop an environment and rebuild its images, then recreate its containers
+#[tauri::command]
+pub async fn rebuild_environment(state: State<'_, AppState>, env_name: String, env_path: Option<String>, no_cache: bool) -> Result<String, String> {
+    info!(target: "rebuild_env", "Rebuilding environment: {} (no_cache: {})", env_name, no_cache);
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    eprintln!("[start_env] Start result - stdout: {}, stderr: {}", stdout, stderr);
+    let root = state.project_root.lock().map_err(|e| e.to_string())?;
+    let project_root = root.clone().ok_or("Project root not set")?;
+    drop(root);
 
-    if !output.status.success() {
-        return Err(format!("Failed to start containers: {}", stderr));
-    }
+    // Use env_path if provided (for worktrees), otherwise use project_root
+    let working_dir = env_path.unwrap_or_else(|| project_root.clone());
+    let services = environment_services(Some(&project_root));
 
-    let started: Vec<&str> = stdout.lines().collect();
-    Ok(format!("Environment '{}' started: {} containers ({})",
-        env_name,
-        started.len(),
-        started.join(", ")
-    ))
+    let stopped = stop_environment_containers(&env_name, &services)?;
+    info!(target: "rebuild_env", "Stopped {} containers: {:?}", stopped.len(), stopped);
+
+    run_environment_setup(&env_name, &working_dir, SetupMode::Rebuild { no_cache }, &[], &CancelToken::default())
 }
 
-/// Stop a specific environment by name
+/// Get recent logs for every container in an environment
 #[tauri::command]
-pub async fn stop_environment(_state: State<'_, AppState>, env_name: String) -> Result<String, String> {
-    // Find all containers for this environment by name pattern
-    let pattern = if env_name == "default" || env_name == "ushadow" {
-        // Default/ushadow env uses ushadow-backend, ushadow-webui pattern
-        "ushadow-".to_string()
-    } else {
-        format!("ushadow-{}-", env_name)
-    };
+pub async fn get_environment_logs(state: State<'_, AppState>, env_name: String, tail: Option<usize>, since: Option<String>) -> Result<Vec<ContainerLogs>, String> {
+    let project_root = state.project_root.lock().map_err(|e| e.to_string())?.clone();
+    let services = environment_services(project_root.as_deref());
 
-    // Get matching container names
-    let output = shell_command("docker ps -a --format '{{.Names}}'")
+    let output = shell_command(&container_runtime().command("ps -a --format '{{.Names}}'"))
         .output()
         .map_err(|e| format!("Failed to list containers (docker not found or not executable): {}", e))?;
 
@@ -625,45 +910,101 @@ pub async fn stop_environment(_state: State<'_, AppState>, env_name: String) ->
     let stdout = String::from_utf8_lossy(&output.stdout);
     let containers: Vec<&str> = stdout
         .lines()
-        .filter(|name| {
-            if env_name == "default" || env_name == "ushadow" {
-                // For default env, match ushadow-{service} or ushadow-{service}-{number}
-                // but NOT ushadow-{envname}-{service}
-                if !name.starts_with(&pattern) {
-                    return false;
-                }
+        .filter(|name| container_belongs_to_env(name, &env_name, &services))
+        .collect();
 
-                // Check if this is a default env container by checking if it matches known services
-                let after_prefix = &name[pattern.len()..];
-                let services = ["backend", "webui", "frontend", "worker", "tailscale"];
+    info!(target: "get_environment_logs", "Found {} containers for '{}'", containers.len(), env_name);
 
-                services.iter().any(|service| {
-                    after_prefix == *service || after_prefix.starts_with(&format!("{}-", service))
-                })
-            } else {
-                name.starts_with(&pattern)
+    let tail = tail.unwrap_or(200).to_string();
+    let mut logs = Vec::new();
+
+    for container in containers {
+        let mut args = vec!["logs", "--tail", tail.as_str()];
+        if let Some(ref since) = since {
+            args.push("--since");
+            args.push(since.as_str());
+        }
+        args.push(container);
+
+        let output = silent_command(container_runtime().binary())
+            .args(&args)
+            .output()
+            .map_err(|e| format!("Failed to get logs for {}: {}", container, e))?;
+
+        // `docker logs` replays the container's stdout and stderr on the matching streams
+        let mut lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.to_string())
+            .collect();
+        lines.extend(String::from_utf8_lossy(&output.stderr).lines().map(|l| l.to_string()));
+
+        logs.push(ContainerLogs {
+            container: container.to_string(),
+            lines,
+        });
+    }
+
+    Ok(logs)
+}
+
+/// Pick the `ushadow-*` containers whose environment has no worktree.
+/// `ps_lines` are `{name}\t{compose project}` rows; the default env and infra are never orphans.
+fn find_orphaned_containers(ps_lines: &str, worktree_names: &HashSet<String>, services: &[String]) -> Vec<String> {
+    ps_lines
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?.trim();
+            let project = fields.next().map(str::trim).filter(|p| !p.is_empty() && *p != "<no value>");
+
+            if project == Some("infra") {
+                return None;
+            }
+            let env_name = project
+                .and_then(env_name_from_compose_project)
+                .or_else(|| env_name_from_container_name(name, services))?;
+            if env_name == "ushadow" || env_name == "infra" || worktree_names.contains(&env_name) {
+                return None;
             }
+            Some(name.to_string())
         })
-        .collect();
+        .collect()
+}
 
-    if containers.is_empty() {
-        return Ok(format!("No containers found for environment '{}'", env_name));
+/// Remove `ushadow-*` containers left behind by deleted worktrees (only reports them when `dry_run`)
+#[tauri::command]
+pub async fn remove_orphaned_containers(main_repo: String, dry_run: bool) -> Result<Vec<String>, String> {
+    let worktrees = list_worktrees(main_repo.clone(), None, None).await?;
+    let worktree_names: HashSet<String> = worktrees.into_iter().map(|wt| wt.name).collect();
+    let services = environment_services(Some(&main_repo));
+
+    let output = silent_command(container_runtime().binary())
+        .args(["ps", "-a", "--filter", "name=ushadow-", "--format", "{{.Names}}\t{{.Label \"com.docker.compose.project\"}}"])
+        .output()
+        .map_err(|e| format!("Failed to list containers: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to list containers: {}", String::from_utf8_lossy(&output.stderr)));
     }
 
-    // Stop all matching containers
-    let container_names = containers.join(" ");
-    let stop_command = format!("docker stop {}", container_names);
+    let orphans = find_orphaned_containers(&String::from_utf8_lossy(&output.stdout), &worktree_names, &services);
+    info!(target: "remove_orphaned_containers", "Found {} orphaned containers: {:?}", orphans.len(), orphans);
 
-    let output = shell_command(&stop_command)
+    if dry_run || orphans.is_empty() {
+        return Ok(orphans);
+    }
+
+    let output = silent_command(container_runtime().binary())
+        .args(["rm", "-f"])
+        .args(&orphans)
         .output()
-        .map_err(|e| format!("Failed to stop containers (docker not found or not executable): {}", e))?;
+        .map_err(|e| format!("Failed to remove containers: {}", e))?;
 
     if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Stop failed: {}", stderr));
+        return Err(format!("Failed to remove containers: {}", String::from_utf8_lossy(&output.stderr)));
     }
 
-    Ok(format!("Environment '{}' stopped ({} containers)", env_name, containers.len()))
+    Ok(orphans)
 }
 
 /// Legacy: Start Docker containers (starts infra)
@@ -695,7 +1036,7 @@ pub fn get_container_status(state: State<AppState>) -> Result<ContainerStatus, S
     };
     drop(root);
 
-    let output = shell_command("docker compose ps --format '{{.Name}}\t{{.Status}}\t{{.Ports}}'")
+    let output = shell_command(&container_runtime().compose_command("ps --format '{{.Name}}\t{{.Status}}\t{{.Ports}}'"))
         .current_dir(&project_root)
         .output()
         .map_err(|e| format!("Failed to get status (docker not found or not executable): {}", e))?;
@@ -733,60 +1074,97 @@ pub fn get_container_status(state: State<AppState>) -> Result<ContainerStatus, S
     })
 }
 
+/// Timeout for launcher health probes
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// GET a URL and return its status code, or None if the request failed (refused, timed out, ...)
+async fn probe_http_status(url: &str) -> Option<u16> {
+    let client = reqwest::Client::builder()
+        .timeout(HEALTH_CHECK_TIMEOUT)
+        .build()
+        .ok()?;
+
+    client.get(url).send().await.ok().map(|r| r.status().as_u16())
+}
+
 /// Check if backend API is healthy
 #[tauri::command]
 pub async fn check_backend_health(port: u16) -> Result<bool, String> {
     let url = format!("http://localhost:{}/health", port);
-
-    let output = silent_command("curl")
-        .args(["-s", "-o", "/dev/null", "-w", "%{http_code}", "--max-time", "2", &url])
-        .output();
-
-    match output {
-        Ok(out) => {
-            let code = String::from_utf8_lossy(&out.stdout);
-            Ok(code.trim() == "200")
-        }
-        Err(_) => Ok(false),
-    }
+    Ok(probe_http_status(&url).await == Some(200))
 }
 
 /// Check if web UI is responding
 #[tauri::command]
 pub async fn check_webui_health(port: u16) -> Result<bool, String> {
     let url = format!("http://localhost:{}", port);
-
-    let output = silent_command("curl")
-        .args(["-s", "-o", "/dev/null", "-w", "%{http_code}", "--max-time", "2", &url])
-        .output();
-
-    match output {
-        Ok(out) => {
-            let code = String::from_utf8_lossy(&out.stdout);
-            let code_num = code.trim();
-            // Accept any 2xx or 3xx response (web UI is serving)
-            Ok(code_num.starts_with('2') || code_num.starts_with('3'))
-        }
-        Err(_) => Ok(false),
-    }
+    // Redirects are followed, so any 2xx means the web UI is serving
+    Ok(matches!(probe_http_status(&url).await, Some(code) if (200..300).contains(&code)))
 }
 
 /// Focus the main window (bring to foreground)
 #[tauri::command]
 pub fn focus_window(window: tauri::Window) -> Result<(), String> {
+    // A minimized or hidden window ignores set_focus, so restore it first
+    if window.is_minimized().unwrap_or(false) {
+        window.unminimize().map_err(|e| e.to_string())?;
+    }
+    if !window.is_visible().unwrap_or(true) {
+        window.show().map_err(|e| e.to_string())?;
+    }
+
+    // Windows/Linux focus-stealing prevention can leave the window behind others;
+    // briefly pinning it on top forces it to the front
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = window.set_always_on_top(true);
+    }
+
     window.set_focus().map_err(|e| e.to_string())?;
 
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = window.set_always_on_top(false);
+    }
+
     // On macOS, also activate the app to ensure it comes to front
     #[cfg(target_os = "macos")]
     {
+        use tauri::Manager;
+        let app_name = window.app_handle().package_info().name.clone();
         let _ = silent_command("osascript")
-            .args(["-e", "tell application \"Ushadow Launcher\" to activate"])
+            .args(["-e", &activate_app_script(&app_name)])
             .spawn();
     }
 
     Ok(())
 }
 
+/// Relaunch the launcher (for settings that only apply at startup)
+#[tauri::command]
+pub async fn relaunch_app(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    info!(target: "relaunch_app", "Relaunching launcher");
+
+    // Wait for any command holding app state to finish before tearing the process down
+    drop(state.project_root.lock().map_err(|e| e.to_string())?);
+    drop(state.config.lock().map_err(|e| e.to_string())?);
+
+    if let Err(e) = flush_kanban_database() {
+        warn!(target: "relaunch_app", "{}", e);
+    }
+
+    // AppHandle::restart runs the app's cleanup (tray, windows) before relaunching
+    app.restart();
+    Ok(())
+}
+
+/// AppleScript that activates an app by name (name comes from the Tauri package config)
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn activate_app_script(app_name: &str) -> String {
+    let escaped = app_name.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("tell application \"{}\" to activate", escaped)
+}
+
 /// Open URL in default browser
 #[tauri::command]
 pub fn open_browser(url: String) -> Result<(), String> {
@@ -973,16 +1351,90 @@ pub fn get_infra_services_from_compose(state: State<AppState>) -> Result<Vec<Inf
             display_name,
             running,
             ports: ports_str,
+            health: None,
         });
     }
 
     Ok(result)
 }
 
+/// Structural problems in a compose file that would stop it from loading
+fn validate_compose_yaml(contents: &str) -> Vec<String> {
+    let yaml: Value = match serde_yaml::from_str(contents) {
+        Ok(yaml) => yaml,
+        Err(e) => return vec![format!("Failed to parse compose YAML: {}", e)],
+    };
+
+    if !yaml.is_mapping() {
+        return vec!["Compose file is not a mapping".to_string()];
+    }
+
+    let services = match yaml.get("services") {
+        Some(services) => services,
+        None => return vec!["No 'services' section found in compose file".to_string()],
+    };
+    let services = match services.as_mapping() {
+        Some(services) => services,
+        None => return vec!["'services' is not a mapping".to_string()],
+    };
+
+    let mut errors = Vec::new();
+    for (service_id, service_config) in services {
+        match service_id.as_str() {
+            Some(name) if !service_config.is_mapping() => {
+                errors.push(format!("Service '{}' is not a mapping", name));
+            }
+            Some(_) => {}
+            None => errors.push("Service name is not a string".to_string()),
+        }
+    }
+    errors
+}
+
+/// Validate a compose file before using it: our own YAML parse plus `docker compose config`
+#[tauri::command]
+pub async fn validate_compose(project_root: String, file: String) -> Result<ComposeValidation, String> {
+    let compose_path = if Path::new(&file).is_absolute() {
+        std::path::PathBuf::from(&file)
+    } else {
+        Path::new(&project_root).join(&file)
+    };
+
+    let contents = std::fs::read_to_string(&compose_path)
+        .map_err(|e| format!("Failed to read compose file {}: {}", compose_path.display(), e))?;
+    let parse_errors = validate_compose_yaml(&contents);
+
+    let output = silent_command(container_runtime().binary())
+        .args(["compose", "-f"])
+        .arg(&compose_path)
+        .args(["config", "--quiet"])
+        .current_dir(&project_root)
+        .output();
+
+    let (docker_checked, docker_error) = match output {
+        Ok(output) if output.status.success() => (true, None),
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            (true, Some(if stderr.is_empty() { "docker compose config failed".to_string() } else { stderr }))
+        }
+        Err(e) => {
+            warn!(target: "validate_compose", "Could not run docker compose config: {}", e);
+            (false, None)
+        }
+    };
+
+    Ok(ComposeValidation {
+        valid: parse_errors.is_empty() && docker_error.is_none(),
+        parse_errors,
+        docker_checked,
+        docker_error,
+    })
+}
+
 /// Check if a service container is running
 fn check_service_running(service_name: &str) -> bool {
     // Check if container exists and is running
-    let output = silent_command("docker")
+    let output = silent_command(container_runtime().binary())
         .args([
             "ps",
             "--filter",
@@ -1006,6 +1458,7 @@ fn check_service_running(service_name: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::discovery::{load_compose_environment_services, DEFAULT_ENV_SERVICES};
 
     #[test]
     fn test_app_state_creation() {
@@ -1013,4 +1466,214 @@ mod tests {
         let root = state.project_root.lock().unwrap();
         assert!(root.is_none());
     }
+
+    #[test]
+    fn test_colliding_env_names_get_distinct_offsets() {
+        // Anagrams have the same byte sum, so the hash hint collides
+        assert_eq!(hashed_port_offset("gold"), hashed_port_offset("dlog"));
+
+        let mut used = HashSet::new();
+        let first = allocate_port_offset("gold", None, &used);
+        used.insert(first);
+        let second = allocate_port_offset("dlog", None, &used);
+
+        assert_ne!(first, second);
+        assert_ne!(second, 0);
+        assert_eq!(second % 10, 0);
+    }
+
+    #[test]
+    fn test_persisted_offset_preferred_when_free() {
+        let used: HashSet<u16> = [10, 20].into_iter().collect();
+        assert_eq!(allocate_port_offset("gold", Some(70), &used), 70);
+        // Persisted offset taken by another env -> falls back
+        assert_ne!(allocate_port_offset("gold", Some(20), &used), 20);
+    }
+
+    #[test]
+    fn test_parse_published_host_ports() {
+        assert_eq!(
+            parse_published_host_ports("0.0.0.0:8010->8000/tcp, :::8010->8000/tcp"),
+            vec![8010, 8010]
+        );
+        assert_eq!(parse_published_host_ports("8000/tcp"), Vec::<u16>::new());
+        assert_eq!(port_to_offset(3020), Some(20));
+        assert_eq!(port_to_offset(8015), Some(10));
+        assert_eq!(port_to_offset(5432), None);
+    }
+
+    #[test]
+    fn test_container_env_filter_default_vs_named() {
+        let services: Vec<String> = DEFAULT_ENV_SERVICES.iter().map(|s| s.to_string()).collect();
+
+        // Default env only matches known services directly under the ushadow- prefix
+        assert!(container_belongs_to_env("ushadow-backend", "default", &services));
+        assert!(container_belongs_to_env("ushadow-webui-1", "ushadow", &services));
+        assert!(!container_belongs_to_env("ushadow-gold-backend", "default", &services));
+        assert!(!container_belongs_to_env("mongo", "default", &services));
+
+        // Named env matches anything under ushadow-{env}-
+        assert!(container_belongs_to_env("ushadow-gold-backend", "gold", &services));
+        assert!(container_belongs_to_env("ushadow-gold-webui-1", "gold", &services));
+        assert!(!container_belongs_to_env("ushadow-backend", "gold", &services));
+        assert!(!container_belongs_to_env("ushadow-silver-backend", "gold", &services));
+    }
+
+    #[test]
+    fn test_container_env_filter_skips_service_containers() {
+        let services: Vec<String> = DEFAULT_ENV_SERVICES.iter().map(|s| s.to_string()).collect();
+        let ps = ["ushadow-gold-backend",
+                  "ushadow-gold-webui-1",
+                  "ushadow-gold-mem0-backend-3f9a2c",
+                  "ushadow-gold-chronicle-backend-a1b2c3",
+                  "ushadow-golden-backend",
+                  "ushadow-backend",
+                  "ushadow-mem0-backend-3f9a2c"];
+
+        let select = |env: &str| -> Vec<&str> {
+            ps.iter().copied().filter(|name| container_belongs_to_env(name, env, &services)).collect()
+        };
+        assert_eq!(select("gold"), vec!["ushadow-gold-backend", "ushadow-gold-webui-1"]);
+        assert_eq!(select("golden"), vec!["ushadow-golden-backend"]);
+        assert_eq!(select("ushadow"), vec!["ushadow-backend"]);
+    }
+
+    #[test]
+    fn test_find_orphaned_containers() {
+        let services: Vec<String> = DEFAULT_ENV_SERVICES.iter().map(|s| s.to_string()).collect();
+        let worktrees: HashSet<String> = ["gold".to_string(), "my-feature".to_string()].into_iter().collect();
+        let ps = "ushadow-backend\tushadow\n\
+                  ushadow-gold-backend\tushadow-gold\n\
+                  ushadow-my-feature-webui\t<no value>\n\
+                  ushadow-silver-backend\tushadow-silver\n\
+                  ushadow-old-thing-worker-1\t\n\
+                  ushadow-infra-redis\tinfra\n";
+
+        assert_eq!(
+            find_orphaned_containers(ps, &worktrees, &services),
+            vec!["ushadow-silver-backend", "ushadow-old-thing-worker-1"]
+        );
+        assert!(find_orphaned_containers("", &worktrees, &services).is_empty());
+    }
+
+    #[test]
+    fn test_compose_services_targeted_by_start_stop() {
+        let root = std::env::temp_dir().join(format!("ushadow-services-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("compose")).unwrap();
+        std::fs::write(
+            root.join("docker-compose.yml"),
+            "name: acme\ninclude:\n  - compose/app.yml\n  - path: compose/extra.yml\n",
+        ).unwrap();
+        std::fs::write(root.join("compose/app.yml"), "services:\n  api:\n    image: x\n  gateway:\n    image: x\n").unwrap();
+        std::fs::write(root.join("compose/extra.yml"), "services:\n  scheduler:\n    image: x\n").unwrap();
+
+        let compose = load_compose_environment_services(root.to_str().unwrap());
+        assert_eq!(compose, vec!["api", "gateway", "scheduler"]);
+
+        // Built-in services stay, compose services are added
+        let mut services: Vec<String> = DEFAULT_ENV_SERVICES.iter().map(|s| s.to_string()).collect();
+        services.extend(compose);
+
+        assert!(container_belongs_to_env("ushadow-gateway", "default", &services));
+        assert!(container_belongs_to_env("ushadow-scheduler-1", "ushadow", &services));
+        assert!(container_belongs_to_env("ushadow-backend", "default", &services));
+        assert!(!container_belongs_to_env("ushadow-gold-gateway", "default", &services));
+        assert!(container_belongs_to_env("ushadow-gold-gateway", "gold", &services));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    /// Serve a fixed status on /health and / from an ephemeral local port
+    fn spawn_status_server(code: u16) -> u16 {
+        use warp::Filter;
+        let status = warp::http::StatusCode::from_u16(code).unwrap();
+        let route = warp::any().map(move || warp::reply::with_status("ok", status));
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        addr.port()
+    }
+
+    /// A local port with nothing listening on it
+    fn closed_port() -> u16 {
+        TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+    }
+
+    #[tokio::test]
+    async fn test_health_checks_ok() {
+        let port = spawn_status_server(200);
+        assert!(check_backend_health(port).await.unwrap());
+        assert!(check_webui_health(port).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_health_checks_server_error() {
+        let port = spawn_status_server(500);
+        assert!(!check_backend_health(port).await.unwrap());
+        assert!(!check_webui_health(port).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_health_checks_connection_refused() {
+        let port = closed_port();
+        assert_eq!(check_backend_health(port).await, Ok(false));
+        assert_eq!(check_webui_health(port).await, Ok(false));
+    }
+
+    #[test]
+    fn test_activate_app_script_uses_given_name() {
+        assert_eq!(activate_app_script("Ushadow"), "tell application \"Ushadow\" to activate");
+        assert_eq!(activate_app_script("My \"Dev\" App"), "tell application \"My \\\"Dev\\\" App\" to activate");
+    }
+
+    #[test]
+    fn test_setup_script_command_modes() {
+        let run_py = "'/repo/setup/run.py'";
+        assert_eq!(
+            build_setup_script_command("uv", run_py, SetupMode::Initialize),
+            "uv run --with pyyaml '/repo/setup/run.py' --dev --quick"
+        );
+        assert_eq!(
+            build_setup_script_command("uv", run_py, SetupMode::Rebuild { no_cache: false }),
+            "uv run --with pyyaml '/repo/setup/run.py' --build"
+        );
+        assert_eq!(
+            build_setup_script_command("/opt/uv", run_py, SetupMode::Rebuild { no_cache: true }),
+            "/opt/uv run --with pyyaml '/repo/setup/run.py' --build --no-cache"
+        );
+    }
+
+    #[test]
+    fn test_validate_compose_yaml() {
+        assert!(validate_compose_yaml("services:\n  redis:\n    image: redis:7\n").is_empty());
+        assert!(validate_compose_yaml("services: [\n")[0].starts_with("Failed to parse compose YAML"));
+        assert_eq!(validate_compose_yaml("- a\n- b\n"), vec!["Compose file is not a mapping".to_string()]);
+        assert_eq!(validate_compose_yaml("version: '3'\n"), vec!["No 'services' section found in compose file".to_string()]);
+        assert_eq!(validate_compose_yaml("services: redis\n"), vec!["'services' is not a mapping".to_string()]);
+        assert_eq!(
+            validate_compose_yaml("services:\n  redis: redis:7\n  postgres:\n    image: postgres\n"),
+            vec!["Service 'redis' is not a mapping".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_validate_restart_policy() {
+        for policy in ["no", "on-failure", "always"] {
+            assert!(validate_restart_policy(policy).is_ok());
+        }
+        for policy in ["", "never", "unless-stopped", "Always"] {
+            assert!(validate_restart_policy(policy).is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_compose_profiles() {
+        let declared = vec!["gpu".to_string(), "infra".to_string()];
+        assert!(validate_compose_profiles(&[], &declared).is_ok());
+        assert!(validate_compose_profiles(&["gpu".to_string()], &declared).is_ok());
+
+        let err = validate_compose_profiles(&["gpu".to_string(), "cuda".to_string()], &declared).unwrap_err();
+        assert_eq!(err, "Unknown compose profile(s): cuda (declared: gpu, infra)");
+        let err = validate_compose_profiles(&["gpu".to_string()], &[]).unwrap_err();
+        assert!(err.ends_with("(declared: none)"));
+    }
 }
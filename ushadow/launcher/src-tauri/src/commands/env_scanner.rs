@@ -1,6 +1,12 @@
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Serialize, Deserialize};
+use super::container_runtime::container_runtime;
+use super::discovery::{environment_services, running_infra_ports};
+use super::docker::container_belongs_to_env;
+use super::repository::get_current_branch;
+use super::utils::{is_secret_name, shell_command};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DetectedPort {
@@ -19,6 +25,304 @@ pub struct DetectedEnvVar {
     pub should_append_env_name: bool,  // For DB names, user names, etc.
 }
 
+/// One side of an environment comparison
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EnvSide {
+    pub path: String,
+    pub env_name: String,
+    pub branch: Option<String>,
+    /// Running containers belonging to this environment
+    pub containers: Vec<String>,
+}
+
+/// A `.env` key present in both environments with different values
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EnvValueDiff {
+    pub key: String,
+    pub value_a: String,
+    pub value_b: String,
+}
+
+/// Side-by-side comparison of two environments' config, branch and running services
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EnvComparison {
+    pub a: EnvSide,
+    pub b: EnvSide,
+    pub same_branch: bool,
+    pub env_only_in_a: Vec<String>,
+    pub env_only_in_b: Vec<String>,
+    /// Differing values; secret-looking keys have both values redacted
+    pub env_differing: Vec<EnvValueDiff>,
+    pub services_only_in_a: Vec<String>,
+    pub services_only_in_b: Vec<String>,
+}
+
+const REDACTED: &str = "<redacted>";
+
+/// Parse KEY=value lines from a .env file (same rules as the scanners above)
+fn parse_env_file(path: &Path) -> BTreeMap<String, String> {
+    let content = fs::read_to_string(path).unwrap_or_default();
+
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && !line.is_empty())
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim().trim_matches('"').trim_matches('\'');
+            (key.trim().to_string(), value.to_string())
+        })
+        .collect()
+}
+
+/// Set `key=value` in a .env file, keeping comments, ordering and the key's quoting style.
+/// Appends the key if missing; the file is replaced atomically. Returns whether anything changed.
+pub(crate) fn set_env_var(path: &Path, key: &str, value: &str) -> Result<bool, String> {
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Invalid env var name: {:?}", key));
+    }
+    if value.contains('\n') || value.contains('\r') {
+        return Err(format!("Value for {} must be a single line", key));
+    }
+
+    let contents = fs::read_to_string(path).unwrap_or_default();
+    let mut found = false;
+    let mut changed = false;
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in contents.lines() {
+        let trimmed = line.trim_start();
+        let assignment = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+        let current = assignment.split_once('=').filter(|(k, _)| k.trim() == key).map(|(_, v)| v.trim());
+
+        match current {
+            Some(current) if !found => {
+                found = true;
+                let quote = ['"', '\''].into_iter().find(|q| current.len() >= 2 && current.starts_with(*q) && current.ends_with(*q));
+                let new_value = match quote {
+                    Some(q) => format!("{}{}{}", q, value, q),
+                    None => value.to_string(),
+                };
+                if new_value != current {
+                    changed = true;
+                    let export = if trimmed.starts_with("export ") { "export " } else { "" };
+                    lines.push(format!("{}{}={}", export, key, new_value));
+                    continue;
+                }
+                lines.push(line.to_string());
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    if !found {
+        lines.push(format!("{}={}", key, value));
+        changed = true;
+    }
+    if !changed {
+        return Ok(false);
+    }
+
+    let tmp = path.with_extension(format!("tmp-{}", std::process::id()));
+    fs::write(&tmp, lines.join("\n") + "\n")
+        .map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    fs::rename(&tmp, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Failed to replace {}: {}", path.display(), e)
+    })?;
+    Ok(true)
+}
+
+/// Hosts meaning "reach the service through its published port on this machine"
+const HOST_SIDE_HOSTS: &[&str] = &["", "localhost", "127.0.0.1", "0.0.0.0", "::1", "host.docker.internal"];
+
+/// `.env` key prefixes an infra service may be configured under (e.g. mongo -> MONGO_, MONGODB_)
+fn infra_env_prefixes(service: &str) -> Vec<String> {
+    let base = service.to_uppercase().replace('-', "_");
+    match base.as_str() {
+        "MONGO" | "MONGODB" => vec!["MONGO".to_string(), "MONGODB".to_string()],
+        "POSTGRES" | "POSTGRESQL" => vec!["POSTGRES".to_string(), "POSTGRESQL".to_string()],
+        _ => vec![base],
+    }
+}
+
+/// `*_HOST`/`*_PORT` assignments needed to point an env at the given infra host ports.
+/// Only keys already in the env are touched, and services addressed by container name
+/// (a non-local `*_HOST`) are left alone since they use the fixed in-network port.
+fn infra_env_updates(env: &BTreeMap<String, String>, infra_ports: &[(String, u16)]) -> Vec<(String, String)> {
+    let mut updates = Vec::new();
+
+    for (service, port) in infra_ports {
+        for prefix in infra_env_prefixes(service) {
+            let host_key = format!("{}_HOST", prefix);
+            let port_key = format!("{}_PORT", prefix);
+
+            if let Some(host) = env.get(&host_key) {
+                if !HOST_SIDE_HOSTS.contains(&host.as_str()) {
+                    continue;
+                }
+                if host.is_empty() {
+                    updates.push((host_key, "localhost".to_string()));
+                }
+            }
+
+            let port = port.to_string();
+            if env.get(&port_key).is_some_and(|current| *current != port) {
+                updates.push((port_key, port));
+            }
+        }
+    }
+    updates
+}
+
+/// Keys only in A, only in B, and keys whose values differ
+fn diff_env_maps(
+    a: &BTreeMap<String, String>,
+    b: &BTreeMap<String, String>,
+) -> (Vec<String>, Vec<String>, Vec<EnvValueDiff>) {
+    let only_in_a = a.keys().filter(|k| !b.contains_key(*k)).cloned().collect();
+    let only_in_b = b.keys().filter(|k| !a.contains_key(*k)).cloned().collect();
+
+    let differing = a
+        .iter()
+        .filter_map(|(key, value_a)| {
+            let value_b = b.get(key).filter(|value_b| *value_b != value_a)?;
+            let (value_a, value_b) = if is_secret_name(key) {
+                (REDACTED.to_string(), REDACTED.to_string())
+            } else {
+                (value_a.clone(), value_b.clone())
+            };
+            Some(EnvValueDiff { key: key.clone(), value_a, value_b })
+        })
+        .collect();
+
+    (only_in_a, only_in_b, differing)
+}
+
+/// Service part of a container name, so containers of different envs can be compared
+fn container_service_name<'a>(container: &'a str, env_name: &str) -> &'a str {
+    let default_env = env_name == "default" || env_name == "ushadow";
+    let prefix = if default_env { "ushadow-".to_string() } else { format!("ushadow-{}-", env_name) };
+    container.strip_prefix(prefix.as_str()).unwrap_or(container)
+}
+
+/// Environment name for a worktree: ENV_NAME from its .env, else the directory name
+fn env_name_for_path(path: &Path, env: &BTreeMap<String, String>) -> String {
+    env.get("ENV_NAME")
+        .filter(|name| !name.is_empty())
+        .cloned()
+        .unwrap_or_else(|| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("ushadow")
+                .to_string()
+        })
+}
+
+/// Compare two environments' .env files, git branches and running containers
+#[tauri::command]
+pub async fn diff_environments(path_a: String, path_b: String) -> Result<EnvComparison, String> {
+    for path in [&path_a, &path_b] {
+        if !Path::new(path).is_dir() {
+            return Err(format!("Environment path does not exist: {}", path));
+        }
+    }
+
+    let env_a = parse_env_file(&Path::new(&path_a).join(".env"));
+    let env_b = parse_env_file(&Path::new(&path_b).join(".env"));
+
+    let output = shell_command(&container_runtime().command("ps --format '{{.Names}}'"))
+        .output()
+        .map_err(|e| format!("Failed to list running containers: {}", e))?;
+    let running = String::from_utf8_lossy(&output.stdout).to_string();
+    let services = environment_services(Some(&path_a));
+
+    let side = |path: &str, env: &BTreeMap<String, String>| -> EnvSide {
+        let env_name = env_name_for_path(Path::new(path), env);
+        let containers = running
+            .lines()
+            .filter(|name| container_belongs_to_env(name, &env_name, &services))
+            .map(|name| name.to_string())
+            .collect();
+        EnvSide {
+            path: path.to_string(),
+            branch: get_current_branch(path.to_string()).ok(),
+            env_name,
+            containers,
+        }
+    };
+    let a = side(&path_a, &env_a);
+    let b = side(&path_b, &env_b);
+
+    let (env_only_in_a, env_only_in_b, env_differing) = diff_env_maps(&env_a, &env_b);
+
+    let services_a: BTreeSet<&str> = a.containers.iter().map(|c| container_service_name(c, &a.env_name)).collect();
+    let services_b: BTreeSet<&str> = b.containers.iter().map(|c| container_service_name(c, &b.env_name)).collect();
+    let services_only_in_a = services_a.difference(&services_b).map(|s| s.to_string()).collect();
+    let services_only_in_b = services_b.difference(&services_a).map(|s| s.to_string()).collect();
+
+    eprintln!(
+        "[diff_environments] {} vs {}: {} differing env values",
+        a.env_name, b.env_name, env_differing.len()
+    );
+
+    Ok(EnvComparison {
+        same_branch: a.branch.is_some() && a.branch == b.branch,
+        a,
+        b,
+        env_only_in_a,
+        env_only_in_b,
+        env_differing,
+        services_only_in_a,
+        services_only_in_b,
+    })
+}
+
+/// Point an environment's `.env` at the infra services' current host ports.
+/// Returns the keys that were changed.
+#[tauri::command]
+pub fn sync_infra_endpoints_to_env(worktree_path: String, project_root: String) -> Result<Vec<String>, String> {
+    let env_path = Path::new(&worktree_path).join(".env");
+    if !env_path.exists() {
+        return Err(format!("No .env found in {}", worktree_path));
+    }
+
+    let infra_ports = running_infra_ports(&project_root)?;
+    let env = parse_env_file(&env_path);
+
+    let mut updated = Vec::new();
+    for (key, value) in infra_env_updates(&env, &infra_ports) {
+        if set_env_var(&env_path, &key, &value)? {
+            eprintln!("[sync_infra_endpoints_to_env] {}={} in {}", key, value, env_path.display());
+            updated.push(key);
+        }
+    }
+    Ok(updated)
+}
+
+/// An environment's `.env`, copied from `.env.example` on first use.
+/// If there's no example either, the (not yet existing) `.env` path is returned.
+pub(crate) fn ensure_env_file(worktree_path: &Path) -> Result<PathBuf, String> {
+    if !worktree_path.is_dir() {
+        return Err(format!("Worktree not found: {}", worktree_path.display()));
+    }
+
+    let env_path = worktree_path.join(".env");
+    let example_path = worktree_path.join(".env.example");
+    if !env_path.exists() && example_path.exists() {
+        fs::copy(&example_path, &env_path)
+            .map_err(|e| format!("Failed to create .env from .env.example: {}", e))?;
+    }
+    Ok(env_path)
+}
+
+/// Path of an environment's `.env` (created from `.env.example` if missing)
+#[tauri::command]
+pub fn get_env_file_path(worktree_path: String) -> Result<String, String> {
+    let env_path = ensure_env_file(Path::new(&worktree_path))?;
+    Ok(env_path.to_string_lossy().to_string())
+}
+
 /// Scan .env.template, .env.example, or .env for port-related variables
 #[tauri::command]
 pub fn scan_env_file(project_root: String) -> Result<Vec<DetectedPort>, String> {
@@ -233,4 +537,113 @@ mod tests {
         assert!(!should_append_env_name("POSTGRES_PASSWORD"));
         assert!(!should_append_env_name("API_KEY"));
     }
+
+    #[test]
+    fn test_diff_env_maps_redacts_secret_values() {
+        let dir = std::env::temp_dir().join(format!("ushadow-env-diff-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.env"), "# comment\nBACKEND_PORT=8000\nAUTH_SECRET=\"abc\"\nSHARED=1\nONLY_A=x\n").unwrap();
+        fs::write(dir.join("b.env"), "BACKEND_PORT='8010'\nAUTH_SECRET=def\nSHARED=1\nONLY_B=y\n").unwrap();
+
+        let a = parse_env_file(&dir.join("a.env"));
+        let b = parse_env_file(&dir.join("b.env"));
+        let (only_a, only_b, differing) = diff_env_maps(&a, &b);
+
+        assert_eq!(only_a, vec!["ONLY_A"]);
+        assert_eq!(only_b, vec!["ONLY_B"]);
+        assert_eq!(differing, vec![
+            EnvValueDiff { key: "AUTH_SECRET".into(), value_a: REDACTED.into(), value_b: REDACTED.into() },
+            EnvValueDiff { key: "BACKEND_PORT".into(), value_a: "8000".into(), value_b: "8010".into() },
+        ]);
+        assert!(parse_env_file(&dir.join("missing.env")).is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_container_service_name_and_env_name() {
+        assert_eq!(container_service_name("ushadow-gold-backend", "gold"), "backend");
+        assert_eq!(container_service_name("ushadow-backend", "ushadow"), "backend");
+        assert_eq!(container_service_name("other", "gold"), "other");
+
+        let mut env = BTreeMap::new();
+        assert_eq!(env_name_for_path(Path::new("/w/ushadow/blue"), &env), "blue");
+        env.insert("ENV_NAME".to_string(), "gold".to_string());
+        assert_eq!(env_name_for_path(Path::new("/w/ushadow/blue"), &env), "gold");
+    }
+
+    #[test]
+    fn test_set_env_var_preserves_file() {
+        let dir = std::env::temp_dir().join(format!("ushadow-setenv-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(".env");
+        fs::write(&path, "# infra\nMONGODB_PORT=27017\nexport REDIS_PORT=\"6379\"\nENV_NAME=gold\n").unwrap();
+
+        assert_eq!(set_env_var(&path, "MONGODB_PORT", "27018"), Ok(true));
+        assert_eq!(set_env_var(&path, "REDIS_PORT", "6380"), Ok(true));
+        assert_eq!(set_env_var(&path, "ENV_NAME", "gold"), Ok(false));
+        assert_eq!(set_env_var(&path, "QDRANT_PORT", "6333"), Ok(true));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# infra\nMONGODB_PORT=27018\nexport REDIS_PORT=\"6380\"\nENV_NAME=gold\nQDRANT_PORT=6333\n"
+        );
+
+        assert!(set_env_var(&path, "BAD KEY", "1").is_err());
+        assert!(set_env_var(&path, "MULTI", "a\nb").is_err());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_infra_env_updates() {
+        let env: BTreeMap<String, String> = [
+            ("MONGODB_HOST", "localhost"),
+            ("MONGODB_PORT", "27017"),
+            ("REDIS_HOST", "redis"),
+            ("REDIS_PORT", "6379"),
+            ("POSTGRES_HOST", ""),
+            ("POSTGRES_PORT", "5433"),
+            ("QDRANT_PORT", "6333"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let infra = vec![
+            ("mongo".to_string(), 27018),
+            ("redis".to_string(), 6380),
+            ("postgres".to_string(), 5433),
+            ("qdrant".to_string(), 6333),
+            ("neo4j".to_string(), 7687),
+        ];
+
+        assert_eq!(
+            infra_env_updates(&env, &infra),
+            vec![
+                ("MONGODB_PORT".to_string(), "27018".to_string()),
+                ("POSTGRES_HOST".to_string(), "localhost".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ensure_env_file_copies_example() {
+        let dir = std::env::temp_dir().join(format!("ushadow-envfile-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // No example: path is resolved but nothing is created
+        assert_eq!(ensure_env_file(&dir), Ok(dir.join(".env")));
+        assert!(!dir.join(".env").exists());
+
+        fs::write(dir.join(".env.example"), "ENV_NAME=example\n").unwrap();
+        let env_path = ensure_env_file(&dir).unwrap();
+        assert_eq!(fs::read_to_string(&env_path).unwrap(), "ENV_NAME=example\n");
+
+        // An existing .env is never overwritten
+        fs::write(&env_path, "ENV_NAME=gold\n").unwrap();
+        ensure_env_file(&dir).unwrap();
+        assert_eq!(fs::read_to_string(&env_path).unwrap(), "ENV_NAME=gold\n");
+
+        assert!(ensure_env_file(&dir.join("missing")).is_err());
+        fs::remove_dir_all(&dir).ok();
+    }
 }
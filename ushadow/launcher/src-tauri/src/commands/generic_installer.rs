@@ -1,10 +1,41 @@
 use super::prerequisites_config::{PrerequisitesConfig, InstallationMethod};
 use super::utils::{shell_command, silent_command};
-use super::platform::{Platform, PlatformOps, current_platform};
+use super::platform::{Platform, PlatformOps, current_platform, is_supported_platform, unsupported_platform_error};
+use super::prerequisites::check_docker;
+use super::http_client::{fetch_with_retry, network_retry_attempts, RETRY_BASE_DELAY};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use tauri::Manager;
+
+/// Event emitted for each line of installer output
+pub const INSTALL_PROGRESS_EVENT: &str = "prereq-install-progress";
+
+/// Payload of `prereq-install-progress`
+#[derive(Serialize, Clone, Debug)]
+pub struct InstallProgress {
+    pub prerequisite_id: String,
+    pub line: String,
+}
+
+/// Callback receiving installer output one line at a time
+type ProgressFn<'a> = &'a (dyn Fn(&str) + Send + Sync);
+
+/// Output of a command run with `run_streaming`
+struct StreamedOutput {
+    success: bool,
+    stdout: String,
+    stderr: String,
+}
 
 /// Generic installer that reads from YAML configuration
 #[tauri::command]
-pub async fn install_prerequisite(prerequisite_id: String) -> Result<String, String> {
+pub async fn install_prerequisite(app: tauri::AppHandle, prerequisite_id: String) -> Result<String, String> {
+    if !is_supported_platform() {
+        return Err(unsupported_platform_error(&format!("Installing '{}'", prerequisite_id)));
+    }
+
     // Load prerequisites config
     let config = PrerequisitesConfig::load()?;
 
@@ -21,13 +52,112 @@ pub async fn install_prerequisite(prerequisite_id: String) -> Result<String, Str
     let method = prereq_methods.get(&platform)
         .ok_or_else(|| format!("No installation method for '{}' on platform '{}'", prerequisite_id, platform))?;
 
+    // Stream installer output to the UI as it arrives
+    let progress = |line: &str| {
+        let payload = InstallProgress {
+            prerequisite_id: prerequisite_id.clone(),
+            line: line.to_string(),
+        };
+        if let Err(e) = app.emit_all(INSTALL_PROGRESS_EVENT, payload) {
+            eprintln!("[install_prerequisite] Failed to emit progress: {}", e);
+        }
+    };
+
     // Execute the installation based on method type
-    execute_installation(&prerequisite_id, method, &platform).await
+    execute_installation(&prerequisite_id, method, &platform, &progress).await
+}
+
+/// Remove a prerequisite using the `uninstall_methods` configured for this platform
+#[tauri::command]
+pub async fn uninstall_prerequisite(prerequisite_id: String) -> Result<String, String> {
+    if !is_supported_platform() {
+        return Err(unsupported_platform_error(&format!("Uninstalling '{}'", prerequisite_id)));
+    }
+
+    let config = PrerequisitesConfig::load()?;
+    let platform = get_current_platform();
+
+    let method = config.uninstall_methods
+        .as_ref()
+        .and_then(|methods| methods.get(&prerequisite_id))
+        .and_then(|platforms| platforms.get(&platform))
+        .ok_or_else(|| format!(
+            "No uninstall method configured for '{}' on platform '{}'; remove it manually",
+            prerequisite_id, platform
+        ))?;
+
+    let pkg_mgr = if method.method == "package_manager" {
+        detect_package_manager(binary_on_path)
+    } else {
+        None
+    };
+    let argv = uninstall_command(&prerequisite_id, method, &Platform::get_package_manager_path(), pkg_mgr)?;
+
+    eprintln!("[uninstall_prerequisite] Running: {}", argv.join(" "));
+    let output = silent_command(&argv[0])
+        .args(&argv[1..])
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", argv[0], e))?;
+
+    if output.status.success() {
+        Ok(format!("{} uninstalled via {}", prerequisite_id, method.method))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("Uninstalling {} failed: {}", prerequisite_id, stderr.trim()))
+    }
+}
+
+/// Command line that removes a prerequisite for the given uninstall method.
+/// `brew` is the Homebrew binary; `pkg_mgr` the detected Linux package manager (for `package_manager`).
+fn uninstall_command(
+    prereq_id: &str,
+    method: &InstallationMethod,
+    brew: &str,
+    pkg_mgr: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let package = || method.package.clone()
+        .ok_or_else(|| format!("No package specified for {} uninstall", method.method));
+
+    let argv: Vec<String> = match method.method.as_str() {
+        "homebrew" => {
+            let mut argv = vec![brew.to_string(), "uninstall".to_string()];
+            if is_homebrew_cask(prereq_id) {
+                argv.push("--cask".to_string());
+            }
+            argv.push(package()?);
+            argv
+        }
+        "winget" => vec![
+            "winget".to_string(),
+            "uninstall".to_string(),
+            "--id".to_string(),
+            package()?,
+            "-e".to_string(),
+            "--accept-source-agreements".to_string(),
+        ],
+        "package_manager" => {
+            let packages = method.packages.as_ref()
+                .ok_or_else(|| "No packages specified for package manager uninstall".to_string())?;
+            let pkg_mgr = pkg_mgr.ok_or_else(|| "No supported package manager found on PATH".to_string())?;
+            let package = package_for_manager(packages, pkg_mgr)
+                .ok_or_else(|| "No compatible package found for this system".to_string())?;
+            std::iter::once("sudo".to_string())
+                .chain(package_remove_command(pkg_mgr, package)?)
+                .collect()
+        }
+        "cargo" => vec!["cargo".to_string(), "uninstall".to_string(), package()?],
+        other => return Err(format!("Uninstall method '{}' is not supported", other)),
+    };
+    Ok(argv)
 }
 
 /// Get the start command for a service prerequisite
 #[tauri::command]
 pub async fn start_prerequisite(prerequisite_id: String) -> Result<String, String> {
+    if !is_supported_platform() {
+        return Err(unsupported_platform_error(&format!("Starting '{}'", prerequisite_id)));
+    }
+
     // Load prerequisites config
     let config = PrerequisitesConfig::load()?;
 
@@ -50,30 +180,177 @@ pub async fn start_prerequisite(prerequisite_id: String) -> Result<String, Strin
     }
 }
 
+/// Stop Docker (Docker Desktop or the system service); no-op if it isn't running
+#[tauri::command]
+pub async fn stop_docker() -> Result<String, String> {
+    let (installed, running, _) = check_docker();
+    if !installed {
+        return Err("Docker is not installed".to_string());
+    }
+    if !running {
+        return Ok("Docker is not running".to_string());
+    }
+
+    eprintln!("[stop_docker] Stopping Docker on {}", get_current_platform());
+    Platform::stop_docker().await
+}
+
 /// Execute installation based on method type
 async fn execute_installation(
     prereq_id: &str,
     method: &InstallationMethod,
     _platform: &str,
+    progress: ProgressFn<'_>,
 ) -> Result<String, String> {
     match method.method.as_str() {
         "homebrew" => install_via_homebrew(prereq_id, method).await,
         "winget" => install_via_winget(prereq_id, method).await,
         "download" => install_via_download(prereq_id, method).await,
-        "script" => install_via_script(prereq_id, method).await,
-        "package_manager" => install_via_package_manager(prereq_id, method).await,
-        "cargo" => install_via_cargo(prereq_id, method).await,
+        "script" => install_via_script(prereq_id, method, progress).await,
+        "package_manager" => install_via_package_manager(prereq_id, method, progress).await,
+        "cargo" => install_via_cargo(prereq_id, method, progress).await,
         _ => Err(format!("Unknown installation method: {}", method.method))
     }
 }
 
+/// Run a command, handing each stdout/stderr line to `on_line` as soon as it is printed
+fn run_streaming(mut cmd: Command, on_line: ProgressFn<'_>) -> Result<StreamedOutput, String> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start process: {}", e))?;
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    // Read both pipes concurrently so neither can fill up and block the child
+    let (stdout, stderr) = std::thread::scope(|scope| {
+        let out = scope.spawn(|| stdout.map(|pipe| collect_lines(pipe, on_line)).unwrap_or_default());
+        let err = stderr.map(|pipe| collect_lines(pipe, on_line)).unwrap_or_default();
+        (out.join().unwrap_or_default(), err)
+    });
+
+    let status = child.wait().map_err(|e| format!("Failed to wait for process: {}", e))?;
+
+    Ok(StreamedOutput {
+        success: status.success(),
+        stdout,
+        stderr,
+    })
+}
+
+/// Forward every line of `pipe` to `on_line`, returning everything read
+fn collect_lines(pipe: impl std::io::Read, on_line: ProgressFn<'_>) -> String {
+    let mut collected = String::new();
+    for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+        on_line(&line);
+        collected.push_str(&line);
+        collected.push('\n');
+    }
+    collected
+}
+
+/// Lowercase hex SHA-256 of `bytes`
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Check downloaded content against the configured checksum (no-op when none is configured)
+fn verify_sha256(bytes: &[u8], expected: Option<&str>, source: &str) -> Result<(), String> {
+    let expected = match expected.map(str::trim).filter(|e| !e.is_empty()) {
+        Some(expected) => expected.to_lowercase(),
+        None => return Ok(()),
+    };
+
+    let actual = sha256_hex(bytes);
+    if actual != expected {
+        return Err(format!(
+            "Checksum mismatch for {}: expected sha256 {}, got {}. Refusing to install.",
+            source, expected, actual
+        ));
+    }
+    Ok(())
+}
+
+/// Linux package managers the installer can drive, in probe order: (config key, binary on PATH)
+const LINUX_PACKAGE_MANAGERS: &[(&str, &str)] = &[
+    ("apt", "apt-get"),
+    ("dnf", "dnf"),
+    ("yum", "yum"),
+    ("pacman", "pacman"),
+    ("zypper", "zypper"),
+];
+
+/// First known package manager whose binary `on_path` reports as available.
+/// Returns its config key (the key used in an installation method's `packages` map).
+pub(crate) fn detect_package_manager(on_path: impl Fn(&str) -> bool) -> Option<&'static str> {
+    LINUX_PACKAGE_MANAGERS
+        .iter()
+        .find(|(_, binary)| on_path(binary))
+        .map(|(key, _)| *key)
+}
+
+/// Whether `binary` resolves on PATH
+pub(crate) fn binary_on_path(binary: &str) -> bool {
+    silent_command("which")
+        .arg(binary)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Full non-interactive install invocation (program first) for `package` with the given package manager
+pub(crate) fn package_install_command(pkg_mgr: &str, package: &str) -> Result<Vec<String>, String> {
+    let argv: &[&str] = match pkg_mgr {
+        "apt" | "apt-get" => &["apt-get", "install", "-y"],
+        "dnf" => &["dnf", "install", "-y"],
+        "yum" => &["yum", "install", "-y"],
+        "pacman" => &["pacman", "-S", "--noconfirm"],
+        "zypper" => &["zypper", "--non-interactive", "install", "-y"],
+        _ => return Err(format!("Unsupported package manager: {}", pkg_mgr)),
+    };
+    Ok(argv.iter().map(|a| a.to_string()).chain(std::iter::once(package.to_string())).collect())
+}
+
+/// Full non-interactive removal invocation (program first) for `package` with the given package manager
+fn package_remove_command(pkg_mgr: &str, package: &str) -> Result<Vec<String>, String> {
+    let argv: &[&str] = match pkg_mgr {
+        "apt" | "apt-get" => &["apt-get", "remove", "-y"],
+        "dnf" => &["dnf", "remove", "-y"],
+        "yum" => &["yum", "remove", "-y"],
+        "pacman" => &["pacman", "-R", "--noconfirm"],
+        "zypper" => &["zypper", "--non-interactive", "remove"],
+        _ => return Err(format!("Unsupported package manager: {}", pkg_mgr)),
+    };
+    Ok(argv.iter().map(|a| a.to_string()).chain(std::iter::once(package.to_string())).collect())
+}
+
+/// Package name for `pkg_mgr`: its own entry in the `packages` map, else the apt name, else any entry
+fn package_for_manager<'a>(packages: &'a std::collections::HashMap<String, String>, pkg_mgr: &str) -> Option<&'a String> {
+    packages.get(pkg_mgr)
+        .or_else(|| packages.get("apt"))
+        .or_else(|| {
+            // Deterministic pick when neither is present
+            let mut keys: Vec<&String> = packages.keys().collect();
+            keys.sort();
+            keys.first().and_then(|k| packages.get(*k))
+        })
+}
+
+/// Prerequisites installed as Homebrew casks (apps) rather than formulae
+fn is_homebrew_cask(prereq_id: &str) -> bool {
+    prereq_id == "docker" || prereq_id == "tailscale"
+}
+
 /// Install via Homebrew - delegates to platform module
 async fn install_via_homebrew(prereq_id: &str, method: &InstallationMethod) -> Result<String, String> {
     let package = method.package.as_ref()
         .ok_or_else(|| "No package specified for Homebrew installation".to_string())?;
 
     // Determine if this is a cask/app or formula
-    let is_app = prereq_id == "docker" || prereq_id == "tailscale";
+    let is_app = is_homebrew_cask(prereq_id);
 
     Platform::install_package(package, is_app).await
 }
@@ -100,7 +377,7 @@ async fn install_via_download(prereq_id: &str, method: &InstallationMethod) -> R
         let pkg_path = tmp_dir.join("Homebrew-5.0.9.pkg");
 
         // Download the pkg file
-        let response = reqwest::get(pkg_url)
+        let response = fetch_with_retry(pkg_url, network_retry_attempts(), RETRY_BASE_DELAY)
             .await
             .map_err(|e| format!("Failed to download installer: {}", e))?;
 
@@ -111,6 +388,7 @@ async fn install_via_download(prereq_id: &str, method: &InstallationMethod) -> R
         let bytes = response.bytes()
             .await
             .map_err(|e| format!("Failed to read installer data: {}", e))?;
+        verify_sha256(&bytes, method.sha256.as_deref(), pkg_url)?;
 
         std::fs::write(&pkg_path, bytes)
             .map_err(|e| format!("Failed to save installer: {}", e))?;
@@ -132,14 +410,14 @@ async fn install_via_download(prereq_id: &str, method: &InstallationMethod) -> R
 }
 
 /// Install via script (download and execute installation script)
-async fn install_via_script(prereq_id: &str, method: &InstallationMethod) -> Result<String, String> {
+async fn install_via_script(prereq_id: &str, method: &InstallationMethod, progress: ProgressFn<'_>) -> Result<String, String> {
     let url = method.url.as_ref()
         .ok_or_else(|| "No URL specified for script installation".to_string())?;
 
     eprintln!("Installing {} via script: {}", prereq_id, url);
 
     // Download script
-    let response = reqwest::get(url)
+    let response = fetch_with_retry(url, network_retry_attempts(), RETRY_BASE_DELAY)
         .await
         .map_err(|e| format!("Failed to download installation script: {}", e))?;
 
@@ -147,10 +425,16 @@ async fn install_via_script(prereq_id: &str, method: &InstallationMethod) -> Res
         return Err(format!("Failed to download script: HTTP {}", response.status()));
     }
 
-    let script_content = response.text()
+    let script_bytes = response.bytes()
         .await
         .map_err(|e| format!("Failed to read script: {}", e))?;
 
+    // Verify before anything touches disk or a shell
+    verify_sha256(&script_bytes, method.sha256.as_deref(), url)?;
+
+    let script_content = String::from_utf8(script_bytes.to_vec())
+        .map_err(|e| format!("Failed to read script: {}", e))?;
+
     // Platform-specific script handling
     #[cfg(target_os = "windows")]
     {
@@ -162,16 +446,13 @@ async fn install_via_script(prereq_id: &str, method: &InstallationMethod) -> Res
 
         // Execute with PowerShell (shell_command already wraps in powershell -NoProfile -Command)
         let cmd = format!("& \"{}\"", script_path.display());
-        let output = shell_command(&cmd)
-            .output()
+        let output = run_streaming(shell_command(&cmd), progress)
             .map_err(|e| format!("Failed to execute script: {}", e))?;
 
-        if output.status.success() {
+        if output.success {
             Ok(format!("{} installed successfully via script", prereq_id))
         } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            Err(format!("Script installation failed:\nstderr: {}\nstdout: {}", stderr, stdout))
+            Err(format!("Script installation failed:\nstderr: {}\nstdout: {}", output.stderr, output.stdout))
         }
     }
 
@@ -193,52 +474,58 @@ async fn install_via_script(prereq_id: &str, method: &InstallationMethod) -> Res
             .map_err(|e| format!("Failed to set script permissions: {}", e))?;
 
         // Execute script
-        let output = shell_command(&format!("bash {}", script_path.display()))
-            .output()
+        let output = run_streaming(shell_command(&format!("bash {}", script_path.display())), progress)
             .map_err(|e| format!("Failed to execute script: {}", e))?;
 
-        if output.status.success() {
+        if output.success {
             Ok(format!("{} installed successfully via script", prereq_id))
         } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(format!("Script installation failed: {}", stderr))
+            Err(format!("Script installation failed: {}", output.stderr))
         }
     }
 }
 
-/// Install via package manager (apt, yum, dnf) - delegates to platform module for Linux
-async fn install_via_package_manager(_prereq_id: &str, method: &InstallationMethod) -> Result<String, String> {
+/// Install via the system package manager (apt, dnf, yum, pacman, zypper) with sudo
+async fn install_via_package_manager(_prereq_id: &str, method: &InstallationMethod, progress: ProgressFn<'_>) -> Result<String, String> {
     let packages = method.packages.as_ref()
         .ok_or_else(|| "No packages specified for package manager installation".to_string())?;
 
-    // Get the platform's package manager
-    let pkg_mgr = Platform::get_package_manager_path();
+    let pkg_mgr = detect_package_manager(binary_on_path).ok_or_else(|| {
+        let known: Vec<&str> = LINUX_PACKAGE_MANAGERS.iter().map(|(_, binary)| *binary).collect();
+        format!("No supported package manager found on PATH (looked for {})", known.join(", "))
+    })?;
 
-    // Find the package for this package manager
-    let package = packages.get(&pkg_mgr)
-        .or_else(|| packages.get("apt"))  // Fallback to apt
-        .or_else(|| packages.values().next())  // Or any available package
+    let package = package_for_manager(packages, pkg_mgr)
         .ok_or_else(|| "No compatible package found for this system".to_string())?;
 
-    Platform::install_package(package, false).await
+    eprintln!("Installing {} via {}", package, pkg_mgr);
+
+    let mut cmd = silent_command("sudo");
+    cmd.args(package_install_command(pkg_mgr, package)?);
+    let output = run_streaming(cmd, progress)
+        .map_err(|e| format!("Failed to run {}: {}", pkg_mgr, e))?;
+
+    if output.success {
+        Ok(format!("{} installed successfully via {}", package, pkg_mgr))
+    } else {
+        Err(format!("{} install failed: {}", pkg_mgr, output.stderr))
+    }
 }
 
 /// Install via cargo
-async fn install_via_cargo(prereq_id: &str, method: &InstallationMethod) -> Result<String, String> {
+async fn install_via_cargo(prereq_id: &str, method: &InstallationMethod, progress: ProgressFn<'_>) -> Result<String, String> {
     let package = method.package.as_ref()
         .ok_or_else(|| "No package specified for cargo installation".to_string())?;
 
     eprintln!("Installing {} via cargo: {}", prereq_id, package);
 
-    let output = shell_command(&format!("cargo install {}", package))
-        .output()
+    let output = run_streaming(shell_command(&format!("cargo install {}", package)), progress)
         .map_err(|e| format!("Failed to run cargo: {}", e))?;
 
-    if output.status.success() {
+    if output.success {
         Ok(format!("{} installed successfully via cargo", prereq_id))
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("cargo install failed: {}", stderr))
+        Err(format!("cargo install failed: {}", output.stderr))
     }
 }
 
@@ -261,3 +548,153 @@ async fn start_docker_linux() -> Result<String, String> {
 fn get_current_platform() -> String {
     current_platform().to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_streaming_reports_each_line() {
+        let dir = std::env::temp_dir().join(format!("ushadow-installer-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("install.sh");
+        std::fs::write(&script, "echo fetching\necho unpacking\necho 'warning: slow mirror' >&2\necho done\n").unwrap();
+
+        let lines = Mutex::new(Vec::new());
+        let on_line = |line: &str| lines.lock().unwrap().push(line.to_string());
+        let mut cmd = Command::new("sh");
+        cmd.arg(&script);
+        let output = run_streaming(cmd, &on_line).unwrap();
+
+        assert!(output.success);
+        assert_eq!(output.stdout, "fetching\nunpacking\ndone\n");
+        assert_eq!(output.stderr, "warning: slow mirror\n");
+
+        // stdout and stderr interleave nondeterministically; stdout order is preserved
+        let lines = lines.into_inner().unwrap();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.contains(&"warning: slow mirror".to_string()));
+        let stdout_lines: Vec<&String> = lines.iter().filter(|l| !l.starts_with("warning")).collect();
+        assert_eq!(stdout_lines, vec!["fetching", "unpacking", "done"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_streaming_failure_keeps_stderr() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo 'E: Unable to locate package' >&2; exit 100"]);
+        let output = run_streaming(cmd, &|_: &str| {}).unwrap();
+        assert!(!output.success);
+        assert_eq!(output.stderr, "E: Unable to locate package\n");
+    }
+
+    #[test]
+    fn test_package_install_command() {
+        assert_eq!(package_install_command("apt", "tmux").unwrap(), vec!["apt-get", "install", "-y", "tmux"]);
+        assert_eq!(package_install_command("dnf", "tmux").unwrap(), vec!["dnf", "install", "-y", "tmux"]);
+        assert_eq!(package_install_command("yum", "tmux").unwrap(), vec!["yum", "install", "-y", "tmux"]);
+        assert_eq!(package_install_command("pacman", "tmux").unwrap(), vec!["pacman", "-S", "--noconfirm", "tmux"]);
+        assert_eq!(
+            package_install_command("zypper", "tmux").unwrap(),
+            vec!["zypper", "--non-interactive", "install", "-y", "tmux"]
+        );
+        assert!(package_install_command("emerge", "tmux").is_err());
+    }
+
+    #[test]
+    fn test_detect_package_manager() {
+        assert_eq!(detect_package_manager(|b| b == "apt-get"), Some("apt"));
+        assert_eq!(detect_package_manager(|b| b == "zypper"), Some("zypper"));
+        assert_eq!(detect_package_manager(|b| b == "pacman" || b == "zypper"), Some("pacman"));
+        // dnf wins over its yum compatibility shim
+        assert_eq!(detect_package_manager(|b| b == "yum" || b == "dnf"), Some("dnf"));
+        assert_eq!(detect_package_manager(|_| false), None);
+    }
+
+    #[test]
+    fn test_package_for_manager_overrides() {
+        let packages: std::collections::HashMap<String, String> = [("apt", "python3"), ("pacman", "python")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(package_for_manager(&packages, "pacman").unwrap(), "python");
+        assert_eq!(package_for_manager(&packages, "zypper").unwrap(), "python3");
+
+        let only_dnf: std::collections::HashMap<String, String> = [("dnf".to_string(), "python3.11".to_string())].into();
+        assert_eq!(package_for_manager(&only_dnf, "apt").unwrap(), "python3.11");
+        assert!(package_for_manager(&std::collections::HashMap::new(), "apt").is_none());
+    }
+
+    #[test]
+    fn test_verify_sha256() {
+        let script = b"#!/bin/sh\necho installing\n";
+        let digest = sha256_hex(script);
+        assert_eq!(sha256_hex(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+
+        // Matching digests pass regardless of case or surrounding whitespace
+        assert!(verify_sha256(script, Some(&digest), "install.sh").is_ok());
+        assert!(verify_sha256(script, Some(&format!(" {} ", digest.to_uppercase())), "install.sh").is_ok());
+
+        // No checksum configured: unchanged behavior
+        assert!(verify_sha256(script, None, "install.sh").is_ok());
+        assert!(verify_sha256(script, Some(""), "install.sh").is_ok());
+
+        let err = verify_sha256(b"#!/bin/sh\necho tampered\n", Some(&digest), "install.sh").unwrap_err();
+        assert!(err.contains("Checksum mismatch for install.sh"));
+        assert!(err.contains(&digest));
+    }
+
+    fn method(kind: &str, package: Option<&str>, packages: &[(&str, &str)]) -> InstallationMethod {
+        InstallationMethod {
+            method: kind.to_string(),
+            package: package.map(String::from),
+            url: None,
+            packages: (!packages.is_empty())
+                .then(|| packages.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()),
+            sha256: None,
+        }
+    }
+
+    #[test]
+    fn test_uninstall_command_dispatch() {
+        let brew = "/opt/homebrew/bin/brew";
+
+        assert_eq!(
+            uninstall_command("tmux", &method("homebrew", Some("tmux"), &[]), brew, None).unwrap(),
+            vec![brew, "uninstall", "tmux"]
+        );
+        assert_eq!(
+            uninstall_command("docker", &method("homebrew", Some("docker"), &[]), brew, None).unwrap(),
+            vec![brew, "uninstall", "--cask", "docker"]
+        );
+        assert_eq!(
+            uninstall_command("git", &method("winget", Some("Git.Git"), &[]), brew, None).unwrap(),
+            vec!["winget", "uninstall", "--id", "Git.Git", "-e", "--accept-source-agreements"]
+        );
+
+        let pkgs = method("package_manager", None, &[("apt", "python3"), ("pacman", "python")]);
+        assert_eq!(
+            uninstall_command("python", &pkgs, brew, Some("apt")).unwrap(),
+            vec!["sudo", "apt-get", "remove", "-y", "python3"]
+        );
+        assert_eq!(
+            uninstall_command("python", &pkgs, brew, Some("pacman")).unwrap(),
+            vec!["sudo", "pacman", "-R", "--noconfirm", "python"]
+        );
+        assert!(uninstall_command("python", &pkgs, brew, None).is_err());
+
+        assert_eq!(
+            uninstall_command("workmux", &method("cargo", Some("workmux"), &[]), brew, None).unwrap(),
+            vec!["cargo", "uninstall", "workmux"]
+        );
+
+        // Nothing to guess for scripts/downloads or missing packages
+        assert!(uninstall_command("uv", &method("script", None, &[]), brew, None).is_err());
+        assert!(uninstall_command("tmux", &method("homebrew", None, &[]), brew, None).is_err());
+    }
+}
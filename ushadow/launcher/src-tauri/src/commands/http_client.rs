@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
+use super::settings::{read_launcher_settings, DEFAULT_NETWORK_RETRIES};
+
+/// First retry delay; doubles on each further attempt
+pub(crate) const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HttpResponse {
@@ -8,6 +13,78 @@ pub struct HttpResponse {
     pub headers: HashMap<String, String>,
 }
 
+/// Configured attempts for retried network requests (at least one)
+pub(crate) fn network_retry_attempts() -> u32 {
+    read_launcher_settings()
+        .map(|s| s.network_retries)
+        .unwrap_or(DEFAULT_NETWORK_RETRIES)
+        .max(1)
+}
+
+/// Delay before retry number `retry` (0-based): `base * 2^retry`, plus up to `base` of jitter.
+/// `jitter` is a fraction in [0, 1).
+fn backoff_delay(base: Duration, retry: u32, jitter: f64) -> Duration {
+    let exponential = base.saturating_mul(2u32.saturating_pow(retry.min(16)));
+    exponential + base.mul_f64(jitter.clamp(0.0, 1.0))
+}
+
+/// Cheap jitter source in [0, 1) (no need for a real RNG here)
+fn jitter_fraction() -> f64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    f64::from(nanos % 1000) / 1000.0
+}
+
+/// Whether a failed send is worth retrying (couldn't connect or timed out)
+fn is_transient_error(e: &reqwest::Error) -> bool {
+    e.is_connect() || e.is_timeout()
+}
+
+/// Send a request, retrying connection failures, timeouts and 5xx responses with exponential
+/// backoff. 4xx responses are returned immediately; after the last attempt the final 5xx
+/// response (or error) is returned as-is. Requests with streaming bodies are sent once.
+pub(crate) async fn send_with_retry(
+    request: reqwest::RequestBuilder,
+    attempts: u32,
+    base_delay: Duration,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let attempts = attempts.max(1);
+    let mut retry = 0;
+
+    loop {
+        let last_attempt = retry + 1 >= attempts;
+        let this_try = match request.try_clone() {
+            Some(r) if !last_attempt => r,
+            _ => return request.send().await,
+        };
+
+        match this_try.send().await {
+            Ok(response) if !response.status().is_server_error() => return Ok(response),
+            Ok(response) => eprintln!("[HTTP] {} from {}, retrying", response.status(), response.url()),
+            Err(e) if is_transient_error(&e) => eprintln!("[HTTP] {}, retrying", e),
+            Err(e) => return Err(e),
+        }
+
+        tokio::time::sleep(backoff_delay(base_delay, retry, jitter_fraction())).await;
+        retry += 1;
+    }
+}
+
+/// GET `url`, retrying transient failures (see `send_with_retry`)
+pub(crate) async fn fetch_with_retry(url: &str, attempts: u32, base_delay: Duration) -> Result<reqwest::Response, String> {
+    // Only bound connecting: installer downloads can legitimately take minutes
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    send_with_retry(client.get(url), attempts, base_delay)
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))
+}
+
 /// Make an HTTP request from Rust (bypasses CORS)
 #[tauri::command]
 pub async fn http_request(
@@ -45,11 +122,14 @@ pub async fn http_request(
         request = request.body(body_content);
     }
 
-    // Send request
+    // Send request (GETs are safe to repeat, so ride out transient failures)
     eprintln!("[HTTP] Sending request...");
-    let response = request
-        .send()
-        .await
+    let sent = if method.eq_ignore_ascii_case("GET") {
+        send_with_retry(request, network_retry_attempts(), RETRY_BASE_DELAY).await
+    } else {
+        request.send().await
+    };
+    let response = sent
         .map_err(|e| {
             eprintln!("[HTTP] Request failed: {}", e);
             format!("HTTP request failed: {}", e)
@@ -79,3 +159,70 @@ pub async fn http_request(
         headers: response_headers,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Serve `fail_status` for the first `failures` requests, then 200; returns (port, hit counter)
+    fn spawn_flaky_server(failures: usize, fail_status: u16) -> (u16, Arc<AtomicUsize>) {
+        use warp::Filter;
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let route = warp::any().map(move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            let status = if n < failures { fail_status } else { 200 };
+            warp::reply::with_status("body", warp::http::StatusCode::from_u16(status).unwrap())
+        });
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        (addr.port(), hits)
+    }
+
+    const FAST: Duration = Duration::from_millis(1);
+
+    #[tokio::test]
+    async fn test_fetch_retries_server_errors_then_succeeds() {
+        let (port, hits) = spawn_flaky_server(2, 503);
+        let response = fetch_with_retry(&format!("http://127.0.0.1:{}/install.sh", port), 3, FAST).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_returns_last_server_error_when_attempts_run_out() {
+        let (port, hits) = spawn_flaky_server(5, 502);
+        let response = fetch_with_retry(&format!("http://127.0.0.1:{}/", port), 3, FAST).await.unwrap();
+        assert_eq!(response.status().as_u16(), 502);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_does_not_retry_client_errors() {
+        let (port, hits) = spawn_flaky_server(5, 404);
+        let response = fetch_with_retry(&format!("http://127.0.0.1:{}/", port), 3, FAST).await.unwrap();
+        assert_eq!(response.status().as_u16(), 404);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_connection_refused_is_an_error() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        assert!(fetch_with_retry(&format!("http://127.0.0.1:{}/", port), 2, FAST).await.is_err());
+    }
+
+    #[test]
+    fn test_backoff_delay_grows_with_bounded_jitter() {
+        let base = Duration::from_millis(100);
+        assert_eq!(backoff_delay(base, 0, 0.0), Duration::from_millis(100));
+        assert_eq!(backoff_delay(base, 1, 0.0), Duration::from_millis(200));
+        assert_eq!(backoff_delay(base, 3, 0.0), Duration::from_millis(800));
+        assert_eq!(backoff_delay(base, 1, 0.5), Duration::from_millis(250));
+        assert!(backoff_delay(base, 2, 0.999) < Duration::from_millis(500));
+        // Huge retry counts don't overflow
+        assert!(backoff_delay(base, 100, 0.0) > Duration::from_secs(60));
+        assert!((0.0..1.0).contains(&jitter_fraction()));
+    }
+}
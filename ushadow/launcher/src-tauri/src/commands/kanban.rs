@@ -1,10 +1,14 @@
-use crate::models::{Epic, Ticket, TicketPriority, TicketStatus};
-use super::worktree::create_worktree_with_workmux;
+use crate::models::{BoardSnapshot, Epic, EpicProgress, EpicWithProgress, KanbanExport, KanbanImportSummary, KanbanIntegrityReport, KanbanRepairResult, Ticket, TicketPriority, TicketStatus};
+use super::worktree::{agent_running_in_pane, check_environment_conflict, check_worktree_exists, create_worktree_with_workmux};
+use super::settings::{load_launcher_settings, tmux_names};
 use super::utils::shell_command;
+use super::repository::{branch_has_own_commits, is_branch_merged};
+use super::ticket_ids::{get_next_ticket_number, get_or_create_ticket_prefix};
 use std::path::PathBuf;
 use std::fs;
 use serde::{Deserialize, Serialize};
 use tauri::api::path::data_dir;
+use tracing::{error, info, warn};
 
 /// Write a `.claude/settings.local.json` into a worktree so Claude Code hooks
 /// fire and update kanban ticket status automatically.
@@ -12,11 +16,11 @@ use tauri::api::path::data_dir;
 fn setup_claude_hooks(worktree_path: &str) {
     let settings_path = format!("{}/.claude/settings.local.json", worktree_path);
     if std::path::Path::new(&settings_path).exists() {
-        eprintln!("[setup_claude_hooks] settings.local.json already exists, skipping");
+        info!(target: "setup_claude_hooks", "settings.local.json already exists, skipping");
         return;
     }
     if let Err(e) = std::fs::create_dir_all(format!("{}/.claude", worktree_path)) {
-        eprintln!("[setup_claude_hooks] Failed to create .claude dir: {}", e);
+        warn!(target: "setup_claude_hooks", "Failed to create .claude dir: {}", e);
         return;
     }
     let content = r#"{
@@ -34,8 +38,8 @@ fn setup_claude_hooks(worktree_path: &str) {
 }
 "#;
     match std::fs::write(&settings_path, content) {
-        Ok(_) => eprintln!("[setup_claude_hooks] ✓ Wrote {}", settings_path),
-        Err(e) => eprintln!("[setup_claude_hooks] Failed to write {}: {}", settings_path, e),
+        Ok(_) => info!(target: "setup_claude_hooks", "✓ Wrote {}", settings_path),
+        Err(e) => warn!(target: "setup_claude_hooks", "Failed to write {}: {}", settings_path, e),
     }
 }
 
@@ -58,6 +62,8 @@ pub struct CreateTicketWorktreeResult {
     pub branch_name: String,
     pub tmux_window_name: String,
     pub tmux_session_name: String,
+    /// True when an existing worktree was reused instead of being created
+    pub reused: bool,
 }
 
 /// Create a worktree and tmux window for a kanban ticket
@@ -65,16 +71,19 @@ pub struct CreateTicketWorktreeResult {
 /// This command handles two scenarios:
 /// 1. Ticket has its own branch (epic_branch is None)
 /// 2. Ticket shares a branch with epic (epic_branch is Some)
+///
+/// If a worktree already exists for the environment (or the branch is already checked out
+/// in another worktree) it is returned as-is with `reused: true` rather than recreated.
 #[tauri::command]
 pub async fn create_ticket_worktree(
     request: CreateTicketWorktreeRequest,
 ) -> Result<CreateTicketWorktreeResult, String> {
-    eprintln!("[create_ticket_worktree] Creating worktree for ticket: {}", request.ticket_title);
+    info!(target: "create_ticket_worktree", "Creating worktree for ticket: {}", request.ticket_title);
 
     // Determine branch to use
     let branch_name = if let Some(epic_branch) = request.epic_branch {
         // Use epic's shared branch
-        eprintln!("[create_ticket_worktree] Using epic's shared branch: {}", epic_branch);
+        info!(target: "create_ticket_worktree", "Using epic's shared branch: {}", epic_branch);
         epic_branch
     } else if let Some(branch_name) = request.branch_name {
         // Use provided branch name
@@ -84,11 +93,53 @@ pub async fn create_ticket_worktree(
         format!("ticket-{}", request.ticket_id)
     };
 
-    // Session = ush-{env_name}, window = ushadow-{env_name}.
+    let settings = load_launcher_settings().await.unwrap_or_default();
+
+    // Idempotency: create_worktree_with_workmux removes and recreates an existing worktree,
+    // so a retried ticket flow would destroy the current worktree state. Reuse it instead.
+    let existing = match check_environment_conflict(request.project_root.clone(), request.environment_name.clone()).await? {
+        Some(conflict) => {
+            if conflict.current_branch != branch_name.to_lowercase() {
+                warn!(
+                    target: "create_ticket_worktree",
+                    "Environment '{}' already exists on branch '{}' (requested '{}'), reusing it",
+                    conflict.name, conflict.current_branch, branch_name
+                );
+            }
+            Some((conflict.path, conflict.current_branch))
+        }
+        None => check_worktree_exists(request.project_root.clone(), branch_name.clone())
+            .await?
+            .map(|wt| (wt.path, wt.branch)),
+    };
+
+    if let Some((worktree_path, existing_branch)) = existing {
+        let env_name = std::path::Path::new(&worktree_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&request.environment_name)
+            .to_string();
+        let names = tmux_names(&env_name, &settings);
+
+        info!(target: "create_ticket_worktree", "✓ Reusing existing worktree at: {}", worktree_path);
+
+        setup_claude_hooks(&worktree_path);
+
+        return Ok(CreateTicketWorktreeResult {
+            worktree_path,
+            branch_name: existing_branch,
+            tmux_window_name: names.window,
+            tmux_session_name: names.session,
+            reused: true,
+        });
+    }
+
+    // Session = {session_prefix}{env_name}, window = {window_prefix}{env_name}.
     // workmux identifies windows by the worktree directory basename, so the window name must
     // be {window_prefix}{dir_basename} (env_name = the directory name, not the branch name).
-    let tmux_window_name = format!("ushadow-{}", request.environment_name);
-    let tmux_session_name = format!("ush-{}", request.environment_name);
+    let names = tmux_names(&request.environment_name, &settings);
+    let tmux_window_name = names.window;
+    let tmux_session_name = names.session;
 
     // Create worktree with tmux integration (custom_window_name=None — derived inside)
     let worktree_info = create_worktree_with_workmux(
@@ -98,10 +149,11 @@ pub async fn create_ticket_worktree(
         request.base_branch.clone(),
         Some(false),
         None, // custom_window_name: ignored, derived from branch_name inside
+        None, // base_ref: tickets always branch from base_branch
     ).await?;
 
-    eprintln!("[create_ticket_worktree] ✓ Worktree created at: {}", worktree_info.path);
-    eprintln!("[create_ticket_worktree] ✓ Session: {}, Window: {}", tmux_session_name, tmux_window_name);
+    info!(target: "create_ticket_worktree", "✓ Worktree created at: {}", worktree_info.path);
+    info!(target: "create_ticket_worktree", "✓ Session: {}, Window: {}", tmux_session_name, tmux_window_name);
 
     setup_claude_hooks(&worktree_info.path);
 
@@ -110,6 +162,7 @@ pub async fn create_ticket_worktree(
         branch_name,
         tmux_window_name,
         tmux_session_name,
+        reused: false,
     })
 }
 
@@ -120,12 +173,12 @@ pub async fn attach_ticket_to_worktree(
     worktree_path: String,
     branch_name: String,
 ) -> Result<CreateTicketWorktreeResult, String> {
-    eprintln!("[attach_ticket_to_worktree] Attaching ticket {} to existing worktree: {}", ticket_id, worktree_path);
+    info!(target: "attach_ticket_to_worktree", "Attaching ticket {} to existing worktree: {}", ticket_id, worktree_path);
 
     // Verify worktree exists
     let path_buf = PathBuf::from(&worktree_path);
     if !path_buf.exists() {
-        eprintln!("[attach_ticket_to_worktree] ERROR: Worktree path does not exist: {}", worktree_path);
+        error!(target: "attach_ticket_to_worktree", "Worktree path does not exist: {}", worktree_path);
         return Err(format!("Worktree path does not exist: {}", worktree_path));
     }
 
@@ -137,9 +190,11 @@ pub async fn attach_ticket_to_worktree(
         .unwrap_or("ushadow")
         .to_string();
 
-    // Session = ush-{env}, window = ushadow-{env_name} (workmux uses dir basename as handle)
-    let tmux_session_name = format!("ush-{}", env_name);
-    let tmux_window_name = format!("ushadow-{}", env_name);
+    // Session = {session_prefix}{env}, window = {window_prefix}{env} (workmux uses dir basename as handle)
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let names = tmux_names(&env_name, &settings);
+    let tmux_session_name = names.session;
+    let tmux_window_name = names.window;
 
     // Ensure tmux server is running
     shell_command("tmux start-server")
@@ -153,7 +208,7 @@ pub async fn attach_ticket_to_worktree(
         .unwrap_or(false);
 
     if !session_exists {
-        eprintln!("[attach_ticket_to_worktree] Creating session '{}'", tmux_session_name);
+        info!(target: "attach_ticket_to_worktree", "Creating session '{}'", tmux_session_name);
         shell_command(&format!(
             "tmux new-session -d -s {} -c '{}' -n '{}'",
             tmux_session_name, worktree_path, tmux_window_name
@@ -171,7 +226,7 @@ pub async fn attach_ticket_to_worktree(
         .unwrap_or(false);
 
         if !window_exists {
-            eprintln!("[attach_ticket_to_worktree] Adding window '{}' to session '{}'", tmux_window_name, tmux_session_name);
+            info!(target: "attach_ticket_to_worktree", "Adding window '{}' to session '{}'", tmux_window_name, tmux_session_name);
             shell_command(&format!(
                 "tmux new-window -t {} -n '{}' -c '{}'",
                 tmux_session_name, tmux_window_name, worktree_path
@@ -179,7 +234,7 @@ pub async fn attach_ticket_to_worktree(
             .output()
             .map_err(|e| format!("Failed to create tmux window: {}", e))?;
         } else {
-            eprintln!("[attach_ticket_to_worktree] ✓ Window '{}' already exists", tmux_window_name);
+            info!(target: "attach_ticket_to_worktree", "✓ Window '{}' already exists", tmux_window_name);
         }
     }
 
@@ -197,7 +252,7 @@ pub async fn attach_ticket_to_worktree(
         })
         .unwrap_or(branch_name);
 
-    eprintln!("[attach_ticket_to_worktree] ✓ session='{}' window='{}' branch='{}'",
+    info!(target: "attach_ticket_to_worktree", "✓ session='{}' window='{}' branch='{}'",
         tmux_session_name, tmux_window_name, actual_branch);
 
     setup_claude_hooks(&worktree_path);
@@ -207,6 +262,7 @@ pub async fn attach_ticket_to_worktree(
         branch_name: actual_branch,
         tmux_window_name,
         tmux_session_name,
+        reused: true,
     })
 }
 
@@ -218,7 +274,7 @@ pub async fn get_tickets_for_tmux_window(
 ) -> Result<Vec<String>, String> {
     // This will need to query the backend API
     // For now, return empty list as placeholder
-    eprintln!("[get_tickets_for_tmux_window] Getting tickets for window: {}", window_name);
+    info!(target: "get_tickets_for_tmux_window", "Getting tickets for window: {}", window_name);
     Ok(vec![])
 }
 
@@ -229,7 +285,7 @@ pub async fn get_ticket_tmux_info(
 ) -> Result<Option<CreateTicketWorktreeResult>, String> {
     // This will need to query the backend API to get ticket's tmux details
     // For now, return None as placeholder
-    eprintln!("[get_ticket_tmux_info] Getting tmux info for ticket: {}", ticket_id);
+    info!(target: "get_ticket_tmux_info", "Getting tmux info for ticket: {}", ticket_id);
     Ok(None)
 }
 
@@ -237,7 +293,7 @@ pub async fn get_ticket_tmux_info(
 // Local Ticket & Epic Storage (SQLite-based)
 // ============================================================================
 
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
 
 /// Get the path to the SQLite database
 fn get_db_path() -> Result<PathBuf, String> {
@@ -253,12 +309,72 @@ fn get_db_path() -> Result<PathBuf, String> {
     Ok(launcher_dir.join("kanban.db"))
 }
 
+/// How long a connection waits on a locked database before failing (GUI and kanban-cli share the file)
+const DB_BUSY_TIMEOUT_MS: u32 = 5000;
+
 /// Get a database connection and ensure schema is initialized
 fn get_db_connection() -> Result<Connection, String> {
     let db_path = get_db_path()?;
-    let conn = Connection::open(&db_path)
+    open_db_connection(&db_path)
+}
+
+/// Open a database file with WAL + busy timeout and ensure schema is initialized
+fn open_db_connection(db_path: &std::path::Path) -> Result<Connection, String> {
+    let mut conn = Connection::open(db_path)
         .map_err(|e| format!("Failed to open database: {}", e))?;
 
+    // WAL lets readers proceed while hooks write; busy_timeout retries instead of "database is locked"
+    conn.execute_batch(&format!(
+        "PRAGMA journal_mode=WAL; PRAGMA busy_timeout={};",
+        DB_BUSY_TIMEOUT_MS
+    )).map_err(|e| format!("Failed to configure database: {}", e))?;
+
+    run_migrations(&mut conn, MIGRATIONS)?;
+
+    Ok(conn)
+}
+
+/// A kanban schema migration step
+type Migration = fn(&Connection) -> Result<(), String>;
+
+/// Kanban schema migrations, applied in order; the number applied is stored in `PRAGMA user_version`.
+/// Append new steps, never edit or reorder released ones. ALTER TABLE appends columns,
+/// so positional `SELECT *` row mapping stays valid as long as new fields are read after `updated_at`.
+const MIGRATIONS: &[Migration] = &[
+    migrate_v1_baseline,
+    migrate_v2_ticket_search,
+    migrate_v3_ticket_dependencies,
+    migrate_v4_project_default_base_branch,
+];
+
+/// Apply pending migrations; returns the resulting schema version
+fn run_migrations(conn: &mut Connection, migrations: &[Migration]) -> Result<u32, String> {
+    loop {
+        // IMMEDIATE takes the write lock first, so a concurrent opener (GUI vs kanban-cli)
+        // re-reads the version after we commit instead of applying the same step twice
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(|e| format!("Failed to start migration: {}", e))?;
+
+        let version: u32 = tx.query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|e| format!("Failed to read schema version: {}", e))?;
+
+        let migration = match migrations.get(version as usize) {
+            Some(m) => m,
+            None => return Ok(version),
+        };
+
+        migration(&tx)?;
+        tx.pragma_update(None, "user_version", version + 1)
+            .map_err(|e| format!("Failed to set schema version: {}", e))?;
+        tx.commit()
+            .map_err(|e| format!("Failed to commit migration {}: {}", version + 1, e))?;
+
+        info!(target: "kanban", "Migrated database schema to version {}", version + 1);
+    }
+}
+
+/// v1: baseline schema (tables as they existed before versioning; no-op on existing databases)
+fn migrate_v1_baseline(conn: &Connection) -> Result<(), String> {
     // Create tables if they don't exist
     conn.execute(
         "CREATE TABLE IF NOT EXISTS epics (
@@ -316,7 +432,313 @@ fn get_db_connection() -> Result<Connection, String> {
         [],
     ).map_err(|e| format!("Failed to create index: {}", e))?;
 
-    Ok(conn)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS projects (
+            id TEXT PRIMARY KEY,
+            ticket_prefix TEXT NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("Failed to create projects table: {}", e))?;
+
+    Ok(())
+}
+
+/// v2: FTS5 index over ticket title/description/tags, kept in sync by triggers
+fn migrate_v2_ticket_search(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS tickets_fts USING fts5(id UNINDEXED, title, description, tags);
+
+        CREATE TRIGGER IF NOT EXISTS tickets_fts_insert AFTER INSERT ON tickets BEGIN
+            INSERT INTO tickets_fts (id, title, description, tags)
+            VALUES (new.id, new.title, COALESCE(new.description, ''), new.tags);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS tickets_fts_delete AFTER DELETE ON tickets BEGIN
+            DELETE FROM tickets_fts WHERE id = old.id;
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS tickets_fts_update AFTER UPDATE OF id, title, description, tags ON tickets BEGIN
+            DELETE FROM tickets_fts WHERE id = old.id;
+            INSERT INTO tickets_fts (id, title, description, tags)
+            VALUES (new.id, new.title, COALESCE(new.description, ''), new.tags);
+        END;
+
+        DELETE FROM tickets_fts;
+        INSERT INTO tickets_fts (id, title, description, tags)
+        SELECT id, title, COALESCE(description, ''), tags FROM tickets;",
+    ).map_err(|e| format!("Failed to create ticket search index: {}", e))
+}
+
+/// v3: ticket -> ticket "depends on" edges
+fn migrate_v3_ticket_dependencies(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS ticket_dependencies (
+            ticket_id TEXT NOT NULL,
+            depends_on_id TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (ticket_id, depends_on_id),
+            FOREIGN KEY (ticket_id) REFERENCES tickets (id) ON DELETE CASCADE,
+            FOREIGN KEY (depends_on_id) REFERENCES tickets (id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ticket_dependencies_depends_on ON ticket_dependencies(depends_on_id);",
+    ).map_err(|e| format!("Failed to create ticket dependencies table: {}", e))
+}
+
+fn migrate_v4_project_default_base_branch(conn: &Connection) -> Result<(), String> {
+    conn.execute("ALTER TABLE projects ADD COLUMN default_base_branch TEXT", [])
+        .map(|_| ())
+        .map_err(|e| format!("Failed to add default base branch column: {}", e))
+}
+
+/// Flush pending database writes to the main db file (WAL checkpoint) before the app exits
+pub(crate) fn flush_kanban_database() -> Result<(), String> {
+    let conn = get_db_connection()?;
+    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")
+        .map_err(|e| format!("Failed to checkpoint database: {}", e))?;
+    conn.close()
+        .map_err(|(_, e)| format!("Failed to close database: {}", e))
+}
+
+/// Clear references the schema can't resolve: tickets pointing at missing epics and
+/// dependencies on tickets that no longer exist
+fn fix_dangling_references(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "UPDATE tickets SET epic_id = NULL WHERE epic_id IS NOT NULL AND epic_id NOT IN (SELECT id FROM epics);
+         DELETE FROM ticket_dependencies
+         WHERE ticket_id NOT IN (SELECT id FROM tickets) OR depends_on_id NOT IN (SELECT id FROM tickets);",
+    ).map_err(|e| format!("Failed to fix up references: {}", e))
+}
+
+/// Run integrity and foreign key checks without touching the schema (works on damaged files)
+fn verify_db_at(db_path: &std::path::Path) -> Result<KanbanIntegrityReport, String> {
+    let conn = Connection::open(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    conn.busy_timeout(std::time::Duration::from_millis(DB_BUSY_TIMEOUT_MS.into()))
+        .map_err(|e| format!("Failed to configure database: {}", e))?;
+
+    let mut report = KanbanIntegrityReport::default();
+
+    let integrity = conn.prepare("PRAGMA integrity_check").and_then(|mut stmt| {
+        let lines = stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<Result<Vec<_>, _>>();
+        lines
+    });
+    match integrity {
+        Ok(lines) => report.integrity_errors = lines.into_iter().filter(|line| line != "ok").collect(),
+        Err(e) => report.integrity_errors.push(e.to_string()),
+    }
+
+    let foreign_keys = conn.prepare("PRAGMA foreign_key_check").and_then(|mut stmt| {
+        let problems = stmt.query_map([], |row| {
+            let table: String = row.get(0)?;
+            let rowid: Option<i64> = row.get(1)?;
+            let parent: String = row.get(2)?;
+            Ok(format!(
+                "{} row {} references a missing {} row",
+                table,
+                rowid.map(|id| id.to_string()).unwrap_or_else(|| "?".to_string()),
+                parent
+            ))
+        })?.collect::<Result<Vec<_>, _>>();
+        problems
+    });
+    match foreign_keys {
+        Ok(problems) => report.foreign_key_errors = problems,
+        Err(e) => report.foreign_key_errors.push(e.to_string()),
+    }
+
+    report.ok = report.integrity_errors.is_empty() && report.foreign_key_errors.is_empty();
+    Ok(report)
+}
+
+/// Copy every row of `table` that can still be read from `old` into `new`; returns rows copied
+fn copy_readable_rows(old: &Connection, new: &Connection, table: &str) -> u32 {
+    let mut stmt = match old.prepare(&format!("SELECT * FROM {}", table)) {
+        Ok(stmt) => stmt,
+        Err(e) => {
+            info!(target: "repair_kanban_db", "Skipping unreadable table {}: {}", table, e);
+            return 0;
+        }
+    };
+
+    let columns: Vec<String> = stmt.column_names().into_iter().map(|c| format!("\"{}\"", c)).collect();
+    let insert = format!(
+        "INSERT OR IGNORE INTO {} ({}) VALUES ({})",
+        table,
+        columns.join(", "),
+        vec!["?"; columns.len()].join(", ")
+    );
+
+    let mut rows = match stmt.query([]) {
+        Ok(rows) => rows,
+        Err(e) => {
+            info!(target: "repair_kanban_db", "Skipping unreadable table {}: {}", table, e);
+            return 0;
+        }
+    };
+
+    let mut copied = 0;
+    loop {
+        match rows.next() {
+            Ok(Some(row)) => {
+                let values: Result<Vec<rusqlite::types::Value>, _> = (0..columns.len()).map(|i| row.get(i)).collect();
+                match values.map(|values| new.execute(&insert, rusqlite::params_from_iter(values))) {
+                    Ok(Ok(n)) if n > 0 => copied += 1,
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) | Err(e) => info!(target: "repair_kanban_db", "Dropped a {} row: {}", table, e),
+                }
+            }
+            Ok(None) => break,
+            Err(e) => {
+                info!(target: "repair_kanban_db", "Stopped reading {} after {} rows: {}", table, copied, e);
+                break;
+            }
+        }
+    }
+    copied
+}
+
+/// Create a fresh database at `new_path` and copy in whatever rows `old_path` still yields
+fn rebuild_db_from(old_path: &std::path::Path, new_path: &std::path::Path) -> Result<u32, String> {
+    let mut new_conn = open_db_connection(new_path)?;
+    let mut recovered = 0;
+
+    match Connection::open(old_path) {
+        Ok(old_conn) => {
+            let tx = new_conn.transaction()
+                .map_err(|e| format!("Failed to start transaction: {}", e))?;
+            for table in ["epics", "projects", "tickets", "ticket_dependencies"] {
+                recovered += copy_readable_rows(&old_conn, &tx, table);
+            }
+            fix_dangling_references(&tx)?;
+            tx.commit()
+                .map_err(|e| format!("Failed to commit recovered rows: {}", e))?;
+        }
+        Err(e) => info!(target: "repair_kanban_db", "Could not open damaged database: {}", e),
+    }
+
+    new_conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")
+        .map_err(|e| format!("Failed to checkpoint database: {}", e))?;
+    new_conn.close()
+        .map_err(|(_, e)| format!("Failed to close database: {}", e))?;
+    Ok(recovered)
+}
+
+/// Append a suffix to a path's file name (`kanban.db` -> `kanban.db-wal`)
+fn path_with_suffix(path: &std::path::Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn repair_db_at(db_path: &std::path::Path) -> Result<KanbanRepairResult, String> {
+    if !db_path.exists() {
+        return Err(format!("No kanban database at {}", db_path.display()));
+    }
+
+    // Back up the database and its WAL before touching anything
+    let backup = path_with_suffix(db_path, &format!(".bak-{}", chrono::Utc::now().format("%Y%m%d%H%M%S")));
+    fs::copy(db_path, &backup)
+        .map_err(|e| format!("Failed to back up database: {}", e))?;
+    let wal = path_with_suffix(db_path, "-wal");
+    if wal.exists() {
+        fs::copy(&wal, path_with_suffix(&backup, "-wal"))
+            .map_err(|e| format!("Failed to back up database WAL: {}", e))?;
+    }
+    info!(target: "repair_kanban_db", "Backed up database to {}", backup.display());
+
+    // First try an in-place repair: rebuild indexes, compact, and clear dangling references
+    let in_place = open_db_connection(db_path).and_then(|conn| {
+        conn.execute_batch("REINDEX; VACUUM;")
+            .map_err(|e| format!("Failed to reindex/vacuum: {}", e))?;
+        fix_dangling_references(&conn)?;
+        drop(conn);
+        verify_db_at(db_path)
+    });
+    match in_place {
+        Ok(report) if report.ok => {
+            return Ok(KanbanRepairResult {
+                backup_path: backup.to_string_lossy().to_string(),
+                rebuilt: false,
+                rows_recovered: 0,
+                report,
+            });
+        }
+        Ok(report) => info!(target: "repair_kanban_db", "In-place repair left problems: {:?}", report),
+        Err(e) => warn!(target: "repair_kanban_db", "In-place repair failed: {}", e),
+    }
+
+    // Unrecoverable in place: recreate the schema and copy over the readable rows
+    let rebuilt_path = path_with_suffix(db_path, ".rebuild");
+    for stale in [rebuilt_path.clone(), path_with_suffix(&rebuilt_path, "-wal"), path_with_suffix(&rebuilt_path, "-shm")] {
+        fs::remove_file(stale).ok();
+    }
+    let rows_recovered = rebuild_db_from(db_path, &rebuilt_path)?;
+
+    for stale in [wal, path_with_suffix(db_path, "-shm")] {
+        fs::remove_file(stale).ok();
+    }
+    fs::rename(&rebuilt_path, db_path)
+        .map_err(|e| format!("Failed to replace database: {}", e))?;
+
+    info!(target: "repair_kanban_db", "Rebuilt database, recovered {} rows", rows_recovered);
+
+    Ok(KanbanRepairResult {
+        backup_path: backup.to_string_lossy().to_string(),
+        rebuilt: true,
+        rows_recovered,
+        report: verify_db_at(db_path)?,
+    })
+}
+
+/// Check the kanban database for corruption and broken references
+#[tauri::command]
+pub async fn verify_kanban_db() -> Result<KanbanIntegrityReport, String> {
+    verify_db_at(&get_db_path()?)
+}
+
+/// Back up and repair the kanban database, rebuilding it from readable rows if needed
+#[tauri::command]
+pub async fn repair_kanban_db() -> Result<KanbanRepairResult, String> {
+    repair_db_at(&get_db_path()?)
+}
+
+/// Map a `SELECT * FROM tickets` row to a Ticket
+fn ticket_from_row(row: &rusqlite::Row) -> Result<Ticket, rusqlite::Error> {
+    Ok(Ticket {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        description: row.get(2)?,
+        status: match row.get::<_, String>(3)?.as_str() {
+            "backlog" => TicketStatus::Backlog,
+            "todo" => TicketStatus::Todo,
+            "in_progress" => TicketStatus::InProgress,
+            "in_review" => TicketStatus::InReview,
+            "done" => TicketStatus::Done,
+            "archived" => TicketStatus::Archived,
+            _ => TicketStatus::Backlog,
+        },
+        priority: match row.get::<_, String>(4)?.as_str() {
+            "low" => TicketPriority::Low,
+            "medium" => TicketPriority::Medium,
+            "high" => TicketPriority::High,
+            "urgent" => TicketPriority::Urgent,
+            _ => TicketPriority::Medium,
+        },
+        epic_id: row.get(5)?,
+        tags: serde_json::from_str(&row.get::<_, String>(6)?).unwrap_or_default(),
+        color: row.get(7)?,
+        tmux_window_name: row.get(8)?,
+        tmux_session_name: row.get(9)?,
+        branch_name: row.get(10)?,
+        worktree_path: row.get(11)?,
+        environment_name: row.get(12)?,
+        project_id: row.get(13)?,
+        assigned_to: row.get(14)?,
+        order: row.get(15)?,
+        created_at: row.get(16)?,
+        updated_at: row.get(17)?,
+    })
 }
 
 /// Get all tickets, optionally filtered by project
@@ -334,52 +756,14 @@ pub async fn get_tickets(project_id: Option<String>) -> Result<Vec<Ticket>, Stri
     let mut stmt = conn.prepare(query)
         .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
-    // Helper function to map row to Ticket
-    let map_row = |row: &rusqlite::Row| -> Result<Ticket, rusqlite::Error> {
-        Ok(Ticket {
-            id: row.get(0)?,
-            title: row.get(1)?,
-            description: row.get(2)?,
-            status: match row.get::<_, String>(3)?.as_str() {
-                "backlog" => TicketStatus::Backlog,
-                "todo" => TicketStatus::Todo,
-                "in_progress" => TicketStatus::InProgress,
-                "in_review" => TicketStatus::InReview,
-                "done" => TicketStatus::Done,
-                "archived" => TicketStatus::Archived,
-                _ => TicketStatus::Backlog,
-            },
-            priority: match row.get::<_, String>(4)?.as_str() {
-                "low" => TicketPriority::Low,
-                "medium" => TicketPriority::Medium,
-                "high" => TicketPriority::High,
-                "urgent" => TicketPriority::Urgent,
-                _ => TicketPriority::Medium,
-            },
-            epic_id: row.get(5)?,
-            tags: serde_json::from_str(&row.get::<_, String>(6)?).unwrap_or_default(),
-            color: row.get(7)?,
-            tmux_window_name: row.get(8)?,
-            tmux_session_name: row.get(9)?,
-            branch_name: row.get(10)?,
-            worktree_path: row.get(11)?,
-            environment_name: row.get(12)?,
-            project_id: row.get(13)?,
-            assigned_to: row.get(14)?,
-            order: row.get(15)?,
-            created_at: row.get(16)?,
-            updated_at: row.get(17)?,
-        })
-    };
-
     // Execute query with or without parameter
     let tickets: Vec<Ticket> = if let Some(pid) = project_id {
-        stmt.query_map([pid], map_row)
+        stmt.query_map([pid], ticket_from_row)
             .map_err(|e| format!("Failed to query tickets: {}", e))?
             .filter_map(|r| r.ok())
             .collect()
     } else {
-        stmt.query_map([], map_row)
+        stmt.query_map([], ticket_from_row)
             .map_err(|e| format!("Failed to query tickets: {}", e))?
             .filter_map(|r| r.ok())
             .collect()
@@ -388,6 +772,52 @@ pub async fn get_tickets(project_id: Option<String>) -> Result<Vec<Ticket>, Stri
     Ok(tickets)
 }
 
+/// Turn free text into an FTS5 query: each word becomes a quoted prefix term (all must match).
+/// Quoting keeps punctuation like `-` or `:` from being parsed as FTS syntax.
+fn build_fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|word| word.replace('"', ""))
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"*", word))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Ranked full-text search over tickets (title matches weigh most, then tags, then description)
+fn search_tickets_in(conn: &Connection, fts_query: &str, project_id: Option<&str>) -> Result<Vec<Ticket>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT t.* FROM tickets_fts
+         JOIN tickets t ON t.id = tickets_fts.id
+         WHERE tickets_fts MATCH ?1 AND (?2 IS NULL OR t.project_id = ?2)
+         ORDER BY bm25(tickets_fts, 0.0, 10.0, 1.0, 4.0), t.\"order\"",
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let tickets = stmt.query_map(params![fts_query, project_id], ticket_from_row)
+        .map_err(|e| format!("Failed to search tickets: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(tickets)
+}
+
+/// Search tickets by title, description and tags; an empty query returns all tickets
+#[tauri::command]
+pub async fn search_tickets(query: String, project_id: Option<String>) -> Result<Vec<Ticket>, String> {
+    let fts_query = match build_fts_query(&query) {
+        Some(q) => q,
+        None => return get_tickets(project_id).await,
+    };
+
+    let conn = get_db_connection()?;
+    search_tickets_in(&conn, &fts_query, project_id.as_deref())
+}
+
 /// Get all epics, optionally filtered by project
 #[tauri::command]
 pub async fn get_epics(project_id: Option<String>) -> Result<Vec<Epic>, String> {
@@ -434,6 +864,80 @@ pub async fn get_epics(project_id: Option<String>) -> Result<Vec<Epic>, String>
     Ok(epics)
 }
 
+fn epic_progress_in(conn: &Connection, epic_id: &str) -> Result<EpicProgress, String> {
+    let mut stmt = conn.prepare(
+        "SELECT status, COUNT(*) FROM tickets WHERE epic_id = ? GROUP BY status"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let counts = stmt.query_map([epic_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?)))
+        .map_err(|e| format!("Failed to query epic progress: {}", e))?;
+
+    let mut progress = EpicProgress::default();
+    for count in counts {
+        let (status, n) = count.map_err(|e| format!("Failed to read epic progress: {}", e))?;
+        match status.as_str() {
+            "backlog" => progress.backlog += n,
+            "todo" => progress.todo += n,
+            "in_progress" => progress.in_progress += n,
+            "in_review" => progress.in_review += n,
+            "done" => progress.done += n,
+            _ => continue,
+        }
+        progress.total += n;
+    }
+
+    if progress.total > 0 {
+        progress.percent_done = progress.done * 100 / progress.total;
+    }
+    Ok(progress)
+}
+
+/// Get completion counts for an epic's tickets
+#[tauri::command]
+pub async fn get_epic_progress(epic_id: String) -> Result<EpicProgress, String> {
+    let conn = get_db_connection()?;
+    epic_progress_in(&conn, &epic_id)
+}
+
+/// Get epics (with progress), tickets and per-status counts for the board in one call
+#[tauri::command]
+pub async fn get_board_snapshot(project_id: Option<String>) -> Result<BoardSnapshot, String> {
+    let epics = get_epics(project_id.clone()).await?;
+    let tickets = get_tickets(project_id).await?;
+
+    let mut status_counts: std::collections::HashMap<String, u32> = [
+        "backlog", "todo", "in_progress", "in_review", "done", "archived",
+    ]
+    .iter()
+    .map(|s| (s.to_string(), 0))
+    .collect();
+
+    for ticket in &tickets {
+        let status_str = match ticket.status {
+            TicketStatus::Backlog => "backlog",
+            TicketStatus::Todo => "todo",
+            TicketStatus::InProgress => "in_progress",
+            TicketStatus::InReview => "in_review",
+            TicketStatus::Done => "done",
+            TicketStatus::Archived => "archived",
+        };
+        *status_counts.entry(status_str.to_string()).or_insert(0) += 1;
+    }
+
+    let epics = epics
+        .into_iter()
+        .map(|epic| {
+            let epic_tickets = tickets.iter().filter(|t| t.epic_id.as_deref() == Some(epic.id.as_str()));
+            let (total_tickets, done_tickets) = epic_tickets.fold((0, 0), |(total, done), t| {
+                (total + 1, done + u32::from(t.status == TicketStatus::Done))
+            });
+            EpicWithProgress { epic, total_tickets, done_tickets }
+        })
+        .collect();
+
+    Ok(BoardSnapshot { epics, tickets, status_counts })
+}
+
 /// Create a new ticket
 #[tauri::command]
 pub async fn create_ticket(
@@ -456,9 +960,9 @@ pub async fn create_ticket(
         _ => TicketPriority::Medium,
     };
 
-    // Generate sequential ticket ID (e.g., ush-1, ush-2, etc.)
-    let prefix = "ush";
-    let next_number = get_next_ticket_number(&conn, prefix)?;
+    // Generate sequential per-project ticket ID (e.g., ush-1, ush-2, etc.)
+    let prefix = get_or_create_ticket_prefix(&conn, project_id.as_deref())?;
+    let next_number = get_next_ticket_number(&conn, &prefix)?;
     let id = format!("{}-{}", prefix, next_number);
 
     // Get current timestamp
@@ -525,6 +1029,128 @@ pub async fn create_ticket(
     })
 }
 
+/// Parse one line of a bulk ticket list: `title | priority | tag,tag`.
+/// Leading markdown list markers ("- ", "* ", "1. ", "- [ ] ") are stripped.
+/// Returns None for blank lines. Priority defaults to "medium" when missing or unknown.
+fn parse_bulk_ticket_line(line: &str) -> Option<(String, String, Vec<String>)> {
+    let mut text = line.trim();
+    for marker in ["- [ ] ", "- [x] ", "* [ ] ", "- ", "* ", "+ "] {
+        if let Some(rest) = text.strip_prefix(marker) {
+            text = rest.trim_start();
+            break;
+        }
+    }
+    // Numbered list marker: "12. title"
+    if let Some((num, rest)) = text.split_once(". ") {
+        if !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()) {
+            text = rest.trim_start();
+        }
+    }
+
+    let mut parts = text.split('|').map(|p| p.trim());
+    let title = parts.next().unwrap_or("").to_string();
+    if title.is_empty() {
+        return None;
+    }
+
+    let priority = match parts.next().map(|p| p.to_lowercase()) {
+        Some(p) if matches!(p.as_str(), "low" | "medium" | "high" | "urgent") => p,
+        _ => "medium".to_string(),
+    };
+
+    let tags = parts
+        .next()
+        .map(|t| {
+            t.split(',')
+                .map(|tag| tag.trim().to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some((title, priority, tags))
+}
+
+/// Create one backlog ticket per non-empty line, in a single transaction.
+/// Each line is a title with an optional `| priority | tag,tag` suffix.
+#[tauri::command]
+pub async fn bulk_create_tickets(
+    lines: Vec<String>,
+    epic_id: Option<String>,
+    project_id: Option<String>,
+) -> Result<Vec<Ticket>, String> {
+    let mut conn = get_db_connection()?;
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let prefix = get_or_create_ticket_prefix(&tx, project_id.as_deref())?;
+    let mut next_number = get_next_ticket_number(&tx, &prefix)?;
+
+    let max_order: i32 = tx.query_row(
+        "SELECT COALESCE(MAX(\"order\"), -1) FROM tickets WHERE status = 'backlog'",
+        [],
+        |row| row.get(0),
+    ).unwrap_or(-1);
+    let mut order = max_order + 1;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut created = Vec::new();
+
+    for line in &lines {
+        let (title, priority, tags) = match parse_bulk_ticket_line(line) {
+            Some(parsed) => parsed,
+            None => continue,
+        };
+
+        let id = format!("{}-{}", prefix, next_number);
+        let tags_json = serde_json::to_string(&tags)
+            .map_err(|e| format!("Failed to serialize tags: {}", e))?;
+
+        tx.execute(
+            "INSERT INTO tickets (id, title, description, status, priority, epic_id, tags, color, tmux_window_name, tmux_session_name, branch_name, worktree_path, environment_name, project_id, assigned_to, \"order\", created_at, updated_at)
+             VALUES (?1, ?2, NULL, 'backlog', ?3, ?4, ?5, NULL, NULL, NULL, NULL, NULL, NULL, ?6, NULL, ?7, ?8, ?8)",
+            params![&id, &title, &priority, &epic_id, &tags_json, &project_id, order, &now],
+        ).map_err(|e| format!("Failed to insert ticket '{}': {}", title, e))?;
+
+        let priority_enum = match priority.as_str() {
+            "low" => TicketPriority::Low,
+            "high" => TicketPriority::High,
+            "urgent" => TicketPriority::Urgent,
+            _ => TicketPriority::Medium,
+        };
+
+        created.push(Ticket {
+            id,
+            title,
+            description: None,
+            status: TicketStatus::Backlog,
+            priority: priority_enum,
+            epic_id: epic_id.clone(),
+            tags,
+            color: None,
+            tmux_window_name: None,
+            tmux_session_name: None,
+            branch_name: None,
+            worktree_path: None,
+            environment_name: None,
+            project_id: project_id.clone(),
+            assigned_to: None,
+            order,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        });
+
+        next_number += 1;
+        order += 1;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit tickets: {}", e))?;
+
+    info!(target: "bulk_create_tickets", "Created {} tickets", created.len());
+    Ok(created)
+}
+
 /// Update a ticket
 #[tauri::command]
 pub async fn update_ticket(
@@ -542,10 +1168,15 @@ pub async fn update_ticket(
     tmux_session_name: Option<String>,
     environment_name: Option<String>,
 ) -> Result<Ticket, String> {
-    let conn = get_db_connection()?;
+    let mut conn = get_db_connection()?;
+
+    // Read-modify-write in one IMMEDIATE transaction so concurrent writers (kanban-cli hooks)
+    // queue on the write lock instead of overwriting each other or failing mid-update
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
 
     // First, get the current ticket to return updated version
-    let mut stmt = conn.prepare("SELECT * FROM tickets WHERE id = ?")
+    let mut stmt = tx.prepare("SELECT * FROM tickets WHERE id = ?")
         .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
     let mut ticket = stmt.query_row([&id], |row| {
@@ -584,6 +1215,7 @@ pub async fn update_ticket(
             updated_at: row.get(17)?,
         })
     }).map_err(|e| format!("Ticket not found: {}", e))?;
+    drop(stmt);
 
     // Update fields in memory
     if let Some(t) = title {
@@ -666,7 +1298,7 @@ pub async fn update_ticket(
     };
 
     // Update in database
-    conn.execute(
+    tx.execute(
         "UPDATE tickets SET title = ?1, description = ?2, status = ?3, priority = ?4, epic_id = ?5, tags = ?6, \"order\" = ?7, worktree_path = ?8, branch_name = ?9, tmux_window_name = ?10, tmux_session_name = ?11, environment_name = ?12, updated_at = ?13 WHERE id = ?14",
         params![
             &ticket.title,
@@ -686,6 +1318,9 @@ pub async fn update_ticket(
         ],
     ).map_err(|e| format!("Failed to update ticket: {}", e))?;
 
+    tx.commit()
+        .map_err(|e| format!("Failed to commit ticket update: {}", e))?;
+
     Ok(ticket)
 }
 
@@ -696,10 +1331,204 @@ pub async fn delete_ticket(id: String) -> Result<(), String> {
 
     conn.execute("DELETE FROM tickets WHERE id = ?", params![&id])
         .map_err(|e| format!("Failed to delete ticket: {}", e))?;
+    conn.execute(
+        "DELETE FROM ticket_dependencies WHERE ticket_id = ?1 OR depends_on_id = ?1",
+        params![&id],
+    ).map_err(|e| format!("Failed to delete ticket dependencies: {}", e))?;
+
+    Ok(())
+}
+
+/// Renumber a column (status + project) to a dense 0..n sequence, with `insert` placed at `index`
+fn renumber_column(
+    tx: &rusqlite::Transaction,
+    status: &str,
+    project_id: Option<&str>,
+    insert: Option<(&str, usize)>,
+) -> Result<(), String> {
+    let mut stmt = tx.prepare(
+        "SELECT id FROM tickets WHERE status = ?1 AND project_id IS ?2 ORDER BY \"order\", created_at, id"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let mut ids: Vec<String> = stmt.query_map(params![status, project_id], |row| row.get(0))
+        .map_err(|e| format!("Failed to query column: {}", e))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to read column: {}", e))?;
+    drop(stmt);
+
+    if let Some((id, index)) = insert {
+        ids.retain(|existing| existing != id);
+        ids.insert(index.min(ids.len()), id.to_string());
+    }
+
+    for (order, id) in ids.iter().enumerate() {
+        tx.execute(
+            "UPDATE tickets SET \"order\" = ?1 WHERE id = ?2",
+            params![order as i32, id],
+        ).map_err(|e| format!("Failed to update order: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Move a ticket to `index` within `new_status`, keeping both source and target columns densely ordered
+fn reorder_ticket_in(conn: &mut Connection, id: &str, new_status: &str, new_index: usize) -> Result<Ticket, String> {
+    if !matches!(new_status, "backlog" | "todo" | "in_progress" | "in_review" | "done" | "archived") {
+        return Err(format!("Invalid ticket status: {}", new_status));
+    }
+
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let (old_status, project_id): (String, Option<String>) = tx.query_row(
+        "SELECT status, project_id FROM tickets WHERE id = ?",
+        [id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).map_err(|e| format!("Ticket not found: {}", e))?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    tx.execute(
+        "UPDATE tickets SET status = ?1, updated_at = ?2 WHERE id = ?3",
+        params![new_status, &now, id],
+    ).map_err(|e| format!("Failed to update ticket: {}", e))?;
+
+    renumber_column(&tx, new_status, project_id.as_deref(), Some((id, new_index)))?;
+    if old_status != new_status {
+        renumber_column(&tx, &old_status, project_id.as_deref(), None)?;
+    }
+
+    let ticket = tx.query_row("SELECT * FROM tickets WHERE id = ?", [id], ticket_from_row)
+        .map_err(|e| format!("Ticket not found: {}", e))?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit reorder: {}", e))?;
+
+    Ok(ticket)
+}
+
+/// Move a ticket to a position within a status column, renumbering siblings
+#[tauri::command]
+pub async fn reorder_ticket(id: String, new_status: String, new_index: usize) -> Result<Ticket, String> {
+    let mut conn = get_db_connection()?;
+    reorder_ticket_in(&mut conn, &id, &new_status, new_index)
+}
+
+/// Whether `depends_on_id` already (transitively) depends on `ticket_id`, i.e. adding
+/// `ticket_id -> depends_on_id` would close a cycle
+fn dependency_creates_cycle(conn: &Connection, ticket_id: &str, depends_on_id: &str) -> Result<bool, String> {
+    let mut stmt = conn.prepare("SELECT depends_on_id FROM ticket_dependencies WHERE ticket_id = ?")
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let mut stack = vec![depends_on_id.to_string()];
+    let mut visited = std::collections::HashSet::new();
+    while let Some(current) = stack.pop() {
+        if current == ticket_id {
+            return Ok(true);
+        }
+        if !visited.insert(current.clone()) {
+            continue;
+        }
+        let next = stmt.query_map([&current], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Failed to query dependencies: {}", e))?;
+        for dep in next {
+            stack.push(dep.map_err(|e| format!("Failed to read dependency: {}", e))?);
+        }
+    }
+
+    Ok(false)
+}
+
+fn add_ticket_dependency_in(conn: &mut Connection, ticket_id: &str, depends_on_id: &str) -> Result<(), String> {
+    if ticket_id == depends_on_id {
+        return Err("A ticket cannot depend on itself".to_string());
+    }
+
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    for id in [ticket_id, depends_on_id] {
+        let exists: bool = tx.query_row("SELECT EXISTS(SELECT 1 FROM tickets WHERE id = ?)", [id], |row| row.get(0))
+            .map_err(|e| format!("Failed to look up ticket: {}", e))?;
+        if !exists {
+            return Err(format!("Ticket not found: {}", id));
+        }
+    }
+
+    if dependency_creates_cycle(&tx, ticket_id, depends_on_id)? {
+        return Err(format!(
+            "Cannot make {} depend on {}: {} already depends on {}",
+            ticket_id, depends_on_id, depends_on_id, ticket_id
+        ));
+    }
+
+    tx.execute(
+        "INSERT OR IGNORE INTO ticket_dependencies (ticket_id, depends_on_id, created_at) VALUES (?1, ?2, ?3)",
+        params![ticket_id, depends_on_id, chrono::Utc::now().to_rfc3339()],
+    ).map_err(|e| format!("Failed to add dependency: {}", e))?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit dependency: {}", e))
+}
+
+/// IDs of the tickets `ticket_id` depends on that aren't done yet
+fn unfinished_dependencies_in(conn: &Connection, ticket_id: &str) -> Result<Vec<String>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT dep.id FROM ticket_dependencies d
+         JOIN tickets dep ON dep.id = d.depends_on_id
+         WHERE d.ticket_id = ? AND dep.status != 'done'
+         ORDER BY dep.id"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let ids = stmt.query_map([ticket_id], |row| row.get(0))
+        .map_err(|e| format!("Failed to query dependencies: {}", e))?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| format!("Failed to read dependencies: {}", e))?;
+    Ok(ids)
+}
+
+fn blocked_tickets_in(conn: &Connection) -> Result<Vec<Ticket>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT * FROM tickets t WHERE EXISTS (
+            SELECT 1 FROM ticket_dependencies d
+            JOIN tickets dep ON dep.id = d.depends_on_id
+            WHERE d.ticket_id = t.id AND dep.status != 'done'
+         ) ORDER BY t.\"order\""
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let tickets = stmt.query_map([], ticket_from_row)
+        .map_err(|e| format!("Failed to query blocked tickets: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read blocked tickets: {}", e))?;
+    Ok(tickets)
+}
+
+/// Mark `ticket_id` as depending on `depends_on_id` (rejected if it would create a cycle)
+#[tauri::command]
+pub async fn add_ticket_dependency(ticket_id: String, depends_on_id: String) -> Result<(), String> {
+    let mut conn = get_db_connection()?;
+    add_ticket_dependency_in(&mut conn, &ticket_id, &depends_on_id)
+}
+
+/// Remove a dependency between two tickets
+#[tauri::command]
+pub async fn remove_ticket_dependency(ticket_id: String, depends_on_id: String) -> Result<(), String> {
+    let conn = get_db_connection()?;
+
+    conn.execute(
+        "DELETE FROM ticket_dependencies WHERE ticket_id = ?1 AND depends_on_id = ?2",
+        params![&ticket_id, &depends_on_id],
+    ).map_err(|e| format!("Failed to remove dependency: {}", e))?;
 
     Ok(())
 }
 
+/// Get tickets that have at least one dependency that isn't done
+#[tauri::command]
+pub async fn get_blocked_tickets() -> Result<Vec<Ticket>, String> {
+    let conn = get_db_connection()?;
+    blocked_tickets_in(&conn)
+}
+
 /// Create a new epic
 #[tauri::command]
 pub async fn create_epic(
@@ -817,6 +1646,55 @@ pub async fn delete_epic(id: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Move tickets whose branch has been merged into `base_branch` to done.
+/// Returns (ticket_id, previous_status) for every ticket that was transitioned.
+#[tauri::command]
+pub async fn sync_ticket_statuses_from_git(
+    main_repo: String,
+    base_branch: String,
+) -> Result<Vec<(String, String)>, String> {
+    let conn = get_db_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, status, branch_name FROM tickets
+         WHERE branch_name IS NOT NULL AND branch_name != '' AND status NOT IN ('done', 'archived')"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let candidates: Vec<(String, String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .map_err(|e| format!("Failed to query tickets: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    drop(stmt);
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut transitions = Vec::new();
+
+    for (id, status, branch) in candidates {
+        if branch == base_branch {
+            continue;
+        }
+        if is_branch_merged(&main_repo, &branch, &base_branch) != Some(true) {
+            continue;
+        }
+        // Freshly created branches are trivially ancestors of base; only trust
+        // ancestry once work has been committed or the ticket reached review.
+        if status != "in_review" && !branch_has_own_commits(&main_repo, &branch) {
+            continue;
+        }
+
+        conn.execute(
+            "UPDATE tickets SET status = 'done', updated_at = ?1 WHERE id = ?2",
+            params![&now, &id],
+        ).map_err(|e| format!("Failed to update ticket {}: {}", id, e))?;
+
+        info!(target: "sync_ticket_statuses_from_git", "{} ({}) merged into {}: {} -> done", id, branch, base_branch, status);
+        transitions.push((id, status));
+    }
+
+    Ok(transitions)
+}
+
 /// Start a coding agent in the tmux window for a ticket
 #[tauri::command]
 pub async fn start_coding_agent_for_ticket(
@@ -824,26 +1702,38 @@ pub async fn start_coding_agent_for_ticket(
     tmux_window_name: String,
     tmux_session_name: String,
     worktree_path: String,
+    allow_blocked: Option<bool>,
 ) -> Result<(), String> {
-    use super::settings::load_launcher_settings;
+    use super::settings::{agent_profile, ansi_c_escape, load_launcher_settings};
 
-    eprintln!("[start_coding_agent_for_ticket] Starting agent for ticket: {}", ticket_id);
-    eprintln!("[start_coding_agent_for_ticket] Tmux window: {}, session: {}", tmux_window_name, tmux_session_name);
-    eprintln!("[start_coding_agent_for_ticket] Worktree path: {}", worktree_path);
+    info!(target: "start_coding_agent_for_ticket", "Starting agent for ticket: {}", ticket_id);
+    info!(target: "start_coding_agent_for_ticket", "Tmux window: {}, session: {}", tmux_window_name, tmux_session_name);
+    info!(target: "start_coding_agent_for_ticket", "Worktree path: {}", worktree_path);
 
     // Load settings to get coding agent configuration
     let settings = load_launcher_settings().await?;
 
     if !settings.coding_agent.auto_start {
-        eprintln!("[start_coding_agent_for_ticket] Auto-start is disabled, skipping");
+        info!(target: "start_coding_agent_for_ticket", "Auto-start is disabled, skipping");
         return Ok(());
     }
 
     // Get ticket details
     let ticket = get_ticket_by_id(&ticket_id)?;
 
+    if !allow_blocked.unwrap_or(false) {
+        let blockers = unfinished_dependencies_in(&get_db_connection()?, &ticket_id)?;
+        if !blockers.is_empty() {
+            return Err(format!(
+                "Ticket {} is blocked by unfinished tickets: {}",
+                ticket_id,
+                blockers.join(", ")
+            ));
+        }
+    }
+
     // Automatically move ticket to in_progress when starting agent
-    eprintln!("[start_coding_agent_for_ticket] Moving ticket to in_progress...");
+    info!(target: "start_coding_agent_for_ticket", "Moving ticket to in_progress...");
     if let Some(branch_name) = &ticket.branch_name {
         // Use kanban-cli to move to in_progress
         let status_update = shell_command(&format!("kanban-cli move-to-progress \"{}\"", branch_name))
@@ -851,19 +1741,19 @@ pub async fn start_coding_agent_for_ticket(
 
         match status_update {
             Ok(output) if output.status.success() => {
-                eprintln!("[start_coding_agent_for_ticket] ✓ Ticket moved to in_progress");
+                info!(target: "start_coding_agent_for_ticket", "✓ Ticket moved to in_progress");
             }
             Ok(output) => {
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                eprintln!("[start_coding_agent_for_ticket] Warning: Failed to update status: {}", stderr);
+                warn!(target: "start_coding_agent_for_ticket", "Failed to update status: {}", stderr);
             }
             Err(e) => {
-                eprintln!("[start_coding_agent_for_ticket] Warning: Failed to run kanban-cli: {}", e);
+                warn!(target: "start_coding_agent_for_ticket", "Failed to run kanban-cli: {}", e);
             }
         }
     }
 
-    eprintln!("[start_coding_agent_for_ticket] Found ticket: {}", ticket.title);
+    info!(target: "start_coding_agent_for_ticket", "Found ticket: {}", ticket.title);
 
     // Build the agent prompt with ticket context
     let prompt = format!(
@@ -872,16 +1762,8 @@ pub async fn start_coding_agent_for_ticket(
         ticket.description.as_ref().unwrap_or(&"No description".to_string())
     );
 
-    // Build the base agent command from settings
-    let base_agent_command = if settings.coding_agent.args.is_empty() {
-        settings.coding_agent.command.clone()
-    } else {
-        format!("{} {}", settings.coding_agent.command, settings.coding_agent.args.join(" "))
-    };
-
-    // Always enable agent teams so any running instance can become a lead
-    // and split panes can coordinate when multiple tickets land in the same worktree.
-    let agent_command = format!("CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS=1 {}", base_agent_command);
+    let profile = agent_profile(&settings);
+    info!(target: "start_coding_agent_for_ticket", "Agent profile: {} ({})", profile.name, profile.command);
 
     // Verify the tmux window exists
     let windows_output = shell_command(&format!(
@@ -896,7 +1778,7 @@ pub async fn start_coding_agent_for_ticket(
         return Err(format!("Tmux window '{}' not found in session '{}'", tmux_window_name, tmux_session_name));
     }
 
-    // Check if a Claude session is already running in this window.
+    // Check if the agent is already running in this window.
     let current_command = shell_command(&format!(
         "tmux display-message -t {}:{} -p '#{{pane_current_command}}'",
         tmux_session_name, tmux_window_name
@@ -907,13 +1789,21 @@ pub async fn start_coding_agent_for_ticket(
 
     let is_shell = matches!(current_command.as_str(), "zsh" | "bash" | "sh" | "fish" | "");
 
-    if !is_shell {
-        // An agent (likely Claude) is already running — it becomes the team lead.
-        // Ask it to spawn a teammate for the new ticket rather than starting a second
-        // Claude instance ourselves. The lead's agent teams support handles pane splitting
-        // and task coordination natively.
-        eprintln!(
-            "[start_coding_agent_for_ticket] Lead agent '{}' running — delegating new ticket via agent teams",
+    let tmux_target = format!("{}:{}", tmux_session_name, tmux_window_name);
+    if agent_running_in_pane(&tmux_target, &current_command, &profile) {
+        if !profile.supports_teams {
+            return Err(format!(
+                "{} is already running in '{}' and can't take on another ticket",
+                profile.name, tmux_window_name
+            ));
+        }
+
+        // The running agent becomes the team lead. Ask it to spawn a teammate for the
+        // new ticket rather than starting a second instance ourselves. The lead's agent
+        // teams support handles pane splitting and task coordination natively.
+        info!(
+            target: "start_coding_agent_for_ticket",
+            "Lead agent '{}' running — delegating new ticket via agent teams",
             current_command
         );
 
@@ -928,36 +1818,29 @@ pub async fn start_coding_agent_for_ticket(
         // Write the teammate request to a temp script to avoid tmux send-keys quoting issues
         let script_key = format!("{}-{}-spawn", tmux_session_name, tmux_window_name).replace('/', "_");
         let temp_script = format!("/tmp/ushadow_spawn_{}.sh", script_key);
-        let ansi_escaped = spawn_request
-            .replace('\\', "\\\\")
-            .replace('\'', "\\'")
-            .replace('\n', "\\n");
+        let ansi_escaped = ansi_c_escape(&spawn_request);
         let script_content = format!("#!/bin/bash\ntmux send-keys -t {}:{} $'{}' Enter\n",
             tmux_session_name, tmux_window_name, ansi_escaped);
         if let Ok(()) = std::fs::write(&temp_script, &script_content) {
             let _ = shell_command(&format!("chmod +x {} && bash {}", temp_script, temp_script)).output();
         }
 
-        eprintln!("[start_coding_agent_for_ticket] ✓ Teammate request sent to lead");
+        info!(target: "start_coding_agent_for_ticket", "✓ Teammate request sent to lead");
         return Ok(());
     }
 
+    if !is_shell {
+        return Err(format!(
+            "Tmux window '{}' is busy running '{}'; not starting {}",
+            tmux_window_name, current_command, profile.name
+        ));
+    }
+
     // No agent running — write a temp script so multi-layer quoting (Rust → shell → tmux → shell)
     // can't corrupt the prompt text.  Uses bash $'...' ANSI-C quoting inside the file.
     let script_key = format!("{}-{}", tmux_session_name, tmux_window_name).replace('/', "_");
     let temp_script = format!("/tmp/ushadow_agent_{}.sh", script_key);
-
-    let ansi_escaped_prompt = prompt
-        .replace('\\', "\\\\")
-        .replace('\'', "\\'")
-        .replace('\n', "\\n");
-
-    let script_content = format!(
-        "#!/bin/bash\ncd '{}'\nexec {} $'{}'\n",
-        worktree_path.replace('\'', "'\\''"),
-        agent_command,
-        ansi_escaped_prompt
-    );
+    let script_content = profile.start_script(Some(&worktree_path), None, Some(&prompt));
 
     std::fs::write(&temp_script, &script_content)
         .map_err(|e| format!("Failed to write agent start script: {}", e))?;
@@ -965,7 +1848,7 @@ pub async fn start_coding_agent_for_ticket(
         .output()
         .map_err(|e| format!("Failed to chmod agent script: {}", e))?;
 
-    eprintln!("[start_coding_agent_for_ticket] Starting agent via script: {}", temp_script);
+    info!(target: "start_coding_agent_for_ticket", "Starting agent via script: {}", temp_script);
 
     let start_agent = shell_command(&format!(
         "tmux send-keys -t {}:{} 'bash {}' Enter",
@@ -981,37 +1864,73 @@ pub async fn start_coding_agent_for_ticket(
         ));
     }
 
-    eprintln!("[start_coding_agent_for_ticket] ✓ Agent starting headlessly in tmux (no terminal needed)");
+    info!(target: "start_coding_agent_for_ticket", "✓ Agent starting headlessly in tmux (no terminal needed)");
     Ok(())
 }
 
-/// Get the next ticket number for a given prefix
-fn get_next_ticket_number(conn: &rusqlite::Connection, prefix: &str) -> Result<i32, String> {
-    // Query all ticket IDs that match the prefix pattern
-    let pattern = format!("{}-%%", prefix);
-    let mut stmt = conn.prepare("SELECT id FROM tickets WHERE id LIKE ?")
-        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+/// Get the ticket ID prefix for a project
+#[tauri::command]
+pub async fn get_project_ticket_prefix(project_id: Option<String>) -> Result<String, String> {
+    let conn = get_db_connection()?;
+    get_or_create_ticket_prefix(&conn, project_id.as_deref())
+}
 
-    let ticket_ids = stmt.query_map([&pattern], |row| {
-        row.get::<_, String>(0)
-    }).map_err(|e| format!("Failed to query tickets: {}", e))?;
-
-    // Find the highest number
-    let mut max_number = 0;
-    for id_result in ticket_ids {
-        if let Ok(id) = id_result {
-            // Extract number from "ush-123" format
-            if let Some(number_str) = id.strip_prefix(&format!("{}-", prefix)) {
-                if let Ok(number) = number_str.parse::<i32>() {
-                    if number > max_number {
-                        max_number = number;
-                    }
-                }
-            }
-        }
+/// Set the ticket ID prefix for a project (only affects newly created tickets)
+#[tauri::command]
+pub async fn set_project_ticket_prefix(project_id: String, prefix: String) -> Result<(), String> {
+    let prefix = prefix.trim().trim_end_matches('-').to_lowercase();
+    if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Invalid ticket prefix '{}': use letters, digits or underscores", prefix));
+    }
+
+    let conn = get_db_connection()?;
+    conn.execute(
+        "INSERT INTO projects (id, ticket_prefix) VALUES (?1, ?2)
+         ON CONFLICT(id) DO UPDATE SET ticket_prefix = excluded.ticket_prefix",
+        params![&project_id, &prefix],
+    ).map_err(|e| format!("Failed to save project prefix: {}", e))?;
+
+    Ok(())
+}
+
+fn default_base_branch_in(conn: &Connection, project_id: &str) -> Result<Option<String>, String> {
+    conn.query_row(
+        "SELECT default_base_branch FROM projects WHERE id = ?",
+        params![project_id],
+        |row| row.get::<_, Option<String>>(0),
+    ).optional()
+        .map(Option::flatten)
+        .map_err(|e| format!("Failed to query default base branch: {}", e))
+}
+
+fn set_default_base_branch_in(conn: &Connection, project_id: &str, branch: Option<&str>) -> Result<(), String> {
+    // Make sure the project row exists (it needs a ticket prefix)
+    get_or_create_ticket_prefix(conn, Some(project_id))?;
+    conn.execute(
+        "UPDATE projects SET default_base_branch = ?2 WHERE id = ?1",
+        params![project_id, branch],
+    ).map_err(|e| format!("Failed to save default base branch: {}", e))?;
+    Ok(())
+}
+
+/// Default base branch configured for a project (worktrees fall back to `main` without one)
+pub(crate) fn project_default_base_branch(project_id: &str) -> Option<String> {
+    let conn = get_db_connection().ok()?;
+    default_base_branch_in(&conn, project_id).ok().flatten()
+}
+
+/// Set the branch new worktrees of a project start from when none is given; an empty branch clears it
+#[tauri::command]
+pub async fn set_default_base_branch(project_id: String, branch: String) -> Result<(), String> {
+    let branch = branch.trim();
+    if branch.chars().any(char::is_whitespace) {
+        return Err(format!("Invalid branch name '{}'", branch));
     }
 
-    Ok(max_number + 1)
+    let conn = get_db_connection()?;
+    set_default_base_branch_in(&conn, &project_id, Some(branch).filter(|b| !b.is_empty()))?;
+    info!(target: "set_default_base_branch", "Default base branch for {} set to {:?}", project_id, branch);
+    Ok(())
 }
 
 /// Helper to get a ticket by ID (internal use)
@@ -1059,6 +1978,235 @@ pub fn get_ticket_by_worktree_path(worktree_path: &str) -> Option<Ticket> {
     }).ok()
 }
 
+/// Point tickets attached to a renamed environment at its new worktree/branch/tmux names.
+/// Returns the number of tickets updated.
+pub(crate) fn retarget_environment_tickets(
+    old_worktree_path: &str,
+    new_worktree_path: &str,
+    new_environment_name: &str,
+    old_branch: &str,
+    new_branch: &str,
+    new_tmux_window_name: &str,
+    new_tmux_session_name: &str,
+) -> Result<usize, String> {
+    let conn = get_db_connection()?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE tickets SET
+            worktree_path = ?1,
+            environment_name = ?2,
+            branch_name = CASE WHEN branch_name = ?3 THEN ?4 ELSE branch_name END,
+            tmux_window_name = CASE WHEN tmux_window_name IS NULL THEN NULL ELSE ?5 END,
+            tmux_session_name = CASE WHEN tmux_session_name IS NULL THEN NULL ELSE ?6 END,
+            updated_at = ?7
+         WHERE worktree_path = ?8",
+        params![
+            new_worktree_path,
+            new_environment_name,
+            old_branch,
+            new_branch,
+            new_tmux_window_name,
+            new_tmux_session_name,
+            &now,
+            old_worktree_path,
+        ],
+    ).map_err(|e| format!("Failed to update tickets: {}", e))
+}
+
+/// Current version of the `export_kanban` document format
+const KANBAN_EXPORT_VERSION: u32 = 1;
+
+fn epic_from_row(row: &rusqlite::Row) -> Result<Epic, rusqlite::Error> {
+    Ok(Epic {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        description: row.get(2)?,
+        color: row.get(3)?,
+        branch_name: row.get(4)?,
+        base_branch: row.get(5)?,
+        project_id: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+/// Snake-case name of a ticket status / priority, as stored in the database
+fn enum_db_str<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Whether `incoming` is strictly newer than `existing` (RFC 3339, falling back to string order)
+fn is_newer(incoming: &str, existing: &str) -> bool {
+    match (
+        chrono::DateTime::parse_from_rfc3339(incoming),
+        chrono::DateTime::parse_from_rfc3339(existing),
+    ) {
+        (Ok(a), Ok(b)) => a > b,
+        _ => incoming > existing,
+    }
+}
+
+fn export_kanban_from(conn: &Connection) -> Result<KanbanExport, String> {
+    let mut stmt = conn.prepare("SELECT * FROM epics ORDER BY created_at")
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+    let epics = stmt.query_map([], epic_from_row)
+        .map_err(|e| format!("Failed to query epics: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read epics: {}", e))?;
+
+    let mut stmt = conn.prepare("SELECT * FROM tickets ORDER BY status, \"order\", id")
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+    let tickets = stmt.query_map([], ticket_from_row)
+        .map_err(|e| format!("Failed to query tickets: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read tickets: {}", e))?;
+
+    Ok(KanbanExport {
+        version: KANBAN_EXPORT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        epics,
+        tickets,
+    })
+}
+
+fn import_kanban_into(conn: &mut Connection, data: &KanbanExport, merge: bool) -> Result<KanbanImportSummary, String> {
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    if !merge {
+        tx.execute_batch("DELETE FROM ticket_dependencies; DELETE FROM tickets; DELETE FROM epics;")
+            .map_err(|e| format!("Failed to clear board: {}", e))?;
+    }
+
+    let mut summary = KanbanImportSummary::default();
+
+    for epic in &data.epics {
+        let existing: Option<String> = tx.query_row("SELECT updated_at FROM epics WHERE id = ?", [&epic.id], |row| row.get(0))
+            .optional()
+            .map_err(|e| format!("Failed to look up epic: {}", e))?;
+        if existing.is_some_and(|existing| !is_newer(&epic.updated_at, &existing)) {
+            summary.skipped += 1;
+            continue;
+        }
+
+        tx.execute(
+            "INSERT INTO epics (id, title, description, color, branch_name, base_branch, project_id, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT(id) DO UPDATE SET title = excluded.title, description = excluded.description,
+                color = excluded.color, branch_name = excluded.branch_name, base_branch = excluded.base_branch,
+                project_id = excluded.project_id, created_at = excluded.created_at, updated_at = excluded.updated_at",
+            params![
+                &epic.id, &epic.title, &epic.description, &epic.color, &epic.branch_name,
+                &epic.base_branch, &epic.project_id, &epic.created_at, &epic.updated_at,
+            ],
+        ).map_err(|e| format!("Failed to import epic {}: {}", epic.id, e))?;
+        summary.epics += 1;
+    }
+
+    for ticket in &data.tickets {
+        let existing: Option<String> = tx.query_row("SELECT updated_at FROM tickets WHERE id = ?", [&ticket.id], |row| row.get(0))
+            .optional()
+            .map_err(|e| format!("Failed to look up ticket: {}", e))?;
+        if existing.is_some_and(|existing| !is_newer(&ticket.updated_at, &existing)) {
+            summary.skipped += 1;
+            continue;
+        }
+
+        let tags_json = serde_json::to_string(&ticket.tags)
+            .map_err(|e| format!("Failed to serialize tags: {}", e))?;
+
+        // Upsert rather than INSERT OR REPLACE so the FTS update trigger fires
+        tx.execute(
+            "INSERT INTO tickets (id, title, description, status, priority, epic_id, tags, color, tmux_window_name, tmux_session_name, branch_name, worktree_path, environment_name, project_id, assigned_to, \"order\", created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)
+             ON CONFLICT(id) DO UPDATE SET title = excluded.title, description = excluded.description,
+                status = excluded.status, priority = excluded.priority, epic_id = excluded.epic_id, tags = excluded.tags,
+                color = excluded.color, tmux_window_name = excluded.tmux_window_name,
+                tmux_session_name = excluded.tmux_session_name, branch_name = excluded.branch_name,
+                worktree_path = excluded.worktree_path, environment_name = excluded.environment_name,
+                project_id = excluded.project_id, assigned_to = excluded.assigned_to, \"order\" = excluded.\"order\",
+                created_at = excluded.created_at, updated_at = excluded.updated_at",
+            params![
+                &ticket.id,
+                &ticket.title,
+                &ticket.description,
+                enum_db_str(&ticket.status),
+                enum_db_str(&ticket.priority),
+                &ticket.epic_id,
+                &tags_json,
+                &ticket.color,
+                &ticket.tmux_window_name,
+                &ticket.tmux_session_name,
+                &ticket.branch_name,
+                &ticket.worktree_path,
+                &ticket.environment_name,
+                &ticket.project_id,
+                &ticket.assigned_to,
+                ticket.order,
+                &ticket.created_at,
+                &ticket.updated_at,
+            ],
+        ).map_err(|e| format!("Failed to import ticket {}: {}", ticket.id, e))?;
+        summary.tickets += 1;
+    }
+
+    // Keep references valid: tickets pointing at epics that weren't imported lose their epic
+    fix_dangling_references(&tx)?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit import: {}", e))?;
+
+    Ok(summary)
+}
+
+/// Export all epics and tickets to a versioned JSON file
+#[tauri::command]
+pub async fn export_kanban(path: String) -> Result<usize, String> {
+    let conn = get_db_connection()?;
+    let data = export_kanban_from(&conn)?;
+
+    let json = serde_json::to_string_pretty(&data)
+        .map_err(|e| format!("Failed to serialize board: {}", e))?;
+    fs::write(&path, json)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+
+    info!(target: "export_kanban", "Exported {} epics and {} tickets to {}", data.epics.len(), data.tickets.len(), path);
+    Ok(data.tickets.len())
+}
+
+/// Import a board exported by `export_kanban`, replacing the current board or merging into it
+/// (by id; the newer `updated_at` wins)
+#[tauri::command]
+pub async fn import_kanban(path: String, merge: bool) -> Result<KanbanImportSummary, String> {
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let data: KanbanExport = serde_json::from_str(&contents)
+        .map_err(|e| format!("Invalid kanban export: {}", e))?;
+
+    if data.version > KANBAN_EXPORT_VERSION {
+        info!(
+            target: "import_kanban",
+            "Export version {} is newer than {}; unknown fields will be ignored",
+            data.version, KANBAN_EXPORT_VERSION
+        );
+    }
+
+    let mut conn = get_db_connection()?;
+    let summary = import_kanban_into(&mut conn, &data, merge)?;
+
+    info!(
+        target: "import_kanban",
+        "{} {} epics and {} tickets ({} skipped as older)",
+        if merge { "Merged" } else { "Imported" },
+        summary.epics, summary.tickets, summary.skipped
+    );
+    Ok(summary)
+}
+
 fn get_ticket_by_id(id: &str) -> Result<Ticket, String> {
     let conn = get_db_connection()?;
 
@@ -1102,3 +2250,532 @@ fn get_ticket_by_id(id: &str) -> Result<Ticket, String> {
         })
     }).map_err(|e| format!("Ticket not found: {}", e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn temp_db_dir(prefix: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("{}-{}", prefix, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Unversioned database from before migrations existed: no projects table, no indexes
+    fn create_old_database(path: &std::path::Path) {
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE epics (
+                id TEXT PRIMARY KEY, title TEXT NOT NULL, description TEXT, color TEXT NOT NULL,
+                branch_name TEXT, base_branch TEXT NOT NULL, project_id TEXT,
+                created_at TEXT NOT NULL, updated_at TEXT NOT NULL
+            );
+            CREATE TABLE tickets (
+                id TEXT PRIMARY KEY, title TEXT NOT NULL, description TEXT, status TEXT NOT NULL,
+                priority TEXT NOT NULL, epic_id TEXT, tags TEXT NOT NULL, color TEXT,
+                tmux_window_name TEXT, tmux_session_name TEXT, branch_name TEXT, worktree_path TEXT,
+                environment_name TEXT, project_id TEXT, assigned_to TEXT, \"order\" INTEGER NOT NULL,
+                created_at TEXT NOT NULL, updated_at TEXT NOT NULL
+            );
+            INSERT INTO tickets (id, title, status, priority, tags, \"order\", created_at, updated_at)
+            VALUES ('ush-1', 'Old ticket', 'todo', 'high', '[\"legacy\"]', 3, 'c', 'u');",
+        ).unwrap();
+    }
+
+    #[test]
+    fn test_migrations_upgrade_old_database_without_data_loss() {
+        let dir = temp_db_dir("ushadow-kanban-migrate");
+        let db_path = dir.join("kanban.db");
+        create_old_database(&db_path);
+
+        let conn = open_db_connection(&db_path).unwrap();
+
+        let version: u32 = conn.query_row("PRAGMA user_version", [], |r| r.get(0)).unwrap();
+        assert_eq!(version as usize, MIGRATIONS.len());
+
+        // Tables added after the old schema now exist
+        let projects: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'projects'", [], |r| r.get(0),
+        ).unwrap();
+        assert_eq!(projects, 1);
+
+        // Existing rows survive untouched
+        let (title, tags, order): (String, String, i32) = conn.query_row(
+            "SELECT title, tags, \"order\" FROM tickets WHERE id = 'ush-1'", [], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        ).unwrap();
+        assert_eq!(title, "Old ticket");
+        assert_eq!(tags, "[\"legacy\"]");
+        assert_eq!(order, 3);
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_migrations_apply_alter_steps_once() {
+        fn add_due_date(conn: &Connection) -> Result<(), String> {
+            conn.execute("ALTER TABLE tickets ADD COLUMN due_date TEXT", [])
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+
+        let dir = temp_db_dir("ushadow-kanban-alter");
+        let db_path = dir.join("kanban.db");
+        create_old_database(&db_path);
+
+        let migrations: &[Migration] = &[migrate_v1_baseline, add_due_date];
+        let mut conn = Connection::open(&db_path).unwrap();
+        assert_eq!(run_migrations(&mut conn, migrations).unwrap(), 2);
+        // Re-running is a no-op (a second ALTER would fail with "duplicate column")
+        assert_eq!(run_migrations(&mut conn, migrations).unwrap(), 2);
+
+        let due: Option<String> = conn.query_row("SELECT due_date FROM tickets WHERE id = 'ush-1'", [], |r| r.get(0)).unwrap();
+        assert_eq!(due, None);
+        let title: String = conn.query_row("SELECT title FROM tickets WHERE id = 'ush-1'", [], |r| r.get(0)).unwrap();
+        assert_eq!(title, "Old ticket");
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_concurrent_writers_do_not_hit_lock_errors() {
+        let dir = std::env::temp_dir().join(format!("ushadow-kanban-wal-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let db_path = Arc::new(dir.join("kanban.db"));
+
+        let conn = open_db_connection(&db_path).unwrap();
+        let mode: String = conn.query_row("PRAGMA journal_mode", [], |r| r.get(0)).unwrap();
+        assert_eq!(mode.to_lowercase(), "wal");
+        conn.execute(
+            "INSERT INTO tickets (id, title, status, priority, tags, \"order\", created_at, updated_at)
+             VALUES ('t1', 'Ticket', 'todo', 'medium', '[]', 0, '', '')",
+            [],
+        ).unwrap();
+        drop(conn);
+
+        // Each writer behaves like update_ticket (GUI) or kanban-cli: own connection, read-modify-write
+        let writers: Vec<_> = (0..8).map(|_| {
+            let db_path = db_path.clone();
+            std::thread::spawn(move || -> Result<(), String> {
+                let mut conn = open_db_connection(&db_path)?;
+                for _ in 0..25 {
+                    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)
+                        .map_err(|e| e.to_string())?;
+                    let order: i32 = tx.query_row("SELECT \"order\" FROM tickets WHERE id = 't1'", [], |r| r.get(0))
+                        .map_err(|e| e.to_string())?;
+                    tx.execute("UPDATE tickets SET \"order\" = ?1 WHERE id = 't1'", params![order + 1])
+                        .map_err(|e| e.to_string())?;
+                    tx.commit().map_err(|e| e.to_string())?;
+                }
+                Ok(())
+            })
+        }).collect();
+
+        for writer in writers {
+            writer.join().unwrap().expect("writer hit a database error");
+        }
+
+        let conn = open_db_connection(&db_path).unwrap();
+        let order: i32 = conn.query_row("SELECT \"order\" FROM tickets WHERE id = 't1'", [], |r| r.get(0)).unwrap();
+        assert_eq!(order, 8 * 25);
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    fn insert_ticket(conn: &Connection, id: &str, title: &str, description: &str, tags: &str, project: &str) {
+        conn.execute(
+            "INSERT INTO tickets (id, title, description, status, priority, tags, project_id, \"order\", created_at, updated_at)
+             VALUES (?1, ?2, ?3, 'todo', 'medium', ?4, ?5, 0, '', '')",
+            params![id, title, description, tags, project],
+        ).unwrap();
+    }
+
+    #[test]
+    fn test_search_tickets_ranks_and_tracks_updates() {
+        let dir = temp_db_dir("ushadow-kanban-fts");
+        let conn = open_db_connection(&dir.join("kanban.db")).unwrap();
+
+        insert_ticket(&conn, "ush-1", "Fix login redirect", "OAuth callback loops", "[\"auth\"]", "p1");
+        insert_ticket(&conn, "ush-2", "Tray menu polish", "Mention login state in the tray", "[]", "p1");
+        insert_ticket(&conn, "ush-3", "Docker logs viewer", "Stream container logs", "[\"docker\"]", "p1");
+        insert_ticket(&conn, "ush-4", "Login page copy", "", "[]", "p2");
+
+        let search = |q: &str, project: Option<&str>| -> Vec<String> {
+            let fts = build_fts_query(q).unwrap();
+            search_tickets_in(&conn, &fts, project).unwrap().into_iter().map(|t| t.id).collect()
+        };
+
+        // Title hit outranks description-only hit; project filter applies
+        assert_eq!(search("login", Some("p1")), vec!["ush-1", "ush-2"]);
+        assert_eq!(search("login", None).len(), 3);
+        // Prefix + tag matching, punctuation doesn't break the query
+        assert_eq!(search("dock", None), vec!["ush-3"]);
+        assert_eq!(search("auth redirect-", None), vec!["ush-1"]);
+        assert_eq!(search("auth", None), vec!["ush-1"]);
+
+        // Triggers keep the index in sync
+        conn.execute("UPDATE tickets SET title = 'Podman logs viewer' WHERE id = 'ush-3'", []).unwrap();
+        assert_eq!(search("podman", None), vec!["ush-3"]);
+        conn.execute("DELETE FROM tickets WHERE id = 'ush-1'", []).unwrap();
+        assert_eq!(search("login", Some("p1")), vec!["ush-2"]);
+
+        assert_eq!(build_fts_query("  \"\"  "), None);
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_reorder_ticket_keeps_columns_contiguous() {
+        let dir = temp_db_dir("ushadow-kanban-reorder");
+        let mut conn = open_db_connection(&dir.join("kanban.db")).unwrap();
+
+        for (id, status, order) in [("a", "todo", 0), ("b", "todo", 0), ("c", "todo", 5), ("d", "backlog", 2), ("e", "backlog", 2)] {
+            conn.execute(
+                "INSERT INTO tickets (id, title, status, priority, tags, project_id, \"order\", created_at, updated_at)
+                 VALUES (?1, ?1, ?2, 'medium', '[]', 'p1', ?3, '', '')",
+                params![id, status, order],
+            ).unwrap();
+        }
+        // Same status in another project must not be touched
+        conn.execute(
+            "INSERT INTO tickets (id, title, status, priority, tags, project_id, \"order\", created_at, updated_at)
+             VALUES ('x', 'x', 'todo', 'medium', '[]', 'p2', 7, '', '')",
+            [],
+        ).unwrap();
+
+        let column = |conn: &Connection, status: &str| -> Vec<(String, i32)> {
+            let mut stmt = conn.prepare(
+                "SELECT id, \"order\" FROM tickets WHERE status = ? AND project_id = 'p1' ORDER BY \"order\""
+            ).unwrap();
+            stmt.query_map([status], |r| Ok((r.get(0)?, r.get(1)?))).unwrap().map(|r| r.unwrap()).collect()
+        };
+        let ids = |col: Vec<(String, i32)>| -> Vec<String> {
+            for (i, (_, order)) in col.iter().enumerate() {
+                assert_eq!(*order, i as i32, "column not contiguous: {:?}", col);
+            }
+            col.into_iter().map(|(id, _)| id).collect()
+        };
+
+        // Move into the middle of another column; source is compacted
+        let moved = reorder_ticket_in(&mut conn, "d", "todo", 1).unwrap();
+        assert_eq!(moved.status, TicketStatus::Todo);
+        assert_eq!(moved.order, 1);
+        assert_eq!(ids(column(&conn, "todo")), vec!["a", "d", "b", "c"]);
+        assert_eq!(ids(column(&conn, "backlog")), vec!["e"]);
+
+        // Reorder within a column, and clamp out-of-range indexes to the end
+        reorder_ticket_in(&mut conn, "c", "todo", 0).unwrap();
+        assert_eq!(ids(column(&conn, "todo")), vec!["c", "a", "d", "b"]);
+        reorder_ticket_in(&mut conn, "a", "backlog", 99).unwrap();
+        assert_eq!(ids(column(&conn, "todo")), vec!["c", "d", "b"]);
+        assert_eq!(ids(column(&conn, "backlog")), vec!["e", "a"]);
+        reorder_ticket_in(&mut conn, "e", "done", 0).unwrap();
+        assert_eq!(ids(column(&conn, "backlog")), vec!["a"]);
+        assert_eq!(ids(column(&conn, "done")), vec!["e"]);
+
+        let other: i32 = conn.query_row("SELECT \"order\" FROM tickets WHERE id = 'x'", [], |r| r.get(0)).unwrap();
+        assert_eq!(other, 7);
+
+        assert!(reorder_ticket_in(&mut conn, "a", "bogus", 0).is_err());
+        assert!(reorder_ticket_in(&mut conn, "missing", "todo", 0).is_err());
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_epic_progress_counts_statuses_and_excludes_archived() {
+        let dir = temp_db_dir("ushadow-kanban-epic-progress");
+        let conn = open_db_connection(&dir.join("kanban.db")).unwrap();
+
+        let statuses = [
+            ("t1", "done", "e1"), ("t2", "done", "e1"), ("t3", "in_review", "e1"), ("t4", "in_progress", "e1"),
+            ("t5", "todo", "e1"), ("t6", "backlog", "e1"), ("t7", "archived", "e1"), ("t8", "done", "e2"),
+        ];
+        for (i, (id, status, epic)) in statuses.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO tickets (id, title, status, priority, epic_id, tags, \"order\", created_at, updated_at)
+                 VALUES (?1, ?1, ?2, 'medium', ?3, '[]', ?4, '', '')",
+                params![id, status, epic, i as i32],
+            ).unwrap();
+        }
+
+        let progress = epic_progress_in(&conn, "e1").unwrap();
+        assert_eq!(progress, EpicProgress {
+            total: 6,
+            done: 2,
+            in_review: 1,
+            in_progress: 1,
+            todo: 1,
+            backlog: 1,
+            percent_done: 33,
+        });
+
+        assert_eq!(epic_progress_in(&conn, "e2").unwrap().percent_done, 100);
+        assert_eq!(epic_progress_in(&conn, "empty").unwrap(), EpicProgress::default());
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_ticket_dependencies_reject_cycles_and_report_blocked() {
+        let dir = temp_db_dir("ushadow-kanban-deps");
+        let mut conn = open_db_connection(&dir.join("kanban.db")).unwrap();
+
+        for (i, (id, status)) in [("a", "todo"), ("b", "in_progress"), ("c", "done"), ("d", "backlog")].iter().enumerate() {
+            conn.execute(
+                "INSERT INTO tickets (id, title, status, priority, tags, \"order\", created_at, updated_at)
+                 VALUES (?1, ?1, ?2, 'medium', '[]', ?3, '', '')",
+                params![id, status, i as i32],
+            ).unwrap();
+        }
+
+        // a -> b -> c, d -> c
+        add_ticket_dependency_in(&mut conn, "a", "b").unwrap();
+        add_ticket_dependency_in(&mut conn, "b", "c").unwrap();
+        add_ticket_dependency_in(&mut conn, "d", "c").unwrap();
+        add_ticket_dependency_in(&mut conn, "a", "b").unwrap(); // duplicate is a no-op
+
+        // Direct, transitive and self cycles are rejected
+        assert!(add_ticket_dependency_in(&mut conn, "b", "a").is_err());
+        assert!(add_ticket_dependency_in(&mut conn, "c", "a").is_err());
+        assert!(add_ticket_dependency_in(&mut conn, "a", "a").is_err());
+        assert!(add_ticket_dependency_in(&mut conn, "a", "missing").is_err());
+        // Diamond shapes are fine
+        add_ticket_dependency_in(&mut conn, "a", "c").unwrap();
+
+        let blocked = |conn: &Connection| -> Vec<String> {
+            blocked_tickets_in(conn).unwrap().into_iter().map(|t| t.id).collect()
+        };
+        // Only a is blocked (b isn't done); b and d depend on c, which is done
+        assert_eq!(blocked(&conn), vec!["a"]);
+        assert_eq!(unfinished_dependencies_in(&conn, "a").unwrap(), vec!["b"]);
+
+        conn.execute("UPDATE tickets SET status = 'todo' WHERE id = 'c'", []).unwrap();
+        assert_eq!(blocked(&conn), vec!["a", "b", "d"]);
+        assert_eq!(unfinished_dependencies_in(&conn, "a").unwrap(), vec!["b", "c"]);
+
+        conn.execute("DELETE FROM ticket_dependencies WHERE ticket_id = 'd'", []).unwrap();
+        conn.execute("UPDATE tickets SET status = 'done' WHERE id IN ('b', 'c')", []).unwrap();
+        assert!(blocked(&conn).is_empty());
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    fn sample_ticket(id: &str, title: &str, epic_id: Option<&str>, updated_at: &str) -> Ticket {
+        Ticket {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: Some("desc".to_string()),
+            status: TicketStatus::InReview,
+            priority: TicketPriority::High,
+            epic_id: epic_id.map(str::to_string),
+            tags: vec!["auth".to_string()],
+            color: None,
+            tmux_window_name: None,
+            tmux_session_name: None,
+            branch_name: Some("feat/x".to_string()),
+            worktree_path: None,
+            environment_name: None,
+            project_id: Some("p1".to_string()),
+            assigned_to: None,
+            order: 2,
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            updated_at: updated_at.to_string(),
+        }
+    }
+
+    fn sample_export() -> KanbanExport {
+        KanbanExport {
+            version: KANBAN_EXPORT_VERSION,
+            exported_at: String::new(),
+            epics: vec![Epic {
+                id: "e1".to_string(),
+                title: "Auth".to_string(),
+                description: None,
+                color: "#ff0000".to_string(),
+                branch_name: None,
+                base_branch: "main".to_string(),
+                project_id: Some("p1".to_string()),
+                created_at: "2026-01-01T00:00:00+00:00".to_string(),
+                updated_at: "2026-01-01T00:00:00+00:00".to_string(),
+            }],
+            tickets: vec![
+                sample_ticket("ush-1", "Login", Some("e1"), "2026-01-02T00:00:00+00:00"),
+                sample_ticket("ush-2", "Logout", Some("gone"), "2026-01-02T00:00:00+00:00"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_kanban_export_import_round_trip() {
+        let dir = temp_db_dir("ushadow-kanban-export");
+        let mut source = open_db_connection(&dir.join("source.db")).unwrap();
+        import_kanban_into(&mut source, &sample_export(), false).unwrap();
+
+        let exported = export_kanban_from(&source).unwrap();
+        let json = serde_json::to_string(&exported).unwrap();
+
+        // Documents from newer versions may carry fields we don't know about
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["future_field"] = serde_json::json!({"x": 1});
+        value["tickets"][0]["due_date"] = serde_json::json!("2027-01-01");
+        let parsed: KanbanExport = serde_json::from_value(value).unwrap();
+
+        let mut target = open_db_connection(&dir.join("target.db")).unwrap();
+        insert_ticket(&target, "old-1", "Stale", "", "[]", "p9");
+        let summary = import_kanban_into(&mut target, &parsed, false).unwrap();
+        assert_eq!((summary.epics, summary.tickets, summary.skipped), (1, 2, 0));
+
+        let roundtrip = export_kanban_from(&target).unwrap();
+        assert_eq!(serde_json::to_value(&roundtrip.epics).unwrap(), serde_json::to_value(&exported.epics).unwrap());
+        assert_eq!(serde_json::to_value(&roundtrip.tickets).unwrap(), serde_json::to_value(&exported.tickets).unwrap());
+
+        // Replace dropped the stale ticket; the dangling epic reference was cleared
+        let ids: Vec<String> = roundtrip.tickets.iter().map(|t| t.id.clone()).collect();
+        assert_eq!(ids, vec!["ush-1", "ush-2"]);
+        assert_eq!(roundtrip.tickets[0].epic_id.as_deref(), Some("e1"));
+        assert_eq!(roundtrip.tickets[1].epic_id, None);
+        assert_eq!(roundtrip.tickets[0].status, TicketStatus::InReview);
+
+        drop(source);
+        drop(target);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_kanban_import_merge_keeps_newest() {
+        let dir = temp_db_dir("ushadow-kanban-merge");
+        let mut conn = open_db_connection(&dir.join("kanban.db")).unwrap();
+        import_kanban_into(&mut conn, &sample_export(), false).unwrap();
+
+        let mut incoming = sample_export();
+        incoming.tickets = vec![
+            // Older than the local copy: ignored
+            sample_ticket("ush-1", "Login (old)", Some("e1"), "2025-12-31T00:00:00+00:00"),
+            // Newer (different offset, same instant + 1h): wins
+            sample_ticket("ush-2", "Logout (new)", None, "2026-01-02T02:00:00+01:00"),
+            sample_ticket("ush-3", "Signup", Some("e1"), "2026-01-03T00:00:00+00:00"),
+        ];
+
+        let summary = import_kanban_into(&mut conn, &incoming, true).unwrap();
+        // The epic is unchanged (same updated_at), so it's skipped along with ush-1
+        assert_eq!((summary.epics, summary.tickets, summary.skipped), (0, 2, 2));
+
+        let title = |id: &str| -> String {
+            conn.query_row("SELECT title FROM tickets WHERE id = ?", [id], |r| r.get(0)).unwrap()
+        };
+        assert_eq!(title("ush-1"), "Login");
+        assert_eq!(title("ush-2"), "Logout (new)");
+        assert_eq!(title("ush-3"), "Signup");
+
+        // The search index follows the upserts
+        let fts = build_fts_query("logout new").unwrap();
+        assert_eq!(search_tickets_in(&conn, &fts, None).unwrap().len(), 1);
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_verify_and_repair_fix_dangling_references() {
+        let dir = temp_db_dir("ushadow-kanban-verify");
+        let path = dir.join("kanban.db");
+        let conn = open_db_connection(&path).unwrap();
+        insert_ticket(&conn, "ush-1", "Login", "", "[]", "p1");
+        conn.execute("UPDATE tickets SET epic_id = 'missing' WHERE id = 'ush-1'", []).unwrap();
+        conn.execute(
+            "INSERT INTO ticket_dependencies (ticket_id, depends_on_id, created_at) VALUES ('ush-1', 'gone', '')",
+            [],
+        ).unwrap();
+        drop(conn);
+
+        let report = verify_db_at(&path).unwrap();
+        assert!(!report.ok);
+        assert!(report.integrity_errors.is_empty());
+        assert_eq!(report.foreign_key_errors.len(), 2, "{:?}", report.foreign_key_errors);
+
+        let result = repair_db_at(&path).unwrap();
+        assert!(!result.rebuilt);
+        assert!(result.report.ok, "{:?}", result.report);
+        assert!(std::path::Path::new(&result.backup_path).exists());
+
+        let conn = open_db_connection(&path).unwrap();
+        let epic: Option<String> = conn.query_row("SELECT epic_id FROM tickets WHERE id = 'ush-1'", [], |r| r.get(0)).unwrap();
+        assert_eq!(epic, None);
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_repair_rebuilds_unreadable_database() {
+        let dir = temp_db_dir("ushadow-kanban-repair");
+        let path = dir.join("kanban.db");
+        fs::write(&path, vec![0x5a_u8; 4096]).unwrap();
+
+        let report = verify_db_at(&path).unwrap();
+        assert!(!report.ok);
+        assert!(!report.integrity_errors.is_empty());
+
+        let result = repair_db_at(&path).unwrap();
+        assert!(result.rebuilt);
+        assert_eq!(result.rows_recovered, 0);
+        assert!(result.report.ok, "{:?}", result.report);
+        assert_eq!(fs::read(&result.backup_path).unwrap(), vec![0x5a_u8; 4096]);
+
+        // The rebuilt file is a working, fully migrated board
+        let conn = open_db_connection(&path).unwrap();
+        insert_ticket(&conn, "ush-1", "Login", "", "[]", "p1");
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_rebuild_copies_readable_rows() {
+        let dir = temp_db_dir("ushadow-kanban-rebuild");
+        let mut conn = open_db_connection(&dir.join("old.db")).unwrap();
+        import_kanban_into(&mut conn, &sample_export(), false).unwrap();
+        drop(conn);
+
+        let recovered = rebuild_db_from(&dir.join("old.db"), &dir.join("new.db")).unwrap();
+        assert_eq!(recovered, 3);
+
+        let conn = open_db_connection(&dir.join("new.db")).unwrap();
+        let fts = build_fts_query("login").unwrap();
+        assert_eq!(search_tickets_in(&conn, &fts, None).unwrap().len(), 1);
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_project_default_base_branch() {
+        let dir = temp_db_dir("ushadow-kanban-base-branch");
+        let conn = open_db_connection(&dir.join("kanban.db")).unwrap();
+
+        assert_eq!(default_base_branch_in(&conn, "/repos/ushadow").unwrap(), None);
+
+        set_default_base_branch_in(&conn, "/repos/ushadow", Some("develop")).unwrap();
+        assert_eq!(default_base_branch_in(&conn, "/repos/ushadow").unwrap(), Some("develop".to_string()));
+        // The project row was created with a derived ticket prefix
+        let prefix: String = conn.query_row(
+            "SELECT ticket_prefix FROM projects WHERE id = '/repos/ushadow'", [], |r| r.get(0),
+        ).unwrap();
+        assert!(!prefix.is_empty());
+
+        set_default_base_branch_in(&conn, "/repos/ushadow", None).unwrap();
+        assert_eq!(default_base_branch_in(&conn, "/repos/ushadow").unwrap(), None);
+        assert_eq!(get_or_create_ticket_prefix(&conn, Some("/repos/ushadow")).unwrap(), prefix);
+
+        drop(conn);
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,90 @@
+use std::path::{Path, PathBuf};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// Prefix of the daily log files (`launcher.log.YYYY-MM-DD`)
+const LOG_FILE_PREFIX: &str = "launcher.log";
+
+/// Default filter when RUST_LOG is unset
+const DEFAULT_LOG_FILTER: &str = "info";
+
+/// Directory the launcher writes its log files to (platform app data dir)
+pub(crate) fn log_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("ushadow-launcher")
+        .join("logs")
+}
+
+/// Newest log file in `dir`, if any have been written
+fn latest_log_file(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(LOG_FILE_PREFIX))
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .map(|entry| entry.path())
+}
+
+/// Subscriber writing to a daily-rolling file in `dir` (and to stderr, for dev runs).
+/// Events are written on a background thread until the returned guard is dropped.
+fn build_subscriber(dir: &Path) -> Result<(impl tracing::Subscriber + Send + Sync, WorkerGuard), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create log directory {}: {}", dir.display(), e))?;
+
+    let (file_writer, guard) = tracing_appender::non_blocking(tracing_appender::rolling::daily(dir, LOG_FILE_PREFIX));
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(file_writer).with_ansi(false))
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr));
+
+    Ok((subscriber, guard))
+}
+
+/// Install the global file logger. Keep the guard alive for the life of the app so
+/// buffered events are flushed on exit.
+pub fn init_logging() -> Result<WorkerGuard, String> {
+    let (subscriber, guard) = build_subscriber(&log_dir())?;
+    subscriber.try_init().map_err(|e| format!("Failed to install logger: {}", e))?;
+    Ok(guard)
+}
+
+/// Path of the current log file (or the log directory before anything has been logged)
+#[tauri::command]
+pub fn get_log_path() -> Result<String, String> {
+    let dir = log_dir();
+    let path = latest_log_file(&dir).unwrap_or(dir);
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_file_receives_events() {
+        let dir = std::env::temp_dir().join(format!("ushadow-logs-{}", uuid::Uuid::new_v4()));
+        assert_eq!(latest_log_file(&dir), None);
+
+        let (subscriber, guard) = build_subscriber(&dir).unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "start_env", "Starting environment: {}", "gold");
+            tracing::warn!(target: "create_worktree", "Pruned stale worktree references");
+        });
+        // Dropping the guard flushes the background writer
+        drop(guard);
+
+        let log_file = latest_log_file(&dir).expect("log file should be created");
+        assert!(log_file.file_name().unwrap().to_string_lossy().starts_with(LOG_FILE_PREFIX));
+
+        let contents = std::fs::read_to_string(&log_file).unwrap();
+        assert!(contents.contains("INFO start_env: Starting environment: gold"));
+        assert!(contents.contains("WARN create_worktree: Pruned stale worktree references"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -12,7 +12,10 @@ mod bundled;  // Bundled resources locator
 pub mod worktree;
 pub mod platform;  // Platform abstraction layer
 mod claude_sessions;  // Claude Code session monitoring
+mod session_watcher;  // Live tail of the Claude session log
+mod notification_inbox;  // Read/unread state for agent notifications
 mod kanban;  // Kanban ticket integration
+mod ticket_ids;  // Ticket ID sequencing (shared with kanban-cli)
 mod oauth_server;  // OAuth callback server for desktop auth
 mod http_client;  // HTTP client for CORS-free requests
 // Embedded terminal module (PTY-based) - DEPRECATED in favor of native terminal integration (iTerm2/Terminal.app/gnome-terminal)
@@ -21,6 +24,14 @@ mod config_commands;
 mod container_discovery;
 mod port_utils;
 mod env_scanner;
+mod container_runtime;  // Docker/Podman selection
+mod recent_envs;  // Most-recently-used environments
+mod support_bundle;  // Diagnostics zip for bug reports
+mod operations;  // Cancellation of long-running commands
+mod status_history;  // Environment status transitions
+mod logging;  // File logging via tracing
+mod multiplexer;  // tmux/zellij abstraction
+mod artifacts;  // Gitignored build artifacts in worktrees
 
 pub use docker::*;
 pub use discovery::*;
@@ -33,6 +44,8 @@ pub use permissions::*;
 pub use settings::*;
 pub use worktree::*;
 pub use claude_sessions::*;  // Export Claude session monitoring functions
+pub use session_watcher::*;
+pub use notification_inbox::*;
 pub use kanban::*;  // Export kanban ticket functions
 pub use oauth_server::*;  // Export OAuth server functions
 pub use http_client::*;  // Export HTTP client functions
@@ -41,3 +54,11 @@ pub use config_commands::*;
 pub use container_discovery::*;
 pub use port_utils::*;
 pub use env_scanner::*;
+pub use container_runtime::*;
+pub use recent_envs::*;
+pub use support_bundle::*;
+pub use operations::*;
+pub use status_history::*;
+pub use logging::*;
+pub use multiplexer::*;
+pub use artifacts::*;
@@ -0,0 +1,441 @@
+use serde::{Deserialize, Serialize};
+use super::settings::read_launcher_settings;
+use super::utils::{shell_command, shell_quote};
+use super::worktree::check_tmux_installed;
+
+/// Terminal multiplexer hosting environment sessions, chosen in launcher settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MultiplexerKind {
+    #[default]
+    Tmux,
+    Zellij,
+}
+
+/// Operations the launcher needs from a multiplexer. A session holds named windows
+/// (zellij calls them tabs), each rooted at a worktree.
+pub trait Multiplexer: Send + Sync {
+    /// CLI binary name
+    fn binary(&self) -> &'static str;
+    /// Fail with an actionable error if the binary isn't installed
+    fn check_installed(&self) -> Result<(), String>;
+    /// Make sure sessions can be created (starts the server where there is one)
+    fn ensure_running(&self) -> Result<(), String>;
+    fn session_exists(&self, session: &str) -> bool;
+    fn window_exists(&self, session: &str, window: &str) -> bool;
+    /// Create a detached session whose first window is `window`, in `dir`
+    fn create_session(&self, session: &str, window: &str, dir: &str) -> Result<(), String>;
+    fn new_window(&self, session: &str, window: &str, dir: &str) -> Result<(), String>;
+    fn select_window(&self, session: &str, window: &str) -> Result<(), String>;
+    /// Type `keys` into the window, followed by Enter
+    fn send_keys(&self, session: &str, window: &str, keys: &str) -> Result<(), String>;
+    fn capture_pane(&self, session: &str, window: &str) -> Result<String, String>;
+    fn list_sessions(&self) -> Result<Vec<String>, String>;
+    /// Foreground command of the window's active pane; `None` if the multiplexer can't tell
+    fn current_command(&self, session: &str, window: &str) -> Option<String>;
+    /// Pid of the process in the window's active pane; `None` if the multiplexer can't tell
+    fn pane_pid(&self, session: &str, window: &str) -> Option<u32>;
+    /// Foreground command of `window` in whichever session has it (empty if unknown);
+    /// `None` if no session has such a window
+    fn find_window_command(&self, window: &str) -> Option<String>;
+    /// Program and arguments that attach a terminal to `session`
+    fn attach_argv(&self, session: &str) -> Vec<String>;
+}
+
+/// Run a shell command, returning stdout, or stderr as the error
+fn run(command: &str) -> Result<String, String> {
+    let output = shell_command(command)
+        .output()
+        .map_err(|e| format!("Failed to run '{}': {}", command, e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Last non-empty line of output; login shell profile banners can precede the real output
+fn last_line(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .last()
+        .map(String::from)
+}
+
+fn lines(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+pub struct Tmux;
+
+impl Tmux {
+    fn target(session: &str, window: &str) -> String {
+        shell_quote(&format!("{}:{}", session, window))
+    }
+
+    fn has_session_command(session: &str) -> String {
+        format!("tmux has-session -t {}", shell_quote(session))
+    }
+
+    fn window_exists_command(session: &str, window: &str) -> String {
+        format!(
+            "tmux list-windows -t {} -F '#{{window_name}}' 2>/dev/null | grep -Fx {}",
+            shell_quote(session),
+            shell_quote(window)
+        )
+    }
+
+    fn new_session_command(session: &str, window: &str, dir: &str) -> String {
+        format!(
+            "tmux new-session -d -s {} -c {} -n {}",
+            shell_quote(session),
+            shell_quote(dir),
+            shell_quote(window)
+        )
+    }
+
+    fn new_window_command(session: &str, window: &str, dir: &str) -> String {
+        format!(
+            "tmux new-window -t {} -n {} -c {}",
+            shell_quote(session),
+            shell_quote(window),
+            shell_quote(dir)
+        )
+    }
+
+    fn select_window_command(session: &str, window: &str) -> String {
+        format!("tmux select-window -t {}", Self::target(session, window))
+    }
+
+    fn send_keys_command(session: &str, window: &str, keys: &str) -> String {
+        format!("tmux send-keys -t {} {} Enter", Self::target(session, window), shell_quote(keys))
+    }
+
+    fn capture_pane_command(session: &str, window: &str) -> String {
+        format!("tmux capture-pane -t {} -p -S -100", Self::target(session, window))
+    }
+
+    fn display_command(session: &str, window: &str, format: &str) -> String {
+        format!("tmux display-message -t {} -p '{}'", Self::target(session, window), format)
+    }
+}
+
+/// Pid of the process in a tmux pane (`session:window` target)
+pub(crate) fn tmux_pane_pid(target: &str) -> Option<u32> {
+    let output = run(&format!("tmux display-message -t {} -p '#{{pane_pid}}'", shell_quote(target))).ok()?;
+    output.lines().filter_map(|l| l.trim().parse::<u32>().ok()).last()
+}
+
+impl Multiplexer for Tmux {
+    fn binary(&self) -> &'static str {
+        "tmux"
+    }
+
+    fn check_installed(&self) -> Result<(), String> {
+        check_tmux_installed().map(|_| ())
+    }
+
+    fn ensure_running(&self) -> Result<(), String> {
+        self.check_installed()?;
+        run("tmux start-server").map(|_| ())
+    }
+
+    fn session_exists(&self, session: &str) -> bool {
+        run(&Self::has_session_command(session)).is_ok()
+    }
+
+    fn window_exists(&self, session: &str, window: &str) -> bool {
+        run(&Self::window_exists_command(session, window))
+            .map(|out| !out.trim().is_empty())
+            .unwrap_or(false)
+    }
+
+    fn create_session(&self, session: &str, window: &str, dir: &str) -> Result<(), String> {
+        run(&Self::new_session_command(session, window, dir)).map(|_| ())
+    }
+
+    fn new_window(&self, session: &str, window: &str, dir: &str) -> Result<(), String> {
+        run(&Self::new_window_command(session, window, dir)).map(|_| ())
+    }
+
+    fn select_window(&self, session: &str, window: &str) -> Result<(), String> {
+        run(&Self::select_window_command(session, window)).map(|_| ())
+    }
+
+    fn send_keys(&self, session: &str, window: &str, keys: &str) -> Result<(), String> {
+        run(&Self::send_keys_command(session, window, keys)).map(|_| ())
+    }
+
+    fn capture_pane(&self, session: &str, window: &str) -> Result<String, String> {
+        run(&Self::capture_pane_command(session, window))
+    }
+
+    fn list_sessions(&self) -> Result<Vec<String>, String> {
+        // tmux exits non-zero when no server is running, i.e. there are no sessions
+        Ok(run("tmux list-sessions -F '#{session_name}'")
+            .map(|out| lines(&out))
+            .unwrap_or_default())
+    }
+
+    fn current_command(&self, session: &str, window: &str) -> Option<String> {
+        // An unreachable pane reads as an idle one, as tmux itself reports nothing
+        Some(
+            run(&Self::display_command(session, window, "#{pane_current_command}"))
+                .ok()
+                .and_then(|out| last_line(&out))
+                .unwrap_or_default(),
+        )
+    }
+
+    fn pane_pid(&self, session: &str, window: &str) -> Option<u32> {
+        tmux_pane_pid(&format!("{}:{}", session, window))
+    }
+
+    fn find_window_command(&self, window: &str) -> Option<String> {
+        let output = run(&format!(
+            "tmux list-panes -a -F '#{{window_name}} #{{pane_current_command}} #{{pane_tty}}' | grep {}",
+            shell_quote(&format!("^{}", window))
+        ))
+        .ok()?;
+        output.split_whitespace().nth(1).map(String::from)
+    }
+
+    fn attach_argv(&self, session: &str) -> Vec<String> {
+        vec!["tmux".to_string(), "attach-session".to_string(), "-t".to_string(), session.to_string()]
+    }
+}
+
+/// Zellij has no server or pane-process introspection, so agent detection relies on tmux-only
+/// features being reported as unknown.
+pub struct Zellij;
+
+impl Zellij {
+    fn action_command(session: &str, action: &str) -> String {
+        format!("zellij --session {} action {}", shell_quote(session), action)
+    }
+
+    fn new_session_command(session: &str, window: &str, dir: &str) -> String {
+        format!(
+            "cd {} && zellij attach --create-background {} && {}",
+            shell_quote(dir),
+            shell_quote(session),
+            Self::action_command(session, &format!("rename-tab {}", shell_quote(window)))
+        )
+    }
+
+    fn new_window_command(session: &str, window: &str, dir: &str) -> String {
+        Self::action_command(
+            session,
+            &format!("new-tab --name {} --cwd {}", shell_quote(window), shell_quote(dir)),
+        )
+    }
+
+    fn select_window_command(session: &str, window: &str) -> String {
+        Self::action_command(session, &format!("go-to-tab-name {}", shell_quote(window)))
+    }
+
+    fn send_keys_command(session: &str, window: &str, keys: &str) -> String {
+        format!(
+            "{} && {} && {}",
+            Self::select_window_command(session, window),
+            Self::action_command(session, &format!("write-chars {}", shell_quote(keys))),
+            // 13 = carriage return
+            Self::action_command(session, "write 13")
+        )
+    }
+}
+
+impl Multiplexer for Zellij {
+    fn binary(&self) -> &'static str {
+        "zellij"
+    }
+
+    fn check_installed(&self) -> Result<(), String> {
+        run("zellij --version")
+            .map(|_| ())
+            .map_err(|_| "zellij is not installed; install it from https://zellij.dev or switch the multiplexer setting back to tmux".to_string())
+    }
+
+    fn ensure_running(&self) -> Result<(), String> {
+        // Every zellij session is its own server
+        self.check_installed()
+    }
+
+    fn session_exists(&self, session: &str) -> bool {
+        self.list_sessions()
+            .map(|sessions| sessions.iter().any(|s| s == session))
+            .unwrap_or(false)
+    }
+
+    fn window_exists(&self, session: &str, window: &str) -> bool {
+        run(&Self::action_command(session, "query-tab-names"))
+            .map(|out| out.lines().any(|tab| tab.trim() == window))
+            .unwrap_or(false)
+    }
+
+    fn create_session(&self, session: &str, window: &str, dir: &str) -> Result<(), String> {
+        run(&Self::new_session_command(session, window, dir)).map(|_| ())
+    }
+
+    fn new_window(&self, session: &str, window: &str, dir: &str) -> Result<(), String> {
+        run(&Self::new_window_command(session, window, dir)).map(|_| ())
+    }
+
+    fn select_window(&self, session: &str, window: &str) -> Result<(), String> {
+        run(&Self::select_window_command(session, window)).map(|_| ())
+    }
+
+    fn send_keys(&self, session: &str, window: &str, keys: &str) -> Result<(), String> {
+        run(&Self::send_keys_command(session, window, keys)).map(|_| ())
+    }
+
+    fn capture_pane(&self, session: &str, window: &str) -> Result<String, String> {
+        let dump = std::env::temp_dir().join(format!("ushadow-zellij-dump-{}.txt", uuid::Uuid::new_v4()));
+        let result = run(&format!(
+            "{} && {}",
+            Self::select_window_command(session, window),
+            Self::action_command(session, &format!("dump-screen {}", shell_quote(&dump.to_string_lossy())))
+        ))
+        .and_then(|_| std::fs::read_to_string(&dump).map_err(|e| format!("Failed to read pane dump: {}", e)));
+        std::fs::remove_file(&dump).ok();
+        result
+    }
+
+    fn list_sessions(&self) -> Result<Vec<String>, String> {
+        // zellij exits non-zero when there are no sessions
+        Ok(run("zellij list-sessions --short --no-formatting")
+            .map(|out| lines(&out))
+            .unwrap_or_default())
+    }
+
+    fn current_command(&self, _session: &str, _window: &str) -> Option<String> {
+        None
+    }
+
+    fn pane_pid(&self, _session: &str, _window: &str) -> Option<u32> {
+        None
+    }
+
+    fn find_window_command(&self, window: &str) -> Option<String> {
+        self.list_sessions()
+            .ok()?
+            .iter()
+            .any(|session| self.window_exists(session, window))
+            .then(String::new)
+    }
+
+    fn attach_argv(&self, session: &str) -> Vec<String> {
+        vec!["zellij".to_string(), "attach".to_string(), session.to_string()]
+    }
+}
+
+static TMUX: Tmux = Tmux;
+static ZELLIJ: Zellij = Zellij;
+
+pub fn multiplexer_for(kind: MultiplexerKind) -> &'static dyn Multiplexer {
+    match kind {
+        MultiplexerKind::Tmux => &TMUX,
+        MultiplexerKind::Zellij => &ZELLIJ,
+    }
+}
+
+/// The multiplexer selected in launcher settings (tmux unless configured otherwise)
+pub fn multiplexer() -> &'static dyn Multiplexer {
+    multiplexer_for(read_launcher_settings().map(|s| s.multiplexer).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::LauncherSettings;
+
+    #[test]
+    fn test_tmux_command_strings() {
+        assert_eq!(Tmux::has_session_command("ush-gold"), "tmux has-session -t 'ush-gold'");
+        assert_eq!(
+            Tmux::window_exists_command("ush-gold", "ushadow-gold"),
+            "tmux list-windows -t 'ush-gold' -F '#{window_name}' 2>/dev/null | grep -Fx 'ushadow-gold'"
+        );
+        assert_eq!(
+            Tmux::new_session_command("ush-gold", "ushadow-gold", "/work/gold"),
+            "tmux new-session -d -s 'ush-gold' -c '/work/gold' -n 'ushadow-gold'"
+        );
+        assert_eq!(
+            Tmux::new_window_command("ush-gold", "ushadow-gold", "/work/gold"),
+            "tmux new-window -t 'ush-gold' -n 'ushadow-gold' -c '/work/gold'"
+        );
+        assert_eq!(
+            Tmux::select_window_command("ush-gold", "ushadow-gold"),
+            "tmux select-window -t 'ush-gold:ushadow-gold'"
+        );
+        assert_eq!(
+            Tmux::send_keys_command("ush-gold", "ushadow-gold", "bash /tmp/ushadow_claude_gold.sh"),
+            "tmux send-keys -t 'ush-gold:ushadow-gold' 'bash /tmp/ushadow_claude_gold.sh' Enter"
+        );
+        assert_eq!(
+            Tmux::capture_pane_command("ush-gold", "ushadow-gold"),
+            "tmux capture-pane -t 'ush-gold:ushadow-gold' -p -S -100"
+        );
+        assert_eq!(
+            Tmux::display_command("ush-gold", "ushadow-gold", "#{pane_current_command}"),
+            "tmux display-message -t 'ush-gold:ushadow-gold' -p '#{pane_current_command}'"
+        );
+        assert_eq!(TMUX.attach_argv("ush-gold"), vec!["tmux", "attach-session", "-t", "ush-gold"]);
+    }
+
+    #[test]
+    fn test_zellij_dispatch() {
+        let settings: LauncherSettings =
+            serde_json::from_str(r#"{"default_admin_email":null,"default_admin_password":null,"default_admin_name":null,"multiplexer":"zellij"}"#)
+                .unwrap();
+        let mux = multiplexer_for(settings.multiplexer);
+        assert_eq!(mux.binary(), "zellij");
+        assert_eq!(mux.attach_argv("ush-gold"), vec!["zellij", "attach", "ush-gold"]);
+        // Pane introspection is tmux-only
+        assert_eq!(mux.current_command("ush-gold", "ushadow-gold"), None);
+        assert_eq!(mux.pane_pid("ush-gold", "ushadow-gold"), None);
+
+        assert_eq!(
+            Zellij::new_session_command("ush-gold", "ushadow-gold", "/work/gold"),
+            "cd '/work/gold' && zellij attach --create-background 'ush-gold' && \
+             zellij --session 'ush-gold' action rename-tab 'ushadow-gold'"
+        );
+        assert_eq!(
+            Zellij::send_keys_command("ush-gold", "ushadow-gold", "bash x.sh"),
+            "zellij --session 'ush-gold' action go-to-tab-name 'ushadow-gold' && \
+             zellij --session 'ush-gold' action write-chars 'bash x.sh' && \
+             zellij --session 'ush-gold' action write 13"
+        );
+
+        assert_eq!(multiplexer_for(MultiplexerKind::default()).binary(), "tmux");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tmux_commands_quote_awkward_paths() {
+        /// Words the shell passes to tmux for a generated command line
+        fn shell_words(command: &str) -> Vec<String> {
+            let printed = std::process::Command::new("sh")
+                .args(["-c", &command.replacen("tmux", "printf '%s\\n'", 1)])
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&printed.stdout).lines().map(String::from).collect()
+        }
+
+        let path = "/Users/me/My Repos/it's $HOME/gold";
+        assert_eq!(
+            shell_words(&Tmux::new_session_command("ush-gold", "ush-gold", path)),
+            vec!["new-session", "-d", "-s", "ush-gold", "-c", path, "-n", "ush-gold"]
+        );
+        assert_eq!(
+            shell_words(&Tmux::new_window_command("workmux", "ush-o'neil", path)),
+            vec!["new-window", "-t", "workmux", "-n", "ush-o'neil", "-c", path]
+        );
+    }
+}
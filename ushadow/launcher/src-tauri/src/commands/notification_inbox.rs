@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use super::claude_sessions::{read_session_events, ClaudeSessionEvent};
+use super::settings::launcher_config_dir;
+
+/// Maximum number of acknowledged notifications remembered (oldest are forgotten first)
+const MAX_ACKNOWLEDGED: usize = 1000;
+
+/// Serializes read-modify-write of the store between concurrent commands
+static ACK_LOCK: Mutex<()> = Mutex::new(());
+
+fn ack_store_path() -> Result<PathBuf, String> {
+    Ok(launcher_config_dir()?.join("acknowledged_notifications.json"))
+}
+
+/// Key identifying one notification event
+fn notification_key(session_id: &str, timestamp: &str) -> String {
+    format!("{}@{}", session_id, timestamp)
+}
+
+/// Load acknowledged keys, oldest first; a missing or corrupt file is treated as empty
+fn load_acknowledged(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn acknowledge_at(path: &Path, key: String) -> Result<(), String> {
+    let mut acknowledged = load_acknowledged(path);
+    if acknowledged.contains(&key) {
+        return Ok(());
+    }
+    acknowledged.push(key);
+    if acknowledged.len() > MAX_ACKNOWLEDGED {
+        acknowledged.drain(..acknowledged.len() - MAX_ACKNOWLEDGED);
+    }
+
+    let json = serde_json::to_string_pretty(&acknowledged)
+        .map_err(|e| format!("Failed to serialize acknowledged notifications: {}", e))?;
+    fs::write(path, json)
+        .map_err(|e| format!("Failed to write acknowledged notifications: {}", e))
+}
+
+/// `Notification` events that haven't been acknowledged
+fn unread_notifications(events: Vec<ClaudeSessionEvent>, acknowledged: &HashSet<String>) -> Vec<ClaudeSessionEvent> {
+    events
+        .into_iter()
+        .filter(|e| e.event_type == "Notification")
+        .filter(|e| !acknowledged.contains(&notification_key(&e.session_id, &e.timestamp)))
+        .collect()
+}
+
+/// Mark a notification event as dealt with so it drops out of the unread list
+#[tauri::command]
+pub async fn acknowledge_notification(session_id: String, timestamp: String) -> Result<(), String> {
+    let _guard = ACK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    acknowledge_at(&ack_store_path()?, notification_key(&session_id, &timestamp))
+}
+
+/// Unacknowledged `Notification` events from the last `hours`, for one worktree or all;
+/// its length is the attention count
+#[tauri::command]
+pub async fn get_unread_notifications(worktree_path: Option<String>, hours: i64) -> Result<Vec<ClaudeSessionEvent>, String> {
+    let events = read_session_events(worktree_path.as_deref(), hours)?;
+    let acknowledged: HashSet<String> = {
+        let _guard = ACK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        load_acknowledged(&ack_store_path()?).into_iter().collect()
+    };
+    Ok(unread_notifications(events, &acknowledged))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(session_id: &str, timestamp: &str, event_type: &str) -> ClaudeSessionEvent {
+        ClaudeSessionEvent {
+            event_type: event_type.to_string(),
+            session_id: session_id.to_string(),
+            cwd: "/repos/gold".to_string(),
+            timestamp: timestamp.to_string(),
+            data: serde_json::json!({ "message": "Claude is waiting for your input" }),
+        }
+    }
+
+    #[test]
+    fn test_acknowledged_notifications_are_excluded() {
+        let dir = std::env::temp_dir().join(format!("ushadow-acks-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("acknowledged_notifications.json");
+
+        let events = vec![
+            notification("s1", "2026-01-01T00:00:00Z", "Notification"),
+            notification("s1", "2026-01-01T00:05:00Z", "Notification"),
+            notification("s2", "2026-01-01T00:00:00Z", "Notification"),
+            notification("s1", "2026-01-01T00:06:00Z", "Stop"),
+        ];
+
+        acknowledge_at(&path, notification_key("s1", "2026-01-01T00:00:00Z")).unwrap();
+        // Acknowledging twice doesn't duplicate the key
+        acknowledge_at(&path, notification_key("s1", "2026-01-01T00:00:00Z")).unwrap();
+        assert_eq!(load_acknowledged(&path).len(), 1);
+
+        let acknowledged: HashSet<String> = load_acknowledged(&path).into_iter().collect();
+        let unread = unread_notifications(events, &acknowledged);
+        let keys: Vec<String> = unread.iter().map(|e| notification_key(&e.session_id, &e.timestamp)).collect();
+        assert_eq!(keys, vec!["s1@2026-01-01T00:05:00Z", "s2@2026-01-01T00:00:00Z"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_acknowledged_store_is_bounded() {
+        let dir = std::env::temp_dir().join(format!("ushadow-acks-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("acknowledged_notifications.json");
+
+        for i in 0..MAX_ACKNOWLEDGED + 5 {
+            acknowledge_at(&path, notification_key("s", &i.to_string())).unwrap();
+        }
+        let acknowledged = load_acknowledged(&path);
+        assert_eq!(acknowledged.len(), MAX_ACKNOWLEDGED);
+        assert_eq!(acknowledged[0], "s@5");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
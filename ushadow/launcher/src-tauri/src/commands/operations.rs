@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::State;
+use super::docker::AppState;
+use super::utils::silent_command;
+
+/// Error returned by a command that was cancelled via `cancel_operation`
+pub const OPERATION_CANCELLED: &str = "Cancelled";
+
+/// How often a running subprocess is checked for cancellation
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Shared flag a long-running command checks between stages
+#[derive(Clone, Default, Debug)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// `Err(OPERATION_CANCELLED)` once cancelled, for use with `?` at safe points
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(OPERATION_CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Registered operations by id
+pub type OperationRegistry = Mutex<HashMap<String, CancelToken>>;
+
+/// Keeps an operation's token registered for as long as the command runs
+pub(crate) struct OperationGuard<'a> {
+    registry: &'a OperationRegistry,
+    id: Option<String>,
+    pub token: CancelToken,
+}
+
+impl<'a> OperationGuard<'a> {
+    /// Register `id` (if the caller supplied one) so `cancel_operation` can reach it
+    pub fn register(registry: &'a OperationRegistry, id: Option<String>) -> Self {
+        let token = CancelToken::default();
+        if let Some(id) = &id {
+            if let Ok(mut ops) = registry.lock() {
+                ops.insert(id.clone(), token.clone());
+            }
+        }
+        Self { registry, id, token }
+    }
+}
+
+impl Drop for OperationGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(id) = &self.id {
+            if let Ok(mut ops) = self.registry.lock() {
+                ops.remove(id);
+            }
+        }
+    }
+}
+
+/// Run `cmd` to completion like `Command::output`, but kill it if `token` is cancelled.
+/// Returns `Ok(None)` when the process was killed because of cancellation.
+pub(crate) fn output_cancellable(cmd: &mut Command, token: &CancelToken) -> std::io::Result<Option<Output>> {
+    // Own process group so the whole tree (shell -> uv -> docker compose) can be signalled
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Drain both pipes on their own threads so a chatty child can't block on a full pipe
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = drain(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if token.is_cancelled() {
+            eprintln!("[output_cancellable] Cancelled, killing process {}", child.id());
+            #[cfg(unix)]
+            {
+                let _ = silent_command("kill").args(["-TERM", &format!("-{}", child.id())]).status();
+            }
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        std::thread::sleep(CANCEL_POLL_INTERVAL);
+    };
+
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    }))
+}
+
+/// Cancel a running operation started with the given `operation_id`.
+/// Returns false if no such operation is running (already finished or never started).
+#[tauri::command]
+pub fn cancel_operation(state: State<AppState>, operation_id: String) -> Result<bool, String> {
+    let ops = state.operations.lock().map_err(|e| e.to_string())?;
+    match ops.get(&operation_id) {
+        Some(token) => {
+            eprintln!("[cancel_operation] Cancelling {}", operation_id);
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_guard_registers_and_unregisters() {
+        let registry = OperationRegistry::default();
+        {
+            let guard = OperationGuard::register(&registry, Some("start-gold".to_string()));
+            assert!(guard.token.check().is_ok());
+
+            registry.lock().unwrap().get("start-gold").unwrap().cancel();
+            assert_eq!(guard.token.check(), Err(OPERATION_CANCELLED.to_string()));
+        }
+        assert!(registry.lock().unwrap().is_empty());
+
+        // Without an id nothing is registered
+        let _guard = OperationGuard::register(&registry, None);
+        assert!(registry.lock().unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_cancellable() {
+        let token = CancelToken::default();
+        let output = output_cancellable(Command::new("sh").args(["-c", "echo ready; echo oops >&2"]), &token)
+            .unwrap()
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "ready\n");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "oops\n");
+
+        let canceller = token.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            canceller.cancel();
+        });
+        let started = Instant::now();
+        let output = output_cancellable(&mut Command::new("sleep").arg("30"), &token).unwrap();
+        assert!(output.is_none());
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
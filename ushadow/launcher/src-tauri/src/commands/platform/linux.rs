@@ -2,46 +2,22 @@
 /// All Linux-specific code lives here, making it easy to maintain and test
 
 use super::PlatformOps;
-use crate::commands::utils::{shell_command, quote_path};
+use crate::commands::generic_installer::{binary_on_path, detect_package_manager, package_install_command};
+use crate::commands::utils::{shell_command, posix_quote};
 use std::process::Command;
 
 pub struct Platform;
 
 impl PlatformOps for Platform {
     fn check_package_manager() -> bool {
-        // Check for common Linux package managers
-        let managers = ["apt", "yum", "dnf", "pacman"];
-
-        for manager in managers {
-            if Command::new("which")
-                .arg(manager)
-                .output()
-                .map(|o| o.status.success())
-                .unwrap_or(false)
-            {
-                return true;
-            }
-        }
-
-        false
+        detect_package_manager(binary_on_path).is_some()
     }
 
     fn get_package_manager_path() -> String {
         // Detect and return the available package manager
-        let managers = ["apt", "dnf", "yum", "pacman"];
-
-        for manager in managers {
-            if Command::new("which")
-                .arg(manager)
-                .output()
-                .map(|o| o.status.success())
-                .unwrap_or(false)
-            {
-                return manager.to_string();
-            }
-        }
-
-        "apt".to_string() // Default fallback
+        detect_package_manager(binary_on_path)
+            .unwrap_or("apt") // Default fallback
+            .to_string()
     }
 
     async fn install_package(package: &str, _is_app: bool) -> Result<String, String> {
@@ -49,16 +25,10 @@ impl PlatformOps for Platform {
 
         eprintln!("Installing {} via {}", package, pkg_mgr);
 
-        let args = match pkg_mgr.as_str() {
-            "apt" => vec!["install", "-y", package],
-            "yum" | "dnf" => vec!["install", "-y", package],
-            "pacman" => vec!["-S", "--noconfirm", package],
-            _ => return Err(format!("Unsupported package manager: {}", pkg_mgr))
-        };
+        let argv = package_install_command(&pkg_mgr, package)?;
 
         let output = Command::new("sudo")
-            .arg(&pkg_mgr)
-            .args(&args)
+            .args(&argv)
             .output()
             .map_err(|e| format!("Failed to run {}: {}", pkg_mgr, e))?;
 
@@ -96,21 +66,53 @@ impl PlatformOps for Platform {
         Err("Failed to start Docker service. Try: sudo systemctl start docker".to_string())
     }
 
+    async fn stop_docker() -> Result<String, String> {
+        // Stop the socket too, otherwise socket activation restarts dockerd on the next CLI call
+        let systemctl_output = Command::new("systemctl")
+            .args(["stop", "docker.socket", "docker"])
+            .output();
+
+        if let Ok(output) = systemctl_output {
+            if output.status.success() {
+                return Ok("Docker service stopped via systemctl".to_string());
+            }
+        }
+
+        // Fallback to service command
+        let service_output = Command::new("service")
+            .args(["docker", "stop"])
+            .output();
+
+        if let Ok(output) = service_output {
+            if output.status.success() {
+                return Ok("Docker service stopped via service command".to_string());
+            }
+        }
+
+        Err("Failed to stop Docker service. Try: sudo systemctl stop docker".to_string())
+    }
+
     fn build_env_command(working_dir: &str, env_vars: std::collections::HashMap<String, String>, command: &str) -> String {
-        // Linux: Use && for command chaining and export for env vars
-        let env_string: Vec<String> = env_vars
+        // Linux: Use && for command chaining and export for env vars.
+        // Values are single-quoted so spaces, $ and quotes reach the command literally.
+        let mut env_string: Vec<String> = env_vars
             .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
+            .map(|(k, v)| format!("{}={}", k, posix_quote(v)))
             .collect();
+        env_string.sort();
 
         // Quote the working directory to handle spaces and special chars
-        let working_dir_quoted = quote_path(working_dir);
-
-        format!(
-            "cd {} && {} {}",
-            working_dir_quoted,
-            env_string.join(" "),
-            command
-        )
+        let working_dir_quoted = posix_quote(working_dir);
+
+        if env_string.is_empty() {
+            format!("cd {} && {}", working_dir_quoted, command)
+        } else {
+            format!(
+                "cd {} && export {} && {}",
+                working_dir_quoted,
+                env_string.join(" "),
+                command
+            )
+        }
     }
 }
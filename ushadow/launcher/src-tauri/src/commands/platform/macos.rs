@@ -2,7 +2,7 @@
 /// All macOS-specific code lives here, making it easy to maintain and test
 
 use super::PlatformOps;
-use crate::commands::utils::{silent_command, shell_command, quote_path};
+use crate::commands::utils::{silent_command, shell_command, posix_quote};
 
 pub struct Platform;
 
@@ -172,21 +172,41 @@ impl PlatformOps for Platform {
         }
     }
 
+    async fn stop_docker() -> Result<String, String> {
+        let output = silent_command("osascript")
+            .args(["-e", "quit app \"Docker\""])
+            .output()
+            .map_err(|e| format!("Failed to quit Docker Desktop: {}", e))?;
+
+        if output.status.success() {
+            Ok("Docker Desktop stopping...".to_string())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(format!("Failed to stop Docker Desktop: {}", stderr.trim()))
+        }
+    }
+
     fn build_env_command(working_dir: &str, env_vars: std::collections::HashMap<String, String>, command: &str) -> String {
-        // Unix/macOS: Use && for command chaining and export for env vars
-        let env_string: Vec<String> = env_vars
+        // Unix/macOS: Use && for command chaining and export for env vars.
+        // Values are single-quoted so spaces, $ and quotes reach the command literally.
+        let mut env_string: Vec<String> = env_vars
             .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
+            .map(|(k, v)| format!("{}={}", k, posix_quote(v)))
             .collect();
+        env_string.sort();
 
         // Quote the working directory to handle spaces and special chars
-        let working_dir_quoted = quote_path(working_dir);
-
-        format!(
-            "cd {} && {} {}",
-            working_dir_quoted,
-            env_string.join(" "),
-            command
-        )
+        let working_dir_quoted = posix_quote(working_dir);
+
+        if env_string.is_empty() {
+            format!("cd {} && {}", working_dir_quoted, command)
+        } else {
+            format!(
+                "cd {} && export {} && {}",
+                working_dir_quoted,
+                env_string.join(" "),
+                command
+            )
+        }
     }
 }
@@ -27,6 +27,9 @@ pub trait PlatformOps {
     /// Start Docker service/application
     async fn start_docker() -> Result<String, String>;
 
+    /// Stop Docker service/application (callers check it's running first)
+    async fn stop_docker() -> Result<String, String>;
+
     /// Build a command string that changes directory and runs a command with environment variables
     /// Returns the complete command string ready to be passed to shell_command()
     fn build_env_command(working_dir: &str, env_vars: HashMap<String, String>, command: &str) -> String;
@@ -42,6 +45,12 @@ mod os;
 // Re-export the platform-specific implementation as "Platform"
 pub use os::Platform;
 
+// Always compile the unsupported stub in tests so it's checked against the full trait on every OS
+#[cfg(all(test, any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+#[path = "unsupported.rs"]
+#[allow(dead_code)]
+mod unsupported;
+
 /// Get current platform name as a string
 pub fn current_platform() -> &'static str {
     #[cfg(target_os = "macos")]
@@ -56,3 +65,121 @@ pub fn current_platform() -> &'static str {
     #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     return "unknown";
 }
+
+/// Whether the launcher has a real platform implementation for this OS
+pub fn is_supported_platform() -> bool {
+    current_platform() != "unknown"
+}
+
+/// Error message for an operation the current OS has no implementation for
+pub fn unsupported_platform_error(operation: &str) -> String {
+    format!(
+        "{} is not supported on this platform ({}). Ushadow Launcher supports macOS, Windows and Linux.",
+        operation,
+        std::env::consts::OS
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::utils::{posix_quote, powershell_quote};
+
+    #[test]
+    fn test_unsupported_platform_error_names_operation_and_os() {
+        let msg = unsupported_platform_error("Starting Docker");
+        assert!(msg.starts_with("Starting Docker is not supported on this platform"));
+        assert!(msg.contains(std::env::consts::OS));
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    #[tokio::test]
+    async fn test_unsupported_platform_degrades_gracefully() {
+        use super::unsupported::Platform as Unsupported;
+
+        assert!(!Unsupported::check_package_manager());
+        assert_eq!(Unsupported::get_package_manager_path(), "");
+
+        let err = Unsupported::install_package("git", false).await.unwrap_err();
+        assert!(err.starts_with("Installing 'git' is not supported on this platform"), "{}", err);
+        let err = Unsupported::start_docker().await.unwrap_err();
+        assert!(err.starts_with("Starting Docker is not supported"), "{}", err);
+        let err = Unsupported::stop_docker().await.unwrap_err();
+        assert!(err.starts_with("Stopping Docker is not supported"), "{}", err);
+
+        let cmd = Unsupported::build_env_command("/tmp/my repo", env(&[("ENV_NAME", "gold")]), "make up");
+        assert_eq!(cmd, "cd '/tmp/my repo' && export ENV_NAME='gold' && make up");
+    }
+
+    fn env(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_posix_quote_special_characters() {
+        assert_eq!(posix_quote("/Users/me/My Repos/ushadow"), "'/Users/me/My Repos/ushadow'");
+        assert_eq!(posix_quote("$HOME `id`"), "'$HOME `id`'");
+        assert_eq!(posix_quote("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn test_powershell_quote_special_characters() {
+        assert_eq!(powershell_quote(r"C:\Program Files\Ushadow"), r"'C:\Program Files\Ushadow'");
+        assert_eq!(powershell_quote("$env:PATH"), "'$env:PATH'");
+        assert_eq!(powershell_quote("it's"), "'it''s'");
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_build_env_command_unix_shape() {
+        let cmd = Platform::build_env_command(
+            "/tmp/my repo",
+            env(&[("PORT_OFFSET", "10"), ("ENV_NAME", "gold $x")]),
+            "uv run setup.py",
+        );
+        assert_eq!(cmd, "cd '/tmp/my repo' && export ENV_NAME='gold $x' PORT_OFFSET='10' && uv run setup.py");
+
+        let cmd = Platform::build_env_command("/tmp/repo", HashMap::new(), "make up");
+        assert_eq!(cmd, "cd '/tmp/repo' && make up");
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_build_env_command_windows_shape() {
+        let cmd = Platform::build_env_command(
+            r"C:\Users\me\My Repos\ushadow",
+            env(&[("PORT_OFFSET", "10"), ("ENV_NAME", "o'brien $x")]),
+            "uv run setup.py",
+        );
+        assert_eq!(
+            cmd,
+            r"Set-Location -LiteralPath 'C:\Users\me\My Repos\ushadow' -ErrorAction Stop; $env:ENV_NAME='o''brien $x'; $env:PORT_OFFSET='10'; uv run setup.py"
+        );
+    }
+
+    /// Run the generated command through a real shell and check the values arrive verbatim
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_build_env_command_unix_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("ushadow dir 'quoted' {}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let tricky = r#"a b $HOME `echo hi` "dq" 'sq' \ ;&|"#;
+        let cmd = Platform::build_env_command(
+            &dir.to_string_lossy(),
+            env(&[("TRICKY", tricky)]),
+            "printf '%s\\n' \"$TRICKY\" && pwd",
+        );
+
+        let output = std::process::Command::new("/bin/sh").args(["-c", &cmd]).output().unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines();
+        assert_eq!(lines.next(), Some(tricky));
+        let pwd = std::path::PathBuf::from(lines.next().unwrap());
+        assert_eq!(pwd.canonicalize().unwrap(), dir.canonicalize().unwrap());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
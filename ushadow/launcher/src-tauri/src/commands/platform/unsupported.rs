@@ -1,8 +1,9 @@
 /// Unsupported platform stub
-/// Provides default implementations that return errors for unsupported platforms
+/// Used on targets other than macOS/Windows/Linux (e.g. BSD). Every operation that needs
+/// OS-specific tooling returns a clear "not supported" error instead of guessing.
 
-use super::PlatformOps;
-use std::process::Command;
+use super::{unsupported_platform_error, PlatformOps};
+use crate::commands::utils::posix_quote;
 
 pub struct Platform;
 
@@ -15,49 +16,29 @@ impl PlatformOps for Platform {
         String::new()
     }
 
-    async fn install_package(_package: &str, _is_app: bool) -> Result<String, String> {
-        Err("Package installation is not supported on this platform".to_string())
-    }
-
-    async fn install_docker() -> Result<String, String> {
-        Err("Docker installation is not supported on this platform".to_string())
+    async fn install_package(package: &str, _is_app: bool) -> Result<String, String> {
+        Err(unsupported_platform_error(&format!("Installing '{}'", package)))
     }
 
     async fn start_docker() -> Result<String, String> {
-        Err("Docker start is not supported on this platform".to_string())
-    }
-
-    async fn install_git() -> Result<String, String> {
-        Err("Git installation is not supported on this platform".to_string())
-    }
-
-    async fn install_tailscale() -> Result<String, String> {
-        Err("Tailscale installation is not supported on this platform".to_string())
-    }
-
-    async fn install_homebrew() -> Result<String, String> {
-        Err("Homebrew is not supported on this platform".to_string())
-    }
-
-    fn create_shell_command(_cmd: &str) -> Command {
-        Command::new("echo")
+        Err(unsupported_platform_error("Starting Docker"))
     }
 
-    fn python_executable() -> &'static str {
-        "python"
+    async fn stop_docker() -> Result<String, String> {
+        Err(unsupported_platform_error("Stopping Docker"))
     }
 
     fn build_env_command(working_dir: &str, env_vars: std::collections::HashMap<String, String>, command: &str) -> String {
-        let env_string: Vec<String> = env_vars
+        let mut env_string: Vec<String> = env_vars
             .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
+            .map(|(k, v)| format!("{}={}", k, posix_quote(v)))
             .collect();
+        env_string.sort();
 
-        format!(
-            "cd '{}' && {} {}",
-            working_dir,
-            env_string.join(" "),
-            command
-        )
+        if env_string.is_empty() {
+            format!("cd {} && {}", posix_quote(working_dir), command)
+        } else {
+            format!("cd {} && export {} && {}", posix_quote(working_dir), env_string.join(" "), command)
+        }
     }
 }
@@ -2,7 +2,7 @@
 /// All Windows-specific code lives here, making it easy to maintain and test
 
 use super::PlatformOps;
-use crate::commands::utils::{silent_command, quote_path};
+use crate::commands::utils::{silent_command, powershell_quote};
 use std::process::Command;
 
 pub struct Platform;
@@ -65,21 +65,45 @@ impl PlatformOps for Platform {
         Err("Docker Desktop.exe not found in expected locations".to_string())
     }
 
+    async fn stop_docker() -> Result<String, String> {
+        // Ask Docker Desktop to exit (no /F so it can shut down its VM cleanly)
+        let desktop = silent_command("taskkill")
+            .args(["/IM", "Docker Desktop.exe"])
+            .output()
+            .map_err(|e| format!("Failed to run taskkill: {}", e))?;
+
+        if desktop.status.success() {
+            return Ok("Docker Desktop stopping...".to_string());
+        }
+
+        // Standalone engine installs run as a Windows service instead (needs admin)
+        let service = silent_command("net")
+            .args(["stop", "docker"])
+            .output();
+
+        if let Ok(output) = service {
+            if output.status.success() {
+                return Ok("Docker service stopped".to_string());
+            }
+        }
+
+        Err("Failed to stop Docker. Quit Docker Desktop from the tray or run 'net stop docker' as Administrator".to_string())
+    }
+
     fn build_env_command(working_dir: &str, env_vars: std::collections::HashMap<String, String>, command: &str) -> String {
-        // Windows PowerShell: Use ; for command chaining and $env: for env vars
-        let env_string: Vec<String> = env_vars
+        // Windows PowerShell: Use ; for command chaining and $env: for env vars.
+        // Single-quoted strings are literal in PowerShell ($ and ` are not expanded).
+        let mut env_string: Vec<String> = env_vars
             .iter()
-            .map(|(k, v)| format!("$env:{}='{}'", k, v))
+            .map(|(k, v)| format!("$env:{}={}", k, powershell_quote(v)))
             .collect();
+        env_string.sort();
 
-        // Quote the working directory to handle spaces and special chars
-        let working_dir_quoted = quote_path(working_dir);
-
-        format!(
-            "cd {}; {}; {}",
-            working_dir_quoted,
-            env_string.join("; "),
-            command
-        )
+        // -LiteralPath so [ ] in folder names aren't treated as wildcards;
+        // -ErrorAction Stop so a bad path aborts instead of running in the wrong directory
+        let mut parts = vec![format!("Set-Location -LiteralPath {} -ErrorAction Stop", powershell_quote(working_dir))];
+        parts.extend(env_string);
+        parts.push(command.to_string());
+        parts.join("; ")
     }
 }
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use super::utils::shell_command;
 
 /// Fallback paths can be either a simple list or platform-specific
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -42,6 +43,9 @@ pub struct InstallationMethod {
     pub package: Option<String>,
     pub url: Option<String>,
     pub packages: Option<HashMap<String, String>>,
+    /// Expected SHA-256 (hex) of the downloaded script or installer; unchecked when absent
+    #[serde(default)]
+    pub sha256: Option<String>,
 }
 
 /// Root configuration structure
@@ -49,6 +53,9 @@ pub struct InstallationMethod {
 pub struct PrerequisitesConfig {
     pub prerequisites: Vec<PrerequisiteDefinition>,
     pub installation_methods: Option<HashMap<String, HashMap<String, InstallationMethod>>>,
+    /// How to remove a prerequisite, per platform (same shape as `installation_methods`)
+    #[serde(default, alias = "uninstall")]
+    pub uninstall_methods: Option<HashMap<String, HashMap<String, InstallationMethod>>>,
 }
 
 /// Platform-specific prerequisite definition (after extraction)
@@ -234,3 +241,95 @@ pub fn get_platform_prerequisites_config(platform: String) -> Result<PlatformPre
         installation_methods: platform_methods,
     })
 }
+
+/// Extract a version number from `--version` output,
+/// e.g. "Docker version 24.0.6, build ed223bc" -> "24.0.6", "tmux 3.3a" -> "3.3a"
+fn parse_version(output: &str) -> Option<String> {
+    let first_line = output.lines().map(str::trim).find(|l| !l.is_empty())?;
+
+    first_line
+        .split_whitespace()
+        .map(|token| token.trim_start_matches('v').trim_end_matches([',', ';', ')']))
+        .find(|token| token.starts_with(|c: char| c.is_ascii_digit()) && token.contains('.'))
+        .map(|token| token.to_string())
+        .or_else(|| Some(first_line.to_string()))
+}
+
+/// Run a prerequisite's check command(s) and return the parsed version of the first that succeeds
+fn query_prerequisite_version(prereq: &PrerequisiteDefinition) -> Option<String> {
+    let commands: Vec<&String> = prereq.check_command.iter()
+        .chain(prereq.check_commands.iter().flatten())
+        .collect();
+
+    for command in commands {
+        let output = match shell_command(command).output() {
+            Ok(o) if o.status.success() => o,
+            _ => continue,
+        };
+
+        // Some tools (e.g. older python) print their version on stderr
+        let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+        if text.trim().is_empty() {
+            text = String::from_utf8_lossy(&output.stderr).to_string();
+        }
+
+        if let Some(filter) = &prereq.version_filter {
+            if !text.trim_start().starts_with(filter.as_str()) {
+                continue;
+            }
+        }
+
+        if let Some(version) = parse_version(&text) {
+            return Some(version);
+        }
+    }
+
+    None
+}
+
+/// Get installed versions of all prerequisites for this platform (id -> version, None if absent)
+#[tauri::command]
+pub async fn get_prerequisite_versions() -> Result<HashMap<String, Option<String>>, String> {
+    let config = PrerequisitesConfig::load()?;
+    let platform = std::env::consts::OS;
+
+    let handles: Vec<_> = config.get_platform_prerequisites(platform)
+        .into_iter()
+        .filter(|p| p.check_command.is_some() || p.check_commands.is_some())
+        .cloned()
+        .map(|prereq| {
+            tauri::async_runtime::spawn_blocking(move || {
+                let version = query_prerequisite_version(&prereq);
+                (prereq.id, version)
+            })
+        })
+        .collect();
+
+    let mut versions = HashMap::new();
+    for handle in handles {
+        let (id, version) = handle.await
+            .map_err(|e| format!("Version check failed: {}", e))?;
+        versions.insert(id, version);
+    }
+
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_from_common_tools() {
+        assert_eq!(parse_version("Docker version 24.0.6, build ed223bc").as_deref(), Some("24.0.6"));
+        assert_eq!(parse_version("git version 2.39.3 (Apple Git-145)").as_deref(), Some("2.39.3"));
+        assert_eq!(parse_version("uv 0.4.18 (Homebrew 2024-09-26)").as_deref(), Some("0.4.18"));
+        assert_eq!(parse_version("tmux 3.3a").as_deref(), Some("3.3a"));
+        assert_eq!(parse_version("1.56.1\n  tailscale commit: abc").as_deref(), Some("1.56.1"));
+        assert_eq!(parse_version("Homebrew 4.1.0\nHomebrew/homebrew-core").as_deref(), Some("4.1.0"));
+        assert_eq!(parse_version("workmux v0.1.2").as_deref(), Some("0.1.2"));
+        // No recognizable number: fall back to the raw line
+        assert_eq!(parse_version("some-tool dev-build").as_deref(), Some("some-tool dev-build"));
+        assert_eq!(parse_version("   \n"), None);
+    }
+}
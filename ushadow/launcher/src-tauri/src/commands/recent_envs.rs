@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use super::settings::launcher_config_dir;
+
+/// Maximum number of environments remembered in the MRU store
+const MAX_RECENT_ENVIRONMENTS: usize = 50;
+
+/// Serializes read-modify-write of the store between concurrent commands
+static RECENT_LOCK: Mutex<()> = Mutex::new(());
+
+fn recent_store_path() -> Result<PathBuf, String> {
+    Ok(launcher_config_dir()?.join("recent_environments.json"))
+}
+
+/// Load env name -> last-used unix millis; a missing or corrupt file is treated as empty
+fn load_recent(path: &Path) -> HashMap<String, i64> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Env names ordered most-recently-used first
+fn most_recent(recent: &HashMap<String, i64>, limit: usize) -> Vec<String> {
+    let mut entries: Vec<(&String, &i64)> = recent.iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    entries.into_iter().take(limit).map(|(name, _)| name.clone()).collect()
+}
+
+fn record_use_at(path: &Path, env_name: &str, timestamp_ms: i64) -> Result<(), String> {
+    let mut recent = load_recent(path);
+    recent.insert(env_name.to_string(), timestamp_ms);
+
+    if recent.len() > MAX_RECENT_ENVIRONMENTS {
+        let keep: Vec<String> = most_recent(&recent, MAX_RECENT_ENVIRONMENTS);
+        recent.retain(|name, _| keep.contains(name));
+    }
+
+    let json = serde_json::to_string_pretty(&recent)
+        .map_err(|e| format!("Failed to serialize recent environments: {}", e))?;
+    fs::write(path, json)
+        .map_err(|e| format!("Failed to write recent environments: {}", e))
+}
+
+/// Record that an environment was just used (best effort; failures are only logged)
+pub(crate) fn record_environment_use(env_name: &str) {
+    if env_name.is_empty() {
+        return;
+    }
+
+    let _guard = RECENT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let result = recent_store_path()
+        .and_then(|path| record_use_at(&path, env_name, chrono::Utc::now().timestamp_millis()));
+
+    if let Err(e) = result {
+        eprintln!("[record_environment_use] {}", e);
+    }
+}
+
+/// Get the most recently used environment names, newest first
+#[tauri::command]
+pub async fn get_recent_environments(limit: usize) -> Result<Vec<String>, String> {
+    let _guard = RECENT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = recent_store_path()?;
+    Ok(most_recent(&load_recent(&path), limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_environments_ordered_and_updated() {
+        let dir = std::env::temp_dir().join(format!("ushadow-recent-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("recent_environments.json");
+
+        assert!(most_recent(&load_recent(&path), 5).is_empty());
+
+        record_use_at(&path, "gold", 100).unwrap();
+        record_use_at(&path, "blue", 200).unwrap();
+        record_use_at(&path, "red", 300).unwrap();
+        record_use_at(&path, "gold", 400).unwrap();
+
+        let recent = load_recent(&path);
+        assert_eq!(most_recent(&recent, 10), vec!["gold", "red", "blue"]);
+        assert_eq!(most_recent(&recent, 2), vec!["gold", "red"]);
+
+        fs::write(&path, "not json").unwrap();
+        assert!(load_recent(&path).is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_recent_environments_store_is_capped() {
+        let dir = std::env::temp_dir().join(format!("ushadow-recent-cap-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("recent_environments.json");
+
+        for i in 0..(MAX_RECENT_ENVIRONMENTS + 5) {
+            record_use_at(&path, &format!("env-{}", i), i as i64).unwrap();
+        }
+
+        let recent = load_recent(&path);
+        assert_eq!(recent.len(), MAX_RECENT_ENVIRONMENTS);
+        assert!(!recent.contains_key("env-0"));
+        assert_eq!(most_recent(&recent, 1), vec![format!("env-{}", MAX_RECENT_ENVIRONMENTS + 4)]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
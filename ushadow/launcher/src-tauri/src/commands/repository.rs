@@ -4,10 +4,11 @@
 /// - Project directory management (default paths, validation)
 /// - Git repository operations (clone, update, branch management)
 /// - Git ancestry checking (determine base branch)
+/// - Stash management for environments
 
 use super::utils::{silent_command, expand_tilde};
 use super::permissions::check_path_permissions;
-use crate::models::ProjectStatus;
+use crate::models::{GitCommandOutput, ProjectStatus, StashEntry};
 use std::path::Path;
 use std::fs;
 
@@ -367,3 +368,190 @@ pub fn get_base_branch(repo_path: String, branch: String) -> Result<Option<Strin
 
     Ok(None)
 }
+
+/// Check whether `branch` has been merged into `base` (its tip is reachable from base).
+/// Returns None if either ref can't be resolved.
+///
+/// A branch that was created but never committed to is trivially "merged", so callers
+/// should use `branch_has_own_commits` to rule that case out.
+pub(crate) fn is_branch_merged(repo_path: &str, branch: &str, base: &str) -> Option<bool> {
+    let output = silent_command("git")
+        .args(["-C", repo_path, "merge-base", "--is-ancestor", branch, base])
+        .output()
+        .ok()?;
+
+    // --is-ancestor exits 0 (ancestor), 1 (not ancestor), or >1 on error (unknown ref)
+    match output.status.code() {
+        Some(0) => Some(true),
+        Some(1) => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether the local branch has moved since it was created (more than one reflog entry).
+/// Used to tell "merged" apart from "freshly branched, nothing committed yet".
+pub(crate) fn branch_has_own_commits(repo_path: &str, branch: &str) -> bool {
+    silent_command("git")
+        .args(["-C", repo_path, "reflog", "show", "--format=%H", &format!("refs/heads/{}", branch)])
+        .output()
+        .map(|o| o.status.success() && String::from_utf8_lossy(&o.stdout).lines().count() > 1)
+        .unwrap_or(false)
+}
+
+// ============================================
+// Stash Management
+// ============================================
+
+/// Field separator for `git stash list --format` (unit separator never appears in messages)
+const STASH_FIELD_SEP: char = '\x1f';
+
+/// Parse `git stash list --format=%gd%x1f%ct%x1f%gs` output
+fn parse_stash_list(output: &str) -> Vec<StashEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, STASH_FIELD_SEP);
+            let reflog = fields.next()?;
+            let created_at = fields.next()?.trim().parse::<i64>().ok();
+            let subject = fields.next().unwrap_or_default();
+
+            let index = reflog.strip_prefix("stash@{")?.strip_suffix('}')?.parse().ok()?;
+
+            // "WIP on main: abc1234 last commit" or "On main: my message"
+            let (branch, message) = match subject
+                .strip_prefix("WIP on ")
+                .or_else(|| subject.strip_prefix("On "))
+                .and_then(|rest| rest.split_once(": "))
+            {
+                Some((branch, message)) => (Some(branch.to_string()), message.to_string()),
+                None => (None, subject.to_string()),
+            };
+
+            Some(StashEntry { index, branch, message, created_at })
+        })
+        .collect()
+}
+
+/// List the stashes of a worktree (newest first)
+#[tauri::command]
+pub fn list_stashes(worktree_path: String) -> Result<Vec<StashEntry>, String> {
+    let output = silent_command("git")
+        .args(["-C", &worktree_path, "stash", "list", "--format=%gd%x1f%ct%x1f%gs"])
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to list stashes: {}", stderr.trim()));
+    }
+
+    Ok(parse_stash_list(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Run `git stash <action> stash@{index}` after checking the stash exists
+fn run_stash_action(worktree_path: &str, action: &str, index: u32) -> Result<GitCommandOutput, String> {
+    let stashes = list_stashes(worktree_path.to_string())?;
+    if !stashes.iter().any(|s| s.index == index) {
+        return Err(format!("No stash at index {} in {}", index, worktree_path));
+    }
+
+    let output = silent_command("git")
+        .args(["-C", worktree_path, "stash", action, &format!("stash@{{{}}}", index)])
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    Ok(GitCommandOutput {
+        success: output.status.success(),
+        stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    })
+}
+
+/// Apply a stash to the worktree, keeping it in the stash list.
+/// A conflicting apply comes back with `success: false` and git's explanation.
+#[tauri::command]
+pub fn apply_stash(worktree_path: String, index: u32) -> Result<GitCommandOutput, String> {
+    run_stash_action(&worktree_path, "apply", index)
+}
+
+/// Delete a stash
+#[tauri::command]
+pub fn drop_stash(worktree_path: String, index: u32) -> Result<GitCommandOutput, String> {
+    run_stash_action(&worktree_path, "drop", index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stash_list() {
+        let output = "stash@{0}\x1f1760000000\x1fOn feature/login: half-done form\n\
+                      stash@{1}\x1f1750000000\x1fWIP on main: 1a2b3c4 Fix: typo in README\n\
+                      stash@{2}\x1f1740000000\x1fautostash\n\
+                      garbage line\n";
+
+        assert_eq!(
+            parse_stash_list(output),
+            vec![
+                StashEntry {
+                    index: 0,
+                    branch: Some("feature/login".to_string()),
+                    message: "half-done form".to_string(),
+                    created_at: Some(1760000000),
+                },
+                StashEntry {
+                    index: 1,
+                    branch: Some("main".to_string()),
+                    message: "1a2b3c4 Fix: typo in README".to_string(),
+                    created_at: Some(1750000000),
+                },
+                StashEntry {
+                    index: 2,
+                    branch: None,
+                    message: "autostash".to_string(),
+                    created_at: Some(1740000000),
+                },
+            ]
+        );
+        assert!(parse_stash_list("").is_empty());
+    }
+
+    #[test]
+    fn test_stash_apply_and_drop() {
+        let repo = std::env::temp_dir().join(format!("ushadow-stash-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&repo).unwrap();
+        let path = repo.to_string_lossy().to_string();
+        let git = |args: &[&str]| {
+            let ok = silent_command("git")
+                .args(["-C", &path, "-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false);
+            assert!(ok, "git {:?} failed", args);
+        };
+
+        git(&["init", "-q"]);
+        std::fs::write(repo.join("notes.txt"), "one\n").unwrap();
+        git(&["add", "notes.txt"]);
+        git(&["commit", "-qm", "init"]);
+        std::fs::write(repo.join("notes.txt"), "two\n").unwrap();
+        git(&["stash", "push", "-m", "wip notes"]);
+
+        let stashes = list_stashes(path.clone()).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].message, "wip notes");
+
+        assert!(apply_stash(path.clone(), 3).is_err());
+        assert!(apply_stash(path.clone(), 0).unwrap().success);
+        assert_eq!(std::fs::read_to_string(repo.join("notes.txt")).unwrap(), "two\n");
+
+        // apply keeps the stash; drop removes it
+        assert_eq!(list_stashes(path.clone()).unwrap().len(), 1);
+        assert!(drop_stash(path.clone(), 0).unwrap().success);
+        assert!(list_stashes(path.clone()).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&repo).ok();
+    }
+}
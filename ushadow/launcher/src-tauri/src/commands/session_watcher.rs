@@ -0,0 +1,167 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::Manager;
+use super::claude_sessions::{get_sessions_file_path, ClaudeSessionEvent};
+
+/// Tauri event carrying each new `ClaudeSessionEvent`
+const SESSION_EVENT: &str = "claude-session-event";
+
+/// Active session log watcher; dropping it stops watching
+static WATCHER: Mutex<Option<RecommendedWatcher>> = Mutex::new(None);
+
+/// Reads events appended to the session log since the last read
+struct LogTail {
+    path: PathBuf,
+    offset: u64,
+}
+
+impl LogTail {
+    /// Start tailing from the current end of the file (only events written from now on)
+    fn from_end(path: &Path) -> Self {
+        let offset = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        LogTail { path: path.to_path_buf(), offset }
+    }
+
+    /// Events in complete lines appended since the last call. A file shorter than what we've
+    /// already read was truncated or rotated, so reading restarts from its beginning.
+    fn read_new(&mut self) -> std::io::Result<Vec<ClaudeSessionEvent>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        if file.metadata()?.len() < self.offset {
+            self.offset = 0;
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+
+        // Leave a trailing partial line for the next read, the hook may still be writing it
+        let complete = match buf.iter().rposition(|&b| b == b'\n') {
+            Some(last_newline) => last_newline + 1,
+            None => return Ok(Vec::new()),
+        };
+        self.offset += complete as u64;
+
+        Ok(String::from_utf8_lossy(&buf[..complete])
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<ClaudeSessionEvent>(line).ok())
+            .collect())
+    }
+}
+
+/// Start pushing new session log events to the frontend as `claude-session-event`.
+/// Replaces any previous watcher.
+#[tauri::command]
+pub async fn watch_claude_sessions(app: tauri::AppHandle) -> Result<(), String> {
+    let log_path = get_sessions_file_path()?;
+    // Watch the directory so the log being created, replaced or rotated is noticed too
+    let log_dir = log_path
+        .parent()
+        .ok_or_else(|| format!("Invalid session log path: {}", log_path.display()))?
+        .to_path_buf();
+    std::fs::create_dir_all(&log_dir)
+        .map_err(|e| format!("Failed to create {}: {}", log_dir.display(), e))?;
+
+    let mut tail = LogTail::from_end(&log_path);
+    let watched_path = log_path.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let event = match res {
+            Ok(event) => event,
+            Err(e) => {
+                eprintln!("[watch_claude_sessions] Watch error: {}", e);
+                return;
+            }
+        };
+        if !event.paths.iter().any(|p| p.file_name() == watched_path.file_name()) {
+            return;
+        }
+
+        match tail.read_new() {
+            Ok(events) => {
+                for session_event in events {
+                    if let Err(e) = app.emit_all(SESSION_EVENT, &session_event) {
+                        eprintln!("[watch_claude_sessions] Failed to emit event: {}", e);
+                    }
+                }
+            }
+            Err(e) => eprintln!("[watch_claude_sessions] Failed to read {}: {}", watched_path.display(), e),
+        }
+    })
+    .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+    watcher
+        .watch(&log_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {}", log_dir.display(), e))?;
+
+    *WATCHER.lock().map_err(|e| e.to_string())? = Some(watcher);
+    eprintln!("[watch_claude_sessions] Watching {}", log_path.display());
+    Ok(())
+}
+
+/// Stop pushing session log events; a no-op if not watching
+#[tauri::command]
+pub async fn stop_watching_claude_sessions() -> Result<(), String> {
+    if WATCHER.lock().map_err(|e| e.to_string())?.take().is_some() {
+        eprintln!("[stop_watching_claude_sessions] Stopped watching the session log");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn append(path: &Path, text: &str) {
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path).unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    fn line(event_type: &str) -> String {
+        format!(
+            "{{\"event_type\":\"{}\",\"session_id\":\"s1\",\"cwd\":\"/repos/gold\",\"timestamp\":\"2026-01-01T00:00:00Z\",\"data\":{{}}}}\n",
+            event_type
+        )
+    }
+
+    fn types(events: Vec<ClaudeSessionEvent>) -> Vec<String> {
+        events.into_iter().map(|e| e.event_type).collect()
+    }
+
+    #[test]
+    fn test_log_tail_yields_appended_events_in_order() {
+        let dir = std::env::temp_dir().join(format!("ushadow-tail-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sessions.jsonl");
+
+        // Events already in the log aren't replayed
+        append(&path, &line("SessionStart"));
+        let mut tail = LogTail::from_end(&path);
+        assert!(tail.read_new().unwrap().is_empty());
+
+        append(&path, &(line("UserPromptSubmit") + &line("PreToolUse")));
+        assert_eq!(types(tail.read_new().unwrap()), vec!["UserPromptSubmit", "PreToolUse"]);
+
+        // A partial line waits until it's complete
+        let stop = line("Stop");
+        let (head, rest) = stop.split_at(20);
+        append(&path, head);
+        assert!(tail.read_new().unwrap().is_empty());
+        append(&path, &(rest.to_string() + "garbage\n" + &line("Notification")));
+        assert_eq!(types(tail.read_new().unwrap()), vec!["Stop", "Notification"]);
+
+        // Truncation/rotation restarts from the beginning of the new file
+        std::fs::write(&path, line("SessionEnd")).unwrap();
+        assert_eq!(types(tail.read_new().unwrap()), vec!["SessionEnd"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(tail.read_new().unwrap().is_empty());
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use super::container_runtime::ContainerRuntime;
+use super::multiplexer::MultiplexerKind;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodingAgentConfig {
@@ -25,6 +28,240 @@ impl Default for CodingAgentConfig {
     }
 }
 
+/// How to launch, resume and detect a particular coding agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentProfile {
+    /// Matches `CodingAgentConfig::agent_type`
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Arguments that resume a session; `{session_id}` is replaced by the session to resume
+    #[serde(default)]
+    pub resume_args: Option<String>,
+    /// Directory holding `.jsonl` session files; `{home}` and `{encoded_path}` are substituted
+    #[serde(default)]
+    pub session_dir: Option<String>,
+    /// Foreground process names (tmux `pane_current_command`) that mean the agent is running
+    #[serde(default)]
+    pub process_names: Vec<String>,
+    /// `KEY=VALUE` pairs set when launching
+    #[serde(default)]
+    pub env: Vec<String>,
+    /// Whether the ticket prompt can be passed as a trailing positional argument
+    #[serde(default)]
+    pub accepts_prompt: bool,
+    /// Whether a running instance can take on more tickets by spawning teammates
+    #[serde(default)]
+    pub supports_teams: bool,
+}
+
+impl AgentProfile {
+    /// Built-in profile for a known agent type
+    pub fn builtin(agent_type: &str) -> Option<Self> {
+        let profile = match agent_type {
+            "claude" => AgentProfile {
+                name: "claude".to_string(),
+                command: "claude".to_string(),
+                args: vec!["--dangerously-skip-permissions".to_string()],
+                resume_args: Some("--resume {session_id}".to_string()),
+                session_dir: Some("{home}/.claude/projects/{encoded_path}".to_string()),
+                process_names: vec!["claude".to_string(), "node".to_string()],
+                // Lets a running instance become a team lead for further tickets in the worktree
+                env: vec!["CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS=1".to_string()],
+                accepts_prompt: true,
+                supports_teams: true,
+            },
+            "aider" => AgentProfile {
+                name: "aider".to_string(),
+                command: "aider".to_string(),
+                args: Vec::new(),
+                resume_args: None,
+                session_dir: None,
+                process_names: vec!["aider".to_string(), "python".to_string(), "python3".to_string()],
+                env: Vec::new(),
+                // aider treats positional arguments as files to edit
+                accepts_prompt: false,
+                supports_teams: false,
+            },
+            "codex" => AgentProfile {
+                name: "codex".to_string(),
+                command: "codex".to_string(),
+                args: Vec::new(),
+                resume_args: None,
+                session_dir: None,
+                process_names: vec!["codex".to_string(), "node".to_string()],
+                env: Vec::new(),
+                accepts_prompt: true,
+                supports_teams: false,
+            },
+            _ => return None,
+        };
+        Some(profile)
+    }
+
+    /// Whether tmux's `pane_current_command` is this agent
+    pub fn is_running(&self, pane_command: &str) -> bool {
+        let pane_command = pane_command.trim();
+        !pane_command.is_empty()
+            && self.process_names.iter().any(|name| name.eq_ignore_ascii_case(pane_command))
+    }
+
+    /// Whether a process command line (`ps -o args`) is this agent, including agents run
+    /// through an interpreter (`node …/claude-code/cli.js`, `python3 -m aider`)
+    pub fn matches_process(&self, args: &str) -> bool {
+        const INTERPRETERS: [&str; 5] = ["node", "python", "python3", "bun", "deno"];
+        let basename = |token: &str| token.rsplit('/').next().unwrap_or(token).to_string();
+        let binary = basename(self.command.split_whitespace().next().unwrap_or(&self.command));
+        let names: Vec<String> = self
+            .process_names
+            .iter()
+            .chain(std::iter::once(&self.name))
+            .chain(std::iter::once(&binary))
+            .filter(|name| !INTERPRETERS.contains(&name.as_str()))
+            .cloned()
+            .collect();
+
+        let mut tokens = args.split_whitespace();
+        let program = match tokens.next() {
+            Some(program) => basename(program),
+            None => return false,
+        };
+        if names.contains(&program) {
+            return true;
+        }
+        // Interpreters only count when one of their arguments names the agent
+        INTERPRETERS.contains(&program.as_str())
+            && tokens.any(|arg| names.iter().any(|name| arg.contains(name.as_str())))
+    }
+
+    /// Session directory for a worktree, if this agent keeps resumable sessions
+    pub fn sessions_dir(&self, home_dir: &str, worktree_path: &str) -> Option<PathBuf> {
+        let template = self.session_dir.as_ref()?;
+        Some(PathBuf::from(
+            template
+                .replace("{home}", home_dir)
+                .replace("{encoded_path}", &worktree_path.replace('/', "-")),
+        ))
+    }
+
+    /// The agent command line (with its env assignments), resuming `session_id` or starting with `prompt`
+    pub fn command_line(&self, session_id: Option<&str>, prompt: Option<&str>) -> String {
+        let mut parts: Vec<String> = self.env.clone();
+        parts.push(self.command.clone());
+
+        match (session_id, &self.resume_args) {
+            (Some(session_id), Some(resume_args)) => {
+                parts.push(resume_args.replace("{session_id}", session_id));
+                parts.extend(self.args.iter().cloned());
+            }
+            _ => {
+                parts.extend(self.args.iter().cloned());
+                if let Some(prompt) = prompt.filter(|_| self.accepts_prompt) {
+                    parts.push(format!("$'{}'", ansi_c_escape(prompt)));
+                }
+            }
+        }
+
+        // Env assignments can't follow `exec`, so they go through `env`
+        if self.env.is_empty() {
+            parts.join(" ")
+        } else {
+            format!("env {}", parts.join(" "))
+        }
+    }
+
+    /// Build the bash script that launches the agent, resuming `session_id` or starting with `prompt`
+    pub fn start_script(&self, worktree_path: Option<&str>, session_id: Option<&str>, prompt: Option<&str>) -> String {
+        let cd = worktree_path
+            .map(|path| format!("cd '{}'\n", path.replace('\'', "'\\''")))
+            .unwrap_or_default();
+        format!("#!/bin/bash\n{}exec {}\n", cd, self.command_line(session_id, prompt))
+    }
+}
+
+/// Escape text for bash `$'...'` ANSI-C quoting: backslash, single quote and newline
+pub fn ansi_c_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace('\n', "\\n")
+}
+
+/// Resolve the agent profile for the configured agent type.
+/// A custom profile in `agent_profiles` wins as-is; built-in (and unknown) agent types take their
+/// command and args from `coding_agent`.
+pub fn agent_profile(settings: &LauncherSettings) -> AgentProfile {
+    let agent = &settings.coding_agent;
+    if let Some(custom) = settings.agent_profiles.iter().find(|p| p.name == agent.agent_type) {
+        return custom.clone();
+    }
+
+    let mut profile = AgentProfile::builtin(&agent.agent_type).unwrap_or_else(|| AgentProfile {
+        name: agent.agent_type.clone(),
+        command: agent.command.clone(),
+        args: Vec::new(),
+        resume_args: None,
+        session_dir: None,
+        process_names: vec![agent.agent_type.clone()],
+        env: Vec::new(),
+        accepts_prompt: true,
+        supports_teams: false,
+    });
+
+    if !agent.command.trim().is_empty() {
+        profile.command = agent.command.clone();
+        if let Some(binary) = agent.command.split_whitespace().next() {
+            let binary = binary.rsplit('/').next().unwrap_or(binary).to_string();
+            if !profile.process_names.contains(&binary) {
+                profile.process_names.push(binary);
+            }
+        }
+    }
+    profile.args = agent.args.clone();
+    profile
+}
+
+/// Default prefix for per-environment tmux sessions (`ush-{env}`)
+pub const DEFAULT_SESSION_PREFIX: &str = "ush-";
+/// Default prefix for environment tmux windows (`ushadow-{env}`)
+pub const DEFAULT_WINDOW_PREFIX: &str = "ushadow-";
+
+fn default_session_prefix() -> String {
+    DEFAULT_SESSION_PREFIX.to_string()
+}
+
+fn default_window_prefix() -> String {
+    DEFAULT_WINDOW_PREFIX.to_string()
+}
+
+/// Default number of attempts for retried network requests
+pub const DEFAULT_NETWORK_RETRIES: u32 = 3;
+
+fn default_network_retries() -> u32 {
+    DEFAULT_NETWORK_RETRIES
+}
+
+/// Default lifetime of cached discovery results
+pub const DEFAULT_DISCOVERY_CACHE_TTL_MS: u64 = 3000;
+
+fn default_discovery_cache_ttl_ms() -> u64 {
+    DEFAULT_DISCOVERY_CACHE_TTL_MS
+}
+
+/// Default backend endpoint that exchanges admin credentials for an access token
+pub const DEFAULT_AUTH_LOGIN_PATH: &str = "/api/auth/login";
+
+fn default_auth_login_path() -> String {
+    DEFAULT_AUTH_LOGIN_PATH.to_string()
+}
+
+/// Default editor binary for "Open in VS Code"
+pub const DEFAULT_EDITOR_COMMAND: &str = "code";
+
+fn default_editor_command() -> String {
+    DEFAULT_EDITOR_COMMAND.to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LauncherSettings {
     pub default_admin_email: Option<String>,
@@ -32,6 +269,50 @@ pub struct LauncherSettings {
     pub default_admin_name: Option<String>,
     #[serde(default)]
     pub coding_agent: CodingAgentConfig,
+    /// Prefix for per-environment tmux session names
+    #[serde(default = "default_session_prefix")]
+    pub session_prefix: String,
+    /// Prefix for environment tmux window names (must match window_prefix in .workmux.yaml)
+    #[serde(default = "default_window_prefix")]
+    pub window_prefix: String,
+    /// Container runtime ("docker" or "podman"); auto-detected when unset
+    #[serde(default)]
+    pub container_runtime: Option<ContainerRuntime>,
+    /// Environment service names (container suffixes); derived from docker-compose.yml when unset
+    #[serde(default)]
+    pub services: Option<Vec<String>>,
+    /// Custom agent profiles, overriding the built-in ones (claude, aider, codex) by name
+    #[serde(default)]
+    pub agent_profiles: Vec<AgentProfile>,
+    /// Where the Claude hook writes session events (default ~/.claude/ushadow_sessions.jsonl);
+    /// reinstall the hooks after changing it
+    #[serde(default)]
+    pub session_log_path: Option<String>,
+    /// Attempts for installer downloads and HTTP GETs before giving up on transient failures
+    #[serde(default = "default_network_retries")]
+    pub network_retries: u32,
+    /// Editor binary used to open environments (`code`, `cursor`, `windsurf`, ...);
+    /// must understand VS Code's settings.json
+    #[serde(default = "default_editor_command")]
+    pub editor_command: String,
+    /// Terminal multiplexer for environment sessions ("tmux" or "zellij")
+    #[serde(default)]
+    pub multiplexer: MultiplexerKind,
+    /// Terminal for attaching to sessions (wezterm, kitty, alacritty, gnome-terminal, ...);
+    /// auto-detected when unset
+    #[serde(default)]
+    pub terminal_emulator: Option<String>,
+    /// Extra infrastructure services to recognize (service id -> display name); also overrides
+    /// display names of compose-declared and built-in infra services
+    #[serde(default)]
+    pub infra_patterns: HashMap<String, String>,
+    /// How long discovery results are reused before docker and git are queried again (0 disables)
+    #[serde(default = "default_discovery_cache_ttl_ms")]
+    pub discovery_cache_ttl_ms: u64,
+    /// Backend path that logs in with the default admin credentials to get an access token
+    /// for authenticated environment URLs
+    #[serde(default = "default_auth_login_path")]
+    pub auth_login_path: String,
 }
 
 impl Default for LauncherSettings {
@@ -41,12 +322,53 @@ impl Default for LauncherSettings {
             default_admin_password: None,
             default_admin_name: Some("Administrator".to_string()),
             coding_agent: CodingAgentConfig::default(),
+            session_prefix: default_session_prefix(),
+            window_prefix: default_window_prefix(),
+            container_runtime: None,
+            services: None,
+            agent_profiles: Vec::new(),
+            session_log_path: None,
+            network_retries: DEFAULT_NETWORK_RETRIES,
+            editor_command: default_editor_command(),
+            multiplexer: MultiplexerKind::default(),
+            terminal_emulator: None,
+            infra_patterns: HashMap::new(),
+            discovery_cache_ttl_ms: DEFAULT_DISCOVERY_CACHE_TTL_MS,
+            auth_login_path: default_auth_login_path(),
         }
     }
 }
 
-/// Get the path to the launcher settings file
-fn get_settings_path() -> Result<PathBuf, String> {
+/// Tmux session/window names for an environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TmuxNames {
+    pub session: String,
+    pub window: String,
+}
+
+/// Build the tmux session and window names for an environment using the configured prefixes.
+/// If `env_name` already carries the window prefix it is stripped first, so callers can pass
+/// either a bare env name or an existing window name without double-prefixing.
+pub fn tmux_names(env_name: &str, settings: &LauncherSettings) -> TmuxNames {
+    let base = strip_window_prefix(env_name, settings);
+    TmuxNames {
+        session: format!("{}{}", settings.session_prefix, base),
+        window: format!("{}{}", settings.window_prefix, base),
+    }
+}
+
+/// Strip the configured window prefix from a tmux window name, returning the env name
+pub fn strip_window_prefix<'a>(window_name: &'a str, settings: &LauncherSettings) -> &'a str {
+    if settings.window_prefix.is_empty() {
+        return window_name;
+    }
+    window_name
+        .strip_prefix(settings.window_prefix.as_str())
+        .unwrap_or(window_name)
+}
+
+/// Get the launcher config directory (~/.config/ushadow-launcher), creating it if needed
+pub(crate) fn launcher_config_dir() -> Result<PathBuf, String> {
     let home_dir = dirs::home_dir()
         .ok_or("Could not determine home directory")?;
 
@@ -58,12 +380,22 @@ fn get_settings_path() -> Result<PathBuf, String> {
             .map_err(|e| format!("Failed to create config directory: {}", e))?;
     }
 
-    Ok(config_dir.join("settings.json"))
+    Ok(config_dir)
+}
+
+/// Get the path to the launcher settings file
+fn get_settings_path() -> Result<PathBuf, String> {
+    Ok(launcher_config_dir()?.join("settings.json"))
 }
 
 /// Load launcher settings from disk
 #[tauri::command]
 pub async fn load_launcher_settings() -> Result<LauncherSettings, String> {
+    read_launcher_settings()
+}
+
+/// Read launcher settings from disk (sync, for non-async callers)
+pub fn read_launcher_settings() -> Result<LauncherSettings, String> {
     let settings_path = get_settings_path()?;
 
     if !settings_path.exists() {
@@ -96,6 +428,13 @@ pub async fn save_launcher_settings(settings: LauncherSettings) -> Result<(), St
     Ok(())
 }
 
+/// The command new ticket agents are launched with, as the settings UI should show it
+#[tauri::command]
+pub async fn preview_agent_command() -> Result<String, String> {
+    let settings = read_launcher_settings()?;
+    Ok(agent_profile(&settings).command_line(None, None))
+}
+
 /// Write admin credentials to a worktree's secrets.yaml file
 #[tauri::command]
 pub async fn write_credentials_to_worktree(
@@ -163,3 +502,165 @@ pub async fn write_credentials_to_worktree(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tmux_names_default_prefixes() {
+        let settings = LauncherSettings::default();
+        let names = tmux_names("gold", &settings);
+        assert_eq!(names.session, "ush-gold");
+        assert_eq!(names.window, "ushadow-gold");
+    }
+
+    #[test]
+    fn test_tmux_names_custom_prefixes() {
+        let settings = LauncherSettings {
+            session_prefix: "acme-s-".to_string(),
+            window_prefix: "acme-".to_string(),
+            ..LauncherSettings::default()
+        };
+        let names = tmux_names("gold", &settings);
+        assert_eq!(names.session, "acme-s-gold");
+        assert_eq!(names.window, "acme-gold");
+
+        // Already-prefixed names are not double-prefixed
+        let names = tmux_names("acme-gold", &settings);
+        assert_eq!(names.window, "acme-gold");
+        assert_eq!(names.session, "acme-s-gold");
+    }
+
+    #[test]
+    fn test_strip_window_prefix() {
+        let settings = LauncherSettings {
+            window_prefix: "acme-".to_string(),
+            ..LauncherSettings::default()
+        };
+        assert_eq!(strip_window_prefix("acme-blue", &settings), "blue");
+        // Default prefix is not stripped once a custom one is configured
+        assert_eq!(strip_window_prefix("ushadow-blue", &settings), "ushadow-blue");
+        assert_eq!(strip_window_prefix("blue", &settings), "blue");
+    }
+
+    #[test]
+    fn test_missing_prefixes_deserialize_to_defaults() {
+        let settings: LauncherSettings = serde_json::from_str(
+            r#"{"default_admin_email":null,"default_admin_password":null,"default_admin_name":null}"#,
+        )
+        .unwrap();
+        assert_eq!(settings.session_prefix, DEFAULT_SESSION_PREFIX);
+        assert_eq!(settings.window_prefix, DEFAULT_WINDOW_PREFIX);
+        assert_eq!(settings.network_retries, DEFAULT_NETWORK_RETRIES);
+        assert_eq!(settings.discovery_cache_ttl_ms, DEFAULT_DISCOVERY_CACHE_TTL_MS);
+        assert_eq!(settings.editor_command, DEFAULT_EDITOR_COMMAND);
+        assert_eq!(settings.multiplexer, MultiplexerKind::Tmux);
+    }
+
+    fn with_agent(agent_type: &str, command: &str, args: &[&str]) -> LauncherSettings {
+        LauncherSettings {
+            coding_agent: CodingAgentConfig {
+                agent_type: agent_type.to_string(),
+                command: command.to_string(),
+                args: args.iter().map(|a| a.to_string()).collect(),
+                auto_start: true,
+            },
+            ..LauncherSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_claude_profile_commands() {
+        let profile = agent_profile(&LauncherSettings::default());
+        assert_eq!(profile.name, "claude");
+
+        assert_eq!(
+            profile.start_script(None, Some("abc-123"), Some("ignored")),
+            "#!/bin/bash\nexec env CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS=1 claude --resume abc-123 --dangerously-skip-permissions\n"
+        );
+        assert_eq!(
+            profile.start_script(Some("/w/it's"), None, Some("Fix 'it'\nnow")),
+            "#!/bin/bash\ncd '/w/it'\\''s'\nexec env CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS=1 claude --dangerously-skip-permissions $'Fix \\'it\\'\\nnow'\n"
+        );
+        assert_eq!(
+            profile.command_line(None, None),
+            "env CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS=1 claude --dangerously-skip-permissions"
+        );
+        assert_eq!(
+            profile.sessions_dir("/home/me", "/repos/gold"),
+            Some(PathBuf::from("/home/me/.claude/projects/-repos-gold"))
+        );
+    }
+
+    #[test]
+    fn test_other_agent_profile_commands() {
+        let aider = agent_profile(&with_agent("aider", "aider", &["--model", "sonnet"]));
+        // No resume support and no positional prompt
+        assert_eq!(aider.start_script(None, Some("abc"), Some("prompt")), "#!/bin/bash\nexec aider --model sonnet\n");
+        assert_eq!(aider.sessions_dir("/home/me", "/repos/gold"), None);
+
+        let codex = agent_profile(&with_agent("codex", "/opt/bin/codex", &[]));
+        assert_eq!(codex.start_script(None, None, Some("Do it")), "#!/bin/bash\nexec /opt/bin/codex $'Do it'\n");
+        assert_eq!(codex.command_line(None, None), "/opt/bin/codex");
+
+        // Custom profiles are used as-is
+        let mut settings = with_agent("aider", "aider", &[]);
+        settings.agent_profiles.push(AgentProfile {
+            resume_args: Some("--restore-chat-history".to_string()),
+            session_dir: Some("{home}/.aider/{encoded_path}".to_string()),
+            ..AgentProfile::builtin("aider").unwrap()
+        });
+        let custom = agent_profile(&settings);
+        assert_eq!(custom.start_script(None, Some("s1"), None), "#!/bin/bash\nexec aider --restore-chat-history\n");
+    }
+
+    #[test]
+    fn test_running_process_detection_per_profile() {
+        let claude = agent_profile(&LauncherSettings::default());
+        assert!(claude.is_running("claude"));
+        assert!(claude.is_running("node"));
+        assert!(!claude.is_running("python3"));
+        assert!(!claude.is_running("zsh"));
+        assert!(!claude.is_running(""));
+
+        let aider = agent_profile(&with_agent("aider", "aider", &[]));
+        assert!(aider.is_running("aider"));
+        assert!(aider.is_running("python3"));
+        assert!(!aider.is_running("node"));
+
+        // Unknown agents are detected by their binary name
+        let custom = agent_profile(&with_agent("goose", "/usr/local/bin/goose session", &[]));
+        assert!(custom.is_running("goose"));
+        assert!(!custom.is_running("bash"));
+    }
+
+    #[test]
+    fn test_agent_process_classification() {
+        let claude = agent_profile(&LauncherSettings::default());
+        assert!(claude.matches_process("claude --dangerously-skip-permissions"));
+        assert!(claude.matches_process("/usr/local/bin/claude --resume abc"));
+        assert!(claude.matches_process("node /usr/lib/node_modules/@anthropic-ai/claude-code/cli.js"));
+        // A bare interpreter is not the agent
+        assert!(!claude.matches_process("node server.js"));
+        assert!(!claude.matches_process("-zsh"));
+        assert!(!claude.matches_process("npm run dev"));
+        assert!(!claude.matches_process(""));
+
+        let aider = agent_profile(&with_agent("aider", "aider", &[]));
+        assert!(aider.matches_process("/home/me/.local/bin/aider --model sonnet"));
+        assert!(aider.matches_process("python3 -m aider"));
+        assert!(!aider.matches_process("python3 manage.py runserver"));
+        assert!(!aider.matches_process("claude"));
+    }
+
+    #[test]
+    fn test_missing_agent_profiles_deserialize_to_empty() {
+        let settings: LauncherSettings = serde_json::from_str(
+            r#"{"default_admin_email":null,"default_admin_password":null,"default_admin_name":null}"#,
+        )
+        .unwrap();
+        assert!(settings.agent_profiles.is_empty());
+        assert_eq!(agent_profile(&settings).name, "claude");
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use crate::models::{ContainerRestartInfo, EnvironmentStatus, StatusChange};
+use super::container_runtime::container_runtime;
+use super::discovery::environment_services;
+use super::docker::container_belongs_to_env;
+use super::settings::launcher_config_dir;
+use super::utils::silent_command;
+
+/// Maximum number of transitions kept on disk (oldest are dropped first)
+const MAX_STATUS_CHANGES: usize = 5000;
+
+/// Last status seen per environment; loaded from the history file on first use.
+/// Also serializes read-modify-write of the history file.
+static LAST_STATUS: Mutex<Option<HashMap<String, EnvironmentStatus>>> = Mutex::new(None);
+
+fn history_path() -> Result<PathBuf, String> {
+    Ok(launcher_config_dir()?.join("status_history.json"))
+}
+
+/// Load the transition log; a missing or corrupt file is treated as empty
+fn load_history(path: &Path) -> Vec<StatusChange> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Most recent recorded status of each environment
+fn latest_statuses(history: &[StatusChange]) -> HashMap<String, EnvironmentStatus> {
+    history
+        .iter()
+        .map(|change| (change.env_name.clone(), change.status.clone()))
+        .collect()
+}
+
+/// Environments whose status differs from the last one seen (including never-seen ones)
+fn status_transitions(
+    last: &HashMap<String, EnvironmentStatus>,
+    current: &[(String, EnvironmentStatus)],
+) -> Vec<(String, EnvironmentStatus)> {
+    current
+        .iter()
+        .filter(|(name, status)| last.get(name) != Some(status))
+        .cloned()
+        .collect()
+}
+
+fn append_changes_at(path: &Path, changes: &[(String, EnvironmentStatus)], timestamp: &str) -> Result<(), String> {
+    let mut history = load_history(path);
+    history.extend(changes.iter().map(|(env_name, status)| StatusChange {
+        env_name: env_name.clone(),
+        status: status.clone(),
+        timestamp: timestamp.to_string(),
+    }));
+
+    if history.len() > MAX_STATUS_CHANGES {
+        history.drain(..history.len() - MAX_STATUS_CHANGES);
+    }
+
+    let json = serde_json::to_string(&history)
+        .map_err(|e| format!("Failed to serialize status history: {}", e))?;
+    fs::write(path, json)
+        .map_err(|e| format!("Failed to write status history: {}", e))
+}
+
+/// Record status transitions from a discovery pass (best effort; failures are only logged)
+pub(crate) fn record_environment_statuses(current: &[(String, EnvironmentStatus)]) {
+    let mut last = LAST_STATUS.lock().unwrap_or_else(|e| e.into_inner());
+
+    let path = match history_path() {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("[record_environment_statuses] {}", e);
+            return;
+        }
+    };
+    let last = last.get_or_insert_with(|| latest_statuses(&load_history(&path)));
+
+    let changes = status_transitions(last, current);
+    if changes.is_empty() {
+        return;
+    }
+
+    let timestamp = chrono::Utc::now().to_rfc3339();
+    if let Err(e) = append_changes_at(&path, &changes, &timestamp) {
+        eprintln!("[record_environment_statuses] {}", e);
+    }
+    for (name, status) in changes {
+        last.insert(name, status);
+    }
+}
+
+/// Transitions for one environment at or after `since`, oldest first
+fn filter_history(history: Vec<StatusChange>, env_name: &str, since: Option<chrono::DateTime<chrono::Utc>>) -> Vec<StatusChange> {
+    history
+        .into_iter()
+        .filter(|change| change.env_name == env_name)
+        .filter(|change| match since {
+            Some(since) => chrono::DateTime::parse_from_rfc3339(&change.timestamp)
+                .map(|t| t >= since)
+                .unwrap_or(false),
+            None => true,
+        })
+        .collect()
+}
+
+/// Status transitions recorded for an environment (e.g. to spot flapping).
+/// `since` is an RFC 3339 timestamp.
+#[tauri::command]
+pub async fn get_status_history(env_name: String, since: Option<String>) -> Result<Vec<StatusChange>, String> {
+    let since = since
+        .map(|s| {
+            chrono::DateTime::parse_from_rfc3339(&s)
+                .map(|t| t.with_timezone(&chrono::Utc))
+                .map_err(|e| format!("Invalid since timestamp '{}': {}", s, e))
+        })
+        .transpose()?;
+
+    let _guard = LAST_STATUS.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(filter_history(load_history(&history_path()?), &env_name, since))
+}
+
+/// Parse `docker inspect --format '{{.Name}}\t{{.RestartCount}}\t{{.State.StartedAt}}'` output
+fn parse_restart_info(output: &str) -> Vec<ContainerRestartInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let container = fields.next()?.trim().trim_start_matches('/').to_string();
+            let restart_count = fields.next()?.trim().parse().ok()?;
+            let started_at = fields.next().map(str::trim).filter(|s| !s.is_empty()).map(String::from);
+            Some(ContainerRestartInfo { container, restart_count, started_at })
+        })
+        .collect()
+}
+
+/// Docker restart counts of an environment's containers
+#[tauri::command]
+pub async fn get_environment_restart_counts(
+    state: tauri::State<'_, crate::AppState>,
+    env_name: String,
+) -> Result<Vec<ContainerRestartInfo>, String> {
+    let project_root = state.project_root.lock().map_err(|e| e.to_string())?.clone();
+    let services = environment_services(project_root.as_deref());
+
+    let runtime = container_runtime().binary();
+    let output = silent_command(runtime)
+        .args(["ps", "-a", "--format", "{{.Names}}"])
+        .output()
+        .map_err(|e| format!("Failed to list containers: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Failed to list containers: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    let containers: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|name| container_belongs_to_env(name, &env_name, &services))
+        .map(String::from)
+        .collect();
+    if containers.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let output = silent_command(runtime)
+        .args(["inspect", "--format", "{{.Name}}\t{{.RestartCount}}\t{{.State.StartedAt}}"])
+        .args(&containers)
+        .output()
+        .map_err(|e| format!("Failed to inspect containers: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Failed to inspect containers: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    Ok(parse_restart_info(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses(entries: &[(&str, EnvironmentStatus)]) -> Vec<(String, EnvironmentStatus)> {
+        entries.iter().map(|(name, status)| (name.to_string(), status.clone())).collect()
+    }
+
+    #[test]
+    fn test_status_history_records_only_transitions() {
+        let dir = std::env::temp_dir().join(format!("ushadow-status-history-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("status_history.json");
+
+        let mut last = latest_statuses(&load_history(&path));
+        let passes = [
+            ("2026-01-01T10:00:00+00:00", statuses(&[("gold", EnvironmentStatus::Running), ("blue", EnvironmentStatus::Stopped)])),
+            ("2026-01-01T10:01:00+00:00", statuses(&[("gold", EnvironmentStatus::Running), ("blue", EnvironmentStatus::Stopped)])),
+            ("2026-01-01T10:02:00+00:00", statuses(&[("gold", EnvironmentStatus::Partial), ("blue", EnvironmentStatus::Stopped)])),
+            ("2026-01-01T10:03:00+00:00", statuses(&[("gold", EnvironmentStatus::Running)])),
+        ];
+        for (timestamp, current) in &passes {
+            let changes = status_transitions(&last, current);
+            if !changes.is_empty() {
+                append_changes_at(&path, &changes, timestamp).unwrap();
+            }
+            last.extend(changes);
+        }
+
+        let gold: Vec<(EnvironmentStatus, String)> = filter_history(load_history(&path), "gold", None)
+            .into_iter()
+            .map(|c| (c.status, c.timestamp))
+            .collect();
+        assert_eq!(
+            gold,
+            vec![
+                (EnvironmentStatus::Running, "2026-01-01T10:00:00+00:00".to_string()),
+                (EnvironmentStatus::Partial, "2026-01-01T10:02:00+00:00".to_string()),
+                (EnvironmentStatus::Running, "2026-01-01T10:03:00+00:00".to_string()),
+            ]
+        );
+        assert_eq!(filter_history(load_history(&path), "blue", None).len(), 1);
+
+        let since = chrono::DateTime::parse_from_rfc3339("2026-01-01T10:02:00Z").unwrap().with_timezone(&chrono::Utc);
+        assert_eq!(filter_history(load_history(&path), "gold", Some(since)).len(), 2);
+
+        // Reloading from disk resumes from the last recorded statuses
+        let reloaded = latest_statuses(&load_history(&path));
+        assert_eq!(reloaded.get("gold"), Some(&EnvironmentStatus::Running));
+        assert!(status_transitions(&reloaded, &statuses(&[("gold", EnvironmentStatus::Running)])).is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_parse_restart_info() {
+        let output = "/ushadow-gold-backend\t4\t2026-01-01T10:03:00.123Z\n\
+                      /ushadow-gold-webui\t0\t\n\
+                      garbage\n";
+        assert_eq!(
+            parse_restart_info(output),
+            vec![
+                ContainerRestartInfo {
+                    container: "ushadow-gold-backend".to_string(),
+                    restart_count: 4,
+                    started_at: Some("2026-01-01T10:03:00.123Z".to_string()),
+                },
+                ContainerRestartInfo {
+                    container: "ushadow-gold-webui".to_string(),
+                    restart_count: 0,
+                    started_at: None,
+                },
+            ]
+        );
+    }
+}
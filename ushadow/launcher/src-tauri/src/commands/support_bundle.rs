@@ -0,0 +1,177 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use super::container_runtime::container_runtime;
+use super::discovery::discover_environments_with_config;
+use super::platform::current_platform;
+use super::prerequisites::check_prerequisites;
+use super::prerequisites_config::get_prerequisite_versions;
+use super::settings::read_launcher_settings;
+use super::utils::is_secret_name;
+
+const REDACTED: &str = "<redacted>";
+
+/// Replace `user:password@` in URLs with a placeholder
+fn redact_url_credentials(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(scheme_end) = rest.find("://") {
+        let (head, tail) = rest.split_at(scheme_end + 3);
+        out.push_str(head);
+        let authority_end = tail.find(['/', ' ', '"', '\n']).unwrap_or(tail.len());
+        match tail[..authority_end].rfind('@') {
+            Some(at) => {
+                out.push_str(REDACTED);
+                rest = &tail[at..];
+            }
+            None => rest = tail,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Redact secret-looking fields (and personal emails) and URL credentials anywhere in a JSON value
+fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if (is_secret_name(key) || key.to_lowercase().contains("email")) && !field.is_null() {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        Value::String(s) => *s = redact_url_credentials(s),
+        _ => {}
+    }
+}
+
+/// Serialize a diagnostics section, redacted; a failed section records its error instead
+fn section<T: Serialize>(result: Result<T, String>) -> Value {
+    let mut value = match result.and_then(|v| serde_json::to_value(v).map_err(|e| e.to_string())) {
+        Ok(value) => value,
+        Err(e) => serde_json::json!({ "error": e }),
+    };
+    redact_json(&mut value);
+    value
+}
+
+/// Write named JSON documents into a zip file
+fn write_bundle(path: &Path, files: &[(&str, Value)]) -> Result<(), String> {
+    let file = fs::File::create(path)
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, value) in files {
+        let json = serde_json::to_vec_pretty(value)
+            .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+        zip.start_file(*name, options)
+            .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+        zip.write_all(&json)
+            .map_err(|e| format!("Failed to write {} to bundle: {}", name, e))?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish bundle: {}", e))?;
+    Ok(())
+}
+
+/// Collect redacted diagnostics (system info, prerequisites, discovery, settings) into a zip for
+/// attaching to bug reports. `output_path` may be a directory or a .zip file path.
+#[tauri::command]
+pub async fn export_logs_bundle(output_path: String, main_repo: String) -> Result<String, String> {
+    let mut path = PathBuf::from(&output_path);
+    if path.is_dir() {
+        path = path.join(format!(
+            "ushadow-diagnostics-{}.zip",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        ));
+    }
+
+    let system_info = serde_json::json!({
+        "launcher_version": env!("CARGO_PKG_VERSION"),
+        "platform": current_platform(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "container_runtime": container_runtime().binary(),
+        "main_repo": main_repo,
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+    });
+
+    let files = [
+        ("system_info.json", section(Ok(system_info))),
+        ("prerequisites.json", section(check_prerequisites())),
+        ("prerequisite_versions.json", section(get_prerequisite_versions().await)),
+        ("discovery.json", section(discover_environments_with_config(Some(main_repo.clone()), None).await)),
+        ("settings.json", section(read_launcher_settings())),
+    ];
+
+    write_bundle(&path, &files)?;
+
+    let path = path.to_string_lossy().to_string();
+    eprintln!("[export_logs_bundle] Wrote diagnostics bundle to {}", path);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_redact_json_hides_secrets_everywhere() {
+        let mut value = serde_json::json!({
+            "default_admin_password": "hunter2",
+            "default_admin_email": "me@example.com",
+            "default_admin_name": "Administrator",
+            "nested": [{ "AUTH_TOKEN": "abc", "url": "https://user:pw@host.ts.net/path" }],
+            "unset_secret": null,
+        });
+        redact_json(&mut value);
+
+        assert_eq!(value["default_admin_password"], REDACTED);
+        assert_eq!(value["default_admin_email"], REDACTED);
+        assert_eq!(value["default_admin_name"], "Administrator");
+        assert_eq!(value["nested"][0]["AUTH_TOKEN"], REDACTED);
+        assert_eq!(value["nested"][0]["url"], "https://<redacted>@host.ts.net/path");
+        assert!(value["unset_secret"].is_null());
+
+        assert_eq!(redact_url_credentials("see http://localhost:8000/api"), "see http://localhost:8000/api");
+        assert_eq!(
+            redact_url_credentials("a=redis://:pw@redis:6379 b=http://x@y"),
+            "a=redis://<redacted>@redis:6379 b=http://<redacted>@y"
+        );
+    }
+
+    #[test]
+    fn test_write_bundle_contains_sections() {
+        let dir = std::env::temp_dir().join(format!("ushadow-bundle-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("bundle.zip");
+
+        let files = [
+            ("a.json", section(Ok(serde_json::json!({ "password": "x" })))),
+            ("b.json", section::<Value>(Err("docker not running".to_string()))),
+        ];
+        write_bundle(&path, &files).unwrap();
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(archive.len(), 2);
+
+        let mut contents = String::new();
+        archive.by_name("a.json").unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&contents).unwrap(), serde_json::json!({ "password": REDACTED }));
+
+        contents.clear();
+        archive.by_name("b.json").unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&contents).unwrap(), serde_json::json!({ "error": "docker not running" }));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,86 @@
+/// Sequential ticket IDs (`{prefix}-{n}`), shared by the launcher and kanban-cli
+/// (kanban-cli includes this file with `#[path]`, so it must only depend on rusqlite)
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Prefix used for tickets that don't belong to a project
+pub const DEFAULT_TICKET_PREFIX: &str = "ush";
+
+/// Derive a ticket prefix from a project id (the project root path), e.g.
+/// "/repos/My-App" -> "myapp". Falls back to the default prefix if nothing usable remains.
+pub fn derive_ticket_prefix(project_id: &str) -> String {
+    let name = std::path::Path::new(project_id.trim_end_matches(['/', '\\']))
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(project_id);
+
+    let prefix: String = name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .take(10)
+        .collect();
+
+    if prefix.is_empty() {
+        DEFAULT_TICKET_PREFIX.to_string()
+    } else {
+        prefix
+    }
+}
+
+/// Look up the ticket prefix for a project, deriving and storing one on first use
+pub fn get_or_create_ticket_prefix(conn: &Connection, project_id: Option<&str>) -> Result<String, String> {
+    let project_id = match project_id {
+        Some(id) if !id.is_empty() => id,
+        _ => return Ok(DEFAULT_TICKET_PREFIX.to_string()),
+    };
+
+    let existing: Option<String> = conn.query_row(
+        "SELECT ticket_prefix FROM projects WHERE id = ?",
+        params![project_id],
+        |row| row.get(0),
+    ).optional()
+        .map_err(|e| format!("Failed to query project prefix: {}", e))?;
+
+    if let Some(prefix) = existing {
+        return Ok(prefix);
+    }
+
+    let prefix = derive_ticket_prefix(project_id);
+    conn.execute(
+        "INSERT INTO projects (id, ticket_prefix) VALUES (?1, ?2)",
+        params![project_id, &prefix],
+    ).map_err(|e| format!("Failed to save project prefix: {}", e))?;
+
+    eprintln!("[get_or_create_ticket_prefix] Using prefix '{}' for project {}", prefix, project_id);
+    Ok(prefix)
+}
+
+/// Get the next ticket number for a given prefix
+pub fn get_next_ticket_number(conn: &Connection, prefix: &str) -> Result<i32, String> {
+    // Query ticket IDs that start with "{prefix}-" (substr avoids LIKE wildcards in the prefix)
+    let id_prefix = format!("{}-", prefix);
+    let mut stmt = conn.prepare("SELECT id FROM tickets WHERE substr(id, 1, length(?1)) = ?1")
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    let ticket_ids = stmt.query_map([&id_prefix], |row| {
+        row.get::<_, String>(0)
+    }).map_err(|e| format!("Failed to query tickets: {}", e))?;
+
+    // Find the highest number
+    let mut max_number = 0;
+    for id_result in ticket_ids {
+        if let Ok(id) = id_result {
+            // Extract number from "ush-123" format
+            if let Some(number_str) = id.strip_prefix(&id_prefix) {
+                if let Ok(number) = number_str.parse::<i32>() {
+                    if number > max_number {
+                        max_number = number;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(max_number + 1)
+}
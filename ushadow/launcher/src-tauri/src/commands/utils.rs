@@ -97,17 +97,46 @@ pub fn expand_tilde(path: &str) -> String {
     path.to_string()
 }
 
+/// Single-quote a string for POSIX shells (sh/bash/zsh): nothing inside is expanded,
+/// and an embedded ' is written as '\''
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+pub fn posix_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Single-quote a string for PowerShell: nothing inside is expanded, and an embedded ' is doubled
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn powershell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// Whether a variable/field name likely holds a credential and must not be shown or exported
+pub fn is_secret_name(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    ["SECRET", "PASSWORD", "PASSWD", "TOKEN", "API_KEY", "APIKEY", "PRIVATE_KEY", "CREDENTIAL", "AUTH_KEY"]
+        .iter()
+        .any(|kw| upper.contains(kw))
+}
+
+/// Quote a single argument for the shell `shell_command` runs, so spaces, quotes
+/// and `$` are passed through literally
+///
+/// On Windows (PowerShell): Uses single quotes and doubles internal single quotes
+/// On Unix: Uses single quotes and escapes internal single quotes as '\''
+pub fn shell_quote(s: &str) -> String {
+    #[cfg(target_os = "windows")]
+    return powershell_quote(s);
+
+    #[cfg(not(target_os = "windows"))]
+    posix_quote(s)
+}
+
 /// Quote a path for safe use in shell commands
 /// Handles paths with spaces, special characters, etc.
 ///
-/// On Windows (PowerShell): Uses single quotes and escapes internal single quotes
-/// On Unix: Uses single quotes and escapes internal single quotes
-///
 /// Example: C:/Program Files/App -> 'C:/Program Files/App'
 pub fn quote_path(path: &str) -> String {
-    // Escape single quotes by replacing ' with ''  (PowerShell and bash compatible)
-    let escaped = path.replace('\'', "''");
-    format!("'{}'", escaped)
+    shell_quote(path)
 }
 
 /// Quote a path from a PathBuf for safe use in shell commands
@@ -1,20 +1,120 @@
-use crate::models::{WorktreeInfo, TmuxSessionInfo, TmuxWindowInfo, ClaudeStatus, EnvironmentConflict};
+use crate::models::{ActionCapabilities, WorktreeInfo, TmuxSessionInfo, TmuxWindowInfo, TmuxLayoutNode, TmuxLayoutWindow, TmuxLayoutPane, ClaudeStatus, EnvironmentConflict};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Command;
-use super::utils::{shell_command, silent_command};
+use super::utils::{expand_tilde, posix_quote, shell_command, shell_quote, silent_command};
+use super::settings::{load_launcher_settings, tmux_names, AgentProfile, DEFAULT_EDITOR_COMMAND};
+use super::generic_installer::binary_on_path;
+use super::env_scanner::ensure_env_file;
+use super::multiplexer::{multiplexer, multiplexer_for, tmux_pane_pid};
+use super::discovery::{determine_base_branch, environment_services};
+use super::docker::container_belongs_to_env;
+use super::prerequisites::check_docker;
+use super::container_runtime::container_runtime;
+use super::recent_envs::record_environment_use;
+use super::kanban::project_default_base_branch;
+use tracing::{error, info, warn};
+
+/// Color names matched inside environment names, in priority order
+const ENV_COLOR_NAMES: &[&str] = &[
+    "blue", "gold", "pink", "purple", "red", "green", "indigo", "orange",
+    "cyan", "teal", "lime", "brown", "silver", "coral", "salmon", "navy",
+    "magenta", "violet", "maroon", "olive", "aqua", "turquoise", "crimson",
+    "lavender", "mint", "peach", "rose", "ruby", "emerald", "sapphire",
+    "amber", "bronze", "copper", "platinum", "slate", "charcoal",
+];
+
+/// Canonical color name -> primary hex, shared by the UI, VS Code colors and terminal tabs.
+/// Mirrors `NAMED_COLORS` in src/utils/colors.ts (and setup/vscode_utils/colors.py).
+const COLOR_PALETTE: &[(&str, &str)] = &[
+    ("red", "#c41e3a"),
+    ("blue", "#0066cc"),
+    ("green", "#2ea043"),
+    ("yellow", "#f0ad4e"),
+    ("gold", "#DAA520"),
+    ("orange", "#ff6b35"),
+    ("purple", "#8b3a8b"),
+    ("pink", "#ff1493"),
+    ("cyan", "#00bcd4"),
+    ("teal", "#009688"),
+    ("lime", "#76ff03"),
+    ("indigo", "#3f51b5"),
+    ("brown", "#795548"),
+    ("grey", "#757575"),
+    ("gray", "#757575"),
+    ("black", "#212121"),
+    ("silver", "#a8a8a8"),
+    ("coral", "#ff7f50"),
+    ("salmon", "#fa8072"),
+    ("navy", "#000080"),
+    ("magenta", "#ff00ff"),
+    ("violet", "#ee82ee"),
+    ("maroon", "#800000"),
+    ("olive", "#808000"),
+    ("aqua", "#00ffff"),
+    ("turquoise", "#40e0d0"),
+    ("crimson", "#dc143c"),
+    ("lavender", "#e6e6fa"),
+    ("mint", "#98ff98"),
+    ("peach", "#ffcba4"),
+    ("rose", "#ff007f"),
+    ("ruby", "#e0115f"),
+    ("emerald", "#50c878"),
+    ("sapphire", "#0f52ba"),
+    ("amber", "#ffbf00"),
+    ("bronze", "#cd7f32"),
+    ("copper", "#b87333"),
+    ("platinum", "#e5e4e2"),
+    ("slate", "#708090"),
+    ("charcoal", "#36454f"),
+    ("ushadow", "#8b3a8b"),
+    ("main", "#2ea043"),
+    ("master", "#2ea043"),
+    ("dev", "#0066cc"),
+    ("develop", "#0066cc"),
+    ("staging", "#f0ad4e"),
+    ("stage", "#f0ad4e"),
+    ("prod", "#c41e3a"),
+    ("production", "#c41e3a"),
+    ("test", "#8b3a8b"),
+    ("qa", "#8b3a8b"),
+    ("feature", "#00bcd4"),
+    ("hotfix", "#ff6b35"),
+    ("bugfix", "#ff6b35"),
+    ("release", "#009688"),
+    ("sandbox", "#76ff03"),
+    ("demo", "#ff1493"),
+    ("default", "#0066cc"),
+];
+
+/// Primary hex for a color name from `get_colors_for_name`, if it's a named color
+pub(crate) fn color_hex(name: &str) -> Option<&'static str> {
+    let name_lower = name.trim().to_lowercase();
+    COLOR_PALETTE.iter().find(|(color, _)| *color == name_lower).map(|(_, hex)| *hex)
+}
+
+/// RGB components of a `#rrggbb` color
+fn hex_to_rgb(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Canonical color name -> hex mapping, so the frontend doesn't keep its own copy
+#[tauri::command]
+pub fn get_color_palette() -> Result<HashMap<String, String>, String> {
+    Ok(COLOR_PALETTE
+        .iter()
+        .map(|(name, hex)| (name.to_string(), hex.to_string()))
+        .collect())
+}
 
 /// Get color name for an environment name
 /// Returns the color name that the frontend will use to look up hex codes
 pub fn get_colors_for_name(name: &str) -> (String, String) {
-    let color_names = [
-        "blue", "gold", "pink", "purple", "red", "green", "indigo", "orange",
-        "cyan", "teal", "lime", "brown", "silver", "coral", "salmon", "navy",
-        "magenta", "violet", "maroon", "olive", "aqua", "turquoise", "crimson",
-        "lavender", "mint", "peach", "rose", "ruby", "emerald", "sapphire",
-        "amber", "bronze", "copper", "platinum", "slate", "charcoal",
-    ];
-
     let name_lower = name.to_lowercase();
 
     // Special case: default "ushadow" environment uses purple
@@ -22,7 +122,7 @@ pub fn get_colors_for_name(name: &str) -> (String, String) {
         return ("purple".to_string(), "purple".to_string());
     }
 
-    for color in &color_names {
+    for color in ENV_COLOR_NAMES {
         if name_lower.contains(color) {
             return (color.to_string(), color.to_string());
         }
@@ -34,7 +134,7 @@ pub fn get_colors_for_name(name: &str) -> (String, String) {
 
 /// Delete a git branch (best effort - won't fail if branch doesn't exist)
 fn delete_branch(main_repo: &str, branch_name: &str) {
-    eprintln!("[delete_branch] Attempting to delete branch '{}'", branch_name);
+    info!(target: "delete_branch", "Attempting to delete branch '{}'", branch_name);
 
     // Try to delete the branch with -D (force delete)
     let output = silent_command("git")
@@ -44,19 +144,19 @@ fn delete_branch(main_repo: &str, branch_name: &str) {
 
     match output {
         Ok(result) if result.status.success() => {
-            eprintln!("[delete_branch] ✓ Successfully deleted branch '{}'", branch_name);
+            info!(target: "delete_branch", "✓ Successfully deleted branch '{}'", branch_name);
         }
         Ok(result) => {
             let stderr = String::from_utf8_lossy(&result.stderr);
             // Don't error if branch doesn't exist
             if !stderr.contains("not found") && !stderr.contains("does not exist") {
-                eprintln!("[delete_branch] Warning: Failed to delete branch '{}': {}", branch_name, stderr);
+                warn!(target: "delete_branch", "Failed to delete branch '{}': {}", branch_name, stderr);
             } else {
-                eprintln!("[delete_branch] Branch '{}' already deleted or doesn't exist", branch_name);
+                info!(target: "delete_branch", "Branch '{}' already deleted or doesn't exist", branch_name);
             }
         }
         Err(e) => {
-            eprintln!("[delete_branch] Warning: Failed to run git branch -D: {}", e);
+            warn!(target: "delete_branch", "Failed to run git branch -D: {}", e);
         }
     }
 }
@@ -118,6 +218,75 @@ fn resolve_base_branch(main_repo: &str, provided_base: &str) -> String {
     format!("origin/{}", provided)
 }
 
+/// Verify that a tag, branch or commit SHA resolves to a commit in the repo
+fn verify_base_ref(main_repo: &str, base_ref: &str) -> Result<(), String> {
+    let output = silent_command("git")
+        .args(["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", base_ref)])
+        .current_dir(main_repo)
+        .output()
+        .map_err(|e| format!("Failed to verify base ref: {}", e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("Base ref '{}' does not exist or is not a commit (tag, branch or SHA expected)", base_ref))
+    }
+}
+
+/// Sparse-checkout paths relative to the repository root ("./services/api/" -> "services/api")
+fn normalize_sparse_paths(paths: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized = Vec::new();
+    for path in paths {
+        let trimmed = path.trim().trim_start_matches("./").trim_end_matches('/');
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.starts_with('/') || trimmed.split('/').any(|part| part == "..") {
+            return Err(format!("Sparse path '{}' must be relative to the repository root", path));
+        }
+        normalized.push(trimmed.to_string());
+    }
+    Ok(normalized)
+}
+
+/// Check each sparse-checkout path is a directory in `rev`'s tree
+fn validate_sparse_paths(main_repo: &str, rev: &str, paths: &[String]) -> Result<(), String> {
+    for path in paths {
+        let output = silent_command("git")
+            .args(["cat-file", "-t", &format!("{}:{}", rev, path)])
+            .current_dir(main_repo)
+            .output()
+            .map_err(|e| format!("Failed to check sparse path: {}", e))?;
+        if !output.status.success() || String::from_utf8_lossy(&output.stdout).trim() != "tree" {
+            return Err(format!("Sparse path '{}' is not a directory in '{}'", path, rev));
+        }
+    }
+    Ok(())
+}
+
+/// Restrict a worktree created with `--no-checkout` to `paths`, then check it out
+fn apply_sparse_checkout(worktree_path: &str, paths: &[String]) -> Result<(), String> {
+    let output = silent_command("git")
+        .args(["sparse-checkout", "set"])
+        .args(paths)
+        .current_dir(worktree_path)
+        .output()
+        .map_err(|e| format!("Failed to set sparse-checkout: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("git sparse-checkout set failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    let output = silent_command("git")
+        .args(["checkout"])
+        .current_dir(worktree_path)
+        .output()
+        .map_err(|e| format!("Failed to check out sparse worktree: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("git checkout failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
 /// Check if a worktree exists for a given branch
 #[tauri::command]
 pub async fn check_worktree_exists(main_repo: String, branch: String) -> Result<Option<WorktreeInfo>, String> {
@@ -155,6 +324,10 @@ pub async fn check_worktree_exists(main_repo: String, branch: String) -> Result<
                         path: path.clone(),
                         branch: current_branch,
                         name,
+                        ahead: None,
+                        behind: None,
+                        dirty: false,
+                        changed_files: 0,
                     }));
                 }
             }
@@ -184,6 +357,10 @@ pub async fn check_worktree_exists(main_repo: String, branch: String) -> Result<
                 path: path.clone(),
                 branch: current_branch,
                 name,
+                ahead: None,
+                behind: None,
+                dirty: false,
+                changed_files: 0,
             }));
         }
     }
@@ -191,6 +368,39 @@ pub async fn check_worktree_exists(main_repo: String, branch: String) -> Result<
     Ok(None)
 }
 
+/// Whether any container in the environment's compose project is running
+pub(crate) fn environment_has_running_containers(env_name: &str) -> bool {
+    silent_command(container_runtime().binary())
+        .args([
+            "ps",
+            "--filter",
+            &format!("label=com.docker.compose.project={}", compose_project_for_env(env_name)),
+            "--filter",
+            "status=running",
+            "--format",
+            "{{.Names}}",
+        ])
+        .output()
+        .map(|o| o.status.success() && !String::from_utf8_lossy(&o.stdout).trim().is_empty())
+        .unwrap_or(false)
+}
+
+/// The environment's own containers, running or not (service-attached containers are excluded)
+pub(crate) fn environment_containers(main_repo: &str, env_name: &str) -> Vec<String> {
+    let services = environment_services(Some(main_repo));
+    silent_command(container_runtime().binary())
+        .args(["ps", "-a", "--filter", "name=ushadow-", "--format", "{{.Names}}"])
+        .output()
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .filter(|name| container_belongs_to_env(name, env_name, &services))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Check if an environment with this name already exists and return conflict info
 #[tauri::command]
 pub async fn check_environment_conflict(
@@ -200,26 +410,100 @@ pub async fn check_environment_conflict(
     let env_name = env_name.to_lowercase();
 
     // Check if a worktree with this name exists
-    let worktrees = list_worktrees(main_repo.clone()).await?;
+    let worktrees = list_worktrees(main_repo.clone(), None, None).await?;
 
     if let Some(worktree) = worktrees.iter().find(|wt| wt.name == env_name) {
-        // Worktree exists - return conflict info
-        // Note: is_running will be set to false here, but the frontend can check
-        // the actual running status from its discovery data
+        // Worktree exists - return conflict info, including whether its containers are up
+        let is_running = environment_has_running_containers(&env_name);
         return Ok(Some(EnvironmentConflict {
             name: env_name,
             current_branch: worktree.branch.clone(),
             path: worktree.path.clone(),
-            is_running: false,  // Frontend will populate this from discovery
+            is_running,
         }));
     }
 
     Ok(None)
 }
 
+/// Resolve a base branch name to a ref that git can actually use for comparison.
+/// Unlike `resolve_base_branch`, returns None instead of guessing when nothing matches.
+fn resolve_divergence_base(repo: &str, base: &str) -> Option<String> {
+    let mut candidates = vec![format!("origin/{}", base), base.to_string()];
+    if base == "main" {
+        candidates.push("origin/master".to_string());
+        candidates.push("master".to_string());
+    }
+
+    candidates.into_iter().find(|candidate| {
+        silent_command("git")
+            .args(["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", candidate)])
+            .current_dir(repo)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Count commits ahead of / behind the branch's base, as (ahead, behind).
+/// Returns None for detached HEADs or when the base can't be resolved.
+fn compute_ahead_behind(repo: &str, branch: &str) -> Option<(u32, u32)> {
+    if branch.is_empty() {
+        return None;
+    }
+
+    let base = determine_base_branch(repo, branch)?;
+    let base_ref = resolve_divergence_base(repo, &base)?;
+
+    let output = silent_command("git")
+        .args(["rev-list", "--left-right", "--count", &format!("{}...{}", base_ref, branch)])
+        .current_dir(repo)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    // Output is "<behind>\t<ahead>" (left = base, right = branch)
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut counts = stdout.split_whitespace().map(|n| n.parse::<u32>().ok());
+    let behind = counts.next()??;
+    let ahead = counts.next()??;
+    Some((ahead, behind))
+}
+
+/// Count uncommitted changes (staged, unstaged and untracked) in a worktree.
+/// Returns 0 if the directory no longer exists or git fails.
+fn count_changed_files(worktree_path: &str) -> u32 {
+    if !std::path::Path::new(worktree_path).is_dir() {
+        return 0;
+    }
+
+    match silent_command("git")
+        .args(["status", "--porcelain"])
+        .current_dir(worktree_path)
+        .output()
+    {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .count() as u32,
+        _ => 0,
+    }
+}
+
 /// List all git worktrees in a repository
+///
+/// When `include_divergence` is true, each worktree also gets ahead/behind counts
+/// relative to its base branch. When `include_status` is true, each worktree gets
+/// its uncommitted-changes count. Both run extra git commands per worktree, so they're opt-in.
 #[tauri::command]
-pub async fn list_worktrees(main_repo: String) -> Result<Vec<WorktreeInfo>, String> {
+pub async fn list_worktrees(
+    main_repo: String,
+    include_divergence: Option<bool>,
+    include_status: Option<bool>,
+) -> Result<Vec<WorktreeInfo>, String> {
     let output = silent_command("git")
         .args(["worktree", "list", "--porcelain"])
         .current_dir(&main_repo)
@@ -253,6 +537,10 @@ pub async fn list_worktrees(main_repo: String) -> Result<Vec<WorktreeInfo>, Stri
                         path: path.clone(),
                         branch,
                         name,
+                        ahead: None,
+                        behind: None,
+                        dirty: false,
+                        changed_files: 0,
                     });
                 }
             }
@@ -282,10 +570,30 @@ pub async fn list_worktrees(main_repo: String) -> Result<Vec<WorktreeInfo>, Stri
                 path: path.clone(),
                 branch,
                 name,
+                ahead: None,
+                behind: None,
+                dirty: false,
+                changed_files: 0,
             });
         }
     }
 
+    if include_divergence.unwrap_or(false) {
+        for worktree in worktrees.iter_mut() {
+            if let Some((ahead, behind)) = compute_ahead_behind(&main_repo, &worktree.branch) {
+                worktree.ahead = Some(ahead);
+                worktree.behind = Some(behind);
+            }
+        }
+    }
+
+    if include_status.unwrap_or(false) {
+        for worktree in worktrees.iter_mut() {
+            worktree.changed_files = count_changed_files(&worktree.path);
+            worktree.dirty = worktree.changed_files > 0;
+        }
+    }
+
     Ok(worktrees)
 }
 
@@ -341,7 +649,7 @@ pub async fn list_git_branches(main_repo: String) -> Result<Vec<String>, String>
     Ok(unique_branches)
 }
 
-/// Create a new git worktree
+/// Create a new git worktree. With `sparse_paths`, only those directories are checked out.
 #[tauri::command]
 pub async fn create_worktree(
     main_repo: String,
@@ -349,10 +657,19 @@ pub async fn create_worktree(
     name: String,
     branch_name: Option<String>,
     base_branch: Option<String>,
+    base_ref: Option<String>,
+    sparse_paths: Option<Vec<String>>,
 ) -> Result<WorktreeInfo, String> {
     // Force lowercase to avoid Docker Compose naming issues
     let name = name.to_lowercase();
 
+    // Explicit base ref (tag or commit SHA) bypasses base-branch inference.
+    // Validate it before touching any existing worktree state.
+    let base_ref = base_ref.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    if let Some(ref base_ref) = base_ref {
+        verify_base_ref(&main_repo, base_ref)?;
+    }
+
     // Extract project name from main_repo path (last directory component)
     let project_name = PathBuf::from(&main_repo)
         .file_name()
@@ -368,12 +685,21 @@ pub async fn create_worktree(
     if !project_worktrees_dir.exists() {
         std::fs::create_dir_all(&project_worktrees_dir)
             .map_err(|e| format!("Failed to create project worktrees directory: {}", e))?;
-        eprintln!("[create_worktree] Created project worktrees directory: {}", project_worktrees_dir.display());
+        info!(target: "create_worktree", "Created project worktrees directory: {}", project_worktrees_dir.display());
     }
 
     // Determine the desired branch name (also lowercase)
     let desired_branch = branch_name.map(|b| b.to_lowercase()).unwrap_or_else(|| name.clone());
 
+    if let Some(ref base_ref) = base_ref {
+        if git_ref_exists(&main_repo, &format!("refs/heads/{}", desired_branch)) {
+            return Err(format!(
+                "Branch '{}' already exists; base ref '{}' can only be used when creating a new branch",
+                desired_branch, base_ref
+            ));
+        }
+    }
+
     // Check if git has this worktree registered or if the branch is in use
     let list_output = silent_command("git")
         .args(["worktree", "list", "--porcelain"])
@@ -418,7 +744,7 @@ pub async fn create_worktree(
             // Check if this worktree has our desired branch
             if let Some(ref branch) = current_branch {
                 if branch == &desired_branch {
-                    eprintln!("[create_worktree] Found existing worktree for branch '{}' at: {}", branch, current_worktree_path);
+                    info!(target: "create_worktree", "Found existing worktree for branch '{}' at: {}", branch, current_worktree_path);
                     conflicting_path = Some(current_worktree_path);
                     break;
                 }
@@ -430,12 +756,12 @@ pub async fn create_worktree(
 
     // If the worktree is registered in git or the directory exists, clean it up
     if is_registered || worktree_path.exists() {
-        eprintln!("[create_worktree] Worktree exists or is registered, attempting cleanup");
+        info!(target: "create_worktree", "Worktree exists or is registered, attempting cleanup");
 
         // Use the conflicting path we found, or the target path if no conflict
         let path_to_remove = conflicting_path.unwrap_or_else(|| worktree_path_str.to_string());
 
-        eprintln!("[create_worktree] Using path for removal: {}", path_to_remove);
+        info!(target: "create_worktree", "Using path for removal: {}", path_to_remove);
 
         // Try to remove the worktree from git's tracking (handles both directory and registration)
         let remove_output = silent_command("git")
@@ -445,10 +771,10 @@ pub async fn create_worktree(
             .map_err(|e| format!("Failed to remove worktree: {}", e))?;
 
         if remove_output.status.success() {
-            eprintln!("[create_worktree] Successfully removed worktree from git tracking");
+            info!(target: "create_worktree", "Successfully removed worktree from git tracking");
         } else {
             // If remove fails, try prune + manual directory removal
-            eprintln!("[create_worktree] git worktree remove failed, trying prune + manual cleanup");
+            warn!(target: "create_worktree", "git worktree remove failed, trying prune + manual cleanup");
 
             let prune_output = silent_command("git")
                 .args(["worktree", "prune"])
@@ -457,14 +783,14 @@ pub async fn create_worktree(
                 .map_err(|e| format!("Failed to prune worktrees: {}", e))?;
 
             if prune_output.status.success() {
-                eprintln!("[create_worktree] Pruned stale worktree references");
+                info!(target: "create_worktree", "Pruned stale worktree references");
             }
 
             // Remove the directory if it exists
             if worktree_path.exists() {
                 std::fs::remove_dir_all(&worktree_path)
                     .map_err(|e| format!("Failed to remove existing worktree directory: {}", e))?;
-                eprintln!("[create_worktree] Removed existing directory");
+                info!(target: "create_worktree", "Removed existing directory");
             }
         }
     }
@@ -510,7 +836,7 @@ pub async fn create_worktree(
     }
 
     // Before creating, clean up any locked/missing worktrees at this path
-    eprintln!("[create_worktree] Checking for locked/missing worktrees...");
+    info!(target: "create_worktree", "Checking for locked/missing worktrees...");
     let _ = silent_command("git")
         .args(["worktree", "unlock", worktree_path.to_str().unwrap()])
         .current_dir(&main_repo)
@@ -520,10 +846,18 @@ pub async fn create_worktree(
         .current_dir(&main_repo)
         .output();
 
+    let sparse_paths = normalize_sparse_paths(&sparse_paths.unwrap_or_default())?;
+    // Sparse worktrees are checked out after sparse-checkout is configured
+    let checkout_flag: &[&str] = if sparse_paths.is_empty() { &[] } else { &["--no-checkout"] };
+
     let (output, final_branch) = if branch_exists {
+        validate_sparse_paths(&main_repo, &desired_branch, &sparse_paths)?;
+
         // Branch exists - checkout directly into worktree
         let output = silent_command("git")
-            .args(["worktree", "add", worktree_path.to_str().unwrap(), &desired_branch])
+            .args(["worktree", "add"])
+            .args(checkout_flag)
+            .args([worktree_path.to_str().unwrap(), &desired_branch])
             .current_dir(&main_repo)
             .output()
             .map_err(|e| format!("Failed to create worktree: {}", e))?;
@@ -532,24 +866,32 @@ pub async fn create_worktree(
         // Branch doesn't exist - create new branch from base branch
         let new_branch_name = desired_branch.clone();
 
-        // Determine base branch to use
-        // Priority: 1) Provided base_branch parameter, 2) Derived from suffix, 3) Default to origin/main
-        let base = if let Some(ref provided_base) = base_branch {
+        // Determine base to use
+        // Priority: 1) Explicit base_ref, 2) Provided base_branch parameter, 3) Derived from suffix,
+        // 4) Project default base branch, 5) origin/main
+        let base = if let Some(ref explicit_ref) = base_ref {
+            info!(target: "create_worktree", "Using explicit base ref '{}'", explicit_ref);
+            explicit_ref.clone()
+        } else if let Some(ref provided_base) = base_branch {
             let resolved = resolve_base_branch(&main_repo, provided_base);
-            eprintln!("[create_worktree] Resolved provided base '{}' to '{}'", provided_base, resolved);
+            info!(target: "create_worktree", "Resolved provided base '{}' to '{}'", provided_base, resolved);
             resolved
         } else if new_branch_name.ends_with("-dev") {
             resolve_base_branch(&main_repo, "dev")
         } else if new_branch_name.ends_with("-main") {
             resolve_base_branch(&main_repo, "main")
         } else {
-            resolve_base_branch(&main_repo, "main")
+            let default_base = project_default_base_branch(&main_repo).unwrap_or_else(|| "main".to_string());
+            resolve_base_branch(&main_repo, &default_base)
         };
 
-        eprintln!("[create_worktree] Creating new branch '{}' from '{}'", new_branch_name, base);
+        info!(target: "create_worktree", "Creating new branch '{}' from '{}'", new_branch_name, base);
+        validate_sparse_paths(&main_repo, &base, &sparse_paths)?;
 
         let output = silent_command("git")
-            .args(["worktree", "add", "-b", &new_branch_name, worktree_path.to_str().unwrap(), &base])
+            .args(["worktree", "add"])
+            .args(checkout_flag)
+            .args(["-b", &new_branch_name, worktree_path.to_str().unwrap(), &base])
             .current_dir(&main_repo)
             .output()
             .map_err(|e| format!("Failed to create worktree: {}", e))?;
@@ -562,11 +904,71 @@ pub async fn create_worktree(
         return Err(format!("Git command failed: {}", stderr));
     }
 
+    if !sparse_paths.is_empty() {
+        info!(target: "create_worktree", "Sparse checkout of {:?}", sparse_paths);
+        apply_sparse_checkout(&worktree_path.to_string_lossy(), &sparse_paths)?;
+    }
+
     Ok(WorktreeInfo {
         path: worktree_path.to_string_lossy().to_string(),
         branch: final_branch,
         name,
+        ahead: None,
+        behind: None,
+        dirty: false,
+        changed_files: 0,
+    })
+}
+
+/// Whether `binary` resolves in the user's login shell, whose PATH includes profile additions
+fn binary_on_login_path(binary: &str) -> bool {
+    #[cfg(target_os = "windows")]
+    let probe = format!("Get-Command {} -ErrorAction Stop", shell_quote(binary));
+    #[cfg(not(target_os = "windows"))]
+    let probe = format!("command -v {}", shell_quote(binary));
+
+    shell_command(&probe)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Whether `open_tmux_in_terminal` has a terminal to launch
+fn terminal_available(configured: Option<&str>, on_path: impl Fn(&str) -> bool) -> bool {
+    if cfg!(target_os = "macos") {
+        // Terminal.app is always there
+        return true;
+    }
+    let configured = configured.map(str::trim).filter(|t| !t.is_empty());
+    configured.into_iter().chain(LINUX_TERMINALS.iter().copied()).any(|terminal| on_path(terminal))
+}
+
+/// Probe the tools behind the "open" actions so the UI can disable the ones that would fail
+#[tauri::command]
+pub async fn get_available_actions() -> Result<ActionCapabilities, String> {
+    let settings = load_launcher_settings().await.unwrap_or_default();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (editor, _) = resolve_editor_command(&settings.editor_command, binary_on_login_path);
+        let browser = if cfg!(target_os = "macos") {
+            binary_on_login_path("open")
+        } else if cfg!(target_os = "windows") {
+            true
+        } else {
+            binary_on_login_path("xdg-open")
+        };
+        let (docker_installed, docker_running, _) = check_docker();
+
+        ActionCapabilities {
+            editor: binary_on_login_path(&editor),
+            terminal: terminal_available(settings.terminal_emulator.as_deref(), binary_on_login_path),
+            browser,
+            multiplexer: multiplexer_for(settings.multiplexer).check_installed().is_ok(),
+            docker: docker_installed && docker_running,
+        }
     })
+    .await
+    .map_err(|e| format!("Failed to probe available actions: {}", e))
 }
 
 /// Open a path in VS Code with environment-specific colors
@@ -581,51 +983,118 @@ pub async fn open_in_vscode_with_tmux(path: String, env_name: String) -> Result<
     open_in_vscode_impl(path, Some(env_name), true).await
 }
 
+/// Open just an environment's `.env` in the configured editor
+#[tauri::command]
+pub async fn open_env_file_in_editor(worktree_path: String, env_name: String) -> Result<String, String> {
+    let env_path = ensure_env_file(std::path::Path::new(&worktree_path))?;
+    record_environment_use(&env_name);
+
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let (editor, fallback_warning) = resolve_editor_command(&settings.editor_command, binary_on_path);
+    if let Some(warning) = fallback_warning {
+        warn!(target: "open_env_file_in_editor", "{}", warning);
+    }
+
+    let env_path = env_path.to_string_lossy().to_string();
+    editor_launch_command(&editor, &env_path)
+        .spawn()
+        .map_err(|e| format!("Failed to open {}: {}", editor, e))?;
+
+    info!(target: "open_env_file_in_editor", "Opened {} for '{}' in {}", env_path, env_name, editor);
+    Ok(env_path)
+}
+
+/// Shell command that sets up VS Code colors for the environment at `path`.
+/// The env name is passed as an argument so it never ends up inside the Python source.
+fn color_setup_command(path: &str, env_name: &str) -> String {
+    const SCRIPT: &str = "import sys; from pathlib import Path; \
+        from setup.vscode_utils.colors import setup_colors_for_directory; \
+        setup_colors_for_directory(Path('.'), sys.argv[1])";
+    format!(
+        "cd {} && uv run --with pyyaml python3 -c {} {}",
+        shell_quote(path),
+        shell_quote(SCRIPT),
+        shell_quote(env_name)
+    )
+}
+
+/// VS Code terminal profile command: split the env's tmux window, attach to it, or fall back
+/// to `fallback`. Always run by a POSIX shell (bash/zsh), even on Windows.
+fn vscode_tmux_command(window_name: &str, fallback: &str) -> String {
+    let target = posix_quote(&format!("workmux:{}", window_name));
+    format!(
+        "tmux -f .tmux.conf has-session -t {target} 2>/dev/null && \
+        tmux -f .tmux.conf split-window -t {target} || \
+        tmux -f .tmux.conf attach-session -t {target} || \
+        {fallback}"
+    )
+}
+
+/// Editor to launch: the configured one, or `code` (with a warning) if it isn't on PATH
+fn resolve_editor_command(configured: &str, on_path: impl Fn(&str) -> bool) -> (String, Option<String>) {
+    let configured = configured.trim();
+    if configured.is_empty() || configured == DEFAULT_EDITOR_COMMAND || on_path(configured) {
+        let editor = if configured.is_empty() { DEFAULT_EDITOR_COMMAND } else { configured };
+        return (editor.to_string(), None);
+    }
+    (
+        DEFAULT_EDITOR_COMMAND.to_string(),
+        Some(format!("Editor '{}' not found on PATH, falling back to '{}'", configured, DEFAULT_EDITOR_COMMAND)),
+    )
+}
+
+/// Command that opens `path` in `editor` (any VS Code fork taking a folder argument)
+fn editor_launch_command(editor: &str, path: &str) -> Command {
+    let mut cmd = silent_command(editor);
+    cmd.arg(path);
+    cmd
+}
+
 async fn open_in_vscode_impl(path: String, env_name: Option<String>, with_tmux: bool) -> Result<(), String> {
     use super::utils::shell_command;
 
+    let launcher_settings = load_launcher_settings().await.unwrap_or_default();
+
     // If env_name is provided, set up VSCode colors using the Python utility
     if let Some(name) = &env_name {
-        eprintln!("[open_in_vscode] Setting up VSCode colors for environment: {}", name);
-
-        // Run Python script to set up colors in the environment directory
-        let color_setup_cmd = format!(
-            "cd '{}' && uv run --with pyyaml python3 -c \"from setup.vscode_utils.colors import setup_colors_for_directory; from pathlib import Path; setup_colors_for_directory(Path('.'), '{}')\"",
-            path, name
-        );
+        record_environment_use(name);
+        info!(target: "open_in_vscode", "Setting up VSCode colors for environment: {}", name);
 
         // Fire-and-forget: color setup is non-critical, don't block VS Code launch
-        let _ = shell_command(&color_setup_cmd).spawn();
-        eprintln!("[open_in_vscode] VSCode color setup dispatched (async)");
+        let _ = shell_command(&color_setup_command(&path, name)).spawn();
+        info!(target: "open_in_vscode", "VSCode color setup dispatched (async)");
     }
 
-    // Open VS Code (don't wait for it to finish)
-    silent_command("code")
-        .arg(&path)
+    // Open the editor (don't wait for it to finish)
+    let (editor, fallback_warning) = resolve_editor_command(&launcher_settings.editor_command, binary_on_path);
+    if let Some(warning) = fallback_warning {
+        warn!(target: "open_in_vscode", "{}", warning);
+    }
+    editor_launch_command(&editor, &path)
         .spawn()
-        .map_err(|e| format!("Failed to open VS Code: {}", e))?;
+        .map_err(|e| format!("Failed to open {}: {}", editor, e))?;
 
     // If with_tmux is true, create a shell script that VS Code can run
     if with_tmux && env_name.is_some() {
         let env_name_lower = env_name.unwrap().to_lowercase();
         // Sanitize env_name by replacing slashes (tmux doesn't allow slashes in window names)
         let sanitized_env_name = env_name_lower.replace('/', "-").replace('\\', "-");
-        let window_name = format!("ushadow-{}", sanitized_env_name);
+        let window_name = tmux_names(&sanitized_env_name, &launcher_settings).window;
 
-        eprintln!("[open_in_vscode] Creating tmux attach script for VS Code terminal");
+        info!(target: "open_in_vscode", "Creating tmux attach script for VS Code terminal");
 
         // Ensure tmux is running and window exists
-        eprintln!("[open_in_vscode] Ensuring tmux is running...");
+        info!(target: "open_in_vscode", "Ensuring tmux is running...");
         match ensure_tmux_running().await {
-            Ok(msg) => eprintln!("[open_in_vscode] {}", msg),
+            Ok(msg) => info!(target: "open_in_vscode", "{}", msg),
             Err(e) => {
-                eprintln!("[open_in_vscode] ERROR: Failed to start tmux: {}", e);
+                error!(target: "open_in_vscode", "Failed to start tmux: {}", e);
                 return Err(format!("Failed to start tmux: {}", e));
             }
         }
 
         // Check if window exists, create if not
-        eprintln!("[open_in_vscode] Checking for tmux window '{}'...", window_name);
+        info!(target: "open_in_vscode", "Checking for tmux window '{}'...", window_name);
         let check_window = shell_command(&format!("tmux list-windows -a -F '#{{window_name}}' | grep '^{}'", window_name))
             .output();
 
@@ -669,40 +1138,40 @@ set -g terminal-overrides 'xterm*:smcup@:rmcup@'\n\
         std::fs::write(&tmux_conf_path, tmux_conf_content)
             .map_err(|e| format!("Failed to write .tmux.conf: {}", e))?;
 
-        eprintln!("[open_in_vscode] Created user-friendly .tmux.conf");
+        info!(target: "open_in_vscode", "Created user-friendly .tmux.conf");
 
         // Reload tmux config if session is already running
         let reload_config = shell_command(&format!(
-            "tmux source-file '{}'",
-            tmux_conf_path
+            "tmux source-file {}",
+            shell_quote(&tmux_conf_path)
         ))
             .output();
 
         if let Ok(output) = reload_config {
             if output.status.success() {
-                eprintln!("[open_in_vscode] Reloaded tmux config for existing session");
+                info!(target: "open_in_vscode", "Reloaded tmux config for existing session");
             }
         }
 
         if !window_exists {
-            eprintln!("[open_in_vscode] Creating tmux window '{}'...", window_name);
+            info!(target: "open_in_vscode", "Creating tmux window '{}'...", window_name);
             // Create the window
             let create_window = shell_command(&format!(
-                "cd '{}' && tmux -f .tmux.conf new-window -t workmux -n {} -c '{}'",
-                path, window_name, path
+                "cd {} && tmux -f .tmux.conf new-window -t workmux -n {} -c {}",
+                shell_quote(&path), shell_quote(&window_name), shell_quote(&path)
             ))
                 .output()
                 .map_err(|e| format!("Failed to create tmux window: {}", e))?;
 
             if !create_window.status.success() {
                 let stderr = String::from_utf8_lossy(&create_window.stderr);
-                eprintln!("[open_in_vscode] ERROR: Failed to create tmux window: {}", stderr);
+                error!(target: "open_in_vscode", "Failed to create tmux window: {}", stderr);
                 return Err(format!("Failed to create tmux window: {}", stderr));
             } else {
-                eprintln!("[open_in_vscode] [OK] Created tmux window '{}'", window_name);
+                info!(target: "open_in_vscode", "[OK] Created tmux window '{}'", window_name);
             }
         } else {
-            eprintln!("[open_in_vscode] [OK] Tmux window '{}' already exists", window_name);
+            info!(target: "open_in_vscode", "[OK] Tmux window '{}' already exists", window_name);
         }
 
         // Create .vscode directory if it doesn't exist
@@ -727,13 +1196,7 @@ set -g terminal-overrides 'xterm*:smcup@:rmcup@'\n\
             let user_shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
 
             // Command that creates a NEW pane each time a new terminal is opened
-            let tmux_command = format!(
-                "tmux -f .tmux.conf has-session -t workmux:{} 2>/dev/null && \
-                tmux -f .tmux.conf split-window -t workmux:{} || \
-                tmux -f .tmux.conf attach-session -t workmux:{} || \
-                exec $SHELL -l",
-                window_name, window_name, window_name
-            );
+            let tmux_command = vscode_tmux_command(&window_name, "exec $SHELL -l");
 
             settings["terminal.integrated.profiles.osx"] = serde_json::json!({
                 "tmux": {
@@ -747,13 +1210,7 @@ set -g terminal-overrides 'xterm*:smcup@:rmcup@'\n\
 
         #[cfg(target_os = "linux")]
         {
-            let tmux_command = format!(
-                "tmux -f .tmux.conf has-session -t workmux:{} 2>/dev/null && \
-                tmux -f .tmux.conf split-window -t workmux:{} || \
-                tmux -f .tmux.conf attach-session -t workmux:{} || \
-                bash",
-                window_name, window_name, window_name
-            );
+            let tmux_command = vscode_tmux_command(&window_name, "bash");
 
             settings["terminal.integrated.profiles.linux"] = serde_json::json!({
                 "tmux": {
@@ -767,13 +1224,7 @@ set -g terminal-overrides 'xterm*:smcup@:rmcup@'\n\
 
         #[cfg(target_os = "windows")]
         {
-            let tmux_command = format!(
-                "tmux -f .tmux.conf has-session -t workmux:{} 2>/dev/null && \
-                tmux -f .tmux.conf split-window -t workmux:{} || \
-                tmux -f .tmux.conf attach-session -t workmux:{} || \
-                bash",
-                window_name, window_name, window_name
-            );
+            let tmux_command = vscode_tmux_command(&window_name, "bash");
 
             settings["terminal.integrated.profiles.windows"] = serde_json::json!({
                 "tmux": {
@@ -792,8 +1243,8 @@ set -g terminal-overrides 'xterm*:smcup@:rmcup@'\n\
         std::fs::write(&settings_path, settings_content)
             .map_err(|e| format!("Failed to write settings.json: {}", e))?;
 
-        eprintln!("[open_in_vscode] Configured VS Code to use tmux terminal by default");
-        eprintln!("[open_in_vscode] Open a terminal in VS Code with Cmd+Shift+` to connect to tmux");
+        info!(target: "open_in_vscode", "Configured VS Code to use tmux terminal by default");
+        info!(target: "open_in_vscode", "Open a terminal in VS Code with Cmd+Shift+` to connect to tmux");
 
         // Note: We don't auto-open the terminal with AppleScript because it can
         // cause focus issues when multiple VS Code windows are open.
@@ -808,12 +1259,12 @@ set -g terminal-overrides 'xterm*:smcup@:rmcup@'\n\
 #[tauri::command]
 pub async fn remove_worktree(main_repo: String, name: String) -> Result<(), String> {
     // First, find the worktree path
-    let worktrees = list_worktrees(main_repo.clone()).await?;
+    let worktrees = list_worktrees(main_repo.clone(), None, None).await?;
     let worktree = worktrees.iter()
         .find(|wt| wt.name == name)
         .ok_or_else(|| format!("Worktree '{}' not found", name))?;
 
-    eprintln!("[remove_worktree] Removing worktree at: {}", worktree.path);
+    info!(target: "remove_worktree", "Removing worktree at: {}", worktree.path);
 
     // Store branch name for deletion after worktree removal
     let branch_name = worktree.branch.clone();
@@ -830,7 +1281,7 @@ pub async fn remove_worktree(main_repo: String, name: String) -> Result<(), Stri
 
         // If it contains modified/untracked files, use --force
         if stderr.contains("modified or untracked files") || stderr.contains("use --force") {
-            eprintln!("[remove_worktree] Worktree has uncommitted changes, forcing removal...");
+            info!(target: "remove_worktree", "Worktree has uncommitted changes, forcing removal...");
 
             let force_output = silent_command("git")
                 .args(["worktree", "remove", "--force", &worktree.path])
@@ -839,7 +1290,7 @@ pub async fn remove_worktree(main_repo: String, name: String) -> Result<(), Stri
                 .map_err(|e| format!("Failed to force remove worktree: {}", e))?;
 
             if force_output.status.success() {
-                eprintln!("[remove_worktree] ✓ Successfully force-removed worktree");
+                info!(target: "remove_worktree", "✓ Successfully force-removed worktree");
                 // Delete the associated branch
                 delete_branch(&main_repo, &branch_name);
                 return Ok(());
@@ -851,7 +1302,7 @@ pub async fn remove_worktree(main_repo: String, name: String) -> Result<(), Stri
 
         // If it's locked or missing, try to unlock and prune
         if stderr.contains("locked") || stderr.contains("missing") {
-            eprintln!("[remove_worktree] Worktree is locked/missing, attempting to unlock and prune...");
+            info!(target: "remove_worktree", "Worktree is locked/missing, attempting to unlock and prune...");
 
             // Try to unlock
             let _ = silent_command("git")
@@ -867,7 +1318,7 @@ pub async fn remove_worktree(main_repo: String, name: String) -> Result<(), Stri
                 .map_err(|e| format!("Failed to prune worktrees: {}", e))?;
 
             if prune_output.status.success() {
-                eprintln!("[remove_worktree] ✓ Successfully pruned locked/missing worktree");
+                info!(target: "remove_worktree", "✓ Successfully pruned locked/missing worktree");
                 // Delete the associated branch
                 delete_branch(&main_repo, &branch_name);
                 return Ok(());
@@ -880,7 +1331,7 @@ pub async fn remove_worktree(main_repo: String, name: String) -> Result<(), Stri
         return Err(format!("Git command failed: {}", stderr));
     }
 
-    eprintln!("[remove_worktree] ✓ Worktree removed successfully");
+    info!(target: "remove_worktree", "✓ Worktree removed successfully");
 
     // Delete the associated branch
     delete_branch(&main_repo, &branch_name);
@@ -888,204 +1339,535 @@ pub async fn remove_worktree(main_repo: String, name: String) -> Result<(), Stri
     Ok(())
 }
 
-/// Delete an environment completely - stop containers, remove worktree, close tmux
-#[tauri::command]
-pub async fn delete_environment(main_repo: String, env_name: String) -> Result<String, String> {
-    let env_name = env_name.to_lowercase();
-    eprintln!("[delete_environment] Deleting environment '{}'", env_name);
-
-    let mut messages = Vec::new();
-
-    // Step 1: Stop containers (best effort - don't fail if they're already stopped)
-    eprintln!("[delete_environment] Stopping containers for '{}'...", env_name);
-
-    // Use correct compose project name (matches run.py logic)
-    let compose_project_name = if env_name == "ushadow" {
-        "ushadow".to_string()
+/// Derive the renamed branch for an environment rename.
+/// Branches named after the env ("gold" or "gold/feature") follow the rename; others are kept.
+fn renamed_branch(branch: &str, old_name: &str, new_name: &str) -> String {
+    if branch == old_name {
+        new_name.to_string()
+    } else if let Some(rest) = branch.strip_prefix(&format!("{}/", old_name)) {
+        format!("{}/{}", new_name, rest)
     } else {
-        format!("ushadow-{}", env_name)
-    };
+        branch.to_string()
+    }
+}
 
-    let stop_result = shell_command(&format!("docker compose -p {} down", compose_project_name))
-        .output();
+/// Rename the branch and move the worktree. If the move fails, the branch rename is
+/// rolled back so the repo is never left half-renamed.
+fn rename_branch_and_move_worktree(
+    main_repo: &str,
+    old_path: &str,
+    new_path: &str,
+    old_branch: &str,
+    new_branch: &str,
+) -> Result<(), String> {
+    let rename_branch = old_branch != new_branch && !old_branch.is_empty();
+
+    if rename_branch {
+        let output = silent_command("git")
+            .args(["branch", "-m", old_branch, new_branch])
+            .current_dir(main_repo)
+            .output()
+            .map_err(|e| format!("Failed to run git branch -m: {}", e))?;
 
-    match stop_result {
-        Ok(output) if output.status.success() => {
-            messages.push(format!("[OK] Stopped containers for '{}'", env_name));
-        }
-        Ok(output) => {
+        if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            if !stderr.contains("No such file") && !stderr.to_lowercase().contains("not found") {
-                eprintln!("[delete_environment] Warning: Failed to stop containers: {}", stderr);
-                messages.push(format!("[WARN] Could not stop containers (may already be stopped)"));
-            }
-        }
-        Err(e) => {
-            eprintln!("[delete_environment] Warning: Failed to run docker compose down: {}", e);
-            messages.push(format!("[WARN] Could not stop containers (may already be stopped)"));
+            return Err(format!("Failed to rename branch '{}' to '{}': {}", old_branch, new_branch, stderr.trim()));
         }
     }
 
-    // Step 2: Kill the per-environment tmux session (ush-{env}) if it exists
-    let sanitized_env_name = env_name.replace('/', "-").replace('\\', "-");
-    let session_name = format!("ush-{}", sanitized_env_name);
-    eprintln!("[delete_environment] Killing tmux session '{}'...", session_name);
-    let close_result = shell_command(&format!("tmux kill-session -t {}", session_name))
+    let move_output = silent_command("git")
+        .args(["worktree", "move", old_path, new_path])
+        .current_dir(main_repo)
         .output();
 
-    match close_result {
-        Ok(output) if output.status.success() => {
-            messages.push(format!("[OK] Killed tmux session '{}'", session_name));
-        }
-        Ok(_) | Err(_) => {
-            // Session might not exist, that's fine
-            eprintln!("[delete_environment] No tmux session found for '{}'", session_name);
-        }
-    }
+    let move_error = match move_output {
+        Ok(output) if output.status.success() => return Ok(()),
+        Ok(output) => String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        Err(e) => e.to_string(),
+    };
 
-    // Step 3: Remove the worktree (if it exists)
-    eprintln!("[delete_environment] Checking if worktree '{}' exists...", env_name);
-    match check_worktree_exists(main_repo.clone(), env_name.clone()).await {
-        Ok(Some(_)) => {
-            // Worktree exists, remove it
-            eprintln!("[delete_environment] Removing worktree '{}'...", env_name);
-            match remove_worktree(main_repo, env_name.clone()).await {
-                Ok(_) => {
-                    messages.push(format!("[OK] Removed worktree '{}'", env_name));
-                }
-                Err(e) => {
-                    return Err(format!("Failed to remove worktree: {}", e));
-                }
-            }
-        }
-        Ok(None) => {
-            // Worktree doesn't exist, skip removal
-            eprintln!("[delete_environment] No worktree found for '{}', skipping removal", env_name);
-            messages.push(format!("• No worktree to remove for '{}'", env_name));
-        }
-        Err(e) => {
-            // Error checking worktree, log but don't fail
-            eprintln!("[delete_environment] Warning: Could not check worktree existence: {}", e);
-            messages.push(format!("[WARN] Could not check for worktree"));
+    if rename_branch {
+        warn!(target: "rename_environment", "Worktree move failed, rolling back branch rename");
+        let rollback = silent_command("git")
+            .args(["branch", "-m", new_branch, old_branch])
+            .current_dir(main_repo)
+            .output();
+
+        if !matches!(rollback, Ok(ref output) if output.status.success()) {
+            return Err(format!(
+                "Failed to move worktree: {}. Rolling back branch rename also failed — branch is now '{}'",
+                move_error, new_branch
+            ));
         }
     }
 
-    Ok(messages.join("\n"))
+    Err(format!("Failed to move worktree: {}", move_error))
 }
 
-/// Create a worktree using workmux (includes tmux integration)
-/// Falls back to regular git worktree if tmux is not available
+/// Rename an environment: git branch, worktree directory, tmux session/windows,
+/// and any kanban tickets pointing at the old worktree.
 #[tauri::command]
-pub async fn create_worktree_with_workmux(
-    main_repo: String,
-    name: String,
-    branch_name: Option<String>,
-    base_branch: Option<String>,
-    _background: Option<bool>,
-    custom_window_name: Option<String>,
-) -> Result<WorktreeInfo, String> {
-    // Force lowercase to avoid Docker Compose naming issues
-    let name = name.to_lowercase();
-    let branch_name = branch_name.map(|b| b.to_lowercase());
-    let base_branch = base_branch.map(|b| b.to_lowercase());
-
-    eprintln!("[create_worktree_with_workmux] Creating worktree '{}' with branch '{:?}' from base '{:?}'", name, branch_name, base_branch);
-
-    // Hybrid approach: Create worktree manually for custom control, then register with workmux
-    // Manual creation ensures: custom directory naming, ticket-based window names, lowercase enforcement
-    // Workmux registration adds: dashboard visibility, lifecycle tracking
-    let main_repo_path = PathBuf::from(&main_repo);
-
-    // Calculate worktrees directory: ../worktrees (sibling to project root)
-    let worktrees_dir = main_repo_path.parent()
-        .ok_or("Could not determine parent directory")?
-        .join("worktrees")
-        .to_string_lossy()
-        .to_string();
-
-    eprintln!("[create_worktree_with_workmux] Worktrees directory: {}", worktrees_dir);
+pub async fn rename_environment(main_repo: String, old_name: String, new_name: String) -> Result<String, String> {
+    let old_name = old_name.to_lowercase();
+    let new_name = new_name.trim().to_lowercase();
 
-    // Clone branch_name before moving it into create_worktree so we can use it below
-    let branch_name_for_window = branch_name.clone();
+    if new_name.is_empty() || new_name.contains(['/', '\\', ' ', ':', '.']) {
+        return Err(format!("Invalid environment name '{}'", new_name));
+    }
+    if old_name == new_name {
+        return Err("New name is the same as the current name".to_string());
+    }
 
-    // Create the worktree directly
-    let worktree = create_worktree(main_repo.clone(), worktrees_dir, name.clone(), branch_name, base_branch).await?;
+    let worktrees = list_worktrees(main_repo.clone(), None, None).await?;
+    if worktrees.iter().any(|wt| wt.name == new_name) {
+        return Err(format!("Environment '{}' already exists", new_name));
+    }
+    let worktree = worktrees.iter()
+        .find(|wt| wt.name == old_name)
+        .ok_or_else(|| format!("Environment '{}' not found", old_name))?;
+
+    let old_path = PathBuf::from(&worktree.path);
+    let new_path = old_path.parent()
+        .ok_or("Could not determine worktree parent directory")?
+        .join(&new_name);
+    if new_path.exists() {
+        return Err(format!("Target path already exists: {}", new_path.display()));
+    }
+    let new_path_str = new_path.to_string_lossy().to_string();
 
-    eprintln!("[create_worktree_with_workmux] Worktree created at: {}", worktree.path);
+    let old_branch = worktree.branch.clone();
+    let new_branch = renamed_branch(&old_branch, &old_name, &new_name);
 
-    // New model: one tmux session per environment, named ush-{env}.
-    // custom_window_name is kept in the signature for backwards-compat but is ignored.
-    let _ = custom_window_name;
-    let _ = branch_name_for_window;
+    info!(target: "rename_environment", "'{}' -> '{}' (branch '{}' -> '{}')", old_name, new_name, old_branch, new_branch);
 
-    let session_name = format!("ush-{}", name);
+    rename_branch_and_move_worktree(&main_repo, &worktree.path, &new_path_str, &old_branch, &new_branch)?;
 
-    // Window name: ushadow-{env_name} — workmux uses the worktree directory basename as its
-    // handle, so this must match {window_prefix}{dir_basename} from .workmux.yaml.
-    // Using the branch name here would break `workmux list`, `workmux dashboard`, and merge.
-    let window_name = format!("ushadow-{}", name);
+    let mut messages = Vec::new();
+    if old_branch != new_branch {
+        messages.push(format!("[OK] Renamed branch '{}' to '{}'", old_branch, new_branch));
+    }
+    messages.push(format!("[OK] Moved worktree to {}", new_path_str));
 
-    eprintln!("[create_worktree_with_workmux] Target session '{}', window '{}'", session_name, window_name);
+    // tmux: rename the per-env session and any windows carrying the old window name
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let old_names = tmux_names(&old_name, &settings);
+    let new_names = tmux_names(&new_name, &settings);
 
-    // Check whether the session already exists
-    let session_exists = shell_command(&format!("tmux has-session -t {}", session_name))
+    let session_renamed = shell_command(&format!(
+        "tmux rename-session -t '{}' '{}'",
+        old_names.session, new_names.session
+    ))
         .output()
         .map(|o| o.status.success())
         .unwrap_or(false);
+    if session_renamed {
+        messages.push(format!("[OK] Renamed tmux session '{}' to '{}'", old_names.session, new_names.session));
+    }
 
-    if !session_exists {
-        // S5: brand-new env — create session with the first window
-        eprintln!("[create_worktree_with_workmux] Session '{}' not found, creating it", session_name);
-        let create_result = shell_command(&format!(
-            "tmux new-session -d -s {} -c '{}' -n '{}'",
-            session_name, worktree.path, window_name
-        ))
-        .output();
-
-        match create_result {
-            Ok(output) if output.status.success() => {
-                eprintln!("[create_worktree_with_workmux] ✓ Created session '{}' with window '{}'", session_name, window_name);
-            }
-            Ok(output) => {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                eprintln!("[create_worktree_with_workmux] Warning: Failed to create session: {}", stderr);
-            }
-            Err(e) => {
-                eprintln!("[create_worktree_with_workmux] Warning: Failed to create session: {}", e);
+    if let Ok(output) = shell_command("tmux list-windows -a -F '#{session_name}:#{window_index} #{window_name}'").output() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines() {
+            if let Some((target, window_name)) = line.split_once(' ') {
+                if window_name == old_names.window {
+                    let renamed = shell_command(&format!(
+                        "tmux rename-window -t '{}' '{}'",
+                        target, new_names.window
+                    ))
+                        .output()
+                        .map(|o| o.status.success())
+                        .unwrap_or(false);
+                    if renamed {
+                        messages.push(format!("[OK] Renamed tmux window '{}' ({})", new_names.window, target));
+                    }
+                }
             }
         }
-    } else {
-        // Session already exists — add a window for this branch if not already present
-        let window_exists = shell_command(&format!(
-            "tmux list-windows -t {} -F '#{{window_name}}' 2>/dev/null | grep -Fx '{}'",
-            session_name, window_name
-        ))
+    }
+
+    // Kanban tickets pointing at the old worktree
+    match super::kanban::retarget_environment_tickets(
+        &worktree.path,
+        &new_path_str,
+        &new_name,
+        &old_branch,
+        &new_branch,
+        &new_names.window,
+        &new_names.session,
+    ) {
+        Ok(0) => {}
+        Ok(count) => messages.push(format!("[OK] Updated {} kanban ticket(s)", count)),
+        Err(e) => {
+            warn!(target: "rename_environment", "{}", e);
+            messages.push(format!("[WARN] Could not update kanban tickets: {}", e));
+        }
+    }
+
+    messages.push("[INFO] Containers keep their existing compose project until the environment is restarted".to_string());
+
+    Ok(messages.join("\n"))
+}
+
+/// Compose project name for an environment (matches run.py logic)
+pub(crate) fn compose_project_for_env(env_name: &str) -> String {
+    if env_name == "ushadow" {
+        "ushadow".to_string()
+    } else {
+        format!("ushadow-{}", env_name)
+    }
+}
+
+/// Compose project shared by all environments (databases etc.); its volumes are never pruned
+const INFRA_COMPOSE_PROJECT: &str = "infra";
+
+/// Compose arguments that bring an environment's project down. With `prune`, its volumes and
+/// the images compose built for it are removed too.
+fn compose_down_args(project: &str, prune: bool) -> Result<String, String> {
+    if !prune {
+        return Ok(format!("-p {} down", project));
+    }
+    if project == INFRA_COMPOSE_PROJECT {
+        return Err(format!("Refusing to prune volumes of the shared '{}' project", INFRA_COMPOSE_PROJECT));
+    }
+    Ok(format!("-p {} down --volumes --rmi local", project))
+}
+
+/// One output line per item of a container runtime listing for a compose project
+fn list_project_resources(args: &[&str]) -> Vec<String> {
+    silent_command(container_runtime().binary())
+        .args(args)
         .output()
-        .map(|o| o.status.success() && !String::from_utf8_lossy(&o.stdout).trim().is_empty())
+        .map(|o| String::from_utf8_lossy(&o.stdout).lines().map(|l| l.to_string()).filter(|l| !l.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Volumes created by a compose project
+pub(crate) fn project_volumes(project: &str) -> Vec<String> {
+    list_project_resources(&["volume", "ls", "--filter", &format!("label=com.docker.compose.project={}", project), "--format", "{{.Name}}"])
+}
+
+/// Images compose built for a project (`{project}-{service}`), which `--rmi local` removes
+fn project_built_images(project: &str) -> Vec<String> {
+    let mut images = list_project_resources(&["ps", "-a", "--filter", &format!("label=com.docker.compose.project={}", project), "--format", "{{.Image}}"]);
+    images.retain(|image| image.starts_with(&format!("{}-", project)));
+    images.sort();
+    images.dedup();
+    images
+}
+
+/// "2 volume(s) (a, b)" / "0 image(s)"
+fn describe_resources(names: &[String], kind: &str) -> String {
+    if names.is_empty() {
+        format!("0 {}(s)", kind)
+    } else {
+        format!("{} {}(s) ({})", names.len(), kind, names.join(", "))
+    }
+}
+
+/// Describe what `delete_environment` would do, without doing it
+async fn plan_delete_environment(main_repo: &str, env_name: &str, prune: bool) -> Vec<String> {
+    let mut plan = Vec::new();
+
+    // Step 1: containers
+    let compose_project_name = compose_project_for_env(env_name);
+    let containers: Vec<String> = silent_command(container_runtime().binary())
+        .args([
+            "ps",
+            "-a",
+            "--filter",
+            &format!("label=com.docker.compose.project={}", compose_project_name),
+            "--format",
+            "{{.Names}}",
+        ])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).lines().map(|l| l.to_string()).collect())
+        .unwrap_or_default();
+
+    if containers.is_empty() {
+        plan.push(format!("• No containers found for compose project '{}'", compose_project_name));
+    } else {
+        plan.push(format!(
+            "[PLAN] Bring down compose project '{}' ({} containers: {})",
+            compose_project_name,
+            containers.len(),
+            containers.join(", ")
+        ));
+    }
+    let leftovers: Vec<String> = environment_containers(main_repo, env_name)
+        .into_iter()
+        .filter(|name| !containers.contains(name))
+        .collect();
+    if !leftovers.is_empty() {
+        plan.push(format!(
+            "[PLAN] Remove {} container(s) outside the compose project: {}",
+            leftovers.len(),
+            leftovers.join(", ")
+        ));
+    }
+    if prune {
+        let volumes = project_volumes(&compose_project_name);
+        let images = project_built_images(&compose_project_name);
+        plan.push(format!(
+            "[PLAN] Remove {} volume(s) and {} image(s) of compose project '{}'",
+            volumes.len(),
+            images.len(),
+            compose_project_name
+        ));
+    }
+
+    // Step 2: tmux session
+    let sanitized_env_name = env_name.replace('/', "-").replace('\\', "-");
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let session_name = tmux_names(&sanitized_env_name, &settings).session;
+    let session_exists = shell_command(&format!("tmux has-session -t {}", session_name))
+        .output()
+        .map(|o| o.status.success())
         .unwrap_or(false);
 
-        if !window_exists {
-            eprintln!("[create_worktree_with_workmux] Session exists, adding window '{}'", window_name);
-            let create_result = shell_command(&format!(
-                "tmux new-window -t {} -n '{}' -c '{}'",
-                session_name, window_name, worktree.path
-            ))
-            .output();
+    if session_exists {
+        plan.push(format!("[PLAN] Kill tmux session '{}'", session_name));
+    } else {
+        plan.push(format!("• No tmux session '{}' to kill", session_name));
+    }
 
-            match create_result {
-                Ok(output) if output.status.success() => {
-                    eprintln!("[create_worktree_with_workmux] ✓ Added window '{}' to session '{}'", window_name, session_name);
+    // Step 3: worktree + branch
+    match list_worktrees(main_repo.to_string(), None, None).await {
+        Ok(worktrees) => match worktrees.iter().find(|wt| wt.name == env_name) {
+            Some(wt) => {
+                plan.push(format!("[PLAN] Remove worktree at {}", wt.path));
+                let changed = count_changed_files(&wt.path);
+                if changed > 0 {
+                    plan.push(format!("[WARN] Worktree has {} uncommitted change(s) that will be lost", changed));
                 }
-                Ok(output) => {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    eprintln!("[create_worktree_with_workmux] Warning: Failed to add window: {}", stderr);
+                if !wt.branch.is_empty() {
+                    plan.push(format!("[PLAN] Permanently delete branch '{}'", wt.branch));
+                }
+            }
+            None => plan.push(format!("• No worktree to remove for '{}'", env_name)),
+        },
+        Err(e) => plan.push(format!("[WARN] Could not check for worktree: {}", e)),
+    }
+
+    plan
+}
+
+/// Delete an environment completely - stop containers, remove worktree, close tmux
+///
+/// With `dry_run`, returns the plan (what would be stopped, killed and removed) without executing it.
+/// With `prune`, the environment's volumes and built images are removed as well.
+#[tauri::command]
+pub async fn delete_environment(
+    main_repo: String,
+    env_name: String,
+    dry_run: Option<bool>,
+    prune: Option<bool>,
+) -> Result<String, String> {
+    let env_name = env_name.to_lowercase();
+    let prune = prune.unwrap_or(false);
+
+    if dry_run.unwrap_or(false) {
+        info!(target: "delete_environment", "Dry run for '{}'", env_name);
+        return Ok(plan_delete_environment(&main_repo, &env_name, prune).await.join("\n"));
+    }
+
+    info!(target: "delete_environment", "Deleting environment '{}'", env_name);
+
+    let mut messages = Vec::new();
+
+    // Step 1: Stop containers (best effort - don't fail if they're already stopped)
+    info!(target: "delete_environment", "Stopping containers for '{}'...", env_name);
+
+    let compose_project_name = compose_project_for_env(&env_name);
+    let down_args = compose_down_args(&compose_project_name, prune)?;
+
+    // Listed before `down` removes them, to report what was reclaimed
+    let (volumes, images) = if prune {
+        (project_volumes(&compose_project_name), project_built_images(&compose_project_name))
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let stop_result = shell_command(&container_runtime().compose_command(&down_args))
+        .output();
+
+    match stop_result {
+        Ok(output) if output.status.success() => {
+            messages.push(format!("[OK] Stopped containers for '{}'", env_name));
+            if prune {
+                let remaining_volumes = project_volumes(&compose_project_name);
+                let removed_volumes: Vec<String> = volumes.into_iter().filter(|v| !remaining_volumes.contains(v)).collect();
+                messages.push(format!(
+                    "[OK] Removed {} and {}",
+                    describe_resources(&removed_volumes, "volume"),
+                    describe_resources(&images, "image")
+                ));
+            }
+        }
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if !stderr.contains("No such file") && !stderr.to_lowercase().contains("not found") {
+                warn!(target: "delete_environment", "Failed to stop containers: {}", stderr);
+                messages.push(format!("[WARN] Could not stop containers (may already be stopped)"));
+            }
+        }
+        Err(e) => {
+            warn!(target: "delete_environment", "Failed to run docker compose down: {}", e);
+            messages.push(format!("[WARN] Could not stop containers (may already be stopped)"));
+        }
+    }
+
+    // Own containers `down` didn't reach (e.g. created outside the compose project)
+    let leftovers = environment_containers(&main_repo, &env_name);
+    if !leftovers.is_empty() {
+        info!(target: "delete_environment", "Removing leftover containers: {:?}", leftovers);
+        match silent_command(container_runtime().binary()).args(["rm", "-f"]).args(&leftovers).output() {
+            Ok(output) if output.status.success() => {
+                messages.push(format!("[OK] Removed {} leftover container(s): {}", leftovers.len(), leftovers.join(", ")));
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                warn!(target: "delete_environment", "Failed to remove leftover containers: {}", stderr);
+                messages.push(format!("[WARN] Could not remove containers: {}", leftovers.join(", ")));
+            }
+            Err(e) => {
+                warn!(target: "delete_environment", "Failed to remove leftover containers: {}", e);
+                messages.push(format!("[WARN] Could not remove containers: {}", leftovers.join(", ")));
+            }
+        }
+    }
+
+    // Step 2: Kill the per-environment tmux session ({session_prefix}{env}) if it exists
+    let sanitized_env_name = env_name.replace('/', "-").replace('\\', "-");
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let session_name = tmux_names(&sanitized_env_name, &settings).session;
+    info!(target: "delete_environment", "Killing tmux session '{}'...", session_name);
+    let close_result = shell_command(&format!("tmux kill-session -t {}", session_name))
+        .output();
+
+    match close_result {
+        Ok(output) if output.status.success() => {
+            messages.push(format!("[OK] Killed tmux session '{}'", session_name));
+        }
+        Ok(_) | Err(_) => {
+            // Session might not exist, that's fine
+            info!(target: "delete_environment", "No tmux session found for '{}'", session_name);
+        }
+    }
+
+    // Step 3: Remove the worktree (if it exists)
+    info!(target: "delete_environment", "Checking if worktree '{}' exists...", env_name);
+    match check_worktree_exists(main_repo.clone(), env_name.clone()).await {
+        Ok(Some(_)) => {
+            // Worktree exists, remove it
+            info!(target: "delete_environment", "Removing worktree '{}'...", env_name);
+            match remove_worktree(main_repo, env_name.clone()).await {
+                Ok(_) => {
+                    messages.push(format!("[OK] Removed worktree '{}'", env_name));
                 }
                 Err(e) => {
-                    eprintln!("[create_worktree_with_workmux] Warning: Failed to add window: {}", e);
+                    return Err(format!("Failed to remove worktree: {}", e));
+                }
+            }
+        }
+        Ok(None) => {
+            // Worktree doesn't exist, skip removal
+            info!(target: "delete_environment", "No worktree found for '{}', skipping removal", env_name);
+            messages.push(format!("• No worktree to remove for '{}'", env_name));
+        }
+        Err(e) => {
+            // Error checking worktree, log but don't fail
+            warn!(target: "delete_environment", "Could not check worktree existence: {}", e);
+            messages.push(format!("[WARN] Could not check for worktree"));
+        }
+    }
+
+    Ok(messages.join("\n"))
+}
+
+/// Create a worktree using workmux (includes tmux integration)
+/// Falls back to regular git worktree if tmux is not available
+#[tauri::command]
+pub async fn create_worktree_with_workmux(
+    main_repo: String,
+    name: String,
+    branch_name: Option<String>,
+    base_branch: Option<String>,
+    _background: Option<bool>,
+    custom_window_name: Option<String>,
+    base_ref: Option<String>,
+) -> Result<WorktreeInfo, String> {
+    // Force lowercase to avoid Docker Compose naming issues
+    let name = name.to_lowercase();
+    let branch_name = branch_name.map(|b| b.to_lowercase());
+    let base_branch = base_branch.map(|b| b.to_lowercase());
+
+    // Fail before creating the worktree rather than leaving it without a session
+    let mux = multiplexer();
+    mux.check_installed()?;
+
+    info!(target: "create_worktree_with_workmux", "Creating worktree '{}' with branch '{:?}' from base '{:?}'", name, branch_name, base_branch);
+
+    // Hybrid approach: Create worktree manually for custom control, then register with workmux
+    // Manual creation ensures: custom directory naming, ticket-based window names, lowercase enforcement
+    // Workmux registration adds: dashboard visibility, lifecycle tracking
+    let main_repo_path = PathBuf::from(&main_repo);
+
+    // Calculate worktrees directory: ../worktrees (sibling to project root)
+    let worktrees_dir = main_repo_path.parent()
+        .ok_or("Could not determine parent directory")?
+        .join("worktrees")
+        .to_string_lossy()
+        .to_string();
+
+    info!(target: "create_worktree_with_workmux", "Worktrees directory: {}", worktrees_dir);
+
+    // Clone branch_name before moving it into create_worktree so we can use it below
+    let branch_name_for_window = branch_name.clone();
+
+    // Create the worktree directly
+    let worktree = create_worktree(main_repo.clone(), worktrees_dir, name.clone(), branch_name, base_branch, base_ref, None).await?;
+
+    info!(target: "create_worktree_with_workmux", "Worktree created at: {}", worktree.path);
+
+    // New model: one tmux session per environment, named {session_prefix}{env}.
+    // custom_window_name is kept in the signature for backwards-compat but is ignored.
+    let _ = custom_window_name;
+    let _ = branch_name_for_window;
+
+    // Window name: {window_prefix}{env_name} — workmux uses the worktree directory basename as its
+    // handle, so this must match {window_prefix}{dir_basename} from .workmux.yaml.
+    // Using the branch name here would break `workmux list`, `workmux dashboard`, and merge.
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let names = tmux_names(&name, &settings);
+    let session_name = names.session;
+    let window_name = names.window;
+
+    info!(target: "create_worktree_with_workmux", "Target session '{}', window '{}'", session_name, window_name);
+
+    if !mux.session_exists(&session_name) {
+        // S5: brand-new env — create session with the first window
+        info!(target: "create_worktree_with_workmux", "Session '{}' not found, creating it", session_name);
+        match mux.create_session(&session_name, &window_name, &worktree.path) {
+            Ok(()) => {
+                info!(target: "create_worktree_with_workmux", "✓ Created session '{}' with window '{}'", session_name, window_name);
+            }
+            Err(e) => {
+                warn!(target: "create_worktree_with_workmux", "Failed to create session: {}", e);
+            }
+        }
+    } else {
+        // Session already exists — add a window for this branch if not already present
+        if !mux.window_exists(&session_name, &window_name) {
+            info!(target: "create_worktree_with_workmux", "Session exists, adding window '{}'", window_name);
+            match mux.new_window(&session_name, &window_name, &worktree.path) {
+                Ok(()) => {
+                    info!(target: "create_worktree_with_workmux", "✓ Added window '{}' to session '{}'", window_name, session_name);
+                }
+                Err(e) => {
+                    warn!(target: "create_worktree_with_workmux", "Failed to add window: {}", e);
                 }
             }
         } else {
-            eprintln!("[create_worktree_with_workmux] ✓ Window '{}' already exists in session '{}'", window_name, session_name);
+            info!(target: "create_worktree_with_workmux", "✓ Window '{}' already exists in session '{}'", window_name, session_name);
         }
     }
 
@@ -1103,7 +1885,7 @@ pub async fn merge_worktree_with_rebase(
     // Force lowercase to match worktree naming
     let name = name.to_lowercase();
 
-    eprintln!("[merge_worktree_with_rebase] Merging worktree '{}' (rebase: {}, keep: {})", name, use_rebase, keep_worktree);
+    info!(target: "merge_worktree_with_rebase", "Merging worktree '{}' (rebase: {}, keep: {})", name, use_rebase, keep_worktree);
 
     // Build workmux merge command
     let mut cmd_parts = vec!["workmux", "merge"];
@@ -1119,7 +1901,7 @@ pub async fn merge_worktree_with_rebase(
     cmd_parts.push(&name);
 
     let workmux_cmd = cmd_parts.join(" ");
-    eprintln!("[merge_worktree_with_rebase] Running: {}", workmux_cmd);
+    info!(target: "merge_worktree_with_rebase", "Running: {}", workmux_cmd);
 
     // Execute workmux merge
     let output = shell_command(&workmux_cmd)
@@ -1135,41 +1917,46 @@ pub async fn merge_worktree_with_rebase(
     }
 
     // Kill the per-env session — workmux merge closes the window but leaves the session orphaned.
-    let session_name = format!("ush-{}", name);
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let session_name = tmux_names(&name, &settings).session;
     let kill_result = shell_command(&format!("tmux kill-session -t {}", session_name))
         .output();
     match kill_result {
         Ok(output) if output.status.success() => {
-            eprintln!("[merge_worktree_with_rebase] ✓ Killed tmux session '{}'", session_name);
+            info!(target: "merge_worktree_with_rebase", "✓ Killed tmux session '{}'", session_name);
         }
         _ => {
-            eprintln!("[merge_worktree_with_rebase] Note: session '{}' not found (already gone)", session_name);
+            info!(target: "merge_worktree_with_rebase", "Note: session '{}' not found (already gone)", session_name);
         }
     }
 
     Ok(format!("Merged and cleaned up worktree '{}'\n{}", name, stdout))
 }
 
-/// List active tmux sessions to monitor agent status
-#[tauri::command]
-pub async fn list_tmux_sessions() -> Result<Vec<String>, String> {
-    let output = shell_command("tmux list-sessions -F '#{session_name}'")
-        .output()
-        .map_err(|e| format!("Failed to list tmux sessions: {}", e))?;
+/// Error returned by tmux-dependent commands when the tmux binary can't be run
+pub(crate) const TMUX_NOT_INSTALLED: &str = "tmux is not installed; install it via the Prerequisites panel";
 
-    if !output.status.success() {
-        // tmux returns error if no sessions exist - this is ok
-        return Ok(Vec::new());
+/// Run `<binary> -V`, returning the reported version
+fn probe_tmux(binary: &str) -> Result<String, String> {
+    match shell_command(&format!("{} -V", binary)).output() {
+        Ok(output) if output.status.success() => {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        }
+        _ => Err(TMUX_NOT_INSTALLED.to_string()),
     }
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let sessions: Vec<String> = stdout
-        .lines()
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
+/// Check tmux is installed, so a missing binary isn't mistaken for "no server running"
+pub(crate) fn check_tmux_installed() -> Result<String, String> {
+    probe_tmux("tmux")
+}
 
-    Ok(sessions)
+/// List active tmux sessions to monitor agent status
+#[tauri::command]
+pub async fn list_tmux_sessions() -> Result<Vec<String>, String> {
+    let mux = multiplexer();
+    mux.check_installed()?;
+    mux.list_sessions()
 }
 
 /// Get tmux window status for a specific worktree
@@ -1193,6 +1980,8 @@ pub async fn get_tmux_window_status(window_name: String) -> Result<Option<String
 /// Ensure tmux server is running and workmux session exists
 #[tauri::command]
 pub async fn ensure_tmux_running() -> Result<String, String> {
+    check_tmux_installed()?;
+
     // Check if tmux is running
     let tmux_check = shell_command("tmux list-sessions")
         .output();
@@ -1201,7 +1990,7 @@ pub async fn ensure_tmux_running() -> Result<String, String> {
         return Ok("Tmux server already running".to_string());
     }
 
-    eprintln!("[ensure_tmux_running] Starting tmux server with workmux session");
+    info!(target: "ensure_tmux_running", "Starting tmux server with workmux session");
 
     // Start a new detached tmux session
     let start_tmux = shell_command("tmux new-session -d -s workmux")
@@ -1219,6 +2008,8 @@ pub async fn ensure_tmux_running() -> Result<String, String> {
 /// Get all tmux sessions and windows info (legacy string format)
 #[tauri::command]
 pub async fn get_tmux_info() -> Result<String, String> {
+    check_tmux_installed()?;
+
     // Check if tmux is running
     let sessions_output = shell_command("tmux list-sessions")
         .output();
@@ -1260,7 +2051,8 @@ pub async fn attach_tmux_to_worktree(
     env_name: String,
     window_name_override: Option<String>
 ) -> Result<String, String> {
-    eprintln!("[attach_tmux_to_worktree] Attaching to worktree at: {}", worktree_path);
+    info!(target: "attach_tmux_to_worktree", "Attaching to worktree at: {}", worktree_path);
+    check_tmux_installed()?;
 
     // Extract worktree name from path for workmux
     let worktree_name = std::path::Path::new(&worktree_path)
@@ -1269,7 +2061,7 @@ pub async fn attach_tmux_to_worktree(
         .to_str()
         .ok_or("Path contains invalid UTF-8")?;
 
-    eprintln!("[attach_tmux_to_worktree] Using workmux to open worktree: {}", worktree_name);
+    info!(target: "attach_tmux_to_worktree", "Using workmux to open worktree: {}", worktree_name);
 
     // Use workmux open which handles everything:
     // - Creates workmux session if needed
@@ -1277,17 +2069,17 @@ pub async fn attach_tmux_to_worktree(
     // - Reuses window if exists
     // - Sets up working directory correctly
     // - Registers in dashboard
-    let workmux_cmd = format!("cd '{}' && workmux open {}", worktree_path, worktree_name);
+    let workmux_cmd = format!("cd {} && workmux open {}", shell_quote(&worktree_path), shell_quote(worktree_name));
 
     let open_result = shell_command(&workmux_cmd).output();
 
     match open_result {
         Ok(output) if output.status.success() => {
-            eprintln!("[attach_tmux_to_worktree] ✓ Workmux window opened/reused");
+            info!(target: "attach_tmux_to_worktree", "✓ Workmux window opened/reused");
         }
         Ok(output) => {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            eprintln!("[attach_tmux_to_worktree] Workmux open warning: {}", stderr);
+            warn!(target: "attach_tmux_to_worktree", "Workmux open warning: {}", stderr);
         }
         Err(e) => {
             return Err(format!("Failed to open workmux window: {}", e));
@@ -1296,7 +2088,8 @@ pub async fn attach_tmux_to_worktree(
 
     // workmux open manages the tmux side. Now we need to make it visible in iTerm.
     // workmux open always uses the "workmux" session.
-    let window_name_derived = format!("ushadow-{}", env_name.to_lowercase());
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let window_name_derived = tmux_names(&env_name.to_lowercase(), &settings).window;
 
     // Pre-select the window so the terminal attach lands on the right pane.
     let _ = shell_command(&format!(
@@ -1357,7 +2150,7 @@ end tell"#,
         };
 
         if found_window {
-            eprintln!("[attach_tmux_to_worktree] S2: Focused existing iTerm window for '{}'", env_name);
+            info!(target: "attach_tmux_to_worktree", "S2: Focused existing iTerm window for '{}'", env_name);
             let _ = Command::new("osascript")
                 .arg("-e")
                 .arg(r#"tell application "iTerm" to activate"#)
@@ -1365,14 +2158,14 @@ end tell"#,
         } else {
             // Open a new iTerm window attached to the workmux session.
             // The session-select above already moved focus to the right window.
-            eprintln!("[attach_tmux_to_worktree] S3: Opening new iTerm window for '{}'", env_name);
+            info!(target: "attach_tmux_to_worktree", "S3: Opening new iTerm window for '{}'", env_name);
             let temp_script = format!("/tmp/ushadow_attach_{}.sh", env_name.replace('/', "_"));
             let script_content = format!(
-                "#!/bin/bash\nprintf '\\033]0;{}\\007'\nexec tmux attach-session -t workmux\n",
-                env_name
+                "#!/bin/bash\nprintf '\\033]0;%s\\007' {}\nexec tmux attach-session -t workmux\n",
+                posix_quote(&env_name)
             );
             if fs::write(&temp_script, &script_content).is_ok() {
-                let _ = shell_command(&format!("chmod +x {}", temp_script)).output();
+                let _ = shell_command(&format!("chmod +x {}", shell_quote(&temp_script))).output();
                 let applescript = format!(
                     r#"tell application "iTerm"
     activate
@@ -1381,7 +2174,7 @@ end tell"#,
         write text "{} && exit"
     end tell
 end tell"#,
-                    temp_script
+                    posix_quote(&temp_script)
                 );
                 let _ = Command::new("osascript")
                     .arg("-e")
@@ -1390,7 +2183,7 @@ end tell"#,
             }
         }
 
-        eprintln!("[attach_tmux_to_worktree] iTerm opened/focused for '{}'", env_name);
+        info!(target: "attach_tmux_to_worktree", "iTerm opened/focused for '{}'", env_name);
     }
 
     #[cfg(not(target_os = "macos"))]
@@ -1406,60 +2199,24 @@ end tell"#,
 pub async fn get_environment_tmux_status(env_name: String) -> Result<crate::models::TmuxStatus, String> {
     use crate::models::{TmuxStatus, TmuxActivityStatus};
 
-    // Check if tmux is running
-    let tmux_check = shell_command("tmux list-sessions")
-        .output();
-
-    if !matches!(tmux_check, Ok(output) if output.status.success()) {
-        // tmux not running
-        return Ok(TmuxStatus {
-            exists: false,
-            window_name: None,
-            current_command: None,
-            activity_status: TmuxActivityStatus::Unknown,
-        });
-    }
-
-    // Workmux prefixes windows with "ushadow-"
-    // Check if the env_name already has the prefix to avoid double-prefixing
-    let window_name = if env_name.starts_with("ushadow-") {
-        env_name.clone()
-    } else {
-        format!("ushadow-{}", env_name)
+    // Workmux prefixes windows with the configured window prefix.
+    // Strip it first if env_name already carries it to avoid double-prefixing.
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let window_name = tmux_names(&env_name, &settings).window;
+
+    // Find the window in any session (none when the multiplexer isn't running)
+    let current_command = match multiplexer_for(settings.multiplexer).find_window_command(&window_name) {
+        Some(command) => command,
+        None => {
+            return Ok(TmuxStatus {
+                exists: false,
+                window_name: None,
+                current_command: None,
+                activity_status: TmuxActivityStatus::Unknown,
+            });
+        }
     };
 
-    // Get window info: name, current command, and pane tty
-    let output = shell_command(&format!(
-        "tmux list-panes -a -F '#{{window_name}} #{{pane_current_command}} #{{pane_tty}}' | grep '^{}'",
-        window_name
-    ))
-        .output()
-        .map_err(|e| format!("Failed to check tmux window: {}", e))?;
-
-    if !output.status.success() {
-        // Window doesn't exist
-        return Ok(TmuxStatus {
-            exists: false,
-            window_name: None,
-            current_command: None,
-            activity_status: TmuxActivityStatus::Unknown,
-        });
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let parts: Vec<&str> = stdout.trim().split_whitespace().collect();
-
-    if parts.len() < 2 {
-        return Ok(TmuxStatus {
-            exists: false,
-            window_name: None,
-            current_command: None,
-            activity_status: TmuxActivityStatus::Unknown,
-        });
-    }
-
-    let current_command = parts[1].to_string();
-
     // Determine activity status based on current command
     let activity_status = match current_command.as_str() {
         "bash" | "zsh" | "sh" | "fish" => TmuxActivityStatus::Waiting,
@@ -1478,14 +2235,81 @@ pub async fn get_environment_tmux_status(env_name: String) -> Result<crate::mode
     Ok(TmuxStatus {
         exists: true,
         window_name: Some(window_name),
-        current_command: Some(current_command),
+        // Empty when the multiplexer can't report pane commands (zellij)
+        current_command: Some(current_command).filter(|c| !c.is_empty()),
         activity_status,
     })
 }
 
+/// `list-panes -a` format: tab-separated, with the path last since it's the only free-form field
+const TMUX_LAYOUT_FORMAT: &str = "#{session_name}\t#{window_index}\t#{window_name}\t#{window_active}\t#{pane_index}\t#{pane_id}\t#{pane_current_command}\t#{pane_tty}\t#{pane_active}\t#{pane_current_path}";
+
+/// Build the session → window → pane tree from `list-panes -a` output, keeping tmux's order
+fn parse_tmux_layout(output: &str) -> Vec<TmuxLayoutNode> {
+    let mut sessions: Vec<TmuxLayoutNode> = Vec::new();
+
+    for line in output.lines() {
+        let fields: Vec<&str> = line.splitn(10, '\t').collect();
+        if fields.len() < 10 {
+            continue;
+        }
+
+        let session = match sessions.iter_mut().position(|s| s.session == fields[0]) {
+            Some(i) => &mut sessions[i],
+            None => {
+                sessions.push(TmuxLayoutNode { session: fields[0].to_string(), windows: Vec::new() });
+                sessions.last_mut().unwrap()
+            }
+        };
+
+        let window = match session.windows.iter_mut().position(|w| w.index == fields[1]) {
+            Some(i) => &mut session.windows[i],
+            None => {
+                session.windows.push(TmuxLayoutWindow {
+                    index: fields[1].to_string(),
+                    name: fields[2].to_string(),
+                    active: fields[3] == "1",
+                    panes: Vec::new(),
+                });
+                session.windows.last_mut().unwrap()
+            }
+        };
+
+        window.panes.push(TmuxLayoutPane {
+            index: fields[4].to_string(),
+            pane_id: fields[5].to_string(),
+            current_command: fields[6].to_string(),
+            tty: fields[7].to_string(),
+            active: fields[8] == "1",
+            current_path: fields[9].to_string(),
+        });
+    }
+
+    sessions
+}
+
+/// Full tmux topology (sessions → windows → panes) the launcher is operating on
+#[tauri::command]
+pub async fn inspect_tmux_layout() -> Result<Vec<TmuxLayoutNode>, String> {
+    check_tmux_installed()?;
+
+    let output = shell_command(&format!("tmux list-panes -a -F '{}'", TMUX_LAYOUT_FORMAT))
+        .output()
+        .map_err(|e| format!("Failed to list tmux panes: {}", e))?;
+
+    if !output.status.success() {
+        // No tmux server running
+        return Ok(vec![]);
+    }
+
+    Ok(parse_tmux_layout(&String::from_utf8_lossy(&output.stdout)))
+}
+
 /// Get all tmux sessions with their windows
 #[tauri::command]
 pub async fn get_tmux_sessions() -> Result<Vec<TmuxSessionInfo>, String> {
+    check_tmux_installed()?;
+
     // Check if tmux is running
     let check = shell_command("tmux list-sessions")
         .output()
@@ -1545,19 +2369,61 @@ pub async fn get_tmux_sessions() -> Result<Vec<TmuxSessionInfo>, String> {
     Ok(sessions)
 }
 
-/// Kill a specific tmux window
+/// Prefix of the error `kill_tmux_window` returns when the window is busy and `force` wasn't set
+pub const AGENT_RUNNING: &str = "Agent running";
+
+/// Whether a pane's foreground command is an idle shell
+fn is_shell_command(command: &str) -> bool {
+    matches!(command, "zsh" | "bash" | "sh" | "fish" | "")
+}
+
+/// Split a fully-qualified `session:window` tmux target
+fn parse_window_target(target: &str) -> Result<(&str, &str), String> {
+    match target.split_once(':') {
+        Some((session, window)) if !session.is_empty() && !window.is_empty() => Ok((session, window)),
+        _ => Err(format!("Invalid tmux target '{}': expected 'session:window'", target)),
+    }
+}
+
+/// Kill a tmux window given as `session:window`. Refuses with an `AGENT_RUNNING` error when
+/// an agent (or any non-shell command) is running in it, unless `force` is set.
 #[tauri::command]
-pub async fn kill_tmux_window(window_name: String) -> Result<String, String> {
-    let output = shell_command(&format!("tmux kill-window -t {}", window_name))
+pub async fn kill_tmux_window(target: String, force: Option<bool>) -> Result<String, String> {
+    parse_window_target(&target)?;
+
+    let current_command = silent_command("tmux")
+        .args(["display-message", "-t", &target, "-p", "#{pane_current_command}"])
+        .output()
+        .map_err(|e| format!("Failed to inspect window: {}", e))?;
+    if !current_command.status.success() {
+        let stderr = String::from_utf8_lossy(&current_command.stderr);
+        return Err(format!("Tmux window '{}' not found: {}", target, stderr.trim()));
+    }
+    let current_command = String::from_utf8_lossy(&current_command.stdout).trim().to_string();
+
+    if !force.unwrap_or(false) {
+        let settings = load_launcher_settings().await.unwrap_or_default();
+        let profile = super::settings::agent_profile(&settings);
+        if !is_shell_command(&current_command) || agent_running_in_pane(&target, &current_command, &profile) {
+            return Err(format!(
+                "{}: '{}' is running in tmux window '{}'; use force to kill it anyway",
+                AGENT_RUNNING, current_command, target
+            ));
+        }
+    }
+
+    let output = silent_command("tmux")
+        .args(["kill-window", "-t", &target])
         .output()
         .map_err(|e| format!("Failed to kill window: {}", e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to kill window '{}': {}", window_name, stderr));
+        return Err(format!("Failed to kill window '{}': {}", target, stderr));
     }
 
-    Ok(format!("Killed tmux window '{}'", window_name))
+    info!(target: "kill_tmux_window", "Killed tmux window '{}' (was running '{}')", target, current_command);
+    Ok(format!("Killed tmux window '{}'", target))
 }
 
 /// Kill the entire tmux server (all sessions and windows)
@@ -1589,6 +2455,9 @@ pub async fn open_tmux_in_terminal(
 ) -> Result<String, String> {
     use std::fs;
 
+    let mux = multiplexer();
+    mux.ensure_running()?;
+
     // Derive the environment name from the parameter, or fall back to the last
     // component of the worktree path (e.g. "beige" from ".../worktrees/ushadow/beige").
     let env_name: String = environment_name
@@ -1602,75 +2471,50 @@ pub async fn open_tmux_in_terminal(
                 .to_string()
         });
 
+    record_environment_use(&env_name);
+
     // Sanitize window_name for tmux (slashes not allowed in window names)
     let sanitized_window = window_name.replace('/', "-").replace('\\', "-");
 
-    let session_name = format!("ush-{}", env_name);
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let session_name = tmux_names(&env_name, &settings).session;
 
-    eprintln!(
-        "[open_tmux_in_terminal] env='{}' session='{}' window='{}' path='{}'",
+    info!(
+        target: "open_tmux_in_terminal",
+        "env='{}' session='{}' window='{}' path='{}'",
         env_name, session_name, sanitized_window, worktree_path
     );
 
-    // ── Ensure the tmux session exists; manage windows only if a branch is given ──
-    let session_exists = shell_command(&format!("tmux has-session -t {}", session_name))
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false);
-
-    if !session_exists {
+    // ── Ensure the session exists; manage windows only if a branch is given ──
+    if !mux.session_exists(&session_name) {
         // S1: create the session.  If we have a target window name use it; otherwise
         // create a default window named after the env.
         let first_window = if sanitized_window.is_empty() { env_name.clone() } else { sanitized_window.clone() };
-        eprintln!("[open_tmux_in_terminal] Session '{}' not found, creating with window '{}' (S1)", session_name, first_window);
-        match shell_command(&format!(
-            "tmux new-session -d -s {} -c '{}' -n '{}'",
-            session_name, worktree_path, first_window
-        ))
-        .output()
-        {
-            Ok(output) if output.status.success() => {
-                eprintln!("[open_tmux_in_terminal] ✓ Created session '{}'", session_name);
-            }
-            Ok(output) => {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                eprintln!("[open_tmux_in_terminal] Warning: Failed to create session: {}", stderr);
+        info!(target: "open_tmux_in_terminal", "Session '{}' not found, creating with window '{}' (S1)", session_name, first_window);
+        match mux.create_session(&session_name, &first_window, &worktree_path) {
+            Ok(()) => {
+                info!(target: "open_tmux_in_terminal", "✓ Created session '{}'", session_name);
             }
             Err(e) => {
-                eprintln!("[open_tmux_in_terminal] Warning: Failed to create session: {}", e);
+                warn!(target: "open_tmux_in_terminal", "Failed to create session: {}", e);
             }
         }
     } else if !sanitized_window.is_empty() {
         // Session exists and a specific window was requested — ensure it exists and select it
-        let window_exists = shell_command(&format!(
-            "tmux list-windows -t {} -F '#{{window_name}}' 2>/dev/null | grep -Fx '{}'",
-            session_name, sanitized_window
-        ))
-        .output()
-        .map(|o| o.status.success() && !String::from_utf8_lossy(&o.stdout).trim().is_empty())
-        .unwrap_or(false);
-
-        if !window_exists {
-            eprintln!(
-                "[open_tmux_in_terminal] Window '{}' missing from session '{}', creating it",
+        if !mux.window_exists(&session_name, &sanitized_window) {
+            info!(
+                target: "open_tmux_in_terminal",
+                "Window '{}' missing from session '{}', creating it",
                 sanitized_window, session_name
             );
-            let _ = shell_command(&format!(
-                "tmux new-window -t {} -n '{}' -c '{}'",
-                session_name, sanitized_window, worktree_path
-            ))
-            .output();
+            let _ = mux.new_window(&session_name, &sanitized_window, &worktree_path);
         }
 
         // Pre-select the window so attach lands on it
-        let _ = shell_command(&format!(
-            "tmux select-window -t {}:'{}'",
-            session_name, sanitized_window
-        ))
-        .output();
-        eprintln!("[open_tmux_in_terminal] Selected window '{}' in session '{}'", sanitized_window, session_name);
+        let _ = mux.select_window(&session_name, &sanitized_window);
+        info!(target: "open_tmux_in_terminal", "Selected window '{}' in session '{}'", sanitized_window, session_name);
     } else {
-        eprintln!("[open_tmux_in_terminal] Session exists, no specific window requested — will attach to current window (S2/S3)");
+        info!(target: "open_tmux_in_terminal", "Session exists, no specific window requested — will attach to current window (S2/S3)");
     }
 
     // ── Spawn agent start/resume in background (only when a specific window is known) ──
@@ -1683,22 +2527,29 @@ pub async fn open_tmux_in_terminal(
         });
     }
 
+    let attach_argv = mux.attach_argv(&session_name);
+
+    // ── A configured terminal wins; auto-detection below is the fallback ──
+    if let Some(terminal) = settings.terminal_emulator.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        match launch_terminal(terminal, &env_name, &attach_argv) {
+            Ok(()) => {
+                info!(target: "open_tmux_in_terminal", "Opened {} for session {}", terminal, session_name);
+                return Ok(format!("Opened {} for session '{}'", terminal, session_name));
+            }
+            Err(e) => {
+                warn!(target: "open_tmux_in_terminal", "{}, falling back to auto-detection", e);
+            }
+        }
+    }
+
     // ── macOS: S2 = focus existing iTerm window; S3/S1 = open new one ──────
     #[cfg(target_os = "macos")]
     {
-        // Map env names to RGB colors (0-255)
-        let (r, g, b) = match env_name.as_str() {
-            "gold" | "yellow" => (255, 215, 0),
-            "green"           => (0, 200, 80),
-            "red"             => (255, 60, 60),
-            "purple" | "pink" => (200, 0, 255),
-            "orange"          => (255, 165, 0),
-            "blue"            => (50, 120, 255),
-            "cyan"            => (0, 220, 220),
-            "brown"           => (165, 42, 42),
-            "beige"           => (220, 200, 160),
-            _                 => (128, 128, 128),
-        };
+        let attach_cmd = shell_join(&attach_argv);
+
+        // iTerm tab color from the shared palette (grey for unnamed colors)
+        let (color_name, _) = get_colors_for_name(&env_name);
+        let (r, g, b) = color_hex(&color_name).and_then(hex_to_rgb).unwrap_or((128, 128, 128));
 
         // S2: Check for an existing iTerm window whose title contains the env name.
         // The attach script sets the window title to the env_name via \033]0;{env_name}\007.
@@ -1741,7 +2592,7 @@ end tell"#,
                 .unwrap_or(false);
 
             if found_window {
-                eprintln!("[open_tmux_in_terminal] S2: Found existing iTerm window for '{}', focused it", env_name);
+                info!(target: "open_tmux_in_terminal", "S2: Found existing iTerm window for '{}', focused it", env_name);
                 // Activate iTerm to bring it to front
                 let _ = Command::new("osascript")
                     .arg("-e")
@@ -1750,17 +2601,17 @@ end tell"#,
                 return Ok(format!("Focused existing iTerm window for '{}'", env_name));
             }
 
-            eprintln!("[open_tmux_in_terminal] S1/S3: No iTerm window for '{}', opening new one", env_name);
+            info!(target: "open_tmux_in_terminal", "S1/S3: No iTerm window for '{}', opening new one", env_name);
 
             // Write a temp attach script that sets the iTerm title and attaches to the session
             let temp_script = format!("/tmp/ushadow_iterm_{}.sh", env_name.replace("/", "_"));
             let script_content = format!(
-                "#!/bin/bash\nprintf '\\033]0;{}\\007\\033]6;1;bg;red;brightness;{}\\007\\033]6;1;bg;green;brightness;{}\\007\\033]6;1;bg;blue;brightness;{}\\007'\nexec tmux attach-session -t {}\n",
-                env_name, r, g, b, session_name
+                "#!/bin/bash\nprintf '\\033]0;{}\\007\\033]6;1;bg;red;brightness;{}\\007\\033]6;1;bg;green;brightness;{}\\007\\033]6;1;bg;blue;brightness;{}\\007'\nexec {}\n",
+                env_name, r, g, b, attach_cmd
             );
             fs::write(&temp_script, &script_content)
                 .map_err(|e| format!("Failed to write attach script: {}", e))?;
-            shell_command(&format!("chmod +x {}", temp_script))
+            shell_command(&format!("chmod +x {}", shell_quote(&temp_script)))
                 .output()
                 .map_err(|e| format!("Failed to chmod attach script: {}", e))?;
 
@@ -1772,7 +2623,7 @@ end tell"#,
         write text "{} && exit"
     end tell
 end tell"#,
-                temp_script
+                posix_quote(&temp_script)
             );
 
             let output = Command::new("osascript")
@@ -1782,25 +2633,25 @@ end tell"#,
                 .map_err(|e| format!("Failed to run iTerm2 AppleScript: {}", e))?;
 
             if output.status.success() {
-                eprintln!("[open_tmux_in_terminal] ✓ Opened new iTerm window for session '{}'", session_name);
+                info!(target: "open_tmux_in_terminal", "✓ Opened new iTerm window for session '{}'", session_name);
                 return Ok(format!("Opened iTerm window for '{}'", env_name));
             } else {
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                eprintln!("[open_tmux_in_terminal] iTerm2 failed: {}, falling back to Terminal.app", stderr);
+                warn!(target: "open_tmux_in_terminal", "iTerm2 failed: {}, falling back to Terminal.app", stderr);
             }
         } else {
-            eprintln!("[open_tmux_in_terminal] iTerm not running, trying Terminal.app");
+            info!(target: "open_tmux_in_terminal", "iTerm not running, trying Terminal.app");
         }
 
         // Fallback: Terminal.app
         let temp_script = format!("/tmp/ushadow_terminal_{}.sh", env_name.replace("/", "_"));
         let script_content = format!(
-            "#!/bin/bash\nprintf '\\033]0;{}\\007'\nexec tmux attach-session -t {}\n",
-            env_name, session_name
+            "#!/bin/bash\nprintf '\\033]0;{}\\007'\nexec {}\n",
+            env_name, attach_cmd
         );
         fs::write(&temp_script, &script_content)
             .map_err(|e| format!("Failed to write Terminal attach script: {}", e))?;
-        shell_command(&format!("chmod +x {}", temp_script))
+        shell_command(&format!("chmod +x {}", shell_quote(&temp_script)))
             .output()
             .map_err(|e| format!("Failed to chmod Terminal attach script: {}", e))?;
 
@@ -1809,7 +2660,7 @@ end tell"#,
     activate
     do script "{}"
 end tell"#,
-            temp_script
+            posix_quote(&temp_script)
         );
 
         let output = Command::new("osascript")
@@ -1823,90 +2674,209 @@ end tell"#,
             return Err(format!("Failed to open Terminal.app: {}", stderr));
         }
 
-        eprintln!("[open_tmux_in_terminal] ✓ Terminal.app success");
+        info!(target: "open_tmux_in_terminal", "✓ Terminal.app success");
         Ok(format!("Opened Terminal.app for session '{}'", session_name))
     }
 
     #[cfg(not(target_os = "macos"))]
     {
         // Linux: try common terminal emulators
-        let attach_cmd = format!("tmux attach-session -t {}", session_name);
-        let terminals: Vec<(&str, Vec<&str>)> = vec![
-            ("gnome-terminal", vec!["--", "tmux", "attach-session", "-t", &session_name]),
-            ("konsole", vec!["-e", "tmux", "attach-session", "-t", &session_name]),
-            ("xfce4-terminal", vec!["-e", attach_cmd.as_str()]),
-            ("xterm", vec!["-e", attach_cmd.as_str()]),
-        ];
-
-        for (terminal, args) in terminals {
-            if Command::new(terminal).args(&args).spawn().is_ok() {
-                eprintln!("[open_tmux_in_terminal] Opened {} for session {}", terminal, session_name);
+        for terminal in LINUX_TERMINALS {
+            if launch_terminal(terminal, &env_name, &attach_argv).is_ok() {
+                info!(target: "open_tmux_in_terminal", "Opened {} for session {}", terminal, session_name);
                 return Ok(format!("Opened {} for session '{}'", terminal, session_name));
             }
         }
 
-        Err("No supported terminal emulator found. Please install gnome-terminal, konsole, xfce4-terminal, or xterm.".to_string())
+        Err(format!("No supported terminal emulator found. Please install one of: {}.", LINUX_TERMINALS.join(", ")))
+    }
+}
+
+/// Terminals tried in order on Linux when `terminal_emulator` isn't set
+const LINUX_TERMINALS: &[&str] = &["gnome-terminal", "konsole", "xfce4-terminal", "xterm", "wezterm", "kitty", "alacritty"];
+
+/// Quote and join an argv into a POSIX shell command line
+fn shell_join(argv: &[String]) -> String {
+    argv.iter().map(|arg| posix_quote(arg)).collect::<Vec<_>>().join(" ")
+}
+
+/// Arguments that make `terminal` run `attach_argv`, titling the window `title` where the
+/// terminal can. `None` for terminals the launcher doesn't know how to drive.
+fn terminal_launch_args(terminal: &str, title: &str, attach_argv: &[String]) -> Option<Vec<String>> {
+    let with = |prefix: &[&str]| -> Vec<String> {
+        prefix.iter().map(|s| s.to_string()).chain(attach_argv.iter().cloned()).collect()
+    };
+    Some(match terminal {
+        "gnome-terminal" => with(&["--"]),
+        "konsole" => with(&["-e"]),
+        "xfce4-terminal" | "xterm" => vec!["-e".to_string(), shell_join(attach_argv)],
+        // wezterm has no title flag, so the title is set with an escape sequence before attaching
+        "wezterm" => vec![
+            "start".to_string(),
+            "--".to_string(),
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("printf '\\033]0;%s\\007' {}; exec {}", posix_quote(title), shell_join(attach_argv)),
+        ],
+        "kitty" => with(&["--title", title]),
+        "alacritty" => with(&["--title", title, "-e"]),
+        _ => return None,
+    })
+}
+
+/// Open `terminal` running `attach_argv`
+fn launch_terminal(terminal: &str, title: &str, attach_argv: &[String]) -> Result<(), String> {
+    let args = terminal_launch_args(terminal, title, attach_argv)
+        .ok_or_else(|| format!("Unsupported terminal emulator '{}'", terminal))?;
+
+    if Command::new(terminal).args(&args).spawn().is_ok() {
+        return Ok(());
+    }
+
+    // macOS apps are usually not on PATH; launch the bundle instead
+    #[cfg(target_os = "macos")]
+    {
+        let app = match terminal {
+            "wezterm" => "WezTerm",
+            "alacritty" => "Alacritty",
+            other => other,
+        };
+        if Command::new("open").args(["-na", app, "--args"]).args(&args).spawn().is_ok() {
+            return Ok(());
+        }
+    }
+
+    Err(format!("Failed to launch terminal emulator '{}'", terminal))
+}
+
+/// Open a terminal attached to an environment's existing tmux session (e.g. after a reboot
+/// or closing every terminal) without creating or changing any windows
+#[tauri::command]
+pub async fn reattach_session(env_name: String, worktree_path: String) -> Result<String, String> {
+    let settings = load_launcher_settings().await.unwrap_or_default();
+    let mux = multiplexer_for(settings.multiplexer);
+    mux.check_installed()?;
+
+    let session_name = tmux_names(&env_name, &settings).session;
+    if !mux.session_exists(&session_name) {
+        return Err(format!("No tmux session '{}' to reattach for environment '{}'", session_name, env_name));
+    }
+
+    info!(target: "reattach_session", "Reattaching to session '{}'", session_name);
+
+    // No window name: open_tmux_in_terminal then only opens/focuses a terminal on the session
+    open_tmux_in_terminal(String::new(), worktree_path, Some(env_name)).await
+}
+
+/// Pids of `root_pid` and all its descendants that match the agent, from `ps -A -o pid=,ppid=,args=` output
+fn agent_pids_in_tree(ps_output: &str, root_pid: u32, profile: &AgentProfile) -> Vec<u32> {
+    let processes: Vec<(u32, u32, &str)> = ps_output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start();
+            let (pid, rest) = line.split_once(char::is_whitespace)?;
+            let rest = rest.trim_start();
+            let (ppid, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            Some((pid.parse().ok()?, ppid.parse().ok()?, args.trim()))
+        })
+        .collect();
+
+    let mut tree = vec![root_pid];
+    let mut i = 0;
+    while i < tree.len() {
+        let parent = tree[i];
+        tree.extend(processes.iter().filter(|(_, ppid, _)| *ppid == parent).map(|(pid, _, _)| *pid));
+        i += 1;
+    }
+
+    processes
+        .iter()
+        .filter(|(pid, _, args)| tree.contains(pid) && profile.matches_process(args))
+        .map(|(pid, _, _)| *pid)
+        .collect()
+}
+
+/// Whether the agent is alive in a tmux pane. `pane_current_command` alone misses agents
+/// running under a wrapper shell, so the pane's process tree is walked as well.
+/// Scrollback text is deliberately not consulted: the agent's banner outlives it.
+pub(crate) fn agent_running_in_pane(tmux_target: &str, current_command: &str, profile: &AgentProfile) -> bool {
+    profile.is_running(current_command)
+        || tmux_pane_pid(tmux_target).map_or(false, |pid| agent_in_process_tree(pid, tmux_target, profile))
+}
+
+/// Whether the agent is running in the process tree under a pane's `pane_pid`
+fn agent_in_process_tree(pane_pid: u32, pane: &str, profile: &AgentProfile) -> bool {
+    let ps = match silent_command("ps").args(["-A", "-o", "pid=,ppid=,args="]).output() {
+        Ok(output) if output.status.success() => output,
+        _ => return false,
+    };
+
+    let pids = agent_pids_in_tree(&String::from_utf8_lossy(&ps.stdout), pane_pid, profile);
+    if !pids.is_empty() {
+        info!(target: "agent_running_in_pane", "{} found in pane {} process tree: {:?}", profile.name, pane, pids);
     }
+    !pids.is_empty()
 }
 
-/// Check if Claude agent is running in a tmux window; start or resume it if not.
+/// Check if the coding agent is running in a multiplexer window; start or resume it if not.
+/// Multiplexers that can't report the pane's command (zellij) are left alone, since typing
+/// into a pane that may already run the agent is worse than not starting it.
 ///
-/// Always tries `claude --resume` first so the user gets their last conversation back.
-/// If Claude starts fresh (no prior session), and there's a ticket for this worktree,
-/// sends the ticket title + description as initial context.
+/// Resumes the agent's most recent session for the worktree when its profile supports it.
+/// If the agent starts fresh and there's a ticket for this worktree, the ticket title and
+/// description are passed as initial context.
 pub async fn check_and_resume_agent(
     tmux_session_name: &str,
     tmux_window_name: &str,
     worktree_path: &str,
 ) -> Result<bool, String> {
     use super::kanban::get_ticket_by_worktree_path;
+    use super::settings::{agent_profile, read_launcher_settings};
 
-    eprintln!("[check_and_resume_agent] Checking agent status for window {}", tmux_window_name);
+    let settings = read_launcher_settings().unwrap_or_default();
+    let profile = agent_profile(&settings);
+    let mux = multiplexer_for(settings.multiplexer);
+    info!(target: "check_and_resume_agent", "Checking {} status for window {}", profile.name, tmux_window_name);
 
     // 1. Check the current foreground process in the pane — this is reliable because
-    //    Claude's startup banner stays in the scrollback after it exits, so scanning
+    //    the agent's startup banner stays in the scrollback after it exits, so scanning
     //    pane text gives false positives.
-    //    Use shell_command but take only the last non-empty line — login shell profile
-    //    output (e.g. workmux version banner) appears before the tmux output and was
-    //    being mistaken for the pane command.
-    let current_command = shell_command(&format!(
-        "tmux display-message -t {}:{} -p '#{{pane_current_command}}'",
-        tmux_session_name, tmux_window_name
-    ))
-    .output()
-    .map(|o| {
-        String::from_utf8_lossy(&o.stdout)
-            .lines()
-            .filter(|l| !l.trim().is_empty())
-            .last()
-            .unwrap_or("")
-            .trim()
-            .to_string()
-    })
-    .unwrap_or_default();
+    let current_command = match mux.current_command(tmux_session_name, tmux_window_name) {
+        Some(command) => command,
+        None => {
+            info!(target: "check_and_resume_agent", "{} can't report the pane command, not starting {}", mux.binary(), profile.name);
+            return Ok(false);
+        }
+    };
 
-    eprintln!("[check_and_resume_agent] Current pane command: '{}'", current_command);
+    info!(target: "check_and_resume_agent", "Current pane command: '{}'", current_command);
 
-    // Shells indicate Claude is not running; anything else (claude, node, python…) is.
-    let is_shell = matches!(current_command.as_str(), "zsh" | "bash" | "sh" | "fish" | "");
-    if !is_shell {
-        eprintln!("[check_and_resume_agent] Agent already running ({}), no action needed", current_command);
+    let pane = format!("{}:{}", tmux_session_name, tmux_window_name);
+    let running = profile.is_running(&current_command)
+        || mux
+            .pane_pid(tmux_session_name, tmux_window_name)
+            .map_or(false, |pid| agent_in_process_tree(pid, &pane, &profile));
+    if running {
+        info!(target: "check_and_resume_agent", "Agent already running ({}), no action needed", current_command);
+        return Ok(false);
+    }
+    // Something else owns the pane (an editor, a dev server…); don't type into it
+    if !is_shell_command(&current_command) {
+        info!(target: "check_and_resume_agent", "Pane busy with '{}', not starting {}", current_command, profile.name);
         return Ok(false);
     }
 
-    // 2. Find the most-recently-modified Claude session file for this worktree.
-    //    Pass its UUID directly to --resume so Claude skips the picker entirely.
-    //    Bare `claude --resume` without a session ID shows an interactive chooser
-    //    whenever multiple sessions exist — not what we want.
+    // 2. Find the most-recently-modified session file for this worktree.
+    //    Pass its ID directly to the resume args so the agent skips any session picker
+    //    (bare `claude --resume` shows an interactive chooser when multiple sessions exist).
     let home_dir = std::env::var("HOME").unwrap_or_default();
-    let encoded_path = worktree_path.replace('/', "-");
-    let sessions_dir = format!("{}/.claude/projects/{}", home_dir, encoded_path);
 
     // Walk the directory, collect (modified_time, session_id) for every .jsonl file,
     // then pick the most recently modified one.
-    let latest_session_id: Option<String> = std::fs::read_dir(&sessions_dir)
-        .ok()
-        .map(|entries| {
+    let latest_session_id: Option<String> = profile
+        .sessions_dir(&home_dir, worktree_path)
+        .and_then(|sessions_dir| std::fs::read_dir(sessions_dir).ok())
+        .and_then(|entries| {
             let mut candidates: Vec<(std::time::SystemTime, String)> = entries
                 .filter_map(|e| e.ok())
                 .filter(|e| e.path().extension().map(|x| x == "jsonl").unwrap_or(false))
@@ -1921,63 +2891,45 @@ pub async fn check_and_resume_agent(
                 .collect();
             candidates.sort_by(|a, b| b.0.cmp(&a.0)); // newest first
             candidates.into_iter().next().map(|(_, id)| id)
-        })
-        .flatten();
+        });
 
-    // Build the claude invocation, writing it to a temp script to avoid multi-layer
+    // Build the agent invocation, writing it to a temp script to avoid multi-layer
     // quoting issues.  tmux send-keys passes each argument as separate keystroke runs,
     // so any unbalanced inner quotes turn "You are working" into "Youareworking".
     // A temp script path has no special characters, so the outer quoting is trivially safe.
-    // Inside the script we use bash $'...' (ANSI-C quoting) which handles \' and \n cleanly.
+    // Inside the script the prompt uses bash $'...' (ANSI-C quoting) which handles \' and \n cleanly.
     let script_key = tmux_window_name.replace('/', "_");
-    let temp_script = format!("/tmp/ushadow_claude_{}.sh", script_key);
+    let temp_script = format!("/tmp/ushadow_{}_{}.sh", profile.name, script_key);
 
     let script_content = if let Some(session_id) = latest_session_id {
-        eprintln!("[check_and_resume_agent] Resuming session {} (no picker)", session_id);
-        format!(
-            "#!/bin/bash\nexec claude --resume {} --dangerously-skip-permissions\n",
-            session_id
-        )
+        info!(target: "check_and_resume_agent", "Resuming session {} (no picker)", session_id);
+        profile.start_script(None, Some(&session_id), None)
     } else {
         let ticket = get_ticket_by_worktree_path(worktree_path);
         if let Some(ticket) = ticket {
-            eprintln!("[check_and_resume_agent] No sessions — starting fresh with ticket context: {}", ticket.title);
+            info!(target: "check_and_resume_agent", "No sessions — starting fresh with ticket context: {}", ticket.title);
             let prompt = format!(
                 "You are working on the following ticket:\n\nTitle: {}\n\nDescription: {}\n\nPlease help implement this feature.",
                 ticket.title,
                 ticket.description.as_ref().unwrap_or(&"No description".to_string())
             );
-            // Escape for bash $'...' ANSI-C quoting: backslash → \\, single-quote → \', newline → \n
-            let ansi_escaped = prompt
-                .replace('\\', "\\\\")
-                .replace('\'', "\\'")
-                .replace('\n', "\\n");
-            format!(
-                "#!/bin/bash\nexec claude --dangerously-skip-permissions $'{}'\n",
-                ansi_escaped
-            )
+            profile.start_script(None, None, Some(&prompt))
         } else {
-            eprintln!("[check_and_resume_agent] No sessions, no ticket — starting plain Claude");
-            "#!/bin/bash\nexec claude --dangerously-skip-permissions\n".to_string()
+            info!(target: "check_and_resume_agent", "No sessions, no ticket — starting plain {}", profile.name);
+            profile.start_script(None, None, None)
         }
     };
 
     if let Err(e) = std::fs::write(&temp_script, &script_content) {
-        eprintln!("[check_and_resume_agent] Warning: could not write temp script: {}", e);
+        warn!(target: "check_and_resume_agent", "could not write temp script: {}", e);
     } else {
         let _ = shell_command(&format!("chmod +x {}", temp_script)).output();
     }
 
-    // tmux send-keys just types the script path — no special characters, no quoting issues
-    eprintln!("[check_and_resume_agent] Running via script: {}", temp_script);
-    let result = shell_command(&format!(
-        "tmux send-keys -t {}:{} 'bash {}' Enter",
-        tmux_session_name, tmux_window_name, temp_script
-    ))
-    .output();
-
-    if let Err(e) = result {
-        eprintln!("[check_and_resume_agent] Failed to start Claude: {}", e);
+    // send_keys just types the script path — no special characters, no quoting issues
+    info!(target: "check_and_resume_agent", "Running via script: {}", temp_script);
+    if let Err(e) = mux.send_keys(tmux_session_name, tmux_window_name, &format!("bash {}", temp_script)) {
+        warn!(target: "check_and_resume_agent", "Failed to start {}: {}", profile.name, e);
         return Ok(false);
     }
 
@@ -1987,6 +2939,13 @@ pub async fn check_and_resume_agent(
 /// Capture the visible content of a tmux pane
 #[tauri::command]
 pub async fn capture_tmux_pane(window_name: String) -> Result<String, String> {
+    // `session:window` targets work with any multiplexer
+    if let Some((session, window)) = window_name.split_once(':') {
+        return multiplexer()
+            .capture_pane(session, window)
+            .map_err(|e| format!("Failed to capture pane: {}", e));
+    }
+
     // Capture the last 100 lines from the pane
     let output = shell_command(&format!(
         "tmux capture-pane -t {} -p -S -100",
@@ -2004,6 +2963,56 @@ pub async fn capture_tmux_pane(window_name: String) -> Result<String, String> {
     Ok(content)
 }
 
+/// Get the scrollback history-limit configured for a tmux window (falls back to the global value)
+#[tauri::command]
+pub async fn get_tmux_history_limit(window_name: String) -> Result<u32, String> {
+    for cmd in [
+        format!("tmux show-window-options -v -t {} history-limit", window_name),
+        "tmux show-options -gv history-limit".to_string(),
+    ] {
+        if let Ok(output) = shell_command(&cmd).output() {
+            if output.status.success() {
+                if let Ok(limit) = String::from_utf8_lossy(&output.stdout).trim().parse::<u32>() {
+                    return Ok(limit);
+                }
+            }
+        }
+    }
+
+    Err(format!("Could not read history-limit for tmux window '{}'", window_name))
+}
+
+/// Export the full scrollback of a tmux pane to a file. Returns the number of lines written.
+#[tauri::command]
+pub async fn export_tmux_pane_history(window_name: String, path: String) -> Result<usize, String> {
+    // -S - starts at the beginning of history, -E - ends at the last visible line
+    let output = shell_command(&format!(
+        "tmux capture-pane -t {} -p -S - -E -",
+        window_name
+    ))
+        .output()
+        .map_err(|e| format!("Failed to capture pane: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to capture pane: {}", stderr));
+    }
+
+    let content = String::from_utf8_lossy(&output.stdout).to_string();
+    let line_count = content.lines().count();
+
+    let path = PathBuf::from(expand_tilde(&path));
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create export directory: {}", e))?;
+    }
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    info!(target: "export_tmux_pane_history", "Wrote {} lines from '{}' to {}", line_count, window_name, path.display());
+    Ok(line_count)
+}
+
 /// Get Claude Code status from a tmux window
 #[tauri::command]
 pub async fn get_claude_status(window_name: String) -> Result<ClaudeStatus, String> {
@@ -2069,11 +3078,11 @@ fn parse_claude_task(output: &str) -> Option<String> {
     // Look for common Claude Code patterns
     let lines: Vec<&str> = output.lines().collect();
 
-    eprintln!("[parse_claude_task] Parsing {} lines of output", lines.len());
+    info!(target: "parse_claude_task", "Parsing {} lines of output", lines.len());
 
     // Print last few lines for debugging
     for line in lines.iter().rev().take(5) {
-        eprintln!("[parse_claude_task] Recent line: {}", line);
+        info!(target: "parse_claude_task", "Recent line: {}", line);
     }
 
     // Look for the most recent non-empty, meaningful line
@@ -2097,7 +3106,7 @@ fn parse_claude_task(output: &str) -> Option<String> {
             || trimmed.contains("Let me")
             || trimmed.contains("I will") {
             if trimmed.len() < 200 {
-                eprintln!("[parse_claude_task] Found action: {}", trimmed);
+                info!(target: "parse_claude_task", "Found action: {}", trimmed);
                 return Some(trimmed.to_string());
             }
         }
@@ -2105,7 +3114,7 @@ fn parse_claude_task(output: &str) -> Option<String> {
         // Look for tool usage
         if (trimmed.contains("Using") || trimmed.contains("Running") || trimmed.contains("Calling"))
             && trimmed.len() < 150 {
-            eprintln!("[parse_claude_task] Found tool usage: {}", trimmed);
+            info!(target: "parse_claude_task", "Found tool usage: {}", trimmed);
             return Some(trimmed.to_string());
         }
 
@@ -2113,7 +3122,7 @@ fn parse_claude_task(output: &str) -> Option<String> {
         if (trimmed.contains("Reading") || trimmed.contains("Writing") || trimmed.contains("Editing"))
             && (trimmed.contains(".") || trimmed.contains("/"))
             && trimmed.len() < 150 {
-            eprintln!("[parse_claude_task] Found file op: {}", trimmed);
+            info!(target: "parse_claude_task", "Found file op: {}", trimmed);
             return Some(trimmed.to_string());
         }
     }
@@ -2123,12 +3132,527 @@ fn parse_claude_task(output: &str) -> Option<String> {
         if line.trim().starts_with("You:") {
             let text = line.trim().strip_prefix("You:")?.trim();
             if !text.is_empty() && text.len() < 150 {
-                eprintln!("[parse_claude_task] Found user prompt: {}", text);
+                info!(target: "parse_claude_task", "Found user prompt: {}", text);
                 return Some(format!("💬 {}", text));
             }
         }
     }
 
-    eprintln!("[parse_claude_task] No meaningful task found");
+    info!(target: "parse_claude_task", "No meaningful task found");
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(repo: &std::path::Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .output()
+            .expect("failed to run git");
+        assert!(status.status.success(), "git {:?} failed: {}", args, String::from_utf8_lossy(&status.stderr));
+    }
+
+    #[test]
+    fn test_compute_ahead_behind_known_divergence() {
+        let repo = std::env::temp_dir().join(format!("ushadow-ahead-behind-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&repo).unwrap();
+
+        git(&repo, &["init", "-q", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["commit", "-q", "--allow-empty", "-m", "root"]);
+        git(&repo, &["checkout", "-q", "-b", "feature-main"]);
+        git(&repo, &["commit", "-q", "--allow-empty", "-m", "feature 1"]);
+        git(&repo, &["commit", "-q", "--allow-empty", "-m", "feature 2"]);
+        git(&repo, &["checkout", "-q", "main"]);
+        git(&repo, &["commit", "-q", "--allow-empty", "-m", "main 1"]);
+
+        let repo_str = repo.to_string_lossy().to_string();
+        assert_eq!(compute_ahead_behind(&repo_str, "feature-main"), Some((2, 1)));
+        assert_eq!(compute_ahead_behind(&repo_str, "main"), Some((0, 0)));
+        // Detached HEAD / no branch
+        assert_eq!(compute_ahead_behind(&repo_str, ""), None);
+
+        let _ = std::fs::remove_dir_all(&repo);
+    }
+
+    #[test]
+    fn test_count_changed_files_staged_and_unstaged() {
+        let repo = std::env::temp_dir().join(format!("ushadow-dirty-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&repo).unwrap();
+
+        git(&repo, &["init", "-q", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        std::fs::write(repo.join("tracked.txt"), "one").unwrap();
+        git(&repo, &["add", "tracked.txt"]);
+        git(&repo, &["commit", "-q", "-m", "root"]);
+
+        let repo_str = repo.to_string_lossy().to_string();
+        assert_eq!(count_changed_files(&repo_str), 0);
+
+        // Unstaged modification
+        std::fs::write(repo.join("tracked.txt"), "two").unwrap();
+        // Staged new file
+        std::fs::write(repo.join("staged.txt"), "new").unwrap();
+        git(&repo, &["add", "staged.txt"]);
+        // Untracked file
+        std::fs::write(repo.join("untracked.txt"), "loose").unwrap();
+
+        assert_eq!(count_changed_files(&repo_str), 3);
+
+        let _ = std::fs::remove_dir_all(&repo);
+
+        // Worktree deleted out-of-band
+        assert_eq!(count_changed_files(&repo_str), 0);
+    }
+
+    fn init_repo_with_worktree(prefix: &str) -> (PathBuf, PathBuf) {
+        let root = std::env::temp_dir().join(format!("{}-{}", prefix, uuid::Uuid::new_v4()));
+        let repo = root.join("main");
+        std::fs::create_dir_all(&repo).unwrap();
+
+        git(&repo, &["init", "-q", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["commit", "-q", "--allow-empty", "-m", "root"]);
+
+        let worktree = root.join("gold");
+        git(&repo, &["worktree", "add", "-q", "-b", "gold/feature", worktree.to_str().unwrap()]);
+        (repo, root)
+    }
+
+    fn branch_exists(repo: &std::path::Path, branch: &str) -> bool {
+        Command::new("git")
+            .args(["show-ref", "--verify", "--quiet", &format!("refs/heads/{}", branch)])
+            .current_dir(repo)
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    #[test]
+    fn test_renamed_branch() {
+        assert_eq!(renamed_branch("gold", "gold", "teal"), "teal");
+        assert_eq!(renamed_branch("gold/feature", "gold", "teal"), "teal/feature");
+        assert_eq!(renamed_branch("feature-dev", "gold", "teal"), "feature-dev");
+        assert_eq!(renamed_branch("goldfish", "gold", "teal"), "goldfish");
+    }
+
+    #[test]
+    fn test_rename_branch_and_move_worktree_happy_path() {
+        let (repo, root) = init_repo_with_worktree("ushadow-rename");
+        let old_path = root.join("gold");
+        let new_path = root.join("teal");
+
+        rename_branch_and_move_worktree(
+            repo.to_str().unwrap(),
+            old_path.to_str().unwrap(),
+            new_path.to_str().unwrap(),
+            "gold/feature",
+            "teal/feature",
+        ).unwrap();
+
+        assert!(new_path.exists());
+        assert!(!old_path.exists());
+        assert!(branch_exists(&repo, "teal/feature"));
+        assert!(!branch_exists(&repo, "gold/feature"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_rename_branch_and_move_worktree_rolls_back_branch() {
+        let (repo, root) = init_repo_with_worktree("ushadow-rename-rollback");
+        let old_path = root.join("gold");
+        let new_path = root.join("teal");
+
+        // A file at the destination makes `git worktree move` fail
+        std::fs::write(&new_path, "occupied").unwrap();
+
+        let result = rename_branch_and_move_worktree(
+            repo.to_str().unwrap(),
+            old_path.to_str().unwrap(),
+            new_path.to_str().unwrap(),
+            "gold/feature",
+            "teal/feature",
+        );
+
+        assert!(result.is_err());
+        assert!(old_path.exists());
+        assert!(branch_exists(&repo, "gold/feature"));
+        assert!(!branch_exists(&repo, "teal/feature"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    fn init_repo_with_tag(prefix: &str) -> (PathBuf, String) {
+        let root = std::env::temp_dir().join(format!("{}-{}", prefix, uuid::Uuid::new_v4()));
+        let repo = root.join("ushadow");
+        std::fs::create_dir_all(&repo).unwrap();
+
+        git(&repo, &["init", "-q", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["commit", "-q", "--allow-empty", "-m", "v1"]);
+        git(&repo, &["tag", "v1.4.0"]);
+        git(&repo, &["commit", "-q", "--allow-empty", "-m", "after tag"]);
+
+        let output = Command::new("git")
+            .args(["rev-parse", "v1.4.0^{commit}"])
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        let tagged_sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (repo, tagged_sha)
+    }
+
+    fn head_of(path: &str) -> String {
+        let output = Command::new("git").args(["rev-parse", "HEAD"]).current_dir(path).output().unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_create_worktree_from_tag() {
+        let (repo, tagged_sha) = init_repo_with_tag("ushadow-base-ref-tag");
+        let root = repo.parent().unwrap().to_path_buf();
+
+        let wt = create_worktree(
+            repo.to_string_lossy().to_string(),
+            root.join("worktrees").to_string_lossy().to_string(),
+            "pinned".to_string(),
+            None,
+            None,
+            Some("v1.4.0".to_string()),
+            None,
+        ).await.unwrap();
+
+        assert_eq!(wt.branch, "pinned");
+        assert_eq!(head_of(&wt.path), tagged_sha);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_create_worktree_from_short_sha() {
+        let (repo, tagged_sha) = init_repo_with_tag("ushadow-base-ref-sha");
+        let root = repo.parent().unwrap().to_path_buf();
+
+        let wt = create_worktree(
+            repo.to_string_lossy().to_string(),
+            root.join("worktrees").to_string_lossy().to_string(),
+            "bisect".to_string(),
+            None,
+            None,
+            Some(tagged_sha[..8].to_string()),
+            None,
+        ).await.unwrap();
+
+        assert_eq!(head_of(&wt.path), tagged_sha);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_create_worktree_invalid_base_ref() {
+        let (repo, _) = init_repo_with_tag("ushadow-base-ref-invalid");
+        let root = repo.parent().unwrap().to_path_buf();
+
+        let err = create_worktree(
+            repo.to_string_lossy().to_string(),
+            root.join("worktrees").to_string_lossy().to_string(),
+            "broken".to_string(),
+            None,
+            None,
+            Some("v9.9.9-does-not-exist".to_string()),
+            None,
+        ).await.unwrap_err();
+
+        assert!(err.contains("v9.9.9-does-not-exist"), "unexpected error: {}", err);
+        assert!(!root.join("worktrees").join("ushadow").join("broken").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_compute_ahead_behind_unresolvable_base() {
+        let repo = std::env::temp_dir().join(format!("ushadow-ahead-behind-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&repo).unwrap();
+
+        git(&repo, &["init", "-q", "-b", "trunk"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["commit", "-q", "--allow-empty", "-m", "root"]);
+        git(&repo, &["checkout", "-q", "-b", "feature-dev"]);
+
+        // Base "dev" doesn't exist in this repo
+        let repo_str = repo.to_string_lossy().to_string();
+        assert_eq!(compute_ahead_behind(&repo_str, "feature-dev"), None);
+
+        let _ = std::fs::remove_dir_all(&repo);
+    }
+
+    #[test]
+    fn test_agent_pids_in_tree_walks_descendants() {
+        let profile = AgentProfile::builtin("claude").unwrap();
+        let ps = "    1     0 /sbin/init
+  100     1 -zsh
+  101   100 bash /tmp/ushadow_claude_ushadow-gold.sh
+  102   101 node /usr/lib/node_modules/@anthropic-ai/claude-code/cli.js --resume abc
+  200     1 -zsh
+  201   200 claude
+  300   100 node server.js
+";
+        // Agent under a wrapper shell in this pane is found; another pane's agent is not
+        assert_eq!(agent_pids_in_tree(ps, 100, &profile), vec![102]);
+        assert_eq!(agent_pids_in_tree(ps, 200, &profile), vec![201]);
+        assert!(agent_pids_in_tree(ps, 300, &profile).is_empty());
+        assert!(agent_pids_in_tree("", 100, &profile).is_empty());
+    }
+
+    #[test]
+    fn test_probe_tmux_missing_binary() {
+        assert_eq!(
+            probe_tmux("ushadow-test-no-such-tmux"),
+            Err(TMUX_NOT_INSTALLED.to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_probe_tmux_reports_version() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("ushadow-tmux-probe-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let fake_tmux = dir.join("tmux");
+        std::fs::write(&fake_tmux, "#!/bin/sh\necho 'tmux 3.4'\n").unwrap();
+        std::fs::set_permissions(&fake_tmux, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert_eq!(probe_tmux(&fake_tmux.to_string_lossy()), Ok("tmux 3.4".to_string()));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_parse_tmux_layout() {
+        let output = "ush-gold\t0\tush-gold\t1\t0\t%1\tclaude\t/dev/ttys001\t0\t/work/gold\n\
+                      ush-gold\t0\tush-gold\t1\t1\t%2\tzsh\t/dev/ttys002\t1\t/work/gold/backend\n\
+                      workmux\t2\tush-blue\t0\t0\t%7\tnode\t/dev/ttys005\t1\t/work/with\ttab\n\
+                      truncated\tline\n";
+
+        let layout = parse_tmux_layout(output);
+        assert_eq!(layout.len(), 2);
+
+        assert_eq!(layout[0].session, "ush-gold");
+        assert_eq!(layout[0].windows.len(), 1);
+        let window = &layout[0].windows[0];
+        assert!(window.active);
+        assert_eq!(window.panes.len(), 2);
+        assert_eq!(window.panes[0].current_command, "claude");
+        assert!(!window.panes[0].active);
+        assert_eq!(window.panes[1].current_path, "/work/gold/backend");
+        assert!(window.panes[1].active);
+
+        assert_eq!(layout[1].session, "workmux");
+        assert_eq!(layout[1].windows[0].name, "ush-blue");
+        assert_eq!(
+            layout[1].windows[0].panes[0],
+            TmuxLayoutPane {
+                index: "0".to_string(),
+                pane_id: "%7".to_string(),
+                current_command: "node".to_string(),
+                current_path: "/work/with\ttab".to_string(),
+                tty: "/dev/ttys005".to_string(),
+                active: true,
+            }
+        );
+    }
+
+    /// Words the shell passes to `program` in a generated command line
+    #[cfg(unix)]
+    fn shell_words(command: &str, program: &str) -> Vec<String> {
+        let printed = Command::new("sh")
+            .args(["-c", &command.replacen(program, "printf '%s\\n'", 1)])
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&printed.stdout).lines().map(String::from).collect()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_color_setup_command_quotes_path_and_name() {
+        let dir = std::env::temp_dir().join(format!("ushadow env's $HOME {}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let env_name = "gold'); import os; ('";
+
+        let words = shell_words(&color_setup_command(&dir.to_string_lossy(), env_name), "uv run");
+        assert_eq!(&words[..4], ["--with", "pyyaml", "python3", "-c"]);
+        assert!(words[4].contains("setup_colors_for_directory(Path('.'), sys.argv[1])"));
+        assert_eq!(words[5], env_name);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_vscode_tmux_command_quotes_target() {
+        let cmd = vscode_tmux_command("ush-gold", "bash");
+        assert!(cmd.starts_with("tmux -f .tmux.conf has-session -t 'workmux:ush-gold' 2>/dev/null && "));
+        assert!(cmd.ends_with("attach-session -t 'workmux:ush-gold' || bash"));
+    }
+
+    #[test]
+    fn test_editor_launch_respects_configured_editor() {
+        let (editor, warning) = resolve_editor_command("cursor", |bin| bin == "cursor");
+        assert_eq!((editor.as_str(), warning), ("cursor", None));
+
+        let cmd = editor_launch_command(&editor, "/work/gold");
+        assert_eq!(cmd.get_program(), "cursor");
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), vec!["/work/gold"]);
+
+        // Missing binary falls back to VS Code with a warning
+        let (editor, warning) = resolve_editor_command("windsurf", |_| false);
+        assert_eq!(editor, "code");
+        assert!(warning.unwrap().contains("windsurf"));
+
+        assert_eq!(resolve_editor_command("", |_| false), ("code".to_string(), None));
+    }
+
+    #[test]
+    fn test_terminal_launch_args() {
+        let argv: Vec<String> = vec!["tmux".into(), "attach-session".into(), "-t".into(), "ush-gold".into()];
+
+        assert_eq!(
+            terminal_launch_args("kitty", "gold", &argv).unwrap(),
+            vec!["--title", "gold", "tmux", "attach-session", "-t", "ush-gold"]
+        );
+        assert_eq!(
+            terminal_launch_args("alacritty", "gold", &argv).unwrap(),
+            vec!["--title", "gold", "-e", "tmux", "attach-session", "-t", "ush-gold"]
+        );
+        assert_eq!(
+            terminal_launch_args("wezterm", "gold's env", &argv).unwrap(),
+            vec![
+                "start",
+                "--",
+                "sh",
+                "-c",
+                "printf '\\033]0;%s\\007' 'gold'\\''s env'; exec 'tmux' 'attach-session' '-t' 'ush-gold'",
+            ]
+        );
+        assert_eq!(
+            terminal_launch_args("gnome-terminal", "gold", &argv).unwrap(),
+            vec!["--", "tmux", "attach-session", "-t", "ush-gold"]
+        );
+        assert_eq!(
+            terminal_launch_args("xterm", "gold", &argv).unwrap(),
+            vec!["-e", "'tmux' 'attach-session' '-t' 'ush-gold'"]
+        );
+        assert_eq!(terminal_launch_args("hyper", "gold", &argv), None);
+    }
+
+    #[test]
+    fn test_color_palette_covers_env_colors() {
+        for color in ENV_COLOR_NAMES {
+            let (name, _) = get_colors_for_name(&format!("{}-feature", color));
+            assert!(color_hex(&name).is_some(), "no palette entry for {}", name);
+        }
+        assert_eq!(color_hex(&get_colors_for_name("ushadow").0), Some("#8b3a8b"));
+        assert_eq!(color_hex("Main"), Some("#2ea043"));
+        assert_eq!(color_hex("my-feature-x"), None);
+
+        assert_eq!(color_hex("gold").and_then(hex_to_rgb), Some((0xDA, 0xA5, 0x20)));
+        assert_eq!(hex_to_rgb("#0066cc"), Some((0, 0x66, 0xcc)));
+        assert_eq!(hex_to_rgb("#fff"), None);
+        assert_eq!(hex_to_rgb("#zzzzzz"), None);
+
+        assert_eq!(get_color_palette().unwrap().len(), COLOR_PALETTE.len());
+    }
+
+    #[tokio::test]
+    async fn test_create_worktree_sparse_checkout() {
+        let root = std::env::temp_dir().join(format!("ushadow-sparse-{}", uuid::Uuid::new_v4()));
+        let repo = root.join("ushadow");
+        for file in ["services/api/main.py", "services/web/index.ts", "docs/README.md", "setup.py"] {
+            let path = repo.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, file).unwrap();
+        }
+        git(&repo, &["init", "-q", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-q", "-m", "init"]);
+        let repo_str = repo.to_string_lossy().to_string();
+        let worktrees_dir = root.join("worktrees").to_string_lossy().to_string();
+
+        let err = create_worktree(
+            repo_str.clone(), worktrees_dir.clone(), "bad".to_string(), None, Some("main".to_string()), None,
+            Some(vec!["services/missing".to_string()]),
+        ).await.unwrap_err();
+        assert!(err.contains("services/missing"), "unexpected error: {}", err);
+        assert!(normalize_sparse_paths(&["../etc".to_string()]).is_err());
+        assert_eq!(normalize_sparse_paths(&[" ./services/api/ ".to_string(), "./".to_string()]).unwrap(), vec!["services/api"]);
+        assert!(validate_sparse_paths(&repo_str, "main", &["setup.py".to_string()]).is_err());
+
+        let wt = create_worktree(
+            repo_str, worktrees_dir, "api".to_string(), None, Some("main".to_string()), None,
+            Some(vec!["./services/api/".to_string()]),
+        ).await.unwrap();
+
+        let wt_path = PathBuf::from(&wt.path);
+        assert!(wt_path.join("services/api/main.py").exists());
+        // Cone mode keeps top-level files but skips unrelated directories
+        assert!(wt_path.join("setup.py").exists());
+        assert!(!wt_path.join("services/web").exists());
+        assert!(!wt_path.join("docs").exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_terminal_available() {
+        if cfg!(target_os = "macos") {
+            assert!(terminal_available(None, |_| false));
+            return;
+        }
+        assert!(!terminal_available(None, |_| false));
+        assert!(terminal_available(None, |bin| bin == "xterm"));
+        assert!(terminal_available(Some("foot"), |bin| bin == "foot"));
+        assert!(!terminal_available(Some(" "), |bin| bin == " "));
+    }
+
+    #[test]
+    fn test_compose_down_args() {
+        let project = compose_project_for_env("gold");
+        assert_eq!(compose_down_args(&project, false).unwrap(), "-p ushadow-gold down");
+        assert_eq!(compose_down_args(&project, true).unwrap(), "-p ushadow-gold down --volumes --rmi local");
+        assert_eq!(
+            compose_down_args(&compose_project_for_env("ushadow"), true).unwrap(),
+            "-p ushadow down --volumes --rmi local"
+        );
+
+        // An environment named "infra" still gets its own project
+        assert_eq!(compose_down_args(&compose_project_for_env("infra"), true).unwrap(), "-p ushadow-infra down --volumes --rmi local");
+        // The shared infra project is never pruned
+        assert_eq!(compose_down_args(INFRA_COMPOSE_PROJECT, false).unwrap(), "-p infra down");
+        assert!(compose_down_args(INFRA_COMPOSE_PROJECT, true).is_err());
+    }
+
+    #[test]
+    fn test_kill_window_target_and_shell_detection() {
+        assert_eq!(parse_window_target("ushadow-gold:ushadow-gold").unwrap(), ("ushadow-gold", "ushadow-gold"));
+        assert_eq!(parse_window_target("workmux:ush-12").unwrap(), ("workmux", "ush-12"));
+        assert!(parse_window_target("ushadow-gold").is_err());
+        assert!(parse_window_target(":ushadow-gold").is_err());
+        assert!(parse_window_target("workmux:").is_err());
+
+        assert!(is_shell_command("zsh"));
+        assert!(is_shell_command(""));
+        assert!(!is_shell_command("claude"));
+        assert!(!is_shell_command("vim"));
+    }
+}
@@ -7,15 +7,15 @@ mod commands;
 mod config;
 mod models;
 
-use commands::{AppState, check_prerequisites, discover_environments, get_os_type,
+use commands::{AppState, init_logging, get_log_path, check_prerequisites, discover_environments, get_os_type,
     // Claude session monitoring
-    install_claude_hooks, read_claude_sessions, get_hooks_installed, read_claude_transcript,
-    send_claude_approval,
-    discover_environments_with_config, discover_environments_v2,
+    install_claude_hooks, read_claude_sessions, get_session_events_for_worktree, get_tool_usage_stats, acknowledge_notification, get_unread_notifications, watch_claude_sessions, stop_watching_claude_sessions, get_hooks_installed, get_claude_hooks_status, repair_claude_hooks, uninstall_claude_hooks, rotate_session_log, compact_claude_sessions, read_claude_transcript,
+    send_claude_approval, send_claude_input,
+    discover_environments_with_config, benchmark_discovery, get_environment_url, invalidate_discovery_cache, discover_environments_v2, get_tray_environments, get_color_palette, get_environment_for_container, get_environment_services, get_compose_profiles, get_environment_by_port, collect_tray_environments,
     start_containers, stop_containers, get_container_status,
     start_infrastructure, stop_infrastructure, restart_infrastructure,
-    start_environment, stop_environment, check_ports,
-    check_backend_health, check_webui_health, open_browser, focus_window, set_project_root,
+    start_environment, get_recent_environments, export_logs_bundle, stop_environment, set_restart_policy, rebuild_environment, cancel_operation, get_environment_logs, get_status_history, get_environment_restart_counts, remove_orphaned_containers, check_ports,
+    check_backend_health, check_webui_health, open_browser, focus_window, relaunch_app, set_project_root,
     create_environment,
     // OAuth server commands
     start_oauth_server, wait_for_oauth_callback,
@@ -23,49 +23,129 @@ use commands::{AppState, check_prerequisites, discover_environments, get_os_type
     http_request,
     // Project/repo management (from repository.rs)
     get_default_project_dir, check_project_dir, clone_ushadow_repo,
-    update_ushadow_repo, get_current_branch, checkout_branch, get_base_branch,
+    update_ushadow_repo, get_current_branch, checkout_branch, list_stashes, apply_stash, drop_stash, get_base_branch,
     // Worktree commands
     list_worktrees, list_git_branches, check_worktree_exists, check_environment_conflict, create_worktree, create_worktree_with_workmux,
     merge_worktree_with_rebase, list_tmux_sessions, get_tmux_window_status,
     get_environment_tmux_status, get_tmux_info, ensure_tmux_running, attach_tmux_to_worktree,
-    open_in_vscode, open_in_vscode_with_tmux, remove_worktree, delete_environment,
-    get_tmux_sessions, kill_tmux_window, kill_tmux_server,
-    open_tmux_in_terminal, capture_tmux_pane, get_claude_status,
+    open_in_vscode, get_available_actions, open_in_vscode_with_tmux, open_env_file_in_editor, remove_worktree, delete_environment, rename_environment,
+    get_tmux_sessions, inspect_tmux_layout, kill_tmux_window, kill_tmux_server,
+    open_tmux_in_terminal, reattach_session, capture_tmux_pane, get_tmux_history_limit, export_tmux_pane_history, get_claude_status,
     // Kanban ticket commands
     create_ticket_worktree, attach_ticket_to_worktree, get_tickets_for_tmux_window, get_ticket_tmux_info,
-    start_coding_agent_for_ticket,
+    start_coding_agent_for_ticket, sync_ticket_statuses_from_git,
     // Kanban ticket/epic CRUD (local storage)
-    get_tickets, get_epics, create_ticket, update_ticket, delete_ticket, create_epic, update_epic, delete_epic,
+    get_tickets, search_tickets, get_epics, get_board_snapshot, get_epic_progress, create_ticket, update_ticket, delete_ticket, reorder_ticket, add_ticket_dependency, remove_ticket_dependency, get_blocked_tickets, export_kanban, import_kanban, verify_kanban_db, repair_kanban_db, create_epic, update_epic, delete_epic, get_project_ticket_prefix, set_project_ticket_prefix, set_default_base_branch, bulk_create_tickets,
     // Settings
-    load_launcher_settings, save_launcher_settings, write_credentials_to_worktree,
+    load_launcher_settings, save_launcher_settings, preview_agent_command, get_container_runtime, write_credentials_to_worktree,
     // Prerequisites config (from prerequisites_config.rs)
-    get_prerequisites_config, get_platform_prerequisites_config,
+    get_prerequisites_config, get_platform_prerequisites_config, get_prerequisite_versions,
     // Generic installer (from generic_installer.rs) - replaces all platform-specific installers
-    install_prerequisite, start_prerequisite,
+    install_prerequisite, uninstall_prerequisite, start_prerequisite, stop_docker,
     // Config commands (from 4bdc-ushadow-launchge)
     load_project_config, get_current_config, check_launcher_config_exists, validate_config_file,
     // Environment scanning
-    scan_env_file, scan_all_env_vars,
+    scan_env_file, scan_all_env_vars, diff_environments, sync_infra_endpoints_to_env, get_env_file_path, get_untracked_heavy_dirs, clean_worktree_artifacts, get_environment_disk_usage,
     // Infrastructure discovery
-    get_infra_services_from_compose,
+    get_infra_services_from_compose, validate_compose,
     // Permissions
     check_install_path};
+use models::TrayEnvItem;
+use std::sync::Mutex;
+use std::time::Duration;
 use tauri::{
-    CustomMenuItem, Manager, Menu, MenuItem, SystemTray,
-    SystemTrayEvent, SystemTrayMenu, SystemTrayMenuItem, Submenu,
+    AppHandle, CustomMenuItem, Manager, Menu, MenuItem, SystemTray,
+    SystemTrayEvent, SystemTrayMenu, SystemTrayMenuItem, SystemTraySubmenu, Submenu,
 };
 
+/// How often the tray's environment submenu is refreshed
+const TRAY_REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Menu item id prefix for environment entries ("env:{name}")
+const TRAY_ENV_ID_PREFIX: &str = "env:";
+
+/// Environments currently shown in the tray menu (used to resolve clicks and skip no-op rebuilds)
+#[derive(Default)]
+struct TrayState {
+    environments: Mutex<Vec<TrayEnvItem>>,
+}
+
 /// Create system tray menu
-fn create_tray_menu() -> SystemTrayMenu {
+fn create_tray_menu(environments: &[TrayEnvItem]) -> SystemTrayMenu {
     let open = CustomMenuItem::new("open".to_string(), "Open Launcher");
     let quit = CustomMenuItem::new("quit".to_string(), "Quit");
 
+    let mut env_menu = SystemTrayMenu::new();
+    if environments.is_empty() {
+        env_menu = env_menu.add_item(
+            CustomMenuItem::new("no_environments".to_string(), "No running environments").disabled(),
+        );
+    }
+    for env in environments {
+        let item = match env.url {
+            Some(ref url) => CustomMenuItem::new(
+                format!("{}{}", TRAY_ENV_ID_PREFIX, env.name),
+                format!("{} ({})", env.name, url.trim_start_matches("http://")),
+            ),
+            None => CustomMenuItem::new(format!("{}{}", TRAY_ENV_ID_PREFIX, env.name), env.name.clone()).disabled(),
+        };
+        env_menu = env_menu.add_item(item);
+    }
+
     SystemTrayMenu::new()
         .add_item(open)
+        .add_submenu(SystemTraySubmenu::new("Environments", env_menu))
         .add_native_item(SystemTrayMenuItem::Separator)
         .add_item(quit)
 }
 
+/// Rebuild the tray menu from the currently running environments (no-op if unchanged)
+fn refresh_tray_menu(app: &AppHandle) {
+    let project_root = app
+        .state::<AppState>()
+        .project_root
+        .lock()
+        .ok()
+        .and_then(|root| root.clone());
+
+    // Docker not running just means nothing to list
+    let environments = collect_tray_environments(project_root).unwrap_or_default();
+
+    let state = app.state::<TrayState>();
+    let mut current = match state.environments.lock() {
+        Ok(guard) => guard,
+        Err(_) => return,
+    };
+    if *current == environments {
+        return;
+    }
+
+    if let Err(e) = app.tray_handle().set_menu(create_tray_menu(&environments)) {
+        eprintln!("[tray] Failed to update tray menu: {}", e);
+        return;
+    }
+    *current = environments;
+}
+
+/// Open the URL of a tray environment entry
+fn open_tray_environment(app: &AppHandle, env_name: &str) {
+    let url = app
+        .state::<TrayState>()
+        .environments
+        .lock()
+        .ok()
+        .and_then(|envs| envs.iter().find(|e| e.name == env_name).and_then(|e| e.url.clone()));
+
+    match url {
+        Some(url) => {
+            if let Err(e) = open_browser(url) {
+                eprintln!("[tray] Failed to open {}: {}", env_name, e);
+            }
+        }
+        None => eprintln!("[tray] No URL for environment {}", env_name),
+    }
+}
+
 /// Create application menu
 fn create_app_menu() -> Menu {
     let launcher = CustomMenuItem::new("show_launcher", "Show Launcher");
@@ -121,11 +201,21 @@ fn create_app_menu() -> Menu {
 }
 
 fn main() {
-    let tray = SystemTray::new().with_menu(create_tray_menu());
+    // Held for the life of the app so buffered log lines are flushed on exit
+    let _log_guard = match init_logging() {
+        Ok(guard) => Some(guard),
+        Err(e) => {
+            eprintln!("[main] File logging disabled: {}", e);
+            None
+        }
+    };
+
+    let tray = SystemTray::new().with_menu(create_tray_menu(&[]));
     let menu = create_app_menu();
 
     tauri::Builder::default()
         .manage(AppState::new())
+        .manage(TrayState::default())
         .menu(menu)
         .on_menu_event(|event| {
             let window = event.window();
@@ -155,7 +245,11 @@ fn main() {
                 "quit" => {
                     std::process::exit(0);
                 }
-                _ => {}
+                other => {
+                    if let Some(env_name) = other.strip_prefix(TRAY_ENV_ID_PREFIX) {
+                        open_tray_environment(app, env_name);
+                    }
+                }
             },
             _ => {}
         })
@@ -173,7 +267,16 @@ fn main() {
             restart_infrastructure,
             // Environment management
             start_environment,
+            get_recent_environments,
+            export_logs_bundle,
             stop_environment,
+            set_restart_policy,
+            rebuild_environment,
+            cancel_operation,
+            get_environment_logs,
+            get_status_history,
+            get_environment_restart_counts,
+            remove_orphaned_containers,
             check_ports,
             // Legacy (for compatibility)
             start_containers,
@@ -183,6 +286,7 @@ fn main() {
             check_webui_health,
             open_browser,
             focus_window,
+            relaunch_app,
             discover_environments,
             create_environment,
             // Project/repo management (from repository.rs)
@@ -193,10 +297,22 @@ fn main() {
             update_ushadow_repo,
             get_current_branch,
             checkout_branch,
+            list_stashes,
+            apply_stash,
+            drop_stash,
             get_base_branch,
             // Worktree management
             discover_environments_with_config,
+            benchmark_discovery,
+            get_environment_url,
+            invalidate_discovery_cache,
             discover_environments_v2,
+            get_tray_environments,
+            get_color_palette,
+            get_environment_for_container,
+            get_environment_services,
+            get_compose_profiles,
+            get_environment_by_port,
             list_worktrees,
             list_git_branches,
             check_worktree_exists,
@@ -211,14 +327,21 @@ fn main() {
             ensure_tmux_running,
             attach_tmux_to_worktree,
             open_in_vscode,
+            get_available_actions,
             open_in_vscode_with_tmux,
+            open_env_file_in_editor,
             remove_worktree,
             delete_environment,
+            rename_environment,
             get_tmux_sessions,
+            inspect_tmux_layout,
             kill_tmux_window,
             kill_tmux_server,
             open_tmux_in_terminal,
+            reattach_session,
             capture_tmux_pane,
+            get_tmux_history_limit,
+            export_tmux_pane_history,
             get_claude_status,
             // Kanban ticket integration
             create_ticket_worktree,
@@ -226,25 +349,46 @@ fn main() {
             get_tickets_for_tmux_window,
             get_ticket_tmux_info,
             start_coding_agent_for_ticket,
+            sync_ticket_statuses_from_git,
             // Kanban ticket/epic CRUD (local storage)
             get_tickets,
+            search_tickets,
             get_epics,
+            get_board_snapshot,
+            get_epic_progress,
             create_ticket,
             update_ticket,
             delete_ticket,
+            reorder_ticket,
+            add_ticket_dependency,
+            remove_ticket_dependency,
+            get_blocked_tickets,
+            export_kanban,
+            import_kanban,
+            verify_kanban_db,
+            repair_kanban_db,
             create_epic,
             update_epic,
             delete_epic,
+            get_project_ticket_prefix,
+            set_project_ticket_prefix,
+            set_default_base_branch,
+            bulk_create_tickets,
             // Settings
             load_launcher_settings,
             save_launcher_settings,
+            preview_agent_command,
+            get_container_runtime,
             write_credentials_to_worktree,
             // Prerequisites config
             get_prerequisites_config,
             get_platform_prerequisites_config,
+            get_prerequisite_versions,
             // Generic installer
             install_prerequisite,
+            uninstall_prerequisite,
             start_prerequisite,
+            stop_docker,
             // Config management (from 4bdc-ushadow-launchge)
             load_project_config,
             get_current_config,
@@ -253,23 +397,52 @@ fn main() {
             // Environment scanning
             scan_env_file,
             scan_all_env_vars,
+            diff_environments,
+            sync_infra_endpoints_to_env,
+            get_env_file_path,
+            get_untracked_heavy_dirs,
+            clean_worktree_artifacts,
+            get_environment_disk_usage,
             // Infrastructure discovery
             get_infra_services_from_compose,
+            validate_compose,
             // Claude session monitoring
             install_claude_hooks,
             read_claude_sessions,
+            get_session_events_for_worktree,
+            get_tool_usage_stats,
+            acknowledge_notification,
+            get_unread_notifications,
+            watch_claude_sessions,
+            stop_watching_claude_sessions,
             get_hooks_installed,
+            get_claude_hooks_status,
+            repair_claude_hooks,
+            uninstall_claude_hooks,
+            rotate_session_log,
+            compact_claude_sessions,
             read_claude_transcript,
             send_claude_approval,
+            send_claude_input,
             // OAuth server
             start_oauth_server,
             wait_for_oauth_callback,
             // HTTP client
             http_request,
+            // Logging
+            get_log_path,
         ])
         .setup(|app| {
             let window = app.get_window("main").unwrap();
             window.show().unwrap();
+
+            // Keep the tray's environment submenu in sync with running containers
+            let handle = app.handle();
+            std::thread::spawn(move || loop {
+                refresh_tray_menu(&handle);
+                std::thread::sleep(TRAY_REFRESH_INTERVAL);
+            });
+
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -56,12 +56,70 @@ pub enum EnvironmentStatus {
     Available,
 }
 
+/// Docker healthcheck state of a running container, from least to most severe
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HealthState {
+    Healthy,
+    Starting,
+    Unhealthy,
+}
+
+/// Recorded change of an environment's status
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StatusChange {
+    pub env_name: String,
+    pub status: EnvironmentStatus,
+    /// RFC 3339 time the new status was first observed
+    pub timestamp: String,
+}
+
+/// Docker restart counter of a container
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ContainerRestartInfo {
+    pub container: String,
+    pub restart_count: u32,
+    /// When the container last started (RFC 3339, as reported by Docker)
+    pub started_at: Option<String>,
+}
+
 /// Git worktree information
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct WorktreeInfo {
     pub path: String,
     pub branch: String,
     pub name: String,
+    /// Commits on the branch that are not on its base (None if not computed or unresolvable)
+    #[serde(default)]
+    pub ahead: Option<u32>,
+    /// Commits on the base that are not on the branch (None if not computed or unresolvable)
+    #[serde(default)]
+    pub behind: Option<u32>,
+    /// Whether the working tree has uncommitted changes (false if not computed)
+    #[serde(default)]
+    pub dirty: bool,
+    /// Number of changed/untracked files reported by `git status --porcelain`
+    #[serde(default)]
+    pub changed_files: u32,
+}
+
+/// Entry from `git stash list`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StashEntry {
+    /// N in `stash@{N}`
+    pub index: u32,
+    /// Branch the stash was taken on, if recorded in its message
+    pub branch: Option<String>,
+    pub message: String,
+    /// Unix timestamp (seconds since epoch)
+    pub created_at: Option<i64>,
+}
+
+/// Outcome of a git command that ran (successfully or not)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GitCommandOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
 }
 
 /// Discovered Ushadow environment
@@ -82,6 +140,33 @@ pub struct UshadowEnvironment {
     pub is_worktree: bool,  // True if this environment is a git worktree
     pub created_at: Option<i64>,  // Unix timestamp (seconds since epoch)
     pub base_branch: Option<String>,  // "main" or "dev" - which base branch this worktree was created from
+    pub health: Option<HealthState>,  // Worst healthcheck state of the running containers; None without healthchecks
+}
+
+/// Environment a container belongs to, with its worktree if one could be found
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EnvRef {
+    pub env_name: String,
+    pub worktree_path: Option<String>,
+    pub branch: Option<String>,
+    /// `com.docker.compose.project` label, if the container has one
+    pub compose_project: Option<String>,
+}
+
+/// Log output for a single container
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ContainerLogs {
+    pub container: String,
+    pub lines: Vec<String>,
+}
+
+/// Running environment entry for the system tray menu
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TrayEnvItem {
+    pub name: String,
+    pub color: String,
+    pub backend_port: Option<u16>,
+    pub url: Option<String>,
 }
 
 /// Infrastructure service status
@@ -91,6 +176,7 @@ pub struct InfraService {
     pub display_name: String,
     pub running: bool,
     pub ports: Option<String>,
+    pub health: Option<HealthState>,
 }
 
 /// Environment discovery result
@@ -100,6 +186,74 @@ pub struct DiscoveryResult {
     pub environments: Vec<UshadowEnvironment>,
     pub docker_ok: bool,
     pub tailscale_ok: bool,
+    /// Ports declared by more than one environment's .env
+    pub port_conflicts: Vec<PortConflict>,
+}
+
+/// Port declared (BACKEND_PORT/WEBUI_PORT) by several environments, which can't all run
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PortConflict {
+    pub port: u16,
+    pub envs: Vec<String>,
+}
+
+/// Gitignored directory in a worktree and its size on disk
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct HeavyDir {
+    pub path: String,  // Relative to the worktree root
+    pub size_bytes: u64,
+}
+
+/// Disk space taken by one container or volume
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ResourceSize {
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+/// Disk space an environment takes: container writable layers, volumes and the worktree checkout
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EnvironmentDiskUsage {
+    pub containers: Vec<ResourceSize>,
+    pub volumes: Vec<ResourceSize>,
+    pub worktree_bytes: u64,
+    pub total_bytes: u64,
+}
+
+/// Time spent in each phase of one discovery pass, in milliseconds
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DiscoveryTimings {
+    pub total_ms: f64,
+    /// Docker and Tailscale prerequisite checks (Tailscale is cached for 10s)
+    pub prerequisites_ms: f64,
+    pub worktree_list_ms: f64,
+    pub docker_ps_ms: f64,
+    /// Wall time of the concurrent container inspect phase
+    pub container_inspect_ms: f64,
+    /// Time of each backend container inspect, in queue order
+    pub container_inspects: Vec<ContainerInspectTiming>,
+    /// Summed time reading worktree .env files
+    pub env_reads_ms: f64,
+    /// Summed time of per-environment Tailscale URL probes
+    pub tailscale_probes_ms: f64,
+    pub environment_count: usize,
+}
+
+/// Duration of one `docker inspect` during discovery
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ContainerInspectTiming {
+    pub container: String,
+    pub duration_ms: f64,
+}
+
+/// Which "open" actions the tools on this machine support
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ActionCapabilities {
+    pub editor: bool,       // Configured editor (or `code`) is on PATH
+    pub terminal: bool,     // A terminal emulator we can launch
+    pub browser: bool,      // A URL opener (open / xdg-open)
+    pub multiplexer: bool,  // tmux or zellij, per settings
+    pub docker: bool,       // Container runtime installed and running
 }
 
 /// Tmux session status for an environment
@@ -136,6 +290,31 @@ pub struct TmuxWindowInfo {
     pub panes: usize,
 }
 
+/// A tmux session with its windows and panes, for diagnosing layout issues
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TmuxLayoutNode {
+    pub session: String,
+    pub windows: Vec<TmuxLayoutWindow>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TmuxLayoutWindow {
+    pub index: String,
+    pub name: String,
+    pub active: bool,
+    pub panes: Vec<TmuxLayoutPane>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TmuxLayoutPane {
+    pub index: String,
+    pub pane_id: String,
+    pub current_command: String,
+    pub current_path: String,
+    pub tty: String,
+    pub active: bool,
+}
+
 /// Claude Code status from tmux
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ClaudeStatus {
@@ -162,6 +341,30 @@ pub struct ComposeServiceDefinition {
     pub profiles: Vec<String>, // Profiles this service belongs to
 }
 
+/// Service declared in an environment's compose files
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ServiceDef {
+    pub name: String,
+    pub image: Option<String>,
+    /// Port mappings as written in compose (e.g. "8000:8000", "${BACKEND_PORT:-8000}:8000")
+    pub ports: Vec<String>,
+    pub profiles: Vec<String>,
+    /// Compose file that declares the service
+    pub file: String,
+}
+
+/// Result of `validate_compose`
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ComposeValidation {
+    pub valid: bool,
+    /// Problems found by parsing the file ourselves
+    pub parse_errors: Vec<String>,
+    /// Whether `docker compose config` could be run at all
+    pub docker_checked: bool,
+    /// Error reported by `docker compose config`, if it rejected the file
+    pub docker_error: Option<String>,
+}
+
 /// Kanban ticket status
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -221,9 +424,79 @@ pub struct Ticket {
     pub updated_at: String,
 }
 
+/// Epic with ticket completion counts
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EpicWithProgress {
+    #[serde(flatten)]
+    pub epic: Epic,
+    pub total_tickets: u32,
+    pub done_tickets: u32,
+}
+
+/// Ticket counts per status for one epic (archived tickets are not counted)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct EpicProgress {
+    pub total: u32,
+    pub done: u32,
+    pub in_review: u32,
+    pub in_progress: u32,
+    pub todo: u32,
+    pub backlog: u32,
+    /// Whole percent of non-archived tickets that are done (0 when the epic has none)
+    pub percent_done: u32,
+}
+
+/// Everything the kanban board renders, loaded in one call
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BoardSnapshot {
+    pub epics: Vec<EpicWithProgress>,
+    pub tickets: Vec<Ticket>,
+    /// Ticket count per status (snake_case status name -> count)
+    pub status_counts: std::collections::HashMap<String, u32>,
+}
+
 /// Kanban data storage structure
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct KanbanData {
     pub tickets: Vec<Ticket>,
     pub epics: Vec<Epic>,
 }
+
+/// Versioned board document written by `export_kanban`; unknown fields are ignored on import
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KanbanExport {
+    pub version: u32,
+    pub exported_at: String,
+    pub epics: Vec<Epic>,
+    pub tickets: Vec<Ticket>,
+}
+
+/// What `import_kanban` wrote
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct KanbanImportSummary {
+    pub epics: u32,
+    pub tickets: u32,
+    /// Records not written because the local copy was at least as new
+    pub skipped: u32,
+}
+
+/// Result of checking the kanban database
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct KanbanIntegrityReport {
+    pub ok: bool,
+    /// Problems reported by `PRAGMA integrity_check` (or the error that stopped it)
+    pub integrity_errors: Vec<String>,
+    /// Rows whose references can't be resolved (`PRAGMA foreign_key_check`)
+    pub foreign_key_errors: Vec<String>,
+}
+
+/// Outcome of `repair_kanban_db`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KanbanRepairResult {
+    /// Copy of the database taken before repairing
+    pub backup_path: String,
+    /// Whether the database had to be recreated from its readable rows
+    pub rebuilt: bool,
+    pub rows_recovered: u32,
+    pub report: KanbanIntegrityReport,
+}
@@ -0,0 +1,170 @@
+/// Integration tests for the kanban-cli binary
+///
+/// Each test seeds its own SQLite database and runs the real binary against it.
+
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn temp_dir(prefix: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("{}-{}", prefix, uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Create a kanban.db with the columns the CLI reads and the given (id, title, status, project, order) rows
+fn seed_db(path: &Path, tickets: &[(&str, &str, &str, &str, i32)]) {
+    let conn = Connection::open(path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE tickets (
+            id TEXT PRIMARY KEY, title TEXT NOT NULL, description TEXT, status TEXT NOT NULL,
+            priority TEXT NOT NULL, epic_id TEXT, tags TEXT NOT NULL, color TEXT, tmux_window_name TEXT,
+            tmux_session_name TEXT, branch_name TEXT, worktree_path TEXT, environment_name TEXT,
+            project_id TEXT, assigned_to TEXT, \"order\" INTEGER NOT NULL,
+            created_at TEXT NOT NULL, updated_at TEXT NOT NULL
+        );
+        CREATE TABLE projects (id TEXT PRIMARY KEY, ticket_prefix TEXT NOT NULL);",
+    ).unwrap();
+    for (id, title, status, project, order) in tickets {
+        conn.execute(
+            "INSERT INTO tickets (id, title, status, priority, tags, project_id, \"order\", branch_name, created_at, updated_at)
+             VALUES (?1, ?2, ?3, 'medium', '[]', ?4, ?5, ?6, '', '')",
+            params![id, title, status, project, order, format!("feat/{}", id)],
+        ).unwrap();
+    }
+}
+
+/// Run kanban-cli against the given database file
+fn run_cli(db_path: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_kanban-cli"))
+        .arg("--db-path")
+        .arg(db_path)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_db_path_override() {
+    let dir = temp_dir("kanban-cli-db-path");
+    let db = dir.join("custom.db");
+    seed_db(&db, &[("ush-1", "First", "todo", "p1", 0)]);
+
+    let output = run_cli(&db, &["set-status", "ush-1", "in_review"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let status: String = Connection::open(&db).unwrap()
+        .query_row("SELECT status FROM tickets WHERE id = 'ush-1'", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(status, "in_review");
+
+    let output = run_cli(&db, &["find-by-branch", "feat/ush-1"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("ush-1 - First (in_review)"));
+
+    // A missing file is reported rather than silently created
+    let missing = dir.join("missing.db");
+    let output = run_cli(&missing, &["list"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("does not exist"));
+    assert!(!missing.exists());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_kanban-cli")).arg("--db-path").output().unwrap();
+    assert!(!output.status.success());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_list_json_filters_and_orders() {
+    let dir = temp_dir("kanban-cli-list");
+    let db = dir.join("kanban.db");
+    seed_db(&db, &[
+        ("ush-2", "Second", "todo", "p1", 1),
+        ("ush-1", "First", "todo", "p1", 0),
+        ("ush-3", "Reviewing", "in_review", "p1", 0),
+        ("oth-1", "Other project", "todo", "p2", 0),
+    ]);
+
+    let output = run_cli(&db, &["list", "--status", "todo", "--project", "p1", "--json"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let tickets: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let tickets = tickets.as_array().unwrap();
+    assert_eq!(tickets.len(), 2);
+    assert_eq!(tickets[0]["id"], "ush-1");
+    assert_eq!(tickets[1]["id"], "ush-2");
+    assert_eq!(tickets[0]["title"], "First");
+    assert_eq!(tickets[0]["status"], "todo");
+    assert_eq!(tickets[0]["branch_name"], "feat/ush-1");
+    assert!(tickets[0]["worktree_path"].is_null());
+
+    // Empty results are not an error
+    let output = run_cli(&db, &["list", "--status", "done", "--json"]);
+    assert!(output.status.success());
+    assert_eq!(serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap(), serde_json::json!([]));
+
+    let output = run_cli(&db, &["list"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("Found 4 ticket(s):"));
+
+    let output = run_cli(&db, &["list", "--status", "bogus"]);
+    assert!(!output.status.success());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_create_assigns_sequential_ids() {
+    let dir = temp_dir("kanban-cli-create");
+    let db = dir.join("kanban.db");
+    seed_db(&db, &[("ush-7", "Existing", "todo", "", 0), ("ush-x", "Not numbered", "todo", "", 1)]);
+
+    let create = |args: &[&str]| -> String {
+        let output = run_cli(&db, args);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    assert_eq!(create(&["create", "--title", "First"]), "ush-8");
+    assert_eq!(create(&["create", "--title", "Second", "--priority", "high", "--description", "Details"]), "ush-9");
+    // Projects get their own derived prefix and numbering
+    assert_eq!(create(&["create", "--title", "Other", "--project", "/repos/My-App"]), "myapp-1");
+    assert_eq!(create(&["create", "--title", "Other 2", "--project", "/repos/My-App"]), "myapp-2");
+
+    let conn = Connection::open(&db).unwrap();
+    let (status, priority, description, order): (String, String, Option<String>, i32) = conn.query_row(
+        "SELECT status, priority, description, \"order\" FROM tickets WHERE id = 'ush-9'",
+        [],
+        |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+    ).unwrap();
+    assert_eq!((status.as_str(), priority.as_str(), description.as_deref(), order), ("backlog", "high", Some("Details"), 1));
+
+    assert!(!run_cli(&db, &["create"]).status.success());
+    assert!(!run_cli(&db, &["create", "--title", "Bad", "--priority", "critical"]).status.success());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_move_to_backlog_and_todo() {
+    let dir = temp_dir("kanban-cli-move");
+    let db = dir.join("kanban.db");
+    seed_db(&db, &[("ush-1", "First", "in_progress", "p1", 0), ("ush-2", "Second", "in_review", "p1", 0)]);
+
+    let status = |id: &str| -> String {
+        Connection::open(&db).unwrap()
+            .query_row("SELECT status FROM tickets WHERE id = ?", [id], |r| r.get(0))
+            .unwrap()
+    };
+
+    assert!(run_cli(&db, &["move-to-backlog", "feat/ush-1"]).status.success());
+    assert_eq!(status("ush-1"), "backlog");
+    assert!(run_cli(&db, &["move-to-todo", "feat/ush-2"]).status.success());
+    assert_eq!(status("ush-2"), "todo");
+
+    // Unknown identifiers are not an error (hooks run in worktrees without tickets)
+    assert!(run_cli(&db, &["move-to-todo", "feat/nothing"]).status.success());
+    assert!(!run_cli(&db, &["move-to-todo"]).status.success());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
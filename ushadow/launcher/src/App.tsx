@@ -311,8 +311,12 @@ function App() {
     }
   }, [log])
 
-  const refreshDiscovery = useCallback(async (silent = false) => {
+  // Results after a user action must be fresh; only polling may reuse the backend's cached discovery
+  const refreshDiscovery = useCallback(async (silent = false, cached = false) => {
     try {
+      if (!cached) {
+        await tauri.invalidateDiscoveryCache()
+      }
       const disc = await tauri.discoverEnvironments()
       setDiscovery(disc)
 
@@ -426,7 +430,7 @@ function App() {
     // Set up periodic polling
     const interval = setInterval(() => {
       refreshPrerequisites(true)
-      refreshDiscovery(true)
+      refreshDiscovery(true, true)
     }, 60000) // 60 seconds - reduced from 30s for better performance
 
     return () => clearInterval(interval)
@@ -125,9 +125,10 @@ export function KanbanBoard({ projectId, backendUrl, projectRoot }: KanbanBoardP
       const { tauri } = await import('../hooks/useTauri')
 
       // Stop agent when moving to todo: kill tmux window
-      if (targetStatus === 'todo' && ticket.tmux_window_name) {
+      if (targetStatus === 'todo' && ticket.tmux_session_name && ticket.tmux_window_name) {
         try {
-          await tauri.killTmuxWindow(ticket.tmux_window_name)
+          // Moving back to todo is meant to stop the agent, so force past the running-agent check
+          await tauri.killTmuxWindow(`${ticket.tmux_session_name}:${ticket.tmux_window_name}`, true)
         } catch (err) {
           console.error('[KanbanBoard] Failed to kill tmux window:', err)
         }
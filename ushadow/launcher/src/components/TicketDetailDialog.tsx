@@ -133,9 +133,10 @@ export function TicketDetailDialog({
       const statusChanged = status !== ticket.status
 
       // Stop agent when moving to todo: kill the tmux window
-      if (statusChanged && status === 'todo' && ticket.tmux_window_name) {
+      if (statusChanged && status === 'todo' && ticket.tmux_session_name && ticket.tmux_window_name) {
         try {
-          await tauri.killTmuxWindow(ticket.tmux_window_name)
+          // Moving back to todo is meant to stop the agent, so force past the running-agent check
+          await tauri.killTmuxWindow(`${ticket.tmux_session_name}:${ticket.tmux_window_name}`, true)
         } catch (err) {
           console.error('[TicketDetail] Failed to kill tmux window:', err)
           // Non-fatal: continue with status update
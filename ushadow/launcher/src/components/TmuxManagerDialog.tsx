@@ -33,9 +33,22 @@ export function TmuxManagerDialog({ isOpen, onClose, onRefresh }: TmuxManagerDia
     }
   }
 
-  const handleKillWindow = async (windowName: string) => {
+  // Kill a window, asking before killing one with an agent (or anything else) running in it
+  const killWindow = async (target: string) => {
     try {
-      await tauri.killTmuxWindow(windowName)
+      await tauri.killTmuxWindow(target)
+    } catch (err) {
+      const message = String(err)
+      if (!message.startsWith('Agent running') || !confirm(`${message}\n\nKill it anyway?`)) {
+        throw err
+      }
+      await tauri.killTmuxWindow(target, true)
+    }
+  }
+
+  const handleKillWindow = async (sessionName: string, windowName: string) => {
+    try {
+      await killWindow(`${sessionName}:${windowName}`)
       await loadSessions()
       onRefresh?.()
     } catch (err) {
@@ -49,7 +62,7 @@ export function TmuxManagerDialog({ isOpen, onClose, onRefresh }: TmuxManagerDia
       const session = sessions.find(s => s.name === sessionName)
       if (session) {
         for (const window of session.windows) {
-          await tauri.killTmuxWindow(window.name)
+          await killWindow(`${sessionName}:${window.name}`)
         }
       }
       await loadSessions()
@@ -193,7 +206,7 @@ export function TmuxManagerDialog({ isOpen, onClose, onRefresh }: TmuxManagerDia
                           </span>
                         </div>
                         <button
-                          onClick={() => handleKillWindow(window.name)}
+                          onClick={() => handleKillWindow(session.name, window.name)}
                           className="px-3 py-1 rounded bg-red-500/20 text-red-400 hover:bg-red-500/30 transition-colors text-xs font-medium flex items-center gap-1"
                           title="Kill this window"
                           data-testid={`kill-window-${window.name}`}
@@ -22,6 +22,36 @@ export interface Prerequisites {
   tmux_version: string | null
 }
 
+// Gitignored directory in a worktree (path relative to the worktree root)
+export interface HeavyDir {
+  path: string
+  size_bytes: number
+}
+
+export interface ResourceSize {
+  name: string
+  size_bytes: number
+}
+
+export interface EnvironmentDiskUsage {
+  containers: ResourceSize[]  // Writable layer of each of the environment's containers
+  volumes: ResourceSize[]
+  worktree_bytes: number
+  total_bytes: number
+}
+
+// Which "open" actions the local tools support
+export interface ActionCapabilities {
+  editor: boolean
+  terminal: boolean
+  browser: boolean
+  multiplexer: boolean  // tmux or zellij, per settings
+  docker: boolean
+}
+
+// Docker healthcheck state of a running container
+export type HealthState = 'Healthy' | 'Starting' | 'Unhealthy'
+
 export interface UshadowEnvironment {
   name: string
   color: string
@@ -37,6 +67,7 @@ export interface UshadowEnvironment {
   branch: string | null
   is_worktree: boolean
   base_branch: string | null  // "main" or "dev" - which base branch this worktree was created from
+  health: HealthState | null  // Worst healthcheck state of the running containers
 }
 
 // Legacy alias for backward compatibility
@@ -47,6 +78,7 @@ export interface InfraService {
   display_name: string
   running: boolean
   ports: string | null
+  health: HealthState | null
 }
 
 // Legacy alias
@@ -56,11 +88,67 @@ export interface ContainerStatus {
   status: string
 }
 
+export interface StatusChange {
+  env_name: string
+  status: 'Running' | 'Partial' | 'Stopped' | 'Available'
+  timestamp: string  // RFC 3339
+}
+
+export interface ContainerRestartInfo {
+  container: string
+  restart_count: number
+  started_at: string | null
+}
+
+export interface ContainerLogs {
+  container: string
+  lines: string[]
+}
+
+export interface EnvRef {
+  env_name: string
+  worktree_path: string | null
+  branch: string | null
+  compose_project: string | null
+}
+
+export interface TrayEnvItem {
+  name: string
+  color: string
+  backend_port: number | null
+  url: string | null
+}
+
+// Port declared in more than one environment's .env
+export interface PortConflict {
+  port: number
+  envs: string[]
+}
+
 export interface Discovery {
   infrastructure: InfraService[]
   environments: UshadowEnvironment[]
   docker_ok: boolean
   tailscale_ok: boolean
+  port_conflicts: PortConflict[]
+}
+
+// Per-phase durations of one discovery pass (ms)
+export interface ContainerInspectTiming {
+  container: string
+  duration_ms: number
+}
+
+export interface DiscoveryTimings {
+  total_ms: number
+  prerequisites_ms: number
+  worktree_list_ms: number
+  docker_ps_ms: number
+  container_inspect_ms: number
+  container_inspects: ContainerInspectTiming[]
+  env_reads_ms: number
+  tailscale_probes_ms: number
+  environment_count: number
 }
 
 // Launcher settings
@@ -71,11 +159,36 @@ export interface CodingAgentConfig {
   auto_start: boolean
 }
 
+export interface AgentProfile {
+  name: string
+  command: string
+  args: string[]
+  resume_args?: string | null
+  session_dir?: string | null
+  process_names: string[]
+  env: string[]
+  accepts_prompt: boolean
+  supports_teams: boolean
+}
+
 export interface LauncherSettings {
   default_admin_email: string | null
   default_admin_password: string | null
   default_admin_name: string | null
   coding_agent: CodingAgentConfig
+  session_prefix?: string
+  window_prefix?: string
+  container_runtime?: 'docker' | 'podman' | null
+  services?: string[] | null
+  agent_profiles?: AgentProfile[]
+  session_log_path?: string | null
+  network_retries?: number  // Attempts for downloads/GETs on transient failures (default 3)
+  editor_command?: string  // Editor binary for "Open in VS Code": code, cursor, windsurf... (default code)
+  multiplexer?: 'tmux' | 'zellij'  // Terminal multiplexer for environment sessions (default tmux)
+  terminal_emulator?: string | null  // wezterm, kitty, alacritty, gnome-terminal...; auto-detected when unset
+  infra_patterns?: Record<string, string>  // Extra infra services to recognize: service id -> display name
+  discovery_cache_ttl_ms?: number  // How long discovery results are reused; 0 disables (default 3000)
+  auth_login_path?: string  // Backend login path used for authenticated environment URLs (default /api/auth/login)
 }
 
 // Prerequisites configuration types
@@ -104,11 +217,13 @@ export interface InstallationMethod {
   package?: string
   url?: string
   packages?: Record<string, string>
+  sha256?: string  // Expected digest of the downloaded script/installer
 }
 
 export interface PrerequisitesConfig {
   prerequisites: Prerequisite[]
   installation_methods?: Record<string, Record<string, InstallationMethod>>
+  uninstall_methods?: Record<string, Record<string, InstallationMethod>>
 }
 
 export interface PlatformPrerequisitesConfig {
@@ -116,6 +231,12 @@ export interface PlatformPrerequisitesConfig {
   installation_methods?: Record<string, InstallationMethod>
 }
 
+// Payload of the 'prereq-install-progress' event emitted while install_prerequisite runs
+export interface InstallProgress {
+  prerequisite_id: string
+  line: string
+}
+
 // Tauri command wrappers with proper typing
 export const tauri = {
   // System checks
@@ -125,6 +246,10 @@ export const tauri = {
   // Prerequisites configuration
   getPrerequisitesConfig: () => invoke<PrerequisitesConfig>('get_prerequisites_config'),
   getPlatformPrerequisitesConfig: (platform: string) => invoke<PlatformPrerequisitesConfig>('get_platform_prerequisites_config', { platform }),
+  getPrerequisiteVersions: () => invoke<Record<string, string | null>>('get_prerequisite_versions'),
+  exportLogsBundle: (outputPath: string, mainRepo: string) =>
+    invoke<string>('export_logs_bundle', { outputPath, mainRepo }),
+  getLogPath: () => invoke<string>('get_log_path'),
 
   // Project management
   getDefaultProjectDir: () => invoke<string>('get_default_project_dir'),
@@ -134,6 +259,9 @@ export const tauri = {
   updateUshadowRepo: (projectDir: string) => invoke<string>('update_ushadow_repo', { projectDir }),
   getCurrentBranch: (path: string) => invoke<string>('get_current_branch', { path }),
   checkoutBranch: (path: string, branch: string) => invoke<string>('checkout_branch', { path, branch }),
+  listStashes: (worktreePath: string) => invoke<StashEntry[]>('list_stashes', { worktreePath }),
+  applyStash: (worktreePath: string, index: number) => invoke<GitCommandOutput>('apply_stash', { worktreePath, index }),
+  dropStash: (worktreePath: string, index: number) => invoke<GitCommandOutput>('drop_stash', { worktreePath, index }),
   getBaseBranch: (repoPath: string, branch: string) => invoke<string | null>('get_base_branch', { repoPath, branch }),
 
   // Infrastructure management
@@ -143,10 +271,39 @@ export const tauri = {
 
   // Environment management
   discoverEnvironments: () => invoke<Discovery>('discover_environments'),
+  benchmarkDiscovery: (mainRepo: string) =>
+    invoke<DiscoveryTimings>('benchmark_discovery', { mainRepo }),
+  invalidateDiscoveryCache: () => invoke<void>('invalidate_discovery_cache'),
+  // Tailscale or localhost URL; withToken appends ?token= from the backend when it can issue one
+  getEnvironmentUrl: (envName: string, mainRepo: string, withToken: boolean) =>
+    invoke<string>('get_environment_url', { envName, mainRepo, withToken }),
+  getTrayEnvironments: () => invoke<TrayEnvItem[]>('get_tray_environments'),
+  getColorPalette: () => invoke<Record<string, string>>('get_color_palette'),
+  getEnvironmentForContainer: (containerName: string) =>
+    invoke<EnvRef | null>('get_environment_for_container', { containerName }),
+  getEnvironmentByPort: (mainRepo: string, port: number) =>
+    invoke<string | null>('get_environment_by_port', { mainRepo, port }),
+  getEnvironmentServices: (worktreePath: string) =>
+    invoke<ServiceDef[]>('get_environment_services', { worktreePath }),
+  getComposeProfiles: (worktreePath: string) => invoke<string[]>('get_compose_profiles', { worktreePath }),
   createEnvironment: (name: string, mode?: 'dev' | 'prod') => invoke<string>('create_environment', { name, mode }),
   checkPorts: () => invoke<[boolean, boolean, number]>('check_ports'),
-  startEnvironment: (envName: string, envPath?: string) => invoke<string>('start_environment', { envName, envPath }),
+  // Pass operationId to allow cancelOperation; a cancelled start rejects with 'Cancelled'.
+  // profiles (from getComposeProfiles) opt into optional compose services
+  startEnvironment: (envName: string, envPath?: string, operationId?: string, profiles?: string[]) =>
+    invoke<string>('start_environment', { envName, envPath, operationId, profiles }),
+  cancelOperation: (operationId: string) => invoke<boolean>('cancel_operation', { operationId }),
+  getRecentEnvironments: (limit: number) => invoke<string[]>('get_recent_environments', { limit }),
   stopEnvironment: (envName: string) => invoke<string>('stop_environment', { envName }),
+  setRestartPolicy: (containerName: string, policy: 'no' | 'on-failure' | 'always') =>
+    invoke<void>('set_restart_policy', { containerName, policy }),
+  rebuildEnvironment: (envName: string, noCache: boolean, envPath?: string) => invoke<string>('rebuild_environment', { envName, envPath, noCache }),
+  getEnvironmentLogs: (envName: string, tail?: number, since?: string) => invoke<ContainerLogs[]>('get_environment_logs', { envName, tail, since }),
+  // Status transitions recorded during discovery; since is an RFC 3339 timestamp
+  getStatusHistory: (envName: string, since?: string) => invoke<StatusChange[]>('get_status_history', { envName, since }),
+  getEnvironmentRestartCounts: (envName: string) =>
+    invoke<ContainerRestartInfo[]>('get_environment_restart_counts', { envName }),
+  removeOrphanedContainers: (mainRepo: string, dryRun: boolean) => invoke<string[]>('remove_orphaned_containers', { mainRepo, dryRun }),
 
   // Legacy (for compatibility)
   startContainers: (envName: string) => invoke<string>('start_containers', { envName }),
@@ -159,7 +316,9 @@ export const tauri = {
 
   // Generic installer (cross-platform, YAML-driven)
   installPrerequisite: (prerequisiteId: string) => invoke<string>('install_prerequisite', { prerequisiteId }),
+  uninstallPrerequisite: (prerequisiteId: string) => invoke<string>('uninstall_prerequisite', { prerequisiteId }),
   startPrerequisite: (prerequisiteId: string) => invoke<string>('start_prerequisite', { prerequisiteId }),
+  stopDocker: () => invoke<string>('stop_docker'),
 
   // Deprecated: Old platform-specific installers (kept for backward compatibility)
   // Use installPrerequisite() instead
@@ -176,6 +335,7 @@ export const tauri = {
 
   // Utilities
   openBrowser: (url: string) => invoke<void>('open_browser', { url }),
+  relaunchApp: () => invoke<void>('relaunch_app'),
 
   // OAuth server
   startOAuthServer: () => invoke<[number, string]>('start_oauth_server'),
@@ -186,14 +346,15 @@ export const tauri = {
     invoke<{status: number, body: string, headers: Record<string, string>}>('http_request', { url, method, headers, body }),
 
   // Worktree management
-  listWorktrees: (mainRepo: string) => invoke<WorktreeInfo[]>('list_worktrees', { mainRepo }),
+  listWorktrees: (mainRepo: string, includeDivergence?: boolean, includeStatus?: boolean) =>
+    invoke<WorktreeInfo[]>('list_worktrees', { mainRepo, includeDivergence, includeStatus }),
   listGitBranches: (mainRepo: string) => invoke<string[]>('list_git_branches', { mainRepo }),
   checkWorktreeExists: (mainRepo: string, branch: string) => invoke<WorktreeInfo | null>('check_worktree_exists', { mainRepo, branch }),
   checkEnvironmentConflict: (mainRepo: string, envName: string) => invoke<EnvironmentConflict | null>('check_environment_conflict', { mainRepo, envName }),
-  createWorktree: (mainRepo: string, worktreesDir: string, name: string, branchName?: string, baseBranch?: string) =>
-    invoke<WorktreeInfo>('create_worktree', { mainRepo, worktreesDir, name, branchName, baseBranch }),
-  createWorktreeWithWorkmux: (mainRepo: string, name: string, branchName?: string, baseBranch?: string, background?: boolean, customWindowName?: string) =>
-    invoke<WorktreeInfo>('create_worktree_with_workmux', { mainRepo, name, branchName, baseBranch, background, customWindowName }),
+  createWorktree: (mainRepo: string, worktreesDir: string, name: string, branchName?: string, baseBranch?: string, baseRef?: string, sparsePaths?: string[]) =>
+    invoke<WorktreeInfo>('create_worktree', { mainRepo, worktreesDir, name, branchName, baseBranch, baseRef, sparsePaths }),
+  createWorktreeWithWorkmux: (mainRepo: string, name: string, branchName?: string, baseBranch?: string, background?: boolean, customWindowName?: string, baseRef?: string) =>
+    invoke<WorktreeInfo>('create_worktree_with_workmux', { mainRepo, name, branchName, baseBranch, background, customWindowName, baseRef }),
   mergeWorktreeWithRebase: (mainRepo: string, name: string, useRebase: boolean, keepWorktree: boolean) =>
     invoke<string>('merge_worktree_with_rebase', { mainRepo, name, useRebase, keepWorktree }),
   listTmuxSessions: () => invoke<string[]>('list_tmux_sessions'),
@@ -203,21 +364,34 @@ export const tauri = {
   ensureTmuxRunning: () => invoke<string>('ensure_tmux_running'),
   attachTmuxToWorktree: (worktreePath: string, envName: string, windowNameOverride?: string) => invoke<string>('attach_tmux_to_worktree', { worktreePath, envName, windowNameOverride }),
   openInVscode: (path: string, envName?: string) => invoke<void>('open_in_vscode', { path, envName }),
+  getAvailableActions: () => invoke<ActionCapabilities>('get_available_actions'),
   openInVscodeWithTmux: (path: string, envName: string) => invoke<void>('open_in_vscode_with_tmux', { path, envName }),
+  openEnvFileInEditor: (worktreePath: string, envName: string) =>
+    invoke<string>('open_env_file_in_editor', { worktreePath, envName }),
   removeWorktree: (mainRepo: string, name: string) => invoke<void>('remove_worktree', { mainRepo, name }),
-  deleteEnvironment: (mainRepo: string, envName: string) => invoke<string>('delete_environment', { mainRepo, envName }),
+  deleteEnvironment: (mainRepo: string, envName: string, dryRun?: boolean, prune?: boolean) =>
+    invoke<string>('delete_environment', { mainRepo, envName, dryRun, prune }),
+  renameEnvironment: (mainRepo: string, oldName: string, newName: string) =>
+    invoke<string>('rename_environment', { mainRepo, oldName, newName }),
 
   // Tmux management
   getTmuxSessions: () => invoke<TmuxSessionInfo[]>('get_tmux_sessions'),
-  killTmuxWindow: (windowName: string) => invoke<string>('kill_tmux_window', { windowName }),
+  inspectTmuxLayout: () => invoke<TmuxLayoutNode[]>('inspect_tmux_layout'),
+  // target is 'session:window'; without force, fails with an 'Agent running' error if the window is busy
+  killTmuxWindow: (target: string, force?: boolean) => invoke<string>('kill_tmux_window', { target, force }),
   killTmuxServer: () => invoke<string>('kill_tmux_server'),
   openTmuxInTerminal: (windowName: string, worktreePath: string, environmentName?: string) => invoke<string>('open_tmux_in_terminal', { windowName, worktreePath, environmentName }),
+  reattachSession: (envName: string, worktreePath: string) => invoke<string>('reattach_session', { envName, worktreePath }),
   captureTmuxPane: (windowName: string) => invoke<string>('capture_tmux_pane', { windowName }),
+  getTmuxHistoryLimit: (windowName: string) => invoke<number>('get_tmux_history_limit', { windowName }),
+  exportTmuxPaneHistory: (windowName: string, path: string) =>
+    invoke<number>('export_tmux_pane_history', { windowName, path }),
   getClaudeStatus: (windowName: string) => invoke<ClaudeStatus>('get_claude_status', { windowName }),
 
   // Settings
   loadLauncherSettings: () => invoke<LauncherSettings>('load_launcher_settings'),
   saveLauncherSettings: (settings: LauncherSettings) => invoke<void>('save_launcher_settings', { settings }),
+  previewAgentCommand: () => invoke<string>('preview_agent_command'),
   writeCredentialsToWorktree: (worktreePath: string, adminEmail: string, adminPassword: string, adminName?: string) =>
     invoke<void>('write_credentials_to_worktree', { worktreePath, adminEmail, adminPassword, adminName }),
 
@@ -230,13 +404,28 @@ export const tauri = {
   // Environment scanning
   scanEnvFile: (projectRoot: string) => invoke<DetectedPort[]>('scan_env_file', { projectRoot }),
   scanAllEnvVars: (projectRoot: string) => invoke<DetectedEnvVar[]>('scan_all_env_vars', { projectRoot }),
+  diffEnvironments: (pathA: string, pathB: string) => invoke<EnvComparison>('diff_environments', { pathA, pathB }),
+  // Update *_HOST/*_PORT keys in the env's .env to the running infra ports; returns changed keys
+  getEnvFilePath: (worktreePath: string) => invoke<string>('get_env_file_path', { worktreePath }),
+  getUntrackedHeavyDirs: (worktreePath: string) =>
+    invoke<HeavyDir[]>('get_untracked_heavy_dirs', { worktreePath }),
+  cleanWorktreeArtifacts: (worktreePath: string, targets: string[]) =>
+    invoke<number>('clean_worktree_artifacts', { worktreePath, targets }),
+  getEnvironmentDiskUsage: (envName: string) =>
+    invoke<EnvironmentDiskUsage>('get_environment_disk_usage', { envName }),
+  syncInfraEndpointsToEnv: (worktreePath: string, projectRoot: string) =>
+    invoke<string[]>('sync_infra_endpoints_to_env', { worktreePath, projectRoot }),
 
   // Infrastructure discovery
   getInfraServicesFromCompose: () => invoke<InfraService[]>('get_infra_services_from_compose'),
+  validateCompose: (projectRoot: string, file: string) => invoke<ComposeValidation>('validate_compose', { projectRoot, file }),
 
   // Kanban ticket/epic management (local storage)
   getTickets: (projectId?: string) => invoke<Ticket[]>('get_tickets', { projectId }),
+  searchTickets: (query: string, projectId?: string) => invoke<Ticket[]>('search_tickets', { query, projectId }),
   getEpics: (projectId?: string) => invoke<Epic[]>('get_epics', { projectId }),
+  getBoardSnapshot: (projectId?: string) => invoke<BoardSnapshot>('get_board_snapshot', { projectId }),
+  getEpicProgress: (epicId: string) => invoke<EpicProgress>('get_epic_progress', { epicId }),
   createTicket: (
     title: string,
     description: string | null,
@@ -262,6 +451,17 @@ export const tauri = {
     environmentName?: string
   ) => invoke<Ticket>('update_ticket', { id, title, description, status, priority, epicId, tags, order, worktreePath, branchName, tmuxWindowName, tmuxSessionName, environmentName }),
   deleteTicket: (id: string) => invoke<void>('delete_ticket', { id }),
+  reorderTicket: (id: string, newStatus: TicketStatus, newIndex: number) =>
+    invoke<Ticket>('reorder_ticket', { id, newStatus, newIndex }),
+  addTicketDependency: (ticketId: string, dependsOnId: string) =>
+    invoke<void>('add_ticket_dependency', { ticketId, dependsOnId }),
+  removeTicketDependency: (ticketId: string, dependsOnId: string) =>
+    invoke<void>('remove_ticket_dependency', { ticketId, dependsOnId }),
+  getBlockedTickets: () => invoke<Ticket[]>('get_blocked_tickets'),
+  exportKanban: (path: string) => invoke<number>('export_kanban', { path }),
+  importKanban: (path: string, merge: boolean) => invoke<KanbanImportSummary>('import_kanban', { path, merge }),
+  verifyKanbanDb: () => invoke<KanbanIntegrityReport>('verify_kanban_db'),
+  repairKanbanDb: () => invoke<KanbanRepairResult>('repair_kanban_db'),
   createEpic: (
     title: string,
     description: string | null,
@@ -278,6 +478,14 @@ export const tauri = {
     branchName?: string
   ) => invoke<Epic>('update_epic', { id, title, description, color, branchName }),
   deleteEpic: (id: string) => invoke<void>('delete_epic', { id }),
+  bulkCreateTickets: (lines: string[], epicId?: string, projectId?: string) =>
+    invoke<Ticket[]>('bulk_create_tickets', { lines, epicId, projectId }),
+  getProjectTicketPrefix: (projectId?: string) =>
+    invoke<string>('get_project_ticket_prefix', { projectId }),
+  setProjectTicketPrefix: (projectId: string, prefix: string) =>
+    invoke<void>('set_project_ticket_prefix', { projectId, prefix }),
+  setDefaultBaseBranch: (projectId: string, branch: string) =>
+    invoke<void>('set_default_base_branch', { projectId, branch }),
 
   // Kanban ticket-worktree integration
   createTicketWorktree: (request: {
@@ -293,6 +501,7 @@ export const tauri = {
     branch_name: string
     tmux_window_name: string
     tmux_session_name: string
+    reused: boolean
   }>('create_ticket_worktree', { request }),
   attachTicketToWorktree: (ticketId: string, worktreePath: string, branchName: string) =>
     invoke<{
@@ -300,22 +509,44 @@ export const tauri = {
       branch_name: string
       tmux_window_name: string
       tmux_session_name: string
+      reused: boolean
     }>('attach_ticket_to_worktree', { ticketId, worktreePath, branchName }),
+  syncTicketStatusesFromGit: (mainRepo: string, baseBranch: string) =>
+    invoke<[string, string][]>('sync_ticket_statuses_from_git', { mainRepo, baseBranch }),
   startCodingAgentForTicket: (
     ticketId: string,
     tmuxWindowName: string,
     tmuxSessionName: string,
-    worktreePath: string
-  ) => invoke<void>('start_coding_agent_for_ticket', { ticketId, tmuxWindowName, tmuxSessionName, worktreePath }),
+    worktreePath: string,
+    allowBlocked?: boolean
+  ) => invoke<void>('start_coding_agent_for_ticket', { ticketId, tmuxWindowName, tmuxSessionName, worktreePath, allowBlocked }),
 
   // Claude session monitoring
   installClaudeHooks: () => invoke<string>('install_claude_hooks'),
   readClaudeSessions: (projectRoot: string) => invoke<ClaudeSessionEvent[]>('read_claude_sessions', { projectRoot }),
+  getSessionEventsForWorktree: (worktreePath: string, hours: number, limit: number) =>
+    invoke<ClaudeSessionEvent[]>('get_session_events_for_worktree', { worktreePath, hours, limit }),
+  acknowledgeNotification: (sessionId: string, timestamp: string) =>
+    invoke<void>('acknowledge_notification', { sessionId, timestamp }),
+  getUnreadNotifications: (worktreePath: string | null, hours: number) =>
+    invoke<ClaudeSessionEvent[]>('get_unread_notifications', { worktreePath, hours }),
+  // New events are pushed as the 'claude-session-event' Tauri event (payload: ClaudeSessionEvent)
+  getToolUsageStats: (worktreePath: string | null, hours: number) =>
+    invoke<ToolStat[]>('get_tool_usage_stats', { worktreePath, hours }),
+  watchClaudeSessions: () => invoke<void>('watch_claude_sessions'),
+  stopWatchingClaudeSessions: () => invoke<void>('stop_watching_claude_sessions'),
   getHooksInstalled: () => invoke<boolean>('get_hooks_installed'),
-  readClaudeTranscript: (sessionId: string, cwd: string) =>
-    invoke<TranscriptMessage[]>('read_claude_transcript', { sessionId, cwd }),
+  getClaudeHooksStatus: () => invoke<HooksStatus>('get_claude_hooks_status'),
+  repairClaudeHooks: () => invoke<string>('repair_claude_hooks'),
+  uninstallClaudeHooks: () => invoke<string>('uninstall_claude_hooks'),
+  rotateSessionLog: (maxMb: number) => invoke<boolean>('rotate_session_log', { maxMb }),
+  compactClaudeSessions: (maxEvents: number) => invoke<number>('compact_claude_sessions', { maxEvents }),
+  readClaudeTranscript: (sessionId: string, cwd: string, since?: string, roles?: string[], limit?: number) =>
+    invoke<TranscriptMessage[]>('read_claude_transcript', { sessionId, cwd, since, roles, limit }),
   sendClaudeApproval: (cwd: string, approve: boolean) =>
     invoke<string>('send_claude_approval', { cwd, approve }),
+  sendClaudeInput: (cwd: string, text: string, submit: boolean) =>
+    invoke<string>('send_claude_input', { cwd, text, submit }),
 }
 
 // DetectedPort type (from env_scanner.rs)
@@ -335,6 +566,24 @@ export interface DetectedEnvVar {
   should_append_env_name: boolean
 }
 
+export interface EnvSide {
+  path: string
+  env_name: string
+  branch: string | null
+  containers: string[]
+}
+
+export interface EnvComparison {
+  a: EnvSide
+  b: EnvSide
+  same_branch: boolean
+  env_only_in_a: string[]
+  env_only_in_b: string[]
+  env_differing: { key: string; value_a: string; value_b: string }[]
+  services_only_in_a: string[]
+  services_only_in_b: string[]
+}
+
 // ComposeServiceDefinition type (from docker-compose parsing)
 export interface ComposeServiceDefinition {
   id: string              // Service name from compose (e.g., "postgres", "redis")
@@ -343,11 +592,47 @@ export interface ComposeServiceDefinition {
   profiles: string[]      // Profiles this service belongs to
 }
 
+// Service declared in an environment's compose files
+export interface ServiceDef {
+  name: string
+  image: string | null
+  ports: string[]      // As written in compose, e.g. "8000:8000"
+  profiles: string[]
+  file: string         // Compose file that declares it
+}
+
+// Result of validate_compose
+export interface ComposeValidation {
+  valid: boolean
+  parse_errors: string[]
+  docker_checked: boolean  // false when docker compose couldn't be run
+  docker_error: string | null
+}
+
+// Entry from `git stash list`
+export interface StashEntry {
+  index: number            // N in stash@{N}
+  branch: string | null
+  message: string
+  created_at: number | null  // Unix timestamp (seconds)
+}
+
+// Outcome of a git command that ran
+export interface GitCommandOutput {
+  success: boolean
+  stdout: string
+  stderr: string
+}
+
 // WorktreeInfo type
 export interface WorktreeInfo {
   path: string
   branch: string
   name: string
+  ahead?: number | null
+  behind?: number | null
+  dirty?: boolean
+  changed_files?: number
 }
 
 // Tmux status types
@@ -374,6 +659,27 @@ export interface TmuxSessionInfo {
   windows: TmuxWindowInfo[]
 }
 
+export interface TmuxLayoutPane {
+  index: string
+  pane_id: string
+  current_command: string
+  current_path: string
+  tty: string
+  active: boolean
+}
+
+export interface TmuxLayoutWindow {
+  index: string
+  name: string
+  active: boolean
+  panes: TmuxLayoutPane[]
+}
+
+export interface TmuxLayoutNode {
+  session: string
+  windows: TmuxLayoutWindow[]
+}
+
 // Claude Code status types
 export interface ClaudeStatus {
   is_running: boolean
@@ -466,6 +772,46 @@ export interface Ticket {
   updated_at: string
 }
 
+export interface EpicWithProgress extends Epic {
+  total_tickets: number
+  done_tickets: number
+}
+
+export interface KanbanImportSummary {
+  epics: number
+  tickets: number
+  skipped: number
+}
+
+export interface KanbanIntegrityReport {
+  ok: boolean
+  integrity_errors: string[]
+  foreign_key_errors: string[]
+}
+
+export interface KanbanRepairResult {
+  backup_path: string
+  rebuilt: boolean
+  rows_recovered: number
+  report: KanbanIntegrityReport
+}
+
+export interface EpicProgress {
+  total: number
+  done: number
+  in_review: number
+  in_progress: number
+  todo: number
+  backlog: number
+  percent_done: number
+}
+
+export interface BoardSnapshot {
+  epics: EpicWithProgress[]
+  tickets: Ticket[]
+  status_counts: Record<string, number>
+}
+
 // Claude session monitoring types
 export interface ClaudeSessionEvent {
   event_type: string        // "SessionStart" | "Stop" | "Notification" | "PreToolUse"
@@ -475,6 +821,22 @@ export interface ClaudeSessionEvent {
   data: Record<string, unknown>  // event-specific payload
 }
 
+export interface HooksStatus {
+  script_path: string
+  script_installed: boolean
+  script_up_to_date: boolean
+  settings_path: string
+  registered_events: string[]
+  missing_events: string[]
+  fully_installed: boolean
+}
+
+export interface ToolStat {
+  tool: string
+  count: number
+  top_paths: { path: string; count: number }[]
+}
+
 export interface ToolCallInfo {
   name: string
   description: string | null